@@ -7,7 +7,9 @@ use casper_types::{bytesrepr, system::mint, ApiError, ProtocolVersion};
 
 use crate::{
     core::{
-        engine_state::{genesis::GenesisError, upgrade::ProtocolUpgradeError},
+        engine_state::{
+            genesis::GenesisError, snapshot::SnapshotError, upgrade::ProtocolUpgradeError,
+        },
         execution,
         runtime::stack,
     },
@@ -106,6 +108,12 @@ pub enum Error {
     /// Failed to retrieve accumulation purse from handle payment system contract.
     #[error("Failed to retrieve accumulation purse from the handle payment contract")]
     FailedToRetrieveAccumulationPurse,
+    /// A trie node reachable from a state root hash is missing from global state.
+    #[error("Missing trie node: {0}")]
+    MissingTrieNode(Digest),
+    /// Failed to export or import a global state snapshot.
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
 }
 
 impl Error {
@@ -125,6 +133,9 @@ impl From<execution::Error> for Error {
             execution::Error::WasmPreprocessing(preprocessing_error) => {
                 Error::WasmPreprocessing(preprocessing_error)
             }
+            execution::Error::Storage(storage::error::Error::MissingTrieNode(trie_key)) => {
+                Error::MissingTrieNode(trie_key)
+            }
             _ => Error::Exec(error),
         }
     }
@@ -0,0 +1,87 @@
+//! Support for paging through the keys stored in global state under a common prefix.
+use casper_hashing::Digest;
+use casper_types::{Key, KeyTag, StoredValue, URefAddr};
+
+/// The maximum number of `Key::Dictionary` entries read by a single request listing the items of
+/// a dictionary.
+pub const MAX_LIST_KEYS_SCANNED: usize = 10_000;
+
+/// Selects the subset of global state walked by a [`ListKeysRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyPrefix {
+    /// All keys of the given variant, e.g. every `Key::Bid` or `Key::Unbond`.
+    Tag(KeyTag),
+    /// All `Key::Dictionary` items of the dictionary whose seed `URef` has the given address.
+    DictionaryItems(URefAddr),
+}
+
+impl KeyPrefix {
+    /// Returns the tag of the keys covered by this prefix.
+    pub fn key_tag(&self) -> KeyTag {
+        match self {
+            KeyPrefix::Tag(key_tag) => *key_tag,
+            KeyPrefix::DictionaryItems(_) => KeyTag::Dictionary,
+        }
+    }
+}
+
+/// Represents a request to list a page of the keys matching a [`KeyPrefix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListKeysRequest {
+    state_hash: Digest,
+    prefix: KeyPrefix,
+    cursor: Option<Key>,
+    limit: usize,
+}
+
+impl ListKeysRequest {
+    /// Creates new request.
+    ///
+    /// Only keys whose serialized bytes sort strictly after those of `cursor` are returned, and at
+    /// most `limit` of them.
+    pub fn new(state_hash: Digest, prefix: KeyPrefix, cursor: Option<Key>, limit: usize) -> Self {
+        ListKeysRequest {
+            state_hash,
+            prefix,
+            cursor,
+            limit,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the prefix of the keys to list.
+    pub fn prefix(&self) -> KeyPrefix {
+        self.prefix
+    }
+
+    /// Returns the key after which the page starts, if any.
+    pub fn cursor(&self) -> Option<&Key> {
+        self.cursor.as_ref()
+    }
+
+    /// Returns the maximum number of entries in the page.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+/// Represents a result of a `list_keys` request.
+#[derive(Debug)]
+pub enum ListKeysResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains a page of matching keys, in ascending order of their serialized bytes, with the
+    /// values stored under them.
+    Success {
+        /// The keys and their stored values.  Dictionary values are unwrapped to the `CLValue`
+        /// written by the contract.
+        entries: Vec<(Key, StoredValue)>,
+        /// The cursor to pass in the next request to continue listing, or `None` if there are no
+        /// further keys.
+        next_cursor: Option<Key>,
+    },
+}
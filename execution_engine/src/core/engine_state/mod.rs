@@ -12,10 +12,13 @@ pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
 pub mod get_bids;
+pub mod list_keys;
 pub mod op;
 mod prune;
 pub mod query;
 pub mod run_genesis_request;
+pub mod snapshot;
+pub mod state_diff;
 pub mod step;
 pub mod system_contract_registry;
 mod transfer;
@@ -25,6 +28,7 @@ use std::{
     cell::RefCell,
     collections::{btree_map::Entry, BTreeMap, BTreeSet},
     convert::TryFrom,
+    io::{Read, Write},
     rc::Rc,
 };
 
@@ -70,9 +74,12 @@ pub use self::{
     execution_result::{ExecutionResult, ForcedTransferResult},
     genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisSuccess},
     get_bids::{GetBidsRequest, GetBidsResult},
+    list_keys::{KeyPrefix, ListKeysRequest, ListKeysResult, MAX_LIST_KEYS_SCANNED},
     prune::{PruneConfig, PruneResult},
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
+    snapshot::{SnapshotError, DEFAULT_SNAPSHOT_CHUNK_SIZE},
+    state_diff::{KeyDiff, StateDiff},
     step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess},
     system_contract_registry::SystemContractRegistry,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
@@ -88,14 +95,17 @@ use crate::{
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
         execution::{self, DirectSystemContractCall, Executor},
-        runtime::RuntimeStack,
+        runtime::{trace::ExecutionTrace, RuntimeStack},
+        runtime_context::dictionary::{self, DictionaryValue},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         global_state::{
-            lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            lmdb::LmdbGlobalState,
+            recording::{ReadSet, RecordingGlobalState},
+            scratch::ScratchGlobalState,
+            CommitProvider, StateProvider, StateReader,
         },
         trie::{merkle_proof::TrieMerkleProof, TrieRaw},
         trie_store::operations::DeleteResult,
@@ -562,26 +572,8 @@ where
         let mut results = ExecutionResults::with_capacity(deploys.len());
 
         for deploy_item in deploys {
-            let result = match deploy_item.session {
-                ExecutableDeployItem::Transfer { .. } => self.transfer(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-                _ => self.deploy(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-            };
+            let result =
+                self.execute_deploy_item(correlation_id, &executor, &exec_request, deploy_item);
             match result {
                 Ok(result) => results.push_back(result),
                 Err(error) => {
@@ -593,6 +585,82 @@ where
         Ok(results)
     }
 
+    /// Runs a deploy execution request as per [`run_execute`](Self::run_execute), additionally
+    /// recording a trace of the gas charged while executing each deploy.
+    ///
+    /// Tracing slows execution down, so this is intended for profiling rather than for executing
+    /// blocks.
+    pub fn run_execute_traced(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ExecutionTrace)>, Error> {
+        let deploys = exec_request.take_deploys();
+        let mut results = Vec::with_capacity(deploys.len());
+
+        for deploy_item in deploys {
+            let trace = Rc::new(RefCell::new(ExecutionTrace::default()));
+            let executor = Executor::new_with_trace(self.config().clone(), Rc::clone(&trace));
+            let result =
+                self.execute_deploy_item(correlation_id, &executor, &exec_request, deploy_item)?;
+            // The executor holds the only other reference to the trace.
+            drop(executor);
+            let trace = Rc::try_unwrap(trace)
+                .map(RefCell::into_inner)
+                .unwrap_or_else(|trace| trace.borrow().clone());
+            results.push((result, trace));
+        }
+
+        Ok(results)
+    }
+
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        exec_request: &ExecuteRequest,
+        deploy_item: DeployItem,
+    ) -> Result<ExecutionResult, Error> {
+        match deploy_item.session {
+            ExecutableDeployItem::Transfer { .. } => self.transfer(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+            ),
+            _ => self.deploy(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+            ),
+        }
+    }
+
+    /// Runs a deploy execution request as per [`run_execute`](Self::run_execute), additionally
+    /// returning the global state keys read while executing it.
+    ///
+    /// The read set allows a caller executing several requests against the same pre-state to
+    /// detect whether a result would be affected by the effects of another.
+    pub fn run_execute_recording_reads(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(ExecutionResults, ReadSet), Error> {
+        let recording_engine_state = EngineState {
+            config: self.config.clone(),
+            state: RecordingGlobalState::new(&self.state),
+        };
+        let results = recording_engine_state.run_execute(correlation_id, exec_request)?;
+        Ok((results, recording_engine_state.state.read_set()))
+    }
+
     fn get_authorized_account(
         &self,
         correlation_id: CorrelationId,
@@ -650,6 +718,25 @@ where
         Ok(BalanceResult::Success { motes, proof })
     }
 
+    /// Returns the balance key of the purse receiving the fees for deploys executed in a block with
+    /// the given proposer, or `None` if fees are burned.
+    pub fn get_fee_purse_balance_key(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        proposer: PublicKey,
+    ) -> Result<Option<Key>, Error> {
+        if matches!(self.config.fee_handling(), FeeHandling::Burn) {
+            return Ok(None);
+        }
+        let fee_purse = self.get_rewards_purse(correlation_id, proposer, state_hash)?;
+        let tracking_copy = self
+            .tracking_copy(state_hash)?
+            .ok_or(Error::RootNotFound(state_hash))?;
+        let balance_key = tracking_copy.get_purse_balance_key(correlation_id, fee_purse.into())?;
+        Ok(Some(balance_key.normalize()))
+    }
+
     /// Executes a native transfer.
     ///
     /// Native transfers do not involve WASM at all, and also skip executing payment code.
@@ -1834,6 +1921,67 @@ where
         Ok(self.state.get_trie_full(correlation_id, &trie_key)?)
     }
 
+    /// Returns the keys whose stored values differ between the global state under
+    /// `before_state_hash` and under `after_state_hash`, along with both values.
+    ///
+    /// Only the subtries which differ between the two state roots are walked.
+    pub fn diff_state_roots(
+        &self,
+        correlation_id: CorrelationId,
+        before_state_hash: Digest,
+        after_state_hash: Digest,
+    ) -> Result<StateDiff, Error>
+    where
+        Error: From<S::Error>,
+    {
+        state_diff::diff_state_roots(
+            &self.state,
+            correlation_id,
+            before_state_hash,
+            after_state_hash,
+        )
+    }
+
+    /// Writes every trie reachable from `state_root_hash` to `writer` as a snapshot, split into
+    /// checksummed chunks of roughly `chunk_size` bytes.  Returns the number of tries written.
+    ///
+    /// See [`snapshot`] for a description of the format.
+    pub fn export_snapshot<W: Write>(
+        &self,
+        correlation_id: CorrelationId,
+        state_root_hash: Digest,
+        writer: W,
+        chunk_size: usize,
+    ) -> Result<u64, Error>
+    where
+        Error: From<S::Error>,
+    {
+        snapshot::export_snapshot(
+            &self.state,
+            correlation_id,
+            state_root_hash,
+            writer,
+            chunk_size,
+        )
+    }
+
+    /// Imports a snapshot written by [`export_snapshot`](Self::export_snapshot) from `reader`,
+    /// verifying each chunk's checksum and that the tries form a complete trie under the
+    /// snapshot's state root hash, which is returned.
+    ///
+    /// The tries of each chunk are stored in a single transaction.  Chunks imported before an error
+    /// is encountered are left in global state.
+    pub fn import_snapshot<R: Read>(
+        &self,
+        correlation_id: CorrelationId,
+        reader: R,
+    ) -> Result<Digest, Error>
+    where
+        Error: From<S::Error>,
+    {
+        snapshot::import_snapshot(&self.state, correlation_id, reader)
+    }
+
     /// Puts a trie if no children are missing from the global state; otherwise reports the missing
     /// children hashes via the `Error` enum.
     pub fn put_trie_if_all_children_present(
@@ -1951,6 +2099,97 @@ where
         Ok(GetBidsResult::Success { bids })
     }
 
+    /// Lists a page of the keys matching the prefix of `list_keys_request`, along with the values
+    /// stored under them.
+    ///
+    /// Keys are returned in ascending order of their serialized bytes, starting after the
+    /// request's cursor.  Only the parts of the trie at or after the cursor are read.
+    ///
+    /// Listing the items of a single dictionary requires reading the values of the
+    /// `Key::Dictionary` entries, as the dictionary's seed `URef` is only recorded in the stored
+    /// values.  At most [`MAX_LIST_KEYS_SCANNED`] dictionary entries are read per request, so a
+    /// page may hold fewer entries than requested while still returning a cursor to continue
+    /// from.
+    pub fn list_keys(
+        &self,
+        correlation_id: CorrelationId,
+        list_keys_request: ListKeysRequest,
+    ) -> Result<ListKeysResult, Error> {
+        let reader = match self
+            .state
+            .checkout(list_keys_request.state_hash())
+            .map_err(Into::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(ListKeysResult::RootNotFound),
+        };
+
+        let prefix = list_keys_request.prefix();
+        let limit = list_keys_request.limit();
+        let is_dictionary_listing = matches!(prefix, KeyPrefix::DictionaryItems(_));
+        // One more key than needed is read to find out whether there are further keys.
+        let keys_to_read = if is_dictionary_listing {
+            MAX_LIST_KEYS_SCANNED
+        } else {
+            limit.saturating_add(1)
+        };
+        let cursor_bytes = list_keys_request
+            .cursor()
+            .map(ToBytes::to_bytes)
+            .transpose()?;
+        let keys = reader
+            .keys_with_prefix_after(
+                correlation_id,
+                &[prefix.key_tag() as u8],
+                cursor_bytes.as_deref(),
+                keys_to_read,
+            )
+            .map_err(Into::into)?;
+
+        let mut entries = Vec::new();
+        let mut next_cursor = None;
+        for (index, key) in keys.iter().enumerate() {
+            if entries.len() >= limit {
+                next_cursor = entries.last().map(|(key, _)| *key);
+                break;
+            }
+            // The scan limit was reached, so the listing continues from the last key read.
+            if is_dictionary_listing && index + 1 == MAX_LIST_KEYS_SCANNED {
+                next_cursor = Some(*key);
+            }
+
+            let stored_value = match reader.read(correlation_id, key).map_err(Into::into)? {
+                Some(stored_value) => stored_value,
+                None => continue,
+            };
+
+            if let KeyPrefix::DictionaryItems(seed_uref_addr) = prefix {
+                let is_item_of_seed_uref = match &stored_value {
+                    StoredValue::CLValue(cl_value) => cl_value
+                        .clone()
+                        .into_t::<DictionaryValue>()
+                        .map(|dictionary_value| {
+                            dictionary_value.seed_uref_addr() == seed_uref_addr.as_slice()
+                        })
+                        .unwrap_or(false),
+                    _ => false,
+                };
+                if !is_item_of_seed_uref {
+                    continue;
+                }
+            }
+
+            let stored_value = dictionary::handle_stored_value(*key, stored_value)
+                .map_err(|error| Error::Exec(error.into()))?;
+            entries.push((*key, stored_value));
+        }
+
+        Ok(ListKeysResult::Success {
+            entries,
+            next_cursor,
+        })
+    }
+
     /// Executes a step request.
     pub fn commit_step(
         &self,
@@ -2579,6 +2818,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToRetrieveUnbondingDelay
             | Error::FailedToRetrieveEraId
             | Error::MissingTrieNodeChildren(_)
+            | Error::MissingTrieNode(_)
+            | Error::Snapshot(_)
             | Error::FailedToRetrieveAccumulationPurse => false,
         },
         ExecutionResult::Success { .. } => false,
@@ -0,0 +1,495 @@
+//! Support for exporting the global state under a state root hash to a snapshot, and importing it
+//! into another global state.
+//!
+//! A snapshot consists of a header followed by a sequence of chunks.  The header holds a magic
+//! number, the format version and the state root hash.  Each chunk holds the number of tries it
+//! contains, the length of its payload, the payload itself (the length of each trie followed by
+//! its serialized bytes) and the hash of the payload.  A chunk containing no tries marks the end of
+//! the snapshot.
+//!
+//! Tries are written children first, so every trie can be checked to have all of its children
+//! present as it is imported.
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+use thiserror::Error;
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr, Key, StoredValue};
+
+use crate::{
+    core::engine_state::Error,
+    shared::newtypes::CorrelationId,
+    storage::{
+        global_state::{PutTriesResult, StateProvider},
+        trie::{lazy_trie_tag, Trie, TrieRaw, TrieTag},
+    },
+};
+
+/// The default size in bytes at which the payload of a snapshot chunk is completed.
+pub const DEFAULT_SNAPSHOT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+const SNAPSHOT_MAGIC: [u8; 8] = *b"CSPRSNAP";
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Errors specific to reading or writing a global state snapshot.
+#[derive(Clone, Error, Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// Failed to read from or write to the snapshot.
+    #[error("Snapshot I/O error: {0}")]
+    Io(String),
+    /// The snapshot doesn't start with the expected magic number.
+    #[error("Not a global state snapshot")]
+    InvalidHeader,
+    /// The snapshot was written in an unsupported version of the format.
+    #[error("Unsupported snapshot version {0}")]
+    UnsupportedVersion(u8),
+    /// The payload of a chunk doesn't match its checksum.
+    #[error("Checksum mismatch in snapshot chunk {0}")]
+    ChecksumMismatch(u64),
+    /// The payload of a chunk doesn't hold the number of tries specified for it.
+    #[error("Malformed snapshot chunk {0}")]
+    MalformedChunk(u64),
+    /// A trie is too large to be written to a snapshot.
+    #[error("Trie {0} is too large to be written to a snapshot")]
+    TrieTooLarge(Digest),
+    /// The last trie of the snapshot isn't the root given in its header.
+    #[error("Snapshot of {expected} ended with trie {imported:?}")]
+    RootMismatch {
+        /// The state root hash given in the header.
+        expected: Digest,
+        /// The hash of the last trie imported, if any.
+        imported: Option<Digest>,
+    },
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error.to_string())
+    }
+}
+
+/// An entry of the stack used to walk the trie depth-first, children before parents.
+enum Frame {
+    /// A trie whose children have yet to be walked.
+    Unvisited(Digest),
+    /// A trie whose children have all been written.
+    Visited(Digest, TrieRaw),
+}
+
+/// Writes every trie reachable from `state_root_hash` to `writer`, completing a chunk whenever its
+/// payload reaches `chunk_size` bytes.  Returns the number of tries written.
+pub(crate) fn export_snapshot<S, W>(
+    state: &S,
+    correlation_id: CorrelationId,
+    state_root_hash: Digest,
+    mut writer: W,
+    chunk_size: usize,
+) -> Result<u64, Error>
+where
+    S: StateProvider,
+    Error: From<S::Error>,
+    W: Write,
+{
+    if state
+        .get_trie_full(correlation_id, &state_root_hash)?
+        .is_none()
+    {
+        return Err(Error::RootNotFound(state_root_hash));
+    }
+
+    write_header(&mut writer, state_root_hash)?;
+
+    let mut total_trie_count = 0_u64;
+    let mut chunk_trie_count = 0_u32;
+    let mut payload = Vec::new();
+    let mut stack = vec![Frame::Unvisited(state_root_hash)];
+    while let Some(frame) = stack.pop() {
+        match frame {
+            Frame::Unvisited(trie_key) => {
+                let trie_raw = state
+                    .get_trie_full(correlation_id, &trie_key)?
+                    .ok_or(Error::MissingTrieNode(trie_key))?;
+                let children = trie_children(trie_raw.inner())?;
+                stack.push(Frame::Visited(trie_key, trie_raw));
+                stack.extend(children.into_iter().map(Frame::Unvisited));
+            }
+            Frame::Visited(trie_key, trie_raw) => {
+                let trie_bytes = trie_raw.inner();
+                let trie_len = u32::try_from(trie_bytes.len())
+                    .map_err(|_| SnapshotError::TrieTooLarge(trie_key))?;
+                payload.extend_from_slice(&trie_len.to_le_bytes());
+                payload.extend_from_slice(trie_bytes);
+                chunk_trie_count += 1;
+                total_trie_count += 1;
+                if payload.len() >= chunk_size {
+                    write_chunk(&mut writer, chunk_trie_count, &payload)?;
+                    chunk_trie_count = 0;
+                    payload.clear();
+                }
+            }
+        }
+    }
+    if chunk_trie_count > 0 {
+        write_chunk(&mut writer, chunk_trie_count, &payload)?;
+    }
+    write_chunk(&mut writer, 0, &[])?;
+    writer.flush().map_err(SnapshotError::from)?;
+
+    Ok(total_trie_count)
+}
+
+/// Reads a snapshot from `reader`, storing every trie in it after checking that all of its
+/// children are already present.  The tries of each chunk are stored in a single transaction.
+/// Returns the state root hash of the snapshot.
+pub(crate) fn import_snapshot<S, R>(
+    state: &S,
+    correlation_id: CorrelationId,
+    mut reader: R,
+) -> Result<Digest, Error>
+where
+    S: StateProvider,
+    Error: From<S::Error>,
+    R: Read,
+{
+    let mut magic = [0; SNAPSHOT_MAGIC.len()];
+    reader.read_exact(&mut magic).map_err(SnapshotError::from)?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::InvalidHeader.into());
+    }
+    let mut version = [0; 1];
+    reader
+        .read_exact(&mut version)
+        .map_err(SnapshotError::from)?;
+    if version[0] != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version[0]).into());
+    }
+    let state_root_hash = read_digest(&mut reader)?;
+
+    let mut last_trie_hash = None;
+    let mut chunk_index = 0_u64;
+    loop {
+        let (trie_count, payload) = read_chunk(&mut reader, chunk_index)?;
+        if trie_count == 0 {
+            if !payload.is_empty() {
+                return Err(SnapshotError::MalformedChunk(chunk_index).into());
+            }
+            break;
+        }
+
+        let mut remaining = payload.as_slice();
+        let tries = (0..trie_count)
+            .map(|_| split_trie(&mut remaining))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SnapshotError::MalformedChunk(chunk_index))?;
+        if !remaining.is_empty() {
+            return Err(SnapshotError::MalformedChunk(chunk_index).into());
+        }
+        match state.put_tries_if_all_children_present(correlation_id, &tries)? {
+            PutTriesResult::Inserted(trie_hashes) => last_trie_hash = trie_hashes.last().copied(),
+            PutTriesResult::MissingChildren(missing_children) => {
+                return Err(Error::MissingTrieNodeChildren(missing_children))
+            }
+        }
+        chunk_index += 1;
+    }
+
+    if last_trie_hash != Some(state_root_hash) {
+        return Err(SnapshotError::RootMismatch {
+            expected: state_root_hash,
+            imported: last_trie_hash,
+        }
+        .into());
+    }
+    Ok(state_root_hash)
+}
+
+/// Returns the hashes of the direct children of the given serialized trie.
+fn trie_children(trie_bytes: &[u8]) -> Result<Vec<Digest>, Error> {
+    // Leaves have no children, so there's no need to deserialize their values.
+    if lazy_trie_tag(trie_bytes) == Some(TrieTag::Leaf) {
+        return Ok(Vec::new());
+    }
+    let trie: Trie<Key, StoredValue> = bytesrepr::deserialize_from_slice(trie_bytes)?;
+    Ok(trie.iter_children().collect())
+}
+
+fn write_header<W: Write>(writer: &mut W, state_root_hash: Digest) -> Result<(), SnapshotError> {
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    writer.write_all(&[SNAPSHOT_VERSION])?;
+    writer.write_all(state_root_hash.as_ref())?;
+    Ok(())
+}
+
+fn write_chunk<W: Write>(
+    writer: &mut W,
+    trie_count: u32,
+    payload: &[u8],
+) -> Result<(), SnapshotError> {
+    writer.write_all(&trie_count.to_le_bytes())?;
+    writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+    writer.write_all(payload)?;
+    writer.write_all(Digest::hash(payload).as_ref())?;
+    Ok(())
+}
+
+fn read_chunk<R: Read>(reader: &mut R, chunk_index: u64) -> Result<(u32, Vec<u8>), SnapshotError> {
+    let mut trie_count = [0; 4];
+    reader.read_exact(&mut trie_count)?;
+    let mut payload_len = [0; 8];
+    reader.read_exact(&mut payload_len)?;
+    let payload_len = u64::from_le_bytes(payload_len);
+
+    // Read via `take` rather than allocating up front, as the length may be corrupt.
+    let mut payload = Vec::new();
+    reader
+        .by_ref()
+        .take(payload_len)
+        .read_to_end(&mut payload)?;
+    if payload.len() as u64 != payload_len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    let checksum = read_digest(reader)?;
+    if Digest::hash(&payload) != checksum {
+        return Err(SnapshotError::ChecksumMismatch(chunk_index));
+    }
+    Ok((u32::from_le_bytes(trie_count), payload))
+}
+
+fn read_digest<R: Read>(reader: &mut R) -> Result<Digest, SnapshotError> {
+    let mut digest = [0; Digest::LENGTH];
+    reader.read_exact(&mut digest)?;
+    Ok(Digest::from(digest))
+}
+
+/// Splits the next length-prefixed trie off the front of `payload`.
+fn split_trie<'a>(payload: &mut &'a [u8]) -> Option<&'a [u8]> {
+    if payload.len() < 4 {
+        return None;
+    }
+    let (trie_len, rest) = payload.split_at(4);
+    let trie_len = u32::from_le_bytes(<[u8; 4]>::try_from(trie_len).ok()?) as usize;
+    if rest.len() < trie_len {
+        return None;
+    }
+    let (trie_bytes, rest) = rest.split_at(trie_len);
+    *payload = rest;
+    Some(trie_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
+
+    use casper_types::{account::AccountHash, CLValue};
+
+    use super::*;
+    use crate::storage::{
+        global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateReader},
+        transaction_source::lmdb::LmdbEnvironment,
+        trie_store::lmdb::LmdbTrieStore,
+        DEFAULT_TEST_MAX_DB_SIZE, DEFAULT_TEST_MAX_READERS,
+    };
+
+    const HEADER_LEN: usize = 8 + 1 + Digest::LENGTH;
+    const END_CHUNK_LEN: usize = 4 + 8 + Digest::LENGTH;
+
+    fn test_pairs() -> Vec<(Key, StoredValue)> {
+        (0..100_u8)
+            .map(|index| {
+                (
+                    Key::Account(AccountHash::new([index; 32])),
+                    StoredValue::CLValue(CLValue::from_t(u64::from(index)).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    fn export(state: &InMemoryGlobalState, root: Digest, chunk_size: usize) -> Vec<u8> {
+        let mut snapshot = Vec::new();
+        export_snapshot(state, CorrelationId::new(), root, &mut snapshot, chunk_size).unwrap();
+        snapshot
+    }
+
+    fn lmdb_state(path: &Path) -> LmdbGlobalState {
+        let environment = Arc::new(
+            LmdbEnvironment::new(
+                path,
+                DEFAULT_TEST_MAX_DB_SIZE,
+                DEFAULT_TEST_MAX_READERS,
+                true,
+            )
+            .unwrap(),
+        );
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        LmdbGlobalState::empty(environment, trie_store).unwrap()
+    }
+
+    fn import(snapshot: &[u8]) -> (InMemoryGlobalState, Result<Digest, Error>) {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let result = import_snapshot(&state, CorrelationId::new(), snapshot);
+        (state, result)
+    }
+
+    #[test]
+    fn should_export_and_import_snapshot() {
+        let correlation_id = CorrelationId::new();
+        let pairs = test_pairs();
+        let (state, root) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+
+        // A small chunk size splits the snapshot into several chunks.
+        let snapshot = export(&state, root, 256);
+        assert!(snapshot.len() > HEADER_LEN + 2 * (256 + END_CHUNK_LEN));
+
+        let (imported_state, result) = import(&snapshot);
+        assert_eq!(result.unwrap(), root);
+        let reader = imported_state
+            .checkout(root)
+            .unwrap()
+            .expect("should have root");
+        for (key, value) in &pairs {
+            assert_eq!(
+                reader.read(correlation_id, key).unwrap().as_ref(),
+                Some(value)
+            );
+        }
+
+        // The chunk size doesn't affect the tries exported.
+        let single_chunk_snapshot = export(&state, root, DEFAULT_SNAPSHOT_CHUNK_SIZE);
+        let (_, result) = import(&single_chunk_snapshot);
+        assert_eq!(result.unwrap(), root);
+    }
+
+    #[test]
+    fn should_export_and_import_snapshot_between_lmdb_states() {
+        let correlation_id = CorrelationId::new();
+        let pairs = test_pairs();
+        let source_dir = tempdir().unwrap();
+        let source_state = lmdb_state(source_dir.path());
+        let root = source_state
+            .put_stored_values(
+                correlation_id,
+                source_state.empty_state_root_hash(),
+                pairs.iter().cloned().collect(),
+            )
+            .unwrap();
+
+        let mut snapshot = Vec::new();
+        let exported_trie_count =
+            export_snapshot(&source_state, correlation_id, root, &mut snapshot, 256).unwrap();
+
+        let target_dir = tempdir().unwrap();
+        let target_state = lmdb_state(target_dir.path());
+        let result = import_snapshot(&target_state, correlation_id, snapshot.as_slice());
+        assert_eq!(result.unwrap(), root);
+        let reader = target_state
+            .checkout(root)
+            .unwrap()
+            .expect("should have root");
+        for (key, value) in &pairs {
+            assert_eq!(
+                reader.read(correlation_id, key).unwrap().as_ref(),
+                Some(value)
+            );
+        }
+
+        // Exporting the imported state yields the same snapshot.
+        let mut reexported_snapshot = Vec::new();
+        let reexported_trie_count = export_snapshot(
+            &target_state,
+            correlation_id,
+            root,
+            &mut reexported_snapshot,
+            256,
+        )
+        .unwrap();
+        assert_eq!(reexported_trie_count, exported_trie_count);
+        assert_eq!(reexported_snapshot, snapshot);
+    }
+
+    #[test]
+    fn should_reject_corrupt_snapshot() {
+        let (state, root) =
+            InMemoryGlobalState::from_pairs(CorrelationId::new(), &test_pairs()).unwrap();
+        let snapshot = export(&state, root, 256);
+
+        let mut corrupt_snapshot = snapshot.clone();
+        corrupt_snapshot[HEADER_LEN + 12 + 10] ^= 1;
+        assert!(matches!(
+            import(&corrupt_snapshot).1,
+            Err(Error::Snapshot(SnapshotError::ChecksumMismatch(0)))
+        ));
+
+        let truncated_snapshot = &snapshot[..snapshot.len() - END_CHUNK_LEN];
+        assert!(matches!(
+            import(truncated_snapshot).1,
+            Err(Error::Snapshot(SnapshotError::Io(_)))
+        ));
+
+        let mut wrong_root_snapshot = snapshot;
+        wrong_root_snapshot[9..HEADER_LEN].copy_from_slice(Digest::hash(b"other").as_ref());
+        assert!(matches!(
+            import(&wrong_root_snapshot).1,
+            Err(Error::Snapshot(SnapshotError::RootMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn should_not_import_any_trie_of_chunk_with_missing_children() {
+        let (state, root) =
+            InMemoryGlobalState::from_pairs(CorrelationId::new(), &test_pairs()).unwrap();
+        let snapshot = export(&state, root, 256);
+
+        // Find the last chunk holding tries, which holds the root.
+        let mut remaining = &snapshot[HEADER_LEN..];
+        let mut last_chunk_start = HEADER_LEN;
+        let mut last_payload = Vec::new();
+        for chunk_index in 0.. {
+            let chunk_start = snapshot.len() - remaining.len();
+            let (trie_count, payload) = read_chunk(&mut remaining, chunk_index).unwrap();
+            if trie_count == 0 {
+                break;
+            }
+            last_chunk_start = chunk_start;
+            last_payload = payload;
+        }
+        assert!(last_chunk_start > HEADER_LEN, "should have several chunks");
+
+        // Without the earlier chunks, the root has missing children.
+        let mut partial_snapshot = snapshot[..HEADER_LEN].to_vec();
+        partial_snapshot.extend_from_slice(&snapshot[last_chunk_start..]);
+        let (imported_state, result) = import(&partial_snapshot);
+        assert!(matches!(result, Err(Error::MissingTrieNodeChildren(_))));
+
+        let mut payload = last_payload.as_slice();
+        while let Some(trie_bytes) = split_trie(&mut payload) {
+            let trie_hash = Digest::hash_into_chunks_if_necessary(trie_bytes);
+            assert!(imported_state
+                .get_trie_full(CorrelationId::new(), &trie_hash)
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn should_not_export_missing_root() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let missing_root = Digest::hash(b"missing");
+        let result = export_snapshot(
+            &state,
+            CorrelationId::new(),
+            missing_root,
+            Vec::new(),
+            DEFAULT_SNAPSHOT_CHUNK_SIZE,
+        );
+        assert!(matches!(result, Err(Error::RootNotFound(root)) if root == missing_root));
+    }
+}
@@ -0,0 +1,373 @@
+//! Support for comparing the global state under two state root hashes.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr, Key, StoredValue};
+
+use crate::{
+    core::engine_state::Error,
+    shared::newtypes::CorrelationId,
+    storage::{
+        global_state::StateProvider,
+        trie::{Trie, RADIX},
+    },
+};
+
+/// The values stored under a single key in two versions of global state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyDiff {
+    /// The key whose value differs.
+    pub key: Key,
+    /// The value under the first state root hash, or `None` if the key was absent.
+    pub before: Option<StoredValue>,
+    /// The value under the second state root hash, or `None` if the key is absent.
+    pub after: Option<StoredValue>,
+}
+
+/// The differences between the global state under two state root hashes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateDiff {
+    /// The first state root hash.
+    pub before_state_hash: Digest,
+    /// The second state root hash.
+    pub after_state_hash: Digest,
+    /// The keys whose values differ, in ascending order.
+    pub entries: Vec<KeyDiff>,
+}
+
+/// Compares the tries rooted at `before_state_hash` and `after_state_hash`.
+///
+/// Subtrees with identical hashes are skipped without being read, so the cost is proportional to
+/// the size of the difference rather than to the size of global state.
+pub(crate) fn diff_state_roots<S>(
+    state: &S,
+    correlation_id: CorrelationId,
+    before_state_hash: Digest,
+    after_state_hash: Digest,
+) -> Result<StateDiff, Error>
+where
+    S: StateProvider,
+    Error: From<S::Error>,
+{
+    let mut walker = DiffWalker {
+        state,
+        correlation_id,
+        values: BTreeMap::new(),
+    };
+    let before = walker.read_trie(&before_state_hash, true)?;
+    let after = walker.read_trie(&after_state_hash, true)?;
+    if before_state_hash != after_state_hash {
+        walker.diff(Some(Subtrie::Read(before)), Some(Subtrie::Read(after)))?;
+    }
+
+    let entries = walker
+        .values
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(key, (before, after))| KeyDiff { key, before, after })
+        .collect();
+    Ok(StateDiff {
+        before_state_hash,
+        after_state_hash,
+        entries,
+    })
+}
+
+/// A subtrie at the position being compared in one of the two tries.
+enum Subtrie {
+    /// A subtrie which hasn't been read yet, given by its hash.
+    Unread(Digest),
+    /// A subtrie which has been read, or an extension shortened to start at the position.
+    Read(Trie<Key, StoredValue>),
+}
+
+/// Returns the children of a node or extension, indexed by the next byte of the path.
+///
+/// An extension has a single child, being the extension with the first byte of its affix removed
+/// or, if the affix is a single byte, the subtrie it points to.
+fn children(trie: Trie<Key, StoredValue>) -> Vec<Option<Subtrie>> {
+    let mut children: Vec<Option<Subtrie>> = (0..RADIX).map(|_| None).collect();
+    match trie {
+        Trie::Node { pointer_block } => {
+            for (index, pointer) in pointer_block.as_indexed_pointers() {
+                children[usize::from(index)] = Some(Subtrie::Unread(*pointer.hash()));
+            }
+        }
+        Trie::Extension { affix, pointer } => {
+            let child = if affix.len() > 1 {
+                Subtrie::Read(Trie::extension(affix[1..].to_vec(), pointer))
+            } else {
+                Subtrie::Unread(*pointer.hash())
+            };
+            children[usize::from(affix[0])] = Some(child);
+        }
+        Trie::Leaf { .. } => {}
+    }
+    children
+}
+
+/// Which of the two compared tries a value was found in.
+#[derive(Clone, Copy)]
+enum Side {
+    Before,
+    After,
+}
+
+struct DiffWalker<'a, S> {
+    state: &'a S,
+    correlation_id: CorrelationId,
+    /// The values found under each key in either trie, as `(before, after)`.
+    values: BTreeMap<Key, (Option<StoredValue>, Option<StoredValue>)>,
+}
+
+impl<'a, S> DiffWalker<'a, S>
+where
+    S: StateProvider,
+    Error: From<S::Error>,
+{
+    fn read_trie(&self, trie_key: &Digest, is_root: bool) -> Result<Trie<Key, StoredValue>, Error> {
+        match self.state.get_trie_full(self.correlation_id, trie_key)? {
+            Some(trie_raw) => Ok(bytesrepr::deserialize_from_slice(trie_raw.inner())?),
+            None if is_root => Err(Error::RootNotFound(*trie_key)),
+            None => Err(Error::MissingTrieNode(*trie_key)),
+        }
+    }
+
+    fn read_subtrie(
+        &self,
+        maybe_subtrie: Option<Subtrie>,
+    ) -> Result<Option<Trie<Key, StoredValue>>, Error> {
+        match maybe_subtrie {
+            Some(Subtrie::Unread(trie_key)) => self.read_trie(&trie_key, false).map(Some),
+            Some(Subtrie::Read(trie)) => Ok(Some(trie)),
+            None => Ok(None),
+        }
+    }
+
+    /// Compares two subtries at the same position, descending only into children which differ.
+    ///
+    /// Where an extension is compared with a node or with an extension having a different affix,
+    /// the extension is treated as a node with a single child, one byte of the affix at a time, so
+    /// that children with identical hashes are still skipped.
+    fn diff(&mut self, before: Option<Subtrie>, after: Option<Subtrie>) -> Result<(), Error> {
+        if let (Some(Subtrie::Unread(before_key)), Some(Subtrie::Unread(after_key))) =
+            (&before, &after)
+        {
+            if before_key == after_key {
+                return Ok(());
+            }
+        }
+        let before = self.read_subtrie(before)?;
+        let after = self.read_subtrie(after)?;
+        if before == after {
+            return Ok(());
+        }
+
+        match (before, after) {
+            (
+                Some(Trie::Extension {
+                    affix: before_affix,
+                    pointer: before_pointer,
+                }),
+                Some(Trie::Extension {
+                    affix: after_affix,
+                    pointer: after_pointer,
+                }),
+            ) if before_affix == after_affix => self.diff(
+                Some(Subtrie::Unread(*before_pointer.hash())),
+                Some(Subtrie::Unread(*after_pointer.hash())),
+            ),
+            (Some(before), Some(after))
+                if !matches!(before, Trie::Leaf { .. }) && !matches!(after, Trie::Leaf { .. }) =>
+            {
+                let before_children = children(before);
+                let after_children = children(after);
+                for (before_child, after_child) in before_children.into_iter().zip(after_children) {
+                    self.diff(before_child, after_child)?;
+                }
+                Ok(())
+            }
+            // A leaf or an empty subtrie is compared with a different subtrie, so every value on
+            // either side is collected and compared by key.  All but at most one of them differ.
+            (before, after) => {
+                if let Some(before) = before {
+                    self.collect_values(before, Side::Before)?;
+                }
+                if let Some(after) = after {
+                    self.collect_values(after, Side::After)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Records every value stored in the given trie.
+    fn collect_values(&mut self, trie: Trie<Key, StoredValue>, side: Side) -> Result<(), Error> {
+        let mut tries = vec![trie];
+        while let Some(trie) = tries.pop() {
+            match trie {
+                Trie::Leaf { key, value } => {
+                    let values = self.values.entry(key).or_default();
+                    match side {
+                        Side::Before => values.0 = Some(value),
+                        Side::After => values.1 = Some(value),
+                    }
+                }
+                Trie::Node { pointer_block } => {
+                    for (_, pointer) in pointer_block.as_indexed_pointers() {
+                        tries.push(self.read_trie(pointer.hash(), false)?);
+                    }
+                }
+                Trie::Extension { pointer, .. } => {
+                    tries.push(self.read_trie(pointer.hash(), false)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, CLValue};
+
+    use super::*;
+    use crate::{
+        shared::{additive_map::AdditiveMap, transform::Transform},
+        storage::{
+            global_state::{in_memory::InMemoryGlobalState, CommitProvider},
+            trie_store::operations::DeleteResult,
+        },
+    };
+
+    fn account_key(index: u8) -> Key {
+        Key::Account(AccountHash::new([index; 32]))
+    }
+
+    fn value(number: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(number).unwrap())
+    }
+
+    #[test]
+    fn should_diff_state_roots() {
+        let correlation_id = CorrelationId::new();
+        let pairs: Vec<_> = (0..50)
+            .map(|index| (account_key(index), value(u64::from(index))))
+            .collect();
+        let (state, before_root) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(account_key(7), Transform::Write(value(700)));
+        effects.insert(account_key(100), Transform::Write(value(100)));
+        // Rewriting the same value leaves the key unchanged.
+        effects.insert(account_key(8), Transform::Write(value(8)));
+        let root = state.commit(correlation_id, before_root, effects).unwrap();
+        let after_root = match state
+            .delete_keys(correlation_id, root, &[account_key(9)])
+            .unwrap()
+        {
+            DeleteResult::Deleted(root) => root,
+            result => panic!("should delete key: {:?}", result),
+        };
+
+        let diff = diff_state_roots(&state, correlation_id, before_root, after_root).unwrap();
+        assert_eq!(diff.before_state_hash, before_root);
+        assert_eq!(diff.after_state_hash, after_root);
+        assert_eq!(
+            diff.entries,
+            vec![
+                KeyDiff {
+                    key: account_key(7),
+                    before: Some(value(7)),
+                    after: Some(value(700)),
+                },
+                KeyDiff {
+                    key: account_key(9),
+                    before: Some(value(9)),
+                    after: None,
+                },
+                KeyDiff {
+                    key: account_key(100),
+                    before: None,
+                    after: Some(value(100)),
+                },
+            ]
+        );
+
+        let reverse_diff = diff_state_roots(&state, correlation_id, after_root, before_root)
+            .unwrap()
+            .entries;
+        assert_eq!(reverse_diff.len(), 3);
+        assert_eq!(reverse_diff[1].before, None);
+        assert_eq!(reverse_diff[1].after, Some(value(9)));
+
+        let no_diff = diff_state_roots(&state, correlation_id, after_root, after_root).unwrap();
+        assert!(no_diff.entries.is_empty());
+
+        let missing_root = Digest::hash(b"missing");
+        assert!(matches!(
+            diff_state_roots(&state, correlation_id, before_root, missing_root),
+            Err(Error::RootNotFound(root)) if root == missing_root
+        ));
+    }
+
+    #[test]
+    fn should_diff_tries_shaped_differently() {
+        let correlation_id = CorrelationId::new();
+        // Returns a key whose account hash is `0xaa` repeated, followed by `suffix` from the given
+        // offset onwards.
+        let shared_prefix_key = |offset: usize, suffix: u8| {
+            let mut hash = [0xaa; 32];
+            hash[offset..].fill(suffix);
+            Key::Account(AccountHash::new(hash))
+        };
+        // The two keys sharing a 31-byte prefix are below an extension.
+        let first_shared_key = shared_prefix_key(31, 1);
+        let second_shared_key = shared_prefix_key(31, 2);
+        let mut pairs: Vec<_> = (0..20)
+            .map(|index| (account_key(index), value(u64::from(index))))
+            .collect();
+        pairs.push((first_shared_key, value(1)));
+        pairs.push((second_shared_key, value(2)));
+        let (state, before_root) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+
+        // The new key diverges from the shared keys partway through the extension's affix.
+        let splitting_key = shared_prefix_key(16, 0xbb);
+        let mut effects = AdditiveMap::new();
+        effects.insert(splitting_key, Transform::Write(value(3)));
+        effects.insert(first_shared_key, Transform::Write(value(10)));
+        let after_root = state.commit(correlation_id, before_root, effects).unwrap();
+
+        let expected = vec![
+            KeyDiff {
+                key: first_shared_key,
+                before: Some(value(1)),
+                after: Some(value(10)),
+            },
+            KeyDiff {
+                key: splitting_key,
+                before: None,
+                after: Some(value(3)),
+            },
+        ];
+        let diff = diff_state_roots(&state, correlation_id, before_root, after_root).unwrap();
+        assert_eq!(diff.entries, expected);
+
+        let reverse_diff = diff_state_roots(&state, correlation_id, after_root, before_root)
+            .unwrap()
+            .entries;
+        let expected_reverse: Vec<_> = expected
+            .into_iter()
+            .map(|KeyDiff { key, before, after }| KeyDiff {
+                key,
+                before: after,
+                after: before,
+            })
+            .collect();
+        assert_eq!(reverse_diff, expected_reverse);
+    }
+}
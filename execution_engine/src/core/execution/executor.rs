@@ -16,7 +16,10 @@ use crate::{
             ExecError,
         },
         execution::{address_generator::AddressGenerator, Error},
-        runtime::{Runtime, RuntimeStack},
+        runtime::{
+            trace::{ExecutionTrace, Tracer},
+            Runtime, RuntimeStack,
+        },
         runtime_context::RuntimeContext,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
@@ -35,12 +38,30 @@ fn try_get_amount(runtime_args: &RuntimeArgs) -> Result<U512, ExecError> {
 /// Executor object deals with execution of WASM modules.
 pub struct Executor {
     config: EngineConfig,
+    trace: Option<Rc<RefCell<ExecutionTrace>>>,
 }
 
 impl Executor {
     /// Creates new executor object.
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            trace: None,
+        }
+    }
+
+    /// Creates new executor object which records everything it executes into `trace`.
+    pub(crate) fn new_with_trace(config: EngineConfig, trace: Rc<RefCell<ExecutionTrace>>) -> Self {
+        Executor {
+            config,
+            trace: Some(trace),
+        }
+    }
+
+    fn new_tracer(&self) -> Option<Tracer> {
+        self.trace
+            .as_ref()
+            .map(|trace| Tracer::new(Rc::clone(trace)))
     }
 
     /// Executes a WASM module.
@@ -100,7 +121,7 @@ impl Executor {
             spending_limit,
         );
 
-        let mut runtime = Runtime::new(self.config.clone(), context);
+        let mut runtime = Runtime::new(self.config.clone(), context, self.new_tracer());
 
         let result = match execution_kind {
             ExecutionKind::Module(module_bytes) => {
@@ -190,7 +211,7 @@ impl Executor {
 
         // Standard payment is executed in the calling account's context; the stack already
         // captures that.
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(self.config.clone(), runtime_context, self.new_tracer());
 
         match runtime.call_host_standard_payment(stack) {
             Ok(()) => ExecutionResult::Success {
@@ -306,7 +327,7 @@ impl Executor {
             remaining_spending_limit,
         );
 
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(self.config.clone(), runtime_context, self.new_tracer());
 
         // DO NOT alter this logic to call a system contract directly (such as via mint_internal,
         // etc). Doing so would bypass necessary context based security checks in some use cases. It
@@ -62,6 +62,119 @@ pub(crate) enum FunctionIndex {
     EnableContractVersion,
 }
 
+impl FunctionIndex {
+    /// Returns the name under which the host function is imported by contracts.
+    pub(crate) fn host_function_name(self) -> &'static str {
+        match self {
+            FunctionIndex::WriteFuncIndex => "casper_write",
+            FunctionIndex::ReadFuncIndex => "casper_read_value",
+            FunctionIndex::AddFuncIndex => "casper_add",
+            FunctionIndex::NewFuncIndex => "casper_new_uref",
+            FunctionIndex::RetFuncIndex => "casper_ret",
+            FunctionIndex::CallContractFuncIndex => "casper_call_contract",
+            FunctionIndex::GetKeyFuncIndex => "casper_get_key",
+            FunctionIndex::GasFuncIndex => "gas",
+            FunctionIndex::HasKeyFuncIndex => "casper_has_key",
+            FunctionIndex::PutKeyFuncIndex => "casper_put_key",
+            FunctionIndex::IsValidURefFnIndex => "casper_is_valid_uref",
+            FunctionIndex::RevertFuncIndex => "casper_revert",
+            FunctionIndex::AddAssociatedKeyFuncIndex => "casper_add_associated_key",
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => "casper_remove_associated_key",
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => "casper_update_associated_key",
+            FunctionIndex::SetActionThresholdFuncIndex => "casper_set_action_threshold",
+            FunctionIndex::LoadNamedKeysFuncIndex => "casper_load_named_keys",
+            FunctionIndex::RemoveKeyFuncIndex => "casper_remove_key",
+            FunctionIndex::GetCallerIndex => "casper_get_caller",
+            FunctionIndex::GetBlocktimeIndex => "casper_get_blocktime",
+            FunctionIndex::CreatePurseIndex => "casper_create_purse",
+            FunctionIndex::TransferToAccountIndex => "casper_transfer_to_account",
+            FunctionIndex::TransferFromPurseToAccountIndex => {
+                "casper_transfer_from_purse_to_account"
+            }
+            FunctionIndex::TransferFromPurseToPurseIndex => "casper_transfer_from_purse_to_purse",
+            FunctionIndex::GetBalanceIndex => "casper_get_balance",
+            FunctionIndex::GetPhaseIndex => "casper_get_phase",
+            FunctionIndex::GetSystemContractIndex => "casper_get_system_contract",
+            FunctionIndex::GetMainPurseIndex => "casper_get_main_purse",
+            FunctionIndex::ReadHostBufferIndex => "casper_read_host_buffer",
+            FunctionIndex::CreateContractPackageAtHash => "casper_create_contract_package_at_hash",
+            FunctionIndex::AddContractVersion => "casper_add_contract_version",
+            FunctionIndex::DisableContractVersion => "casper_disable_contract_version",
+            FunctionIndex::CallVersionedContract => "casper_call_versioned_contract",
+            FunctionIndex::CreateContractUserGroup => "casper_create_contract_user_group",
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => "casper_print",
+            FunctionIndex::GetRuntimeArgsizeIndex => "casper_get_named_arg_size",
+            FunctionIndex::GetRuntimeArgIndex => "casper_get_named_arg",
+            FunctionIndex::RemoveContractUserGroupIndex => "casper_remove_contract_user_group",
+            FunctionIndex::ExtendContractUserGroupURefsIndex => {
+                "casper_provision_contract_user_group_uref"
+            }
+            FunctionIndex::RemoveContractUserGroupURefsIndex => {
+                "casper_remove_contract_user_group_urefs"
+            }
+            FunctionIndex::Blake2b => "casper_blake2b",
+            FunctionIndex::RecordTransfer => "casper_record_transfer",
+            FunctionIndex::RecordEraInfo => "casper_record_era_info",
+            FunctionIndex::NewDictionaryFuncIndex => "casper_new_dictionary",
+            FunctionIndex::DictionaryGetFuncIndex => "casper_dictionary_get",
+            FunctionIndex::DictionaryPutFuncIndex => "casper_dictionary_put",
+            FunctionIndex::LoadCallStack => "casper_load_call_stack",
+            FunctionIndex::LoadAuthorizationKeys => "casper_load_authorization_keys",
+            FunctionIndex::RandomBytes => "casper_random_bytes",
+            FunctionIndex::DictionaryReadFuncIndex => "casper_dictionary_read",
+            FunctionIndex::EnableContractVersion => "casper_enable_contract_version",
+        }
+    }
+
+    /// Returns the positions of the arguments giving the sizes of the buffers which are passed to
+    /// the host function in Wasm memory.
+    pub(crate) fn size_arg_positions(self) -> &'static [usize] {
+        match self {
+            FunctionIndex::WriteFuncIndex
+            | FunctionIndex::AddFuncIndex
+            | FunctionIndex::GetKeyFuncIndex
+            | FunctionIndex::PutKeyFuncIndex
+            | FunctionIndex::DisableContractVersion
+            | FunctionIndex::GetRuntimeArgIndex
+            | FunctionIndex::RemoveContractUserGroupIndex
+            | FunctionIndex::ExtendContractUserGroupURefsIndex
+            | FunctionIndex::Blake2b
+            | FunctionIndex::RecordEraInfo
+            | FunctionIndex::DictionaryGetFuncIndex
+            | FunctionIndex::EnableContractVersion => &[1, 3],
+            FunctionIndex::ReadFuncIndex
+            | FunctionIndex::RetFuncIndex
+            | FunctionIndex::HasKeyFuncIndex
+            | FunctionIndex::IsValidURefFnIndex
+            | FunctionIndex::AddAssociatedKeyFuncIndex
+            | FunctionIndex::RemoveAssociatedKeyFuncIndex
+            | FunctionIndex::UpdateAssociatedKeyFuncIndex
+            | FunctionIndex::RemoveKeyFuncIndex
+            | FunctionIndex::CreatePurseIndex
+            | FunctionIndex::GetBalanceIndex
+            | FunctionIndex::ReadHostBufferIndex
+            | FunctionIndex::GetRuntimeArgsizeIndex
+            | FunctionIndex::RandomBytes
+            | FunctionIndex::DictionaryReadFuncIndex => &[1],
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => &[1],
+            FunctionIndex::NewFuncIndex | FunctionIndex::GetSystemContractIndex => &[2],
+            FunctionIndex::CallContractFuncIndex
+            | FunctionIndex::TransferToAccountIndex
+            | FunctionIndex::RemoveContractUserGroupURefsIndex
+            | FunctionIndex::DictionaryPutFuncIndex => &[1, 3, 5],
+            FunctionIndex::TransferFromPurseToAccountIndex
+            | FunctionIndex::TransferFromPurseToPurseIndex
+            | FunctionIndex::CallVersionedContract => &[1, 3, 5, 7],
+            FunctionIndex::AddContractVersion => &[1, 4, 6, 8],
+            FunctionIndex::CreateContractUserGroup => &[1, 3, 6],
+            FunctionIndex::RecordTransfer => &[1, 3, 5, 7, 9],
+            _ => &[],
+        }
+    }
+}
+
 impl From<FunctionIndex> for usize {
     fn from(index: FunctionIndex) -> usize {
         // NOTE: This can't fail as `FunctionIndex` is represented by usize,
@@ -80,7 +193,7 @@ impl TryFrom<usize> for FunctionIndex {
 #[cfg(test)]
 mod tests {
     use super::FunctionIndex;
-    use std::convert::TryFrom;
+    use std::{collections::BTreeSet, convert::TryFrom};
 
     #[test]
     fn primitive_to_enum() {
@@ -97,4 +210,15 @@ mod tests {
     fn invalid_index() {
         assert!(FunctionIndex::try_from(123_456_789usize).is_err());
     }
+
+    #[test]
+    fn host_function_names_are_unique() {
+        let mut names = BTreeSet::new();
+        let mut index = 0;
+        while let Ok(function_index) = FunctionIndex::try_from(index) {
+            assert!(names.insert(function_index.host_function_name()));
+            index += 1;
+        }
+        assert!(names.contains("casper_read_value"));
+    }
 }
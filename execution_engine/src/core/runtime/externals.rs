@@ -13,7 +13,7 @@ use casper_types::{
     StoredValue, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
-use super::{args::Args, Error, Runtime};
+use super::{args::Args, Error, Runtime, RuntimeStack};
 use crate::{
     core::resolvers::v1_function_index::FunctionIndex,
     shared::host_function_costs::{Cost, HostFunction, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY},
@@ -32,6 +32,59 @@ where
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
 
+        if self.tracer.is_none() {
+            return self.invoke_host_function(func, args);
+        }
+
+        let depth = self.stack.as_ref().map_or(0, RuntimeStack::len);
+        let gas_before = self.gas_counter();
+
+        if func == FunctionIndex::GasFuncIndex {
+            let result = self.invoke_host_function(func, args);
+            let gas = self
+                .gas_counter()
+                .checked_sub(gas_before)
+                .unwrap_or_default();
+            if let Some(tracer) = self.tracer.as_ref() {
+                tracer.push_wasm_gas(depth, gas);
+            }
+            return result;
+        }
+
+        // Malformed arguments are reported when the host function is invoked below.
+        let args_size: usize = func
+            .size_arg_positions()
+            .iter()
+            .map(|position| args.nth_checked::<u32>(*position).unwrap_or_default() as usize)
+            .sum();
+        let outer_entry = self.tracer.as_mut().and_then(|tracer| {
+            tracer.start_host_function(depth, func.host_function_name(), args_size)
+        });
+
+        let result = self.invoke_host_function(func, args);
+
+        let total_cost = self
+            .gas_counter()
+            .checked_sub(gas_before)
+            .unwrap_or_default();
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.finish_host_function(total_cost, outer_entry);
+        }
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    /// Handles the invocation of the host function identified by `func`.
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let host_function_costs = self.config.wasm_config().take_host_function_costs();
 
         match func {
@@ -7,6 +7,7 @@ mod host_function_flag;
 mod mint_internal;
 pub mod stack;
 mod standard_payment_internal;
+pub mod trace;
 mod utils;
 
 use std::{
@@ -47,7 +48,10 @@ use crate::{
     core::{
         engine_state::EngineConfig,
         execution::{self, Error},
-        runtime::host_function_flag::HostFunctionFlag,
+        runtime::{
+            host_function_flag::HostFunctionFlag,
+            trace::{CallTarget, TraceEntry, Tracer},
+        },
         runtime_context::{self, RuntimeContext},
         tracking_copy::TrackingCopyExt,
     },
@@ -63,6 +67,7 @@ use crate::{
 };
 pub use stack::{RuntimeStack, RuntimeStackFrame, RuntimeStackOverflow};
 
+#[derive(Clone, Copy)]
 enum CallContractIdentifier {
     Contract {
         contract_hash: ContractHash,
@@ -82,6 +87,7 @@ pub struct Runtime<'a, R> {
     context: RuntimeContext<'a, R>,
     stack: Option<RuntimeStack>,
     host_function_flag: HostFunctionFlag,
+    tracer: Option<Tracer>,
 }
 
 impl<'a, R> Runtime<'a, R>
@@ -89,8 +95,12 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<Error>,
 {
-    /// Creates a new runtime instance.
-    pub(crate) fn new(config: EngineConfig, context: RuntimeContext<'a, R>) -> Self {
+    /// Creates a new runtime instance, recording an execution trace if `tracer` is provided.
+    pub(crate) fn new(
+        config: EngineConfig,
+        context: RuntimeContext<'a, R>,
+        tracer: Option<Tracer>,
+    ) -> Self {
         Runtime {
             config,
             memory: None,
@@ -99,10 +109,12 @@ where
             context,
             stack: None,
             host_function_flag: HostFunctionFlag::default(),
+            tracer,
         }
     }
 
-    /// Creates a new runtime instance by cloning the config, and host function flag from `self`.
+    /// Creates a new runtime instance by cloning the config, host function flag and tracer from
+    /// `self`.
     fn new_invocation_runtime(
         &self,
         context: RuntimeContext<'a, R>,
@@ -119,6 +131,7 @@ where
             context,
             stack: Some(stack),
             host_function_flag: self.host_function_flag.clone(),
+            tracer: self.tracer.as_ref().map(Tracer::new_nested),
         }
     }
 
@@ -137,6 +150,7 @@ where
             context,
             stack: Some(stack),
             host_function_flag: self.host_function_flag.clone(),
+            tracer: self.tracer.as_ref().map(Tracer::new_nested),
         }
     }
 
@@ -1144,6 +1158,50 @@ where
         identifier: CallContractIdentifier,
         entry_point_name: &str,
         args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
+        let tracer = match self.tracer.as_ref() {
+            Some(tracer) => tracer.clone(),
+            None => return self.execute_contract_untraced(identifier, entry_point_name, args),
+        };
+
+        let target = match identifier {
+            CallContractIdentifier::Contract { contract_hash } => {
+                CallTarget::Contract { contract_hash }
+            }
+            CallContractIdentifier::ContractPackage {
+                contract_package_hash,
+                version,
+            } => CallTarget::ContractPackage {
+                contract_package_hash,
+                version,
+            },
+        };
+        // The called contract runs with a new frame pushed onto the current stack.
+        let depth = self.stack.as_ref().map_or(0, RuntimeStack::len) + 1;
+        tracer.push(TraceEntry::ContractCall {
+            depth,
+            target,
+            entry_point: entry_point_name.to_string(),
+        });
+
+        let gas_before = self.gas_counter();
+        let result = self.execute_contract_untraced(identifier, entry_point_name, args);
+        tracer.push(TraceEntry::ContractReturn {
+            depth,
+            gas: self
+                .gas_counter()
+                .checked_sub(gas_before)
+                .unwrap_or_default(),
+            success: result.is_ok(),
+        });
+        result
+    }
+
+    fn execute_contract_untraced(
+        &mut self,
+        identifier: CallContractIdentifier,
+        entry_point_name: &str,
+        args: RuntimeArgs,
     ) -> Result<CLValue, Error> {
         let (contract, contract_hash, contract_package) = match identifier {
             CallContractIdentifier::Contract { contract_hash } => {
@@ -2776,6 +2834,9 @@ where
     {
         let cost = host_function.calculate_gas_cost(weights);
         self.gas(cost)?;
+        if let Some(tracer) = self.tracer.as_ref() {
+            tracer.add_host_function_cost(cost);
+        }
         Ok(())
     }
 
@@ -0,0 +1,291 @@
+//! Opt-in tracing of the gas charged while executing a deploy.
+use std::{cell::RefCell, mem, rc::Rc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{ContractHash, ContractPackageHash, ContractVersion, Gas, U512};
+
+/// The maximum number of entries recorded in an [`ExecutionTrace`].
+pub const MAX_TRACE_ENTRIES: usize = 10_000;
+
+/// The contract targeted by a traced contract call.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum CallTarget {
+    /// A contract called by its hash.
+    Contract {
+        /// The hash of the contract.
+        contract_hash: ContractHash,
+    },
+    /// A contract called via its package, at the given or else the latest version.
+    ContractPackage {
+        /// The hash of the contract package.
+        contract_package_hash: ContractPackageHash,
+        /// The version of the contract, if specified.
+        version: Option<ContractVersion>,
+    },
+}
+
+/// A single entry of an [`ExecutionTrace`].
+///
+/// Every entry records the height of the call stack at which it occurred, so nested contract
+/// calls can be reconstructed from a flat trace.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum TraceEntry {
+    /// A call into a stored contract.
+    ContractCall {
+        /// The height of the call stack within the called contract.
+        depth: usize,
+        /// The called contract.
+        target: CallTarget,
+        /// The name of the called entry point.
+        entry_point: String,
+    },
+    /// The end of the most recent contract call at the same depth.
+    ContractReturn {
+        /// The height of the call stack within the returning contract.
+        depth: usize,
+        /// The total gas charged during the call, including any nested calls.
+        #[schemars(with = "U512")]
+        gas: Gas,
+        /// Whether the call succeeded or not.
+        success: bool,
+    },
+    /// An invocation of a host function.
+    HostFunction {
+        /// The height of the call stack from which the host function was invoked.
+        depth: usize,
+        /// The name under which the host function is imported, e.g. `casper_read_value`.
+        name: String,
+        /// The combined size in bytes of the buffers passed to the host function in Wasm memory,
+        /// as given by its `*_size` arguments.
+        args_size: usize,
+        /// The gas charged for the invocation as per the host function costs.
+        #[schemars(with = "U512")]
+        cost: Gas,
+        /// The total gas charged while handling the invocation, including storage costs and any
+        /// contract calls made by it.
+        #[schemars(with = "U512")]
+        total_cost: Gas,
+    },
+    /// Consecutive blocks of Wasm instructions executed at the same depth, charged via the `gas`
+    /// calls injected into the contract's code.
+    WasmGas {
+        /// The height of the call stack executing the instructions.
+        depth: usize,
+        /// The number of blocks of instructions.
+        block_count: u64,
+        /// The total gas charged for the blocks.
+        #[schemars(with = "U512")]
+        gas: Gas,
+    },
+}
+
+/// A trace of the gas charged while executing a deploy.
+///
+/// At most [`MAX_TRACE_ENTRIES`] entries are recorded; any further entries are only counted.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionTrace {
+    entries: Vec<TraceEntry>,
+    omitted_entries: u64,
+}
+
+impl ExecutionTrace {
+    /// Returns the entries of the trace, in the order in which they occurred.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries which were not recorded as the trace was full.
+    pub fn omitted_entries(&self) -> u64 {
+        self.omitted_entries
+    }
+
+    /// Returns the total gas charged for Wasm instructions in the recorded entries, as opposed to
+    /// host function calls.
+    pub fn wasm_gas(&self) -> Gas {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                TraceEntry::WasmGas { gas, .. } => Some(*gas),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Records a block of Wasm instructions, merging it into the previous entry if that was also
+    /// for Wasm instructions at the same depth.
+    fn push_wasm_gas(&mut self, depth: usize, amount: Gas) {
+        if let Some(TraceEntry::WasmGas {
+            depth: last_depth,
+            block_count,
+            gas,
+        }) = self.entries.last_mut()
+        {
+            if *last_depth == depth {
+                *block_count += 1;
+                *gas += amount;
+                return;
+            }
+        }
+        self.push(TraceEntry::WasmGas {
+            depth,
+            block_count: 1,
+            gas: amount,
+        });
+    }
+
+    /// Records the given entry, returning its index, or `None` if the trace is full.
+    fn push(&mut self, entry: TraceEntry) -> Option<usize> {
+        if self.entries.len() >= MAX_TRACE_ENTRIES {
+            self.omitted_entries += 1;
+            return None;
+        }
+        self.entries.push(entry);
+        Some(self.entries.len() - 1)
+    }
+}
+
+/// Records entries into an [`ExecutionTrace`] shared by all the runtimes involved in executing a
+/// deploy.
+#[derive(Clone, Debug)]
+pub(crate) struct Tracer {
+    trace: Rc<RefCell<ExecutionTrace>>,
+    /// The index of the entry for the host function currently being handled by the runtime owning
+    /// this tracer.
+    host_function_entry: Option<usize>,
+}
+
+impl Tracer {
+    pub(crate) fn new(trace: Rc<RefCell<ExecutionTrace>>) -> Self {
+        Tracer {
+            trace,
+            host_function_entry: None,
+        }
+    }
+
+    /// Returns a tracer for a nested runtime, recording into the same trace.
+    pub(crate) fn new_nested(&self) -> Self {
+        Tracer::new(Rc::clone(&self.trace))
+    }
+
+    pub(crate) fn push(&self, entry: TraceEntry) {
+        self.trace.borrow_mut().push(entry);
+    }
+
+    pub(crate) fn push_wasm_gas(&self, depth: usize, amount: Gas) {
+        self.trace.borrow_mut().push_wasm_gas(depth, amount);
+    }
+
+    /// Records the start of a host function invocation, returning the entry of any invocation
+    /// already being handled, which should be passed back to `finish_host_function`.
+    pub(crate) fn start_host_function(
+        &mut self,
+        depth: usize,
+        name: &str,
+        args_size: usize,
+    ) -> Option<usize> {
+        let entry = self.trace.borrow_mut().push(TraceEntry::HostFunction {
+            depth,
+            name: name.to_string(),
+            args_size,
+            cost: Gas::default(),
+            total_cost: Gas::default(),
+        });
+        mem::replace(&mut self.host_function_entry, entry)
+    }
+
+    /// Adds to the cost of the host function invocation currently being handled.
+    pub(crate) fn add_host_function_cost(&self, amount: Gas) {
+        let index = match self.host_function_entry {
+            Some(index) => index,
+            None => return,
+        };
+        if let Some(TraceEntry::HostFunction { cost, .. }) =
+            self.trace.borrow_mut().entries.get_mut(index)
+        {
+            *cost += amount;
+        }
+    }
+
+    /// Records the total cost of the host function invocation currently being handled.
+    pub(crate) fn finish_host_function(&mut self, total: Gas, outer_entry: Option<usize>) {
+        let index = match mem::replace(&mut self.host_function_entry, outer_entry) {
+            Some(index) => index,
+            None => return,
+        };
+        if let Some(TraceEntry::HostFunction { total_cost, .. }) =
+            self.trace.borrow_mut().entries.get_mut(index)
+        {
+            *total_cost = total;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_merge_consecutive_wasm_gas_at_same_depth() {
+        let trace = Rc::new(RefCell::new(ExecutionTrace::default()));
+        let tracer = Tracer::new(Rc::clone(&trace));
+        tracer.push_wasm_gas(1, Gas::from(2_u64));
+        tracer.push_wasm_gas(1, Gas::from(3_u64));
+        tracer.push_wasm_gas(2, Gas::from(5_u64));
+        tracer.push_wasm_gas(1, Gas::from(7_u64));
+        let trace = trace.borrow();
+
+        assert_eq!(
+            trace.entries(),
+            &[
+                TraceEntry::WasmGas {
+                    depth: 1,
+                    block_count: 2,
+                    gas: Gas::from(5_u64),
+                },
+                TraceEntry::WasmGas {
+                    depth: 2,
+                    block_count: 1,
+                    gas: Gas::from(5_u64),
+                },
+                TraceEntry::WasmGas {
+                    depth: 1,
+                    block_count: 1,
+                    gas: Gas::from(7_u64),
+                },
+            ]
+        );
+        assert_eq!(trace.wasm_gas(), Gas::from(17_u64));
+    }
+
+    #[test]
+    fn should_count_entries_beyond_the_limit() {
+        let trace = Rc::new(RefCell::new(ExecutionTrace::default()));
+        let mut tracer = Tracer::new(Rc::clone(&trace));
+        for depth in 0..MAX_TRACE_ENTRIES {
+            tracer.push_wasm_gas(depth % 2, Gas::from(1_u64));
+        }
+        tracer.push_wasm_gas(0, Gas::from(1_u64));
+        let outer_entry = tracer.start_host_function(1, "casper_read_value", 32);
+        tracer.add_host_function_cost(Gas::from(1_u64));
+        tracer.finish_host_function(Gas::from(1_u64), outer_entry);
+        // Still merged into the last recorded entry.
+        tracer.push_wasm_gas(1, Gas::from(1_u64));
+        let trace = trace.borrow();
+
+        assert_eq!(trace.entries().len(), MAX_TRACE_ENTRIES);
+        assert_eq!(trace.omitted_entries(), 2);
+        assert_eq!(
+            trace.entries().last(),
+            Some(&TraceEntry::WasmGas {
+                depth: 1,
+                block_count: 2,
+                gas: Gas::from(2_u64),
+            })
+        );
+    }
+}
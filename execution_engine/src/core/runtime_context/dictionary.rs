@@ -35,6 +35,11 @@ impl DictionaryValue {
     pub fn into_cl_value(self) -> CLValue {
         self.cl_value
     }
+
+    /// Get a reference to the address of the dictionary's seed [`casper_types::URef`].
+    pub fn seed_uref_addr(&self) -> &[u8] {
+        self.seed_uref_addr.as_slice()
+    }
 }
 
 impl CLTyped for DictionaryValue {
@@ -602,6 +602,17 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
     ) -> Result<Vec<Key>, Self::Error> {
         self.reader.keys_with_prefix(correlation_id, prefix)
     }
+
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.reader
+            .keys_with_prefix_after(correlation_id, prefix, after, limit)
+    }
 }
 
 /// Error conditions of a proof validation.
@@ -8,10 +8,11 @@ use casper_types::{
     account::{
         Account, AccountHash, ActionThresholds, AssociatedKeys, Weight, ACCOUNT_HASH_LENGTH,
     },
+    bytesrepr::{self, Bytes, ToBytes},
     contracts::NamedKeys,
     gens::*,
     AccessRights, CLValue, Contract, EntryPoints, HashAddr, Key, KeyTag, ProtocolVersion,
-    StoredValue, URef, U256, U512,
+    StateProof, StateProofError, StoredValue, URef, U256, U512,
 };
 
 use super::{
@@ -78,6 +79,16 @@ impl StateReader<Key, StoredValue> for CountingDb {
     ) -> Result<Vec<Key>, Self::Error> {
         Ok(Vec::new())
     }
+
+    fn keys_with_prefix_after(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+        _after: Option<&[u8]>,
+        _limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -829,6 +840,91 @@ fn validate_query_proof_should_work() {
     );
 }
 
+#[test]
+fn standalone_state_proof_verification_should_agree_with_engine() {
+    let contract_key = Key::Hash([1; 32]);
+    let large_value_name = "large value".to_string();
+    let contract_value = StoredValue::Contract(Contract::new(
+        [2; 32].into(),
+        [3; 32].into(),
+        iter::once((large_value_name.clone(), Key::Hash([4; 32]))).collect(),
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    // Large enough for its trie leaf to be hashed in chunks.
+    let large_value = StoredValue::CLValue(
+        CLValue::from_t(Bytes::from(vec![7_u8; 9 * 1024 * 1024])).expect("should convert"),
+    );
+    let uref_key = Key::URef(URef::new([5; 32], AccessRights::READ_ADD_WRITE));
+    let uref_value = StoredValue::CLValue(CLValue::from_t(1_u64).expect("should convert"));
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (contract_key, contract_value),
+            (Key::Hash([4; 32]), large_value.clone()),
+            (uref_key, uref_value.clone()),
+        ],
+    )
+    .unwrap();
+    let view = global_state
+        .checkout(root_hash)
+        .expect("should checkout")
+        .expect("should have view");
+    let tracking_copy = TrackingCopy::new(view);
+
+    let query_proof_bytes = |base_key: Key, path: &[String]| {
+        let result = tracking_copy
+            .query(correlation_id, &EngineConfig::default(), base_key, path)
+            .expect("should query");
+        match result {
+            TrackingCopyQueryResult::Success { proofs, .. } => {
+                proofs.to_bytes().expect("should serialize")
+            }
+            _ => panic!("query was not successful: {:?}", result),
+        }
+    };
+
+    let path = &[large_value_name];
+    let proof_bytes = query_proof_bytes(contract_key, path);
+    let proofs: Vec<StateProof> =
+        bytesrepr::deserialize_from_slice(&proof_bytes).expect("should parse proofs");
+    assert_eq!(proofs.to_bytes().expect("should serialize"), proof_bytes);
+    for proof in &proofs {
+        assert_eq!(
+            proof.compute_state_root_hash().expect("should hash"),
+            root_hash.value()
+        );
+    }
+    casper_types::verify_state_proofs(
+        &root_hash.value(),
+        &proofs,
+        &contract_key,
+        path,
+        &large_value,
+    )
+    .expect("should verify");
+    assert_eq!(
+        casper_types::verify_state_proofs(
+            &root_hash.value(),
+            &proofs,
+            &contract_key,
+            &["missing".to_string()],
+            &large_value,
+        ),
+        Err(StateProofError::PathCold)
+    );
+
+    // Keys are normalized before comparison, as in `validate_query_proof`.
+    let proof_bytes = query_proof_bytes(uref_key, &[]);
+    let proofs: Vec<StateProof> =
+        bytesrepr::deserialize_from_slice(proof_bytes).expect("should parse proofs");
+    casper_types::verify_state_proofs(&root_hash.value(), &proofs, &uref_key, &[], &uref_value)
+        .expect("should verify");
+}
+
 #[test]
 fn get_keys_should_return_keys_in_the_account_keyspace() {
     // account 1
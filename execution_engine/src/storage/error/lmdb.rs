@@ -3,6 +3,7 @@ use std::sync;
 use lmdb as lmdb_external;
 use thiserror::Error;
 
+use casper_hashing::Digest;
 use casper_types::bytesrepr;
 
 use crate::storage::{error::in_memory, global_state::CommitError};
@@ -26,6 +27,19 @@ pub enum Error {
     /// Error committing to execution engine.
     #[error(transparent)]
     CommitError(#[from] CommitError),
+
+    /// A garbage collection of the trie store was requested while one was already running.
+    #[error("Garbage collection of the trie store is already in progress")]
+    GarbageCollectionInProgress,
+
+    /// A garbage collection of the trie store was abandoned as more tries than the given maximum
+    /// were reachable from the retained state roots.
+    #[error("Garbage collection abandoned: more than {0} tries are reachable")]
+    TooManyReachableTries(usize),
+
+    /// A trie node reachable from a state root hash is missing from the trie store.
+    #[error("Missing trie node: {0}")]
+    MissingTrieNode(Digest),
 }
 
 impl wasmi::HostError for Error {}
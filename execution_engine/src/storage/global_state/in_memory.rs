@@ -7,7 +7,10 @@ use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         error::{self, in_memory},
-        global_state::{commit, CommitProvider, StateProvider, StateReader},
+        global_state::{
+            commit, put_tries_if_all_children_present, CommitProvider, PutTriesResult,
+            StateProvider, StateReader,
+        },
         store::Store,
         transaction_source::{
             in_memory::{
@@ -21,8 +24,9 @@ use crate::{
         trie_store::{
             in_memory::InMemoryTrieStore,
             operations::{
-                self, delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult, WriteResult,
+                self, delete, keys_with_prefix, keys_with_prefix_after, missing_children, put_trie,
+                read, read_with_proof, DeleteResult, KeysWithPrefixAfterResult, ReadResult,
+                WriteResult,
             },
         },
     },
@@ -197,6 +201,31 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix_after::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+            after,
+            limit,
+        )?;
+        txn.commit()?;
+        match keys {
+            KeysWithPrefixAfterResult::Found(keys) => Ok(keys),
+            KeysWithPrefixAfterResult::MissingTrieNode(trie_key) => {
+                Err(error::Error::MissingTrieNode(trie_key))
+            }
+        }
+    }
 }
 
 impl CommitProvider for InMemoryGlobalState {
@@ -265,6 +294,19 @@ impl StateProvider for InMemoryGlobalState {
         Ok(trie_hash)
     }
 
+    fn put_tries_if_all_children_present(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[&[u8]],
+    ) -> Result<PutTriesResult, Self::Error> {
+        put_tries_if_all_children_present::<_, _, Self::Error>(
+            &*self.environment,
+            &*self.trie_store,
+            correlation_id,
+            tries,
+        )
+    }
+
     /// Finds all of the keys of missing directly descendant `Trie<Key,StoredValue>` values.
     fn missing_children(
         &self,
@@ -8,7 +8,8 @@ use crate::{
     storage::{
         error,
         global_state::{
-            commit, put_stored_values, scratch::ScratchGlobalState, CommitProvider, StateProvider,
+            commit, put_stored_values, put_tries_if_all_children_present,
+            scratch::ScratchGlobalState, CommitProvider, PutTriesResult, StateProvider,
             StateReader,
         },
         store::Store,
@@ -17,10 +18,10 @@ use crate::{
             merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
         },
         trie_store::{
-            lmdb::{LmdbTrieStore, ScratchTrieStore},
+            lmdb::{GarbageCollectionResult, LmdbTrieStore, ScratchTrieStore},
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, keys_with_prefix, keys_with_prefix_after, missing_children, put_trie, read,
+                read_with_proof, DeleteResult, KeysWithPrefixAfterResult, ReadResult,
             },
         },
     },
@@ -127,6 +128,32 @@ impl LmdbGlobalState {
     pub fn empty_state_root_hash(&self) -> Digest {
         self.empty_root_hash
     }
+
+    /// Deletes every trie which isn't reachable from the empty root or from the state roots
+    /// returned by `retained_roots`.
+    ///
+    /// See [`LmdbTrieStore::collect_garbage`] for details.
+    pub fn collect_garbage<F>(
+        &self,
+        retained_roots: F,
+        batch_size: usize,
+        max_reachable_tries: usize,
+    ) -> Result<GarbageCollectionResult, error::Error>
+    where
+        F: FnOnce() -> Vec<Digest>,
+    {
+        let empty_root_hash = self.empty_root_hash;
+        self.trie_store.collect_garbage(
+            &self.environment,
+            || {
+                let mut roots = retained_roots();
+                roots.push(empty_root_hash);
+                roots
+            },
+            batch_size,
+            max_reachable_tries,
+        )
+    }
 }
 
 fn compute_empty_root_hash() -> Result<(Digest, Trie<Key, StoredValue>), error::Error> {
@@ -208,6 +235,31 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix_after::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+            after,
+            limit,
+        )?;
+        txn.commit()?;
+        match keys {
+            KeysWithPrefixAfterResult::Found(keys) => Ok(keys),
+            KeysWithPrefixAfterResult::MissingTrieNode(trie_key) => {
+                Err(error::Error::MissingTrieNode(trie_key))
+            }
+        }
+    }
 }
 
 impl CommitProvider for LmdbGlobalState {
@@ -275,6 +327,19 @@ impl StateProvider for LmdbGlobalState {
         Ok(trie_hash)
     }
 
+    fn put_tries_if_all_children_present(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[&[u8]],
+    ) -> Result<PutTriesResult, Self::Error> {
+        put_tries_if_all_children_present::<_, _, Self::Error>(
+            &*self.environment,
+            &*self.trie_store,
+            correlation_id,
+            tries,
+        )
+    }
+
     /// Finds all of the keys of missing directly descendant `Trie<K,V>` values.
     fn missing_children(
         &self,
@@ -505,4 +570,144 @@ mod tests {
                 .unwrap()
         );
     }
+    #[test]
+    fn collect_garbage_deletes_tries_unreachable_from_retained_roots() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state(create_test_pairs);
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let result = state
+            .collect_garbage(|| vec![updated_hash], 1, usize::MAX)
+            .unwrap();
+        assert!(result.deleted_tries > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        // The empty root is always retained, and a second run has nothing left to delete.
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+        let second_result = state
+            .collect_garbage(|| vec![updated_hash], 1, usize::MAX)
+            .unwrap();
+        assert_eq!(second_result.deleted_tries, 0);
+        assert_eq!(second_result.reachable_tries, result.reachable_tries);
+    }
+
+    #[test]
+    fn collect_garbage_fails_if_already_in_progress() {
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        state
+            .collect_garbage(
+                || {
+                    let nested_result = state.collect_garbage(Vec::new, 1, usize::MAX);
+                    assert!(matches!(
+                        nested_result,
+                        Err(error::Error::GarbageCollectionInProgress)
+                    ));
+                    vec![root_hash]
+                },
+                1,
+                usize::MAX,
+            )
+            .unwrap();
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn collect_garbage_keeps_tries_written_while_running() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &create_test_pairs_updated() {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+        let original_root = state
+            .get_trie_full(correlation_id, &root_hash)
+            .unwrap()
+            .unwrap();
+        let new_pair = TestPair {
+            key: Key::Account(AccountHash::new([4_u8; 32])),
+            value: StoredValue::CLValue(CLValue::from_t(4_i32).unwrap()),
+        };
+
+        let mut new_hash = None;
+        let result = state
+            .collect_garbage(
+                || {
+                    // Once the collection is tracking writes, rewrite a trie which is otherwise
+                    // garbage via `put_raw`, and write new tries via
+                    // `ScratchTrieStore::write_root_to_db`.
+                    state
+                        .put_trie(correlation_id, original_root.inner())
+                        .unwrap();
+                    new_hash = Some(
+                        state
+                            .put_stored_values(
+                                correlation_id,
+                                updated_hash,
+                                HashMap::from([(new_pair.key, new_pair.value.clone())]),
+                            )
+                            .unwrap(),
+                    );
+                    vec![updated_hash]
+                },
+                1,
+                usize::MAX,
+            )
+            .unwrap();
+        assert!(result.deleted_tries > 0);
+
+        // Neither of the newly written tries were swept, though not reachable from `updated_hash`.
+        assert!(state
+            .get_trie_full(correlation_id, &root_hash)
+            .unwrap()
+            .is_some());
+        let new_checkout = state.checkout(new_hash.unwrap()).unwrap().unwrap();
+        assert_eq!(
+            new_checkout.read(correlation_id, &new_pair.key).unwrap(),
+            Some(new_pair.value)
+        );
+    }
+
+    #[test]
+    fn collect_garbage_abandoned_if_too_many_reachable_tries() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &create_test_pairs_updated() {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let result = state.collect_garbage(|| vec![updated_hash], 1, 1);
+        assert!(matches!(
+            result,
+            Err(error::Error::TooManyReachableTries(1))
+        ));
+        // Nothing was deleted.
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
 }
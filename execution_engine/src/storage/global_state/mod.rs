@@ -6,6 +6,9 @@ pub mod in_memory;
 /// Lmdb implementation of global state.
 pub mod lmdb;
 
+/// Global state wrapper recording the keys read through it.
+pub mod recording;
+
 /// Lmdb implementation of global state with cache.
 pub mod scratch;
 
@@ -26,7 +29,7 @@ use crate::{
         transaction_source::{Transaction, TransactionSource},
         trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
         trie_store::{
-            operations::{read, write, ReadResult, WriteResult},
+            operations::{missing_children, put_trie, read, write, ReadResult, WriteResult},
             TrieStore,
         },
     },
@@ -55,6 +58,16 @@ pub trait StateReader<K, V> {
         correlation_id: CorrelationId,
         prefix: &[u8],
     ) -> Result<Vec<K>, Self::Error>;
+
+    /// Returns at most `limit` of the keys in the trie matching `prefix` whose serialized bytes
+    /// sort strictly after `after`, in ascending order of their serialized bytes.
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<K>, Self::Error>;
 }
 
 /// An error emitted by the execution engine on commit
@@ -80,6 +93,15 @@ pub enum CommitError {
     TrieNotFoundInCache(Digest),
 }
 
+/// The result of `StateProvider::put_tries_if_all_children_present`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PutTriesResult {
+    /// All the tries were inserted.  Holds their hashes, in the order given.
+    Inserted(Vec<Digest>),
+    /// None of the tries were inserted, as one had these children missing.
+    MissingChildren(Vec<Digest>),
+}
+
 /// Provides `commit` method.
 pub trait CommitProvider: StateProvider {
     /// Applies changes and returns a new post state hash.
@@ -116,6 +138,14 @@ pub trait StateProvider {
     /// Insert a trie node into the trie
     fn put_trie(&self, correlation_id: CorrelationId, trie: &[u8]) -> Result<Digest, Self::Error>;
 
+    /// Inserts the given trie nodes in a single transaction if each has all of its children
+    /// present in the state or earlier in `tries`.  Otherwise none are inserted.
+    fn put_tries_if_all_children_present(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[&[u8]],
+    ) -> Result<PutTriesResult, Self::Error>;
+
     /// Finds all the children of `trie_raw` which aren't present in the state.
     fn missing_children(
         &self,
@@ -170,6 +200,37 @@ where
     Ok(state_root)
 }
 
+/// Put multiple tries to the store in a single rw transaction, if each has all of its children
+/// present in the store or earlier in `tries`.
+pub fn put_tries_if_all_children_present<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    tries: &[&[u8]],
+) -> Result<PutTriesResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let mut trie_hashes = Vec::with_capacity(tries.len());
+    for trie_bytes in tries {
+        let missing_children =
+            missing_children::<Key, StoredValue, _, _, E>(correlation_id, &txn, store, trie_bytes)?;
+        if !missing_children.is_empty() {
+            // Dropping the transaction without committing it discards the tries put so far.
+            return Ok(PutTriesResult::MissingChildren(missing_children));
+        }
+        let trie_hash =
+            put_trie::<Key, StoredValue, _, _, E>(correlation_id, &mut txn, store, trie_bytes)?;
+        trie_hashes.push(trie_hash);
+    }
+    txn.commit()?;
+    Ok(PutTriesResult::Inserted(trie_hashes))
+}
+
 /// Commit `effects` to the store.
 pub fn commit<'a, R, S, H, E>(
     environment: &'a R,
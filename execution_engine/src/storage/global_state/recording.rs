@@ -0,0 +1,249 @@
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::ToBytes, Key, StoredValue};
+
+use crate::{
+    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    storage::{
+        global_state::{CommitProvider, PutTriesResult, StateProvider, StateReader},
+        trie::{merkle_proof::TrieMerkleProof, TrieRaw},
+        trie_store::operations::DeleteResult,
+    },
+};
+
+/// The global state keys read, and key prefixes listed, via a [`RecordingGlobalState`].
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct ReadSet {
+    keys: BTreeSet<Key>,
+    prefixes: BTreeSet<Vec<u8>>,
+}
+
+impl ReadSet {
+    /// Returns the keys which were read.
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.keys.iter()
+    }
+
+    /// Returns the key prefixes which were listed.
+    pub fn prefixes(&self) -> impl Iterator<Item = &[u8]> {
+        self.prefixes.iter().map(Vec::as_slice)
+    }
+
+    /// Returns `true` if writing any of `written_keys` could change what was read, i.e. if any of
+    /// them was read or matches a listed prefix.
+    pub fn is_affected_by<'a>(&self, written_keys: impl IntoIterator<Item = &'a Key>) -> bool {
+        written_keys.into_iter().any(|key| {
+            self.keys.contains(&key.normalize()) || self.is_listing_affected_by(Some(key))
+        })
+    }
+
+    /// Returns `true` if writing any of `written_keys` could change the keys listed, i.e. if any of
+    /// them matches a listed prefix.
+    pub fn is_listing_affected_by<'a>(
+        &self,
+        written_keys: impl IntoIterator<Item = &'a Key>,
+    ) -> bool {
+        if self.prefixes.is_empty() {
+            return false;
+        }
+        written_keys
+            .into_iter()
+            .any(|key| match key.normalize().to_bytes() {
+                Ok(key_bytes) => self
+                    .prefixes
+                    .iter()
+                    .any(|prefix| key_bytes.starts_with(prefix)),
+                // Err on the side of caution if the key can't be compared.
+                Err(_) => true,
+            })
+    }
+}
+
+/// Global state which delegates to an inner state while recording the keys read through it.
+///
+/// Writes are not recorded, as they are already captured in the resulting execution journal.
+pub struct RecordingGlobalState<'a, S> {
+    state: &'a S,
+    read_set: Rc<RefCell<ReadSet>>,
+}
+
+impl<'a, S> RecordingGlobalState<'a, S> {
+    /// Creates a new recording state wrapping `state`, with an empty read set.
+    pub fn new(state: &'a S) -> Self {
+        RecordingGlobalState {
+            state,
+            read_set: Rc::new(RefCell::new(ReadSet::default())),
+        }
+    }
+
+    /// Returns the keys read via this state or any reader checked out from it so far.
+    pub fn read_set(&self) -> ReadSet {
+        self.read_set.borrow().clone()
+    }
+}
+
+/// Represents a "view" of a [`RecordingGlobalState`] at a particular root hash.
+pub struct RecordingStateReader<R> {
+    reader: R,
+    read_set: Rc<RefCell<ReadSet>>,
+}
+
+impl<R> StateReader<Key, StoredValue> for RecordingStateReader<R>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        self.read_set.borrow_mut().keys.insert(key.normalize());
+        self.reader.read(correlation_id, key)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.read_set.borrow_mut().keys.insert(key.normalize());
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.read_set.borrow_mut().prefixes.insert(prefix.to_vec());
+        self.reader.keys_with_prefix(correlation_id, prefix)
+    }
+
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.read_set.borrow_mut().prefixes.insert(prefix.to_vec());
+        self.reader
+            .keys_with_prefix_after(correlation_id, prefix, after, limit)
+    }
+}
+
+impl<'a, S> StateProvider for RecordingGlobalState<'a, S>
+where
+    S: StateProvider,
+{
+    type Error = S::Error;
+
+    type Reader = RecordingStateReader<S::Reader>;
+
+    fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error> {
+        let maybe_reader = self.state.checkout(state_hash)?;
+        Ok(maybe_reader.map(|reader| RecordingStateReader {
+            reader,
+            read_set: Rc::clone(&self.read_set),
+        }))
+    }
+
+    fn empty_root(&self) -> Digest {
+        self.state.empty_root()
+    }
+
+    fn get_trie_full(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Digest,
+    ) -> Result<Option<TrieRaw>, Self::Error> {
+        self.state.get_trie_full(correlation_id, trie_key)
+    }
+
+    fn put_trie(&self, correlation_id: CorrelationId, trie: &[u8]) -> Result<Digest, Self::Error> {
+        self.state.put_trie(correlation_id, trie)
+    }
+
+    fn put_tries_if_all_children_present(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[&[u8]],
+    ) -> Result<PutTriesResult, Self::Error> {
+        self.state
+            .put_tries_if_all_children_present(correlation_id, tries)
+    }
+
+    fn missing_children(
+        &self,
+        correlation_id: CorrelationId,
+        trie_raw: &[u8],
+    ) -> Result<Vec<Digest>, Self::Error> {
+        self.state.missing_children(correlation_id, trie_raw)
+    }
+
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys_to_delete: &[Key],
+    ) -> Result<DeleteResult, Self::Error> {
+        self.state.delete_keys(correlation_id, root, keys_to_delete)
+    }
+}
+
+impl<'a, S> CommitProvider for RecordingGlobalState<'a, S>
+where
+    S: CommitProvider,
+{
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<Digest, Self::Error> {
+        self.state.commit(correlation_id, state_hash, effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, CLValue, KeyTag};
+
+    use super::*;
+    use crate::storage::global_state::in_memory::InMemoryGlobalState;
+
+    #[test]
+    fn should_record_reads() {
+        let read_key = Key::Account(AccountHash::new([1; 32]));
+        let unread_key = Key::Account(AccountHash::new([2; 32]));
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            CorrelationId::new(),
+            &[(read_key, StoredValue::CLValue(CLValue::unit()))],
+        )
+        .unwrap();
+
+        let recording_state = RecordingGlobalState::new(&state);
+        let reader = recording_state.checkout(root_hash).unwrap().unwrap();
+        assert!(reader
+            .read(CorrelationId::new(), &read_key)
+            .unwrap()
+            .is_some());
+        let read_set = recording_state.read_set();
+        assert_eq!(read_set.keys().collect::<Vec<_>>(), vec![&read_key]);
+        assert!(read_set.is_affected_by(&[read_key]));
+        assert!(!read_set.is_affected_by(&[unread_key]));
+
+        // Listing a prefix is affected by writes to any key with that prefix.
+        let _ = reader
+            .keys_with_prefix(CorrelationId::new(), &[KeyTag::Account as u8])
+            .unwrap();
+        let read_set = recording_state.read_set();
+        assert!(read_set.is_affected_by(&[unread_key]));
+        assert!(read_set.is_listing_affected_by(&[unread_key]));
+        assert!(!read_set.is_affected_by(&[Key::Hash([2; 32])]));
+        assert!(!read_set.is_listing_affected_by(&[Key::Hash([2; 32])]));
+    }
+}
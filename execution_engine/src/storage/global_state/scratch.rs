@@ -14,15 +14,18 @@ use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         error,
-        global_state::{CommitError, CommitProvider, StateProvider, StateReader},
+        global_state::{
+            put_tries_if_all_children_present, CommitError, CommitProvider, PutTriesResult,
+            StateProvider, StateReader,
+        },
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
         trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
         trie_store::{
             lmdb::LmdbTrieStore,
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, keys_with_prefix, keys_with_prefix_after, missing_children, put_trie, read,
+                read_with_proof, DeleteResult, KeysWithPrefixAfterResult, ReadResult,
             },
         },
     },
@@ -190,6 +193,31 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys = keys_with_prefix_after::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &self.root_hash,
+            prefix,
+            after,
+            limit,
+        )?;
+        txn.commit()?;
+        match keys {
+            KeysWithPrefixAfterResult::Found(keys) => Ok(keys),
+            KeysWithPrefixAfterResult::MissingTrieNode(trie_key) => {
+                Err(error::Error::MissingTrieNode(trie_key))
+            }
+        }
+    }
 }
 
 impl CommitProvider for ScratchGlobalState {
@@ -310,6 +338,19 @@ impl StateProvider for ScratchGlobalState {
         Ok(trie_hash)
     }
 
+    fn put_tries_if_all_children_present(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[&[u8]],
+    ) -> Result<PutTriesResult, Self::Error> {
+        put_tries_if_all_children_present::<_, _, Self::Error>(
+            &*self.environment,
+            &*self.trie_store,
+            correlation_id,
+            tries,
+        )
+    }
+
     /// Finds all of the keys of missing directly descendant `Trie<K,V>` values
     fn missing_children(
         &self,
@@ -105,7 +105,8 @@
 //! ```
 use std::{
     borrow::Cow,
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
+    convert::TryFrom,
     sync::{Arc, Mutex},
 };
 
@@ -113,7 +114,7 @@ use casper_types::{
     bytesrepr::{self, Bytes, ToBytes},
     Key, StoredValue,
 };
-use lmdb::{Database, DatabaseFlags, Transaction};
+use lmdb::{Cursor, Database, DatabaseFlags, Transaction};
 
 use casper_hashing::Digest;
 
@@ -132,6 +133,31 @@ use crate::storage::{
 #[derive(Debug, Clone)]
 pub struct LmdbTrieStore {
     db: Database,
+    /// The hashes of the tries written while a garbage collection is in progress, shared by all
+    /// clones of the store.
+    written_during_gc: Arc<Mutex<Option<HashSet<Digest>>>>,
+}
+
+/// The outcome of a garbage collection run over an [`LmdbTrieStore`].
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct GarbageCollectionResult {
+    /// The number of tries found to be reachable from the retained state roots.
+    pub reachable_tries: u64,
+    /// The number of tries deleted.
+    pub deleted_tries: u64,
+}
+
+/// Stops tracking the tries written to the store when dropped.
+struct WriteTracking<'a> {
+    written_during_gc: &'a Mutex<Option<HashSet<Digest>>>,
+}
+
+impl<'a> Drop for WriteTracking<'a> {
+    fn drop(&mut self) {
+        if let Ok(mut written) = self.written_during_gc.lock() {
+            *written = None;
+        }
+    }
 }
 
 impl LmdbTrieStore {
@@ -143,14 +169,20 @@ impl LmdbTrieStore {
     ) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().create_db(Some(&name), flags)?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore {
+            db,
+            written_during_gc: Default::default(),
+        })
     }
 
     /// Constructor for `LmdbTrieStore` which opens an existing lmdb store file.
     pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().open_db(Some(&name))?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore {
+            db,
+            written_during_gc: Default::default(),
+        })
     }
 
     fn name(maybe_name: Option<&str>) -> String {
@@ -163,6 +195,159 @@ impl LmdbTrieStore {
     pub fn get_db(&self) -> Database {
         self.db
     }
+
+    /// Deletes every trie which isn't reachable from the state roots returned by
+    /// `retained_roots`.
+    ///
+    /// The reachable tries are marked under read transactions of at most `batch_size` reads each,
+    /// so that no single transaction pins old pages of the database for the whole walk.  The
+    /// remaining tries are then deleted in write transactions of at most `batch_size` deletions
+    /// each, so other writers are only ever blocked for the duration of one batch.
+    ///
+    /// The hashes of all reachable tries are held in memory until the collection completes, which
+    /// takes roughly 40 to 80 bytes per reachable trie.  If more than `max_reachable_tries` are
+    /// found, the collection is abandoned before deleting anything.
+    ///
+    /// `retained_roots` is called once tries written to this store are being tracked.  Any trie
+    /// written via this store or one of its clones from then on is kept, even if it isn't
+    /// reachable from the returned roots, so these need only include the roots which may be
+    /// built upon while the collection is running.  Retained roots which are missing from the
+    /// store are ignored.
+    pub fn collect_garbage<F>(
+        &self,
+        env: &LmdbEnvironment,
+        retained_roots: F,
+        batch_size: usize,
+        max_reachable_tries: usize,
+    ) -> Result<GarbageCollectionResult, error::Error>
+    where
+        F: FnOnce() -> Vec<Digest>,
+    {
+        let _tracking = self.track_writes()?;
+        let batch_size = batch_size.max(1);
+
+        // Mark every trie reachable from the retained roots.  As tries are never modified, and
+        // only deleted by garbage collection, the read transaction can be renewed during the walk.
+        let mut reachable = HashSet::new();
+        {
+            let mut tries_to_visit = retained_roots();
+            let mut txn = env.create_read_txn()?;
+            let mut reads_in_txn = 0;
+            while let Some(trie_key) = tries_to_visit.pop() {
+                if reachable.contains(&trie_key) {
+                    continue;
+                }
+                if reachable.len() >= max_reachable_tries {
+                    return Err(error::Error::TooManyReachableTries(max_reachable_tries));
+                }
+                if reads_in_txn >= batch_size {
+                    txn.commit()?;
+                    txn = env.create_read_txn()?;
+                    reads_in_txn = 0;
+                }
+                reads_in_txn += 1;
+                let trie_bytes = match txn.read(self.db, trie_key.as_ref())? {
+                    Some(trie_bytes) => trie_bytes,
+                    None => continue,
+                };
+                let lazy_trie: LazyTrieLeaf<Key, StoredValue> =
+                    trie::lazy_trie_deserialize(trie_bytes)?;
+                tries_to_visit.extend(trie::lazy_trie_iter_children(&lazy_trie));
+                reachable.insert(trie_key);
+            }
+            txn.commit()?;
+        }
+
+        // Sweep every trie which wasn't marked.  The read transaction used to find them is closed
+        // before each batch is deleted, as a thread may only hold one transaction at a time.
+        let mut deleted_tries = 0;
+        let mut last_key: Option<Digest> = None;
+        loop {
+            let mut batch = Vec::with_capacity(batch_size);
+            let mut exhausted = true;
+            {
+                let txn = env.create_read_txn()?;
+                {
+                    let mut cursor = txn.open_ro_cursor(self.db)?;
+                    let rows = match &last_key {
+                        Some(last_key) => cursor.iter_from(last_key),
+                        // A new cursor iterates from the start, whereas `iter_start` panics if the
+                        // database is empty.
+                        None => cursor.iter(),
+                    };
+                    for row in rows {
+                        let (raw_key, _) = row?;
+                        let trie_key =
+                            Digest::try_from(raw_key).map_err(|_| bytesrepr::Error::Formatting)?;
+                        if Some(trie_key) == last_key || reachable.contains(&trie_key) {
+                            continue;
+                        }
+                        batch.push(trie_key);
+                        if batch.len() >= batch_size {
+                            exhausted = false;
+                            break;
+                        }
+                    }
+                }
+                txn.commit()?;
+            }
+            last_key = batch.last().copied().or(last_key);
+            deleted_tries += self.delete_unless_written(env, &mut batch)?;
+            if exhausted {
+                break;
+            }
+        }
+
+        Ok(GarbageCollectionResult {
+            reachable_tries: reachable.len() as u64,
+            deleted_tries,
+        })
+    }
+
+    /// Starts tracking the tries written to the store until the returned value is dropped.
+    fn track_writes(&self) -> Result<WriteTracking<'_>, error::Error> {
+        let mut written = self.written_during_gc.lock()?;
+        if written.is_some() {
+            return Err(error::Error::GarbageCollectionInProgress);
+        }
+        *written = Some(HashSet::new());
+        Ok(WriteTracking {
+            written_during_gc: &self.written_during_gc,
+        })
+    }
+
+    /// Deletes the given tries in a single write transaction, skipping any which have been written
+    /// since garbage collection started.  Returns the number of tries deleted.
+    fn delete_unless_written(
+        &self,
+        env: &LmdbEnvironment,
+        batch: &mut Vec<Digest>,
+    ) -> Result<u64, error::Error> {
+        if batch.is_empty() {
+            return Ok(0);
+        }
+        // Holding the write transaction guarantees no other write is in progress while the set of
+        // written tries is checked, so the transaction must be created before taking the lock.
+        let mut txn = env.create_read_write_txn()?;
+        let written = self.written_during_gc.lock()?;
+        let mut deleted_tries = 0;
+        for trie_key in batch.drain(..) {
+            if written
+                .as_ref()
+                .map_or(false, |written| written.contains(&trie_key))
+            {
+                continue;
+            }
+            match txn.del(self.db, &trie_key, None) {
+                Ok(()) => deleted_tries += 1,
+                Err(lmdb::Error::NotFound) => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+        drop(written);
+        txn.commit()?;
+        Ok(deleted_tries)
+    }
 }
 
 impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
@@ -173,6 +358,24 @@ impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
     fn handle(&self) -> Self::Handle {
         self.db
     }
+
+    fn put_raw<T>(
+        &self,
+        txn: &mut T,
+        key: &Digest,
+        value_bytes: Cow<'_, [u8]>,
+    ) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Digest: AsRef<[u8]>,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(written) = self.written_during_gc.lock()?.as_mut() {
+            written.insert(*key);
+        }
+        txn.write(self.db, key.as_ref(), &value_bytes)
+            .map_err(Into::into)
+    }
 }
 
 impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
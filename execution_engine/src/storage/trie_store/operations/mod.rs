@@ -1199,6 +1199,93 @@ where
     }
 }
 
+/// The result of [`keys_with_prefix_after`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeysWithPrefixAfterResult<K> {
+    /// The keys found, in ascending order of their serialized bytes.
+    Found(Vec<K>),
+    /// A trie node which had to be read to find the keys is missing from the store.
+    MissingTrieNode(Digest),
+}
+
+/// Returns at most `limit` of the keys in the subtrie matching `prefix` whose serialized bytes
+/// sort strictly after `after`, in ascending order of their serialized bytes.
+///
+/// Only the subtries which may contain such keys are read, so the cost is proportional to the
+/// depth of the trie and the number of keys returned, rather than to the number of keys skipped.
+pub fn keys_with_prefix_after<K, V, T, S>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Digest,
+    prefix: &[u8],
+    after: Option<&[u8]>,
+    limit: usize,
+) -> Result<KeysWithPrefixAfterResult<K>, S::Error>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error> + From<bytesrepr::Error>,
+{
+    // Whether the subtrie under `path` may contain keys matching the prefix and sorting after the
+    // lower bound.
+    let may_contain_keys = |path: &[u8]| {
+        let prefix_len = cmp::min(path.len(), prefix.len());
+        if path[..prefix_len] != prefix[..prefix_len] {
+            return false;
+        }
+        match after {
+            Some(after) => {
+                let after_len = cmp::min(path.len(), after.len());
+                path[..after_len] >= after[..after_len]
+            }
+            None => true,
+        }
+    };
+
+    let mut keys = Vec::new();
+    // The subtries still to visit, with the path leading to each.  Children are pushed in
+    // descending order so that they are popped in ascending order.
+    let mut tries_to_visit = vec![(*root, vec![])];
+    while let Some((trie_hash, path)) = tries_to_visit.pop() {
+        if keys.len() >= limit {
+            break;
+        }
+        match store.get(txn, &trie_hash)? {
+            None => return Ok(KeysWithPrefixAfterResult::MissingTrieNode(trie_hash)),
+            Some(Trie::Leaf { key, .. }) => {
+                let key_bytes = key.to_bytes()?;
+                if key_bytes.starts_with(prefix)
+                    && after.map_or(true, |after| key_bytes.as_slice() > after)
+                {
+                    keys.push(key);
+                }
+            }
+            Some(Trie::Node { pointer_block }) => {
+                for index in (0..RADIX).rev() {
+                    if let Some(pointer) = &pointer_block[index] {
+                        let mut child_path = path.clone();
+                        child_path.push(index as u8);
+                        if may_contain_keys(&child_path) {
+                            tries_to_visit.push((*pointer.hash(), child_path));
+                        }
+                    }
+                }
+            }
+            Some(Trie::Extension { affix, pointer }) => {
+                let mut child_path = path;
+                child_path.extend_from_slice(&affix);
+                if may_contain_keys(&child_path) {
+                    tries_to_visit.push((*pointer.hash(), child_path));
+                }
+            }
+        }
+    }
+    Ok(KeysWithPrefixAfterResult::Found(keys))
+}
+
 /// Returns the iterator over the keys at a given root hash.
 ///
 /// The root should be the apex of the trie.
@@ -314,3 +314,97 @@ mod keys_with_prefix_iterator {
         test_prefix(&[0, 0, 0, 0, 0, 0, 1]); // 1 leaf
     }
 }
+
+mod keys_with_prefix_after {
+    use casper_types::bytesrepr::ToBytes;
+
+    use crate::{
+        shared::newtypes::CorrelationId,
+        storage::{
+            transaction_source::{Transaction, TransactionSource},
+            trie::Trie,
+            trie_store::operations::{
+                self,
+                tests::{LmdbTestContext, TestKey, TestValue, TEST_LEAVES, TEST_TRIE_GENERATORS},
+                KeysWithPrefixAfterResult,
+            },
+        },
+    };
+
+    #[test]
+    fn lmdb_keys_with_prefix_after_cursor_had_expected_results() {
+        let correlation_id = CorrelationId::new();
+        let generator = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1];
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let all_keys = {
+            let mut tmp = TEST_LEAVES
+                .iter()
+                .filter_map(Trie::key)
+                .cloned()
+                .collect::<Vec<TestKey>>();
+            tmp.sort();
+            tmp
+        };
+
+        let prefixes: [&[u8]; 3] = [&[], &[0, 0], &[0, 1]];
+        let cursors = std::iter::once(None).chain(all_keys.iter().map(Some));
+        for cursor in cursors {
+            let cursor_bytes = cursor.map(|key| key.to_bytes().unwrap());
+            for prefix in prefixes {
+                for limit in 0..=all_keys.len() {
+                    let expected = all_keys
+                        .iter()
+                        .filter(|key| key.0.starts_with(prefix))
+                        .filter(|key| cursor.map_or(true, |cursor| *key > cursor))
+                        .take(limit)
+                        .cloned()
+                        .collect::<Vec<TestKey>>();
+                    let txn = context.environment.create_read_txn().unwrap();
+                    let actual = operations::keys_with_prefix_after::<TestKey, TestValue, _, _>(
+                        correlation_id,
+                        &txn,
+                        &context.store,
+                        &root_hash,
+                        prefix,
+                        cursor_bytes.as_deref(),
+                        limit,
+                    )
+                    .unwrap();
+                    txn.commit().unwrap();
+                    assert_eq!(actual, KeysWithPrefixAfterResult::Found(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lmdb_keys_with_prefix_after_should_report_missing_trie_node() {
+        let correlation_id = CorrelationId::new();
+        let generator = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1];
+        let (root_hash, mut tries) = generator().unwrap();
+        let missing_index = tries
+            .iter()
+            .position(|hashed_trie| matches!(hashed_trie.trie, Trie::Leaf { .. }))
+            .unwrap();
+        let missing_trie_key = tries.remove(missing_index).hash;
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let txn = context.environment.create_read_txn().unwrap();
+        let result = operations::keys_with_prefix_after::<TestKey, TestValue, _, _>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            &[],
+            None,
+            TEST_LEAVES.len(),
+        )
+        .unwrap();
+        txn.commit().unwrap();
+        assert_eq!(
+            result,
+            KeysWithPrefixAfterResult::MissingTrieNode(missing_trie_key)
+        );
+    }
+}
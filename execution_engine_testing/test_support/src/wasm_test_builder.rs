@@ -31,6 +31,7 @@ use casper_execution_engine::{
             DEFAULT_MAX_QUERY_DEPTH,
         },
         execution,
+        runtime::trace::ExecutionTrace,
     },
     shared::{
         additive_map::AdditiveMap,
@@ -101,6 +102,8 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_results: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Traces recorded by subsequent `exec_with_trace` calls.
+    exec_traces: Vec<Vec<ExecutionTrace>>,
     upgrade_results: Vec<Result<UpgradeSuccess, engine_state::Error>>,
     prune_results: Vec<Result<PruneResult, engine_state::Error>>,
     genesis_hash: Option<Digest>,
@@ -141,6 +144,7 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_results: self.exec_results.clone(),
+            exec_traces: self.exec_traces.clone(),
             upgrade_results: self.upgrade_results.clone(),
             prune_results: self.prune_results.clone(),
             genesis_hash: self.genesis_hash,
@@ -168,6 +172,7 @@ impl InMemoryWasmTestBuilder {
 
         WasmTestBuilder {
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             engine_state: Rc::new(engine_state),
@@ -193,6 +198,7 @@ impl InMemoryWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: maybe_post_state_hash,
@@ -316,6 +322,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
@@ -421,6 +428,7 @@ impl LmdbWasmTestBuilder {
         let mut builder = WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
@@ -748,6 +756,29 @@ where
         self
     }
 
+    /// Runs an [`ExecuteRequest`] as per [`exec`](Self::exec), additionally recording a trace of
+    /// the gas charged while executing each deploy.
+    pub fn exec_with_trace(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        let hash = self.post_state_hash.expect("expected post_state_hash");
+        exec_request.parent_state_hash = hash;
+
+        let results = self
+            .engine_state
+            .run_execute_traced(CorrelationId::new(), exec_request)
+            .expect("should execute with trace");
+        let mut exec_results = Vec::with_capacity(results.len());
+        let mut exec_traces = Vec::with_capacity(results.len());
+        for (execution_result, trace) in results {
+            self.transforms
+                .push(execution_result.execution_journal().clone());
+            exec_results.push(Rc::new(execution_result));
+            exec_traces.push(trace);
+        }
+        self.exec_results.push(exec_results);
+        self.exec_traces.push(exec_traces);
+        self
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self.post_state_hash.expect("Should have genesis hash");
@@ -987,6 +1018,11 @@ where
         Some(exec_results.iter().map(Rc::clone).collect())
     }
 
+    /// Returns the traces of the last `exec_with_trace` call.
+    pub fn get_last_exec_traces(&self) -> Option<&[ExecutionTrace]> {
+        self.exec_traces.last().map(Vec::as_slice)
+    }
+
     /// Returns the owned results of a specific exec.
     pub fn get_exec_result_owned(&self, index: usize) -> Option<Vec<Rc<ExecutionResult>>> {
         let exec_results = self.exec_results.get(index)?;
@@ -2,7 +2,8 @@ use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
     PRODUCTION_RUN_GENESIS_REQUEST,
 };
-use casper_types::{bytesrepr::Bytes, runtime_args, ContractHash, RuntimeArgs};
+use casper_execution_engine::core::runtime::trace::TraceEntry;
+use casper_types::{bytesrepr::Bytes, runtime_args, ContractHash, Gas, RuntimeArgs};
 
 const HOST_FUNCTION_COSTS_NAME: &str = "host_function_costs.wasm";
 const CONTRACT_KEY_NAME: &str = "contract";
@@ -235,3 +236,97 @@ fn should_measure_argument_size_in_host_function_call() {
         call_2_cost
     );
 }
+
+#[ignore]
+#[test]
+fn should_trace_nested_contract_calls() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        HOST_FUNCTION_COSTS_NAME,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    builder.exec(exec_request_1).expect_success().commit();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let contract_hash: ContractHash = account
+        .named_keys()
+        .get(CONTRACT_KEY_NAME)
+        .expect("contract hash")
+        .into_hash()
+        .expect("should be hash")
+        .into();
+
+    let exec_request_2 = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        CALLS_DO_NOTHING_LEVEL2_NAME,
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    builder
+        .exec_with_trace(exec_request_2)
+        .expect_success()
+        .commit();
+    let gas_cost = builder.last_exec_gas_cost();
+
+    let traces = builder.get_last_exec_traces().expect("should have traces");
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0].omitted_entries(), 0);
+    let entries = traces[0].entries();
+
+    let calls: Vec<(usize, &str)> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            TraceEntry::ContractCall {
+                depth, entry_point, ..
+            } => Some((*depth, entry_point.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(calls.len(), 2, "{:?}", calls);
+    assert_eq!(calls[0].1, CALLS_DO_NOTHING_LEVEL1_NAME);
+    assert_eq!(calls[1].1, DO_NOTHING_NAME);
+    assert_eq!(calls[1].0, calls[0].0 + 1);
+
+    assert!(entries.iter().all(|entry| match entry {
+        TraceEntry::ContractReturn { success, .. } => *success,
+        _ => true,
+    }));
+
+    let versioned_calls: Vec<(usize, Gas, Gas)> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            TraceEntry::HostFunction {
+                name,
+                args_size,
+                cost,
+                total_cost,
+                ..
+            } if name == "casper_call_versioned_contract" => Some((*args_size, *cost, *total_cost)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(versioned_calls.len(), 2);
+    for (args_size, cost, total_cost) in versioned_calls {
+        assert!(args_size > 0);
+        assert!(!cost.value().is_zero());
+        assert!(
+            total_cost > cost,
+            "nested call should cost more than the host function itself"
+        );
+    }
+
+    // Wasm instructions account for only part of the deploy's total cost.
+    assert!(traces[0].wasm_gas() > Gas::default());
+    assert!(traces[0].wasm_gas() < gas_cost);
+}
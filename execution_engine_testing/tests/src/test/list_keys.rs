@@ -0,0 +1,82 @@
+use casper_engine_test_support::{
+    InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_PROPOSER_ADDR,
+    PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
+};
+use casper_execution_engine::{
+    core::engine_state::{KeyPrefix, ListKeysRequest, ListKeysResult},
+    shared::newtypes::CorrelationId,
+};
+use casper_hashing::Digest;
+use casper_types::{Key, KeyTag, StoredValue};
+
+fn list_keys_page(
+    builder: &InMemoryWasmTestBuilder,
+    prefix: KeyPrefix,
+    cursor: Option<Key>,
+    limit: usize,
+) -> (Vec<(Key, StoredValue)>, Option<Key>) {
+    let request = ListKeysRequest::new(builder.get_post_state_hash(), prefix, cursor, limit);
+    match builder
+        .get_engine_state()
+        .list_keys(CorrelationId::new(), request)
+        .expect("should list keys")
+    {
+        ListKeysResult::Success {
+            entries,
+            next_cursor,
+        } => (entries, next_cursor),
+        ListKeysResult::RootNotFound => panic!("should find root"),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_page_through_account_keys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let prefix = KeyPrefix::Tag(KeyTag::Account);
+    let (all_entries, next_cursor) = list_keys_page(&builder, prefix, None, usize::MAX);
+    assert!(next_cursor.is_none());
+
+    let all_keys: Vec<Key> = all_entries.iter().map(|(key, _)| *key).collect();
+    for account_hash in [*DEFAULT_ACCOUNT_ADDR, *DEFAULT_PROPOSER_ADDR, *SYSTEM_ADDR] {
+        assert!(all_keys.contains(&Key::Account(account_hash)));
+    }
+    assert!(all_keys.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(all_entries
+        .iter()
+        .all(|(_, stored_value)| matches!(stored_value, StoredValue::Account(_))));
+
+    let mut paged_keys = vec![];
+    let mut cursor = None;
+    loop {
+        let (entries, next_cursor) = list_keys_page(&builder, prefix, cursor, 1);
+        assert!(entries.len() <= 1);
+        paged_keys.extend(entries.into_iter().map(|(key, _)| key));
+        if next_cursor.is_none() {
+            break;
+        }
+        cursor = next_cursor;
+    }
+    assert_eq!(paged_keys, all_keys);
+}
+
+#[ignore]
+#[test]
+fn should_not_list_keys_under_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let request = ListKeysRequest::new(
+        Digest::hash(b"missing root"),
+        KeyPrefix::Tag(KeyTag::Bid),
+        None,
+        10,
+    );
+    let result = builder
+        .get_engine_state()
+        .list_keys(CorrelationId::new(), request)
+        .expect("should list keys");
+    assert!(matches!(result, ListKeysResult::RootNotFound));
+}
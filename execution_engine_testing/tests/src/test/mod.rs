@@ -9,6 +9,7 @@ mod gas_counter;
 mod get_balance;
 mod groups;
 mod host_function_costs;
+mod list_keys;
 mod manage_groups;
 mod private_chain;
 mod regression;
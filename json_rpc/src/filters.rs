@@ -7,7 +7,9 @@
 mod tests;
 
 use bytes::Bytes;
+use futures::future;
 use http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
+use serde::Serialize;
 use serde_json::{json, Map, Value};
 use tracing::{debug, trace, warn};
 use warp::{
@@ -66,65 +68,231 @@ pub fn base_filter<P: AsRef<str>>(path: P, max_body_bytes: u32) -> BoxedFilter<(
         .boxed()
 }
 
-/// Handles parsing a JSON-RPC request from the given HTTP body, executing it using the appropriate
-/// handler, and providing a JSON-RPC response (which could be a success or failure).
+/// The JSON-RPC response(s) to be sent to the client.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ResponseOrBatch {
+    /// The response to a single request.
+    Single(Response),
+    /// The responses to a batch of requests.
+    Batch(Vec<Response>),
+}
+
+/// Validates and executes a single JSON-RPC request, providing a JSON-RPC response (which could be
+/// a success or failure).
+///
+/// Returns an `Err(Rejection)` only if the request is a Notification as per the JSON-RPC
+/// specification, i.e. the request doesn't contain an "id" field.
+async fn handle_request(
+    unvalidated_request: Map<String, Value>,
+    handlers: &RequestHandlers,
+    allow_unknown_fields: bool,
+) -> Result<Response, Rejection> {
+    match Request::new(unvalidated_request, allow_unknown_fields) {
+        Ok(request) => Ok(handlers.handle_request(request).await),
+        Err(ErrorOrRejection::Error { id, error }) => {
+            debug!(?error, "got an invalid request");
+            Ok(Response::new_failure(id, error))
+        }
+        Err(ErrorOrRejection::Rejection(rejection)) => {
+            debug!(?rejection, "rejecting an invalid request");
+            Err(rejection)
+        }
+    }
+}
+
+/// Executes a batch of JSON-RPC requests concurrently, providing the JSON-RPC responses in the
+/// same order as the corresponding requests.
+///
+/// If the batch is empty or contains more than `max_batch_size` entries, none of the requests are
+/// executed and a single JSON-RPC failure response is provided instead.
+///
+/// Notifications in the batch are not executed and have no corresponding response.  Returns an
+/// `Err(Rejection)` only if every entry in the batch is a Notification.
+async fn handle_batch(
+    unvalidated_requests: Vec<Value>,
+    handlers: &RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
+) -> Result<ResponseOrBatch, Rejection> {
+    if unvalidated_requests.is_empty() {
+        debug!("got an empty batch");
+        let error = Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Batch must contain at least one request",
+        );
+        return Ok(ResponseOrBatch::Single(Response::new_failure(
+            Value::Null,
+            error,
+        )));
+    }
+
+    if unvalidated_requests.len() > max_batch_size as usize {
+        debug!(
+            batch_size = unvalidated_requests.len(),
+            max_batch_size, "got an oversized batch"
+        );
+        let error = Error::new(
+            ReservedErrorCode::InvalidRequest,
+            format!(
+                "Batch contains {} requests, but the maximum allowed is {}",
+                unvalidated_requests.len(),
+                max_batch_size
+            ),
+        );
+        return Ok(ResponseOrBatch::Single(Response::new_failure(
+            Value::Null,
+            error,
+        )));
+    }
+
+    let responses: Vec<Response> = future::join_all(unvalidated_requests.into_iter().map(
+        |unvalidated_request| async move {
+            match unvalidated_request {
+                Value::Object(unvalidated_request) => {
+                    handle_request(unvalidated_request, handlers, allow_unknown_fields)
+                        .await
+                        .ok()
+                }
+                _ => {
+                    debug!("got a batch entry which is not an object");
+                    let error = Error::new(
+                        ReservedErrorCode::InvalidRequest,
+                        "Expected batch entry to be an Object",
+                    );
+                    Some(Response::new_failure(Value::Null, error))
+                }
+            }
+        },
+    ))
+    .await
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if responses.is_empty() {
+        debug!("rejecting a batch consisting only of notifications");
+        return Err(reject::custom(MissingId));
+    }
+    Ok(ResponseOrBatch::Batch(responses))
+}
+
+/// Handles parsing a JSON-RPC request or batch of requests from the given HTTP body, executing
+/// them using the appropriate handlers, and providing the JSON-RPC response(s) (each of which could
+/// be a success or failure).
 ///
 /// Returns an `Err(Rejection)` only if the request is a Notification as per the JSON-RPC
-/// specification, i.e. the request doesn't contain an "id" field.  In this case, no JSON-RPC
-/// response is sent to the client.
+/// specification, i.e. the request doesn't contain an "id" field, or if every request in a batch
+/// is a Notification.  In this case, no JSON-RPC response is sent to the client.
 ///
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
+///
+/// Batches with more than `max_batch_size` requests are not executed, causing the server to
+/// respond with a single error.
 async fn handle_body(
     body: Bytes,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
-) -> Result<Response, Rejection> {
-    let response = match serde_json::from_slice::<Map<String, Value>>(&body) {
-        Ok(unvalidated_request) => match Request::new(unvalidated_request, allow_unknown_fields) {
-            Ok(request) => handlers.handle_request(request).await,
-            Err(ErrorOrRejection::Error { id, error }) => {
-                debug!(?error, "got an invalid request");
-                Response::new_failure(id, error)
-            }
-            Err(ErrorOrRejection::Rejection(rejection)) => {
-                debug!(?rejection, "rejecting an invalid request");
-                return Err(rejection);
-            }
-        },
+    max_batch_size: u32,
+) -> Result<ResponseOrBatch, Rejection> {
+    let response = match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Object(unvalidated_request)) => ResponseOrBatch::Single(
+            handle_request(unvalidated_request, &handlers, allow_unknown_fields).await?,
+        ),
+        Ok(Value::Array(unvalidated_requests)) => {
+            handle_batch(
+                unvalidated_requests,
+                &handlers,
+                allow_unknown_fields,
+                max_batch_size,
+            )
+            .await?
+        }
+        Ok(_) => {
+            debug!("got json which is neither an object nor an array");
+            let error = Error::new(
+                ReservedErrorCode::InvalidRequest,
+                "Expected an Object or an Array of Objects",
+            );
+            ResponseOrBatch::Single(Response::new_failure(Value::Null, error))
+        }
         Err(error) => {
             debug!(%error, "got bad json");
             let error = Error::new(ReservedErrorCode::ParseError, error.to_string());
-            Response::new_failure(Value::Null, error)
+            ResponseOrBatch::Single(Response::new_failure(Value::Null, error))
         }
     };
     Ok(response)
 }
 
-/// Returns a boxed warp filter which handles parsing a JSON-RPC request from the given HTTP body,
-/// executing it using the appropriate handler, and providing a reply.
+/// Returns a boxed warp filter which handles parsing a JSON-RPC request or batch of requests from
+/// the given HTTP body, executing them using the appropriate handlers, and providing a reply.
 ///
-/// The reply will normally be built from a JSON-RPC response (which could be a success or failure).
+/// The reply will normally be built from a JSON-RPC response (which could be a success or failure),
+/// or from an array of JSON-RPC responses if the body contained a batch of requests.
 ///
 /// However, the reply could be built from a [`Rejection`] if the request is a Notification as per
 /// the JSON-RPC specification, i.e. the request doesn't contain an "id" field.  In this case, no
-/// JSON-RPC response is sent to the client, only an HTTP response.
+/// JSON-RPC response is sent to the client, only an HTTP response.  The same applies to a batch in
+/// which every request is a Notification.
 ///
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
+///
+/// Batch requests are not supported; see [`main_filter_with_max_batch_size`] to support them.
 pub fn main_filter(
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+) -> BoxedFilter<(WithStatus<reply::Json>,)> {
+    main_filter_with_max_batch_size(handlers, allow_unknown_fields, 0)
+}
+
+/// Returns a boxed warp filter like [`main_filter`], but which also supports batch requests.
+///
+/// `max_batch_size` sets an upper limit for the number of requests in a single batch.  Larger
+/// batches cause the server to respond with an error.  If set to 0, batches are not supported.
+pub fn main_filter_with_max_batch_size(
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
 ) -> BoxedFilter<(WithStatus<reply::Json>,)> {
     body::bytes()
         .and_then(move |body| {
             let handlers = handlers.clone();
-            async move { handle_body(body, handlers, allow_unknown_fields).await }
+            async move { handle_body(body, handlers, allow_unknown_fields, max_batch_size).await }
         })
         .map(|response| reply::with_status(reply::json(&response), StatusCode::OK))
         .boxed()
 }
 
+/// Handles a JSON-RPC request or batch of requests received via a transport other than an HTTP
+/// POST, e.g. as a WebSocket message, executing them using the appropriate handlers.
+///
+/// Returns the JSON-encoded response(s) to be sent to the client, or `None` if the request is a
+/// Notification as per the JSON-RPC specification, or if every request in a batch is a
+/// Notification.
+///
+/// `allow_unknown_fields` and `max_batch_size` have the same meaning as for
+/// [`main_filter_with_max_batch_size`].
+pub async fn handle_message(
+    message: Bytes,
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
+) -> Option<String> {
+    let response = handle_body(message, handlers, allow_unknown_fields, max_batch_size)
+        .await
+        .ok()?;
+    match serde_json::to_string(&response) {
+        Ok(encoded_response) => Some(encoded_response),
+        Err(error) => {
+            warn!(%error, "failed to encode json-rpc response");
+            None
+        }
+    }
+}
+
 /// Handler for rejections where no JSON-RPC response is sent, but an HTTP response is required.
 ///
 /// The HTTP response body will be a JSON object of the form:
@@ -1,4 +1,5 @@
 mod base_filter_with_recovery_tests;
+mod handle_message_tests;
 mod main_filter_with_recovery_tests;
 
 use serde::Deserialize;
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use serde_json::Value;
+
+use crate::{
+    filters::handle_message, Error, Params, RequestHandlers, RequestHandlersBuilder,
+    ReservedErrorCode, Response,
+};
+
+const ECHO: &str = "echo";
+const MAX_BATCH_SIZE: u32 = 2;
+
+async fn echo(params: Option<Params>) -> Result<Value, Error> {
+    params
+        .map(Value::from)
+        .ok_or_else(|| Error::new(ReservedErrorCode::InvalidParams, "no params"))
+}
+
+fn handlers() -> RequestHandlers {
+    let mut handlers = RequestHandlersBuilder::new();
+    handlers.register_handler(ECHO, Arc::new(echo));
+    handlers.build()
+}
+
+async fn handle(message: &'static str) -> Option<String> {
+    handle_message(Bytes::from(message), handlers(), false, MAX_BATCH_SIZE).await
+}
+
+#[tokio::test]
+async fn should_handle_request() {
+    let encoded_response = handle(r#"{"jsonrpc":"2.0","id":1,"method":"echo","params":[2]}"#)
+        .await
+        .expect("should respond");
+
+    let response: Response = serde_json::from_str(&encoded_response).unwrap();
+    assert_eq!(response.id(), 1);
+    assert_eq!(response.result(), Some(vec![2]));
+}
+
+#[tokio::test]
+async fn should_handle_batch_request() {
+    let encoded_response = handle(
+        r#"[{"jsonrpc":"2.0","id":1,"method":"echo","params":[2]},{"jsonrpc":"2.0","id":3,"method":"echo"}]"#,
+    )
+    .await
+    .expect("should respond");
+
+    let responses: Vec<Response> = serde_json::from_str(&encoded_response).unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].result(), Some(vec![2]));
+    assert_eq!(
+        responses[1].error().unwrap(),
+        &Error::new(ReservedErrorCode::InvalidParams, "no params")
+    );
+}
+
+#[tokio::test]
+async fn should_not_respond_to_notification() {
+    assert!(handle(r#"{"jsonrpc":"2.0","method":"echo","params":[2]}"#)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn should_extend_cloned_handlers() {
+    let mut builder = RequestHandlersBuilder::new();
+    builder.register_handler(ECHO, Arc::new(echo));
+    let mut extended_builder = builder.clone();
+    extended_builder.register_handler(
+        "ping",
+        Arc::new(|_params: Option<Params>| async { Ok::<_, Error>("pong") }),
+    );
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+
+    let encoded_response = handle_message(
+        Bytes::from(message),
+        extended_builder.build(),
+        false,
+        MAX_BATCH_SIZE,
+    )
+    .await
+    .expect("should respond");
+    let response: Response = serde_json::from_str(&encoded_response).unwrap();
+    assert_eq!(response.result(), Some("pong".to_string()));
+
+    let encoded_response =
+        handle_message(Bytes::from(message), builder.build(), false, MAX_BATCH_SIZE)
+            .await
+            .expect("should respond");
+    let response: Response = serde_json::from_str(&encoded_response).unwrap();
+    assert_eq!(
+        response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::MethodNotFound,
+            "'ping' is not a supported json-rpc method on this server"
+        )
+    );
+}
@@ -10,12 +10,13 @@ use warp::{filters::BoxedFilter, Filter, Reply};
 
 use super::ResponseBodyOnRejection;
 use crate::{
-    filters::{handle_rejection, main_filter},
+    filters::{handle_rejection, main_filter, main_filter_with_max_batch_size},
     Error, Params, RequestHandlersBuilder, ReservedErrorCode, Response,
 };
 
 const GET_GOOD_THING: &str = "get good thing";
 const GET_BAD_THING: &str = "get bad thing";
+const MAX_BATCH_SIZE: u32 = 3;
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
 struct GoodThing {
@@ -49,13 +50,18 @@ async fn from_http_response(response: http::Response<hyper::Body>) -> Response {
     serde_json::from_slice(&body_bytes).unwrap()
 }
 
+async fn batch_from_http_response(response: http::Response<hyper::Body>) -> Vec<Response> {
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
 fn main_filter_with_recovery() -> BoxedFilter<(impl Reply,)> {
     let mut handlers = RequestHandlersBuilder::new();
     handlers.register_handler(GET_GOOD_THING, Arc::new(get_good_thing));
     handlers.register_handler(GET_BAD_THING, Arc::new(get_bad_thing));
     let handlers = handlers.build();
 
-    main_filter(handlers, false)
+    main_filter_with_max_batch_size(handlers, false, MAX_BATCH_SIZE)
         .recover(handle_rejection)
         .boxed()
 }
@@ -318,3 +324,218 @@ async fn should_handle_invalid_json() {
         )
     );
 }
+
+#[tokio::test]
+async fn should_handle_json_which_is_neither_object_nor_array() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_body` and return Response::Failure (invalid
+    // request) to the client as it is valid JSON, but can't be a request or batch of requests.
+    let http_response = warp::test::request()
+        .body(r#""a""#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Expected an Object or an Array of Objects"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_request() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return one response per request which
+    // has an ID, in the order of the requests.  The notification (the request with no ID) gets no
+    // response.
+    let http_response = warp::test::request()
+        .body(
+            r#"[
+                {"jsonrpc":"2.0","id":"a","method":"get good thing","params":["one"]},
+                {"jsonrpc":"2.0","method":"get good thing","params":["two"]},
+                {"jsonrpc":"2.0","id":"b","method":"get good thing"}
+            ]"#,
+        )
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_responses = batch_from_http_response(http_response).await;
+    assert_eq!(rpc_responses.len(), 2);
+
+    assert_eq!(rpc_responses[0].id(), "a");
+    assert_eq!(
+        rpc_responses[0].result(),
+        Some(GoodThing {
+            good_thing: "one".to_string()
+        })
+    );
+
+    assert_eq!(rpc_responses[1].id(), "b");
+    assert_eq!(
+        rpc_responses[1].error().unwrap(),
+        &Error::new(ReservedErrorCode::InvalidParams, "no params")
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_request_with_invalid_entry() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return Response::Failure (invalid
+    // request) for the entry which isn't an object, without affecting the valid entry.
+    let http_response = warp::test::request()
+        .body(r#"[1,{"jsonrpc":"2.0","id":"a","method":"get good thing","params":["one"]}]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_responses = batch_from_http_response(http_response).await;
+    assert_eq!(rpc_responses.len(), 2);
+
+    assert_eq!(rpc_responses[0].id(), &Value::Null);
+    assert_eq!(
+        rpc_responses[0].error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Expected batch entry to be an Object"
+        )
+    );
+
+    assert_eq!(rpc_responses[1].id(), "a");
+    assert_eq!(
+        rpc_responses[1].result(),
+        Some(GoodThing {
+            good_thing: "one".to_string()
+        })
+    );
+}
+
+#[tokio::test]
+async fn should_handle_empty_batch() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return a single Response::Failure
+    // (invalid request) to the client as the batch is empty.
+    let http_response = warp::test::request()
+        .body(r#"[]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Batch must contain at least one request"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_oversized_batch() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return a single Response::Failure
+    // (invalid request) to the client as the batch has more than `MAX_BATCH_SIZE` entries.
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"get good thing","params":["one"]}"#;
+    let body = format!("[{}]", vec![request; MAX_BATCH_SIZE as usize + 1].join(","));
+    let http_response = warp::test::request()
+        .body(body)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Batch contains 4 requests, but the maximum allowed is 3"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_reject_batch_if_batches_not_supported() {
+    let _ = env_logger::try_init();
+
+    let mut handlers = RequestHandlersBuilder::new();
+    handlers.register_handler(GET_GOOD_THING, Arc::new(get_good_thing));
+    let filter = main_filter(handlers.build(), false)
+        .recover(handle_rejection)
+        .boxed();
+
+    // This should get handled by `filters::handle_batch` and return a single Response::Failure
+    // (invalid request) to the client as `main_filter` doesn't support batches.
+    let http_response = warp::test::request()
+        .body(r#"[{"jsonrpc":"2.0","id":1,"method":"get good thing","params":["one"]}]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Batch contains 1 requests, but the maximum allowed is 0"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_of_notifications() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return no JSON-RPC response, only an
+    // HTTP response (bad request) to the client as no request in the batch has an ID.
+    let http_response = warp::test::request()
+        .body(r#"[{"jsonrpc":"2.0","method":"get good thing","params":["one"]}]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::BAD_REQUEST);
+    let response_body = ResponseBodyOnRejection::from_response(http_response).await;
+    assert_eq!(
+        response_body.message,
+        "The request is missing the 'id' field"
+    );
+}
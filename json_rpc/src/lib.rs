@@ -118,15 +118,39 @@ pub enum CorsOrigin {
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
 ///
+/// Batch requests are not supported; see [`route_with_max_batch_size`] to support them.
+///
 /// For further details, see the docs for the [`filters`] functions.
 pub fn route<P: AsRef<str>>(
     path: P,
     max_body_bytes: u32,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+) -> BoxedFilter<(impl Reply,)> {
+    route_with_max_batch_size(path, max_body_bytes, handlers, allow_unknown_fields, 0)
+}
+
+/// Constructs a set of warp filters suitable for use in a JSON-RPC server which supports batch
+/// requests.
+///
+/// `max_batch_size` sets an upper limit for the number of requests in a single JSON-RPC batch.
+/// Larger batches cause the server to respond with an error.  If set to 0, batches are not
+/// supported.
+///
+/// All other arguments have the same meaning as for [`route`].
+pub fn route_with_max_batch_size<P: AsRef<str>>(
+    path: P,
+    max_body_bytes: u32,
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
 ) -> BoxedFilter<(impl Reply,)> {
     filters::base_filter(path, max_body_bytes)
-        .and(filters::main_filter(handlers, allow_unknown_fields))
+        .and(filters::main_filter_with_max_batch_size(
+            handlers,
+            allow_unknown_fields,
+            max_batch_size,
+        ))
         .recover(filters::handle_rejection)
         .boxed()
 }
@@ -152,6 +176,8 @@ pub fn route<P: AsRef<str>>(
 ///   * allows "content-type" as a header
 ///   * allows the method "POST"
 ///
+/// Batch requests are not supported; see [`route_with_cors_and_max_batch_size`] to support them.
+///
 /// For further details, see the docs for the [`filters`] functions.
 pub fn route_with_cors<P: AsRef<str>>(
     path: P,
@@ -159,9 +185,39 @@ pub fn route_with_cors<P: AsRef<str>>(
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
     cors_header: &CorsOrigin,
+) -> BoxedFilter<(impl Reply,)> {
+    route_with_cors_and_max_batch_size(
+        path,
+        max_body_bytes,
+        handlers,
+        allow_unknown_fields,
+        0,
+        cors_header,
+    )
+}
+
+/// Constructs a set of warp filters suitable for use in a JSON-RPC server which supports batch
+/// requests, along with a CORS filter.
+///
+/// `max_batch_size` sets an upper limit for the number of requests in a single JSON-RPC batch.
+/// Larger batches cause the server to respond with an error.  If set to 0, batches are not
+/// supported.
+///
+/// All other arguments have the same meaning as for [`route_with_cors`].
+pub fn route_with_cors_and_max_batch_size<P: AsRef<str>>(
+    path: P,
+    max_body_bytes: u32,
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
+    cors_header: &CorsOrigin,
 ) -> BoxedFilter<(impl Reply,)> {
     filters::base_filter(path, max_body_bytes)
-        .and(filters::main_filter(handlers, allow_unknown_fields))
+        .and(filters::main_filter_with_max_batch_size(
+            handlers,
+            allow_unknown_fields,
+            max_batch_size,
+        ))
         .recover(filters::handle_rejection)
         .with(match cors_header {
             CorsOrigin::Any => warp::cors()
@@ -58,8 +58,9 @@ impl RequestHandlers {
 /// A builder for [`RequestHandlers`].
 //
 // This builder exists so the internal `HashMap` can be populated before it is made immutable behind
-// the `Arc` in the `RequestHandlers`.
-#[derive(Default)]
+// the `Arc` in the `RequestHandlers`.  It is cloneable so that a common set of handlers can be
+// extended with further, e.g. connection-specific, handlers.
+#[derive(Clone, Default)]
 pub struct RequestHandlersBuilder(HashMap<&'static str, RequestHandler>);
 
 impl RequestHandlersBuilder {
@@ -2,6 +2,10 @@ use std::env;
 
 use vergen::EmitBuilder;
 
+/// The protobuf definitions of the gRPC server's API.
+#[cfg(feature = "grpc-codegen")]
+const GRPC_PROTO_FILE: &str = "proto/casper/node/v1/node.proto";
+
 fn main() {
     if let Err(error) = EmitBuilder::builder().fail_on_error().git_sha(true).emit() {
         println!("cargo:warning={}", error);
@@ -13,4 +17,24 @@ fn main() {
         "cargo:rustc-env=NODE_BUILD_PROFILE={}",
         env::var("PROFILE").unwrap()
     );
+
+    #[cfg(feature = "grpc-codegen")]
+    generate_grpc_code();
+}
+
+/// Generates the gRPC server's protobuf types and service traits into `OUT_DIR`.
+///
+/// The generated code is checked in, so that building the node requires neither `protoc` nor
+/// `tonic-build`.  With the `grpc-codegen` feature enabled, it is generated afresh using the
+/// vendored `protoc`, and a test checks that the checked-in code matches it.
+#[cfg(feature = "grpc-codegen")]
+fn generate_grpc_code() {
+    env::set_var(
+        "PROTOC",
+        protoc_bin_vendored::protoc_bin_path().expect("should find vendored protoc"),
+    );
+    tonic_build::configure()
+        .build_client(false)
+        .compile(&[GRPC_PROTO_FILE], &["proto"])
+        .expect("should compile gRPC protobuf definitions");
 }
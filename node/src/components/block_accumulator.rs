@@ -270,6 +270,7 @@ impl BlockAccumulator {
             + From<PeerBehaviorAnnouncement>
             + From<MarkBlockCompletedRequest>
             + From<FatalAnnouncement>
+            + From<BlockAccumulatorAnnouncement>
             + Send,
     {
         let block_hash = meta_block.block.hash();
@@ -374,6 +375,7 @@ impl BlockAccumulator {
             + From<PeerBehaviorAnnouncement>
             + From<MarkBlockCompletedRequest>
             + From<FatalAnnouncement>
+            + From<BlockAccumulatorAnnouncement>
             + Send,
     {
         let block_hash = finality_signature.block_hash;
@@ -675,10 +677,28 @@ impl BlockAccumulator {
         REv: From<PeerBehaviorAnnouncement>
             + From<StorageRequest>
             + From<MarkBlockCompletedRequest>
+            + From<BlockAccumulatorAnnouncement>
             + Send,
         I: IntoIterator<Item = (NodeId, Error)>,
     {
-        let mut effects = match should_store {
+        let mut effects = Effects::new();
+        // Both of these outcomes are only returned once, when the block first reaches sufficient
+        // finality.
+        if let ShouldStore::SufficientlySignedBlock { meta_block, .. }
+        | ShouldStore::CompletedBlock { meta_block, .. } = &should_store
+        {
+            let header = meta_block.block.header();
+            effects.extend(
+                effect_builder
+                    .announce_block_finalized(
+                        *meta_block.block.hash(),
+                        header.era_id(),
+                        header.height(),
+                    )
+                    .ignore(),
+            );
+        }
+        effects.extend(match should_store {
             ShouldStore::SufficientlySignedBlock {
                 meta_block,
                 block_signatures,
@@ -743,7 +763,7 @@ impl BlockAccumulator {
                 debug!("not storing block or finality signatures");
                 Effects::new()
             }
-        };
+        });
         effects.extend(faulty_senders.into_iter().flat_map(|(node_id, error)| {
             effect_builder
                 .announce_block_peer_with_justification(
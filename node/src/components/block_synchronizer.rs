@@ -35,6 +35,7 @@ use casper_types::Timestamp;
 use super::network::blocklist::BlocklistJustification;
 use crate::{
     components::{
+        contract_runtime::GlobalStateSyncTracker,
         fetcher::{
             EmptyValidationMetadata, Error as FetcherError, FetchItem, FetchResult, FetchedData,
         },
@@ -221,6 +222,7 @@ impl BlockSynchronizer {
         chainspec: Arc<Chainspec>,
         max_simultaneous_peers: u8,
         validator_matrix: ValidatorMatrix,
+        global_state_syncs: GlobalStateSyncTracker,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(BlockSynchronizer {
@@ -231,7 +233,10 @@ impl BlockSynchronizer {
             validator_matrix,
             forward: None,
             historical: None,
-            global_sync: GlobalStateSynchronizer::new(config.max_parallel_trie_fetches as usize),
+            global_sync: GlobalStateSynchronizer::new(
+                config.max_parallel_trie_fetches as usize,
+                global_state_syncs,
+            ),
             metrics: Metrics::new(registry)?,
         })
     }
@@ -18,7 +18,7 @@ use casper_types::Timestamp;
 
 use super::{TrieAccumulator, TrieAccumulatorError, TrieAccumulatorEvent, TrieAccumulatorResponse};
 use crate::{
-    components::Component,
+    components::{contract_runtime::GlobalStateSyncTracker, Component},
     effect::{
         announcements::PeerBehaviorAnnouncement,
         requests::{
@@ -230,10 +230,15 @@ pub(super) struct GlobalStateSynchronizer {
     fetch_queue: FetchQueue,
     in_flight: HashSet<TrieHash>,
     last_progress: Option<Timestamp>,
+    #[data_size(skip)]
+    global_state_syncs: GlobalStateSyncTracker,
 }
 
 impl GlobalStateSynchronizer {
-    pub(super) fn new(max_parallel_trie_fetches: usize) -> Self {
+    pub(super) fn new(
+        max_parallel_trie_fetches: usize,
+        global_state_syncs: GlobalStateSyncTracker,
+    ) -> Self {
         Self {
             max_parallel_trie_fetches,
             trie_accumulator: TrieAccumulator::new(),
@@ -242,6 +247,7 @@ impl GlobalStateSynchronizer {
             fetch_queue: Default::default(),
             in_flight: Default::default(),
             last_progress: None,
+            global_state_syncs,
         }
     }
 
@@ -265,6 +271,7 @@ impl GlobalStateSynchronizer {
 
         let mut effects = match &mut self.request_state {
             None => {
+                self.global_state_syncs.sync_started(state_root_hash);
                 self.request_state = Some(RequestState::new(request));
                 self.touch();
                 self.enqueue_trie_for_fetching(effect_builder, TrieHash(state_root_hash))
@@ -434,6 +441,8 @@ impl GlobalStateSynchronizer {
         match self.request_state.take() {
             Some(request_state) => {
                 debug!(root_hash=%request_state.root_hash, "cancelling request");
+                self.global_state_syncs
+                    .sync_finished(request_state.root_hash.0, false);
                 self.fetch_queue.handle_request_cancelled();
                 self.in_flight = HashSet::new();
                 request_state.respond(Err(error))
@@ -450,6 +459,7 @@ impl GlobalStateSynchronizer {
             Some(request_state) => {
                 let root_hash = request_state.root_hash;
                 debug!(%root_hash, "finishing request");
+                self.global_state_syncs.sync_finished(root_hash.0, true);
                 let unreliable_peers = request_state.unreliable_peers.iter().copied().collect();
                 request_state.respond(Ok(Response::new(root_hash, unreliable_peers)))
             }
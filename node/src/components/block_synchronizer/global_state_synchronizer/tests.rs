@@ -104,7 +104,9 @@ fn random_sync_global_state_request(
 async fn fetch_request_without_peers_is_canceled() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let global_state_syncs = GlobalStateSyncTracker::default();
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), global_state_syncs.clone());
 
     // Create a responder to allow assertion of the error
     let (sender, receiver) = oneshot::channel();
@@ -119,6 +121,7 @@ async fn fetch_request_without_peers_is_canceled() {
     assert_eq!(global_state_synchronizer.fetch_queue.queue.len(), 1);
     assert_eq!(global_state_synchronizer.in_flight.len(), 0);
     assert!(global_state_synchronizer.last_progress.is_some());
+    assert!(global_state_syncs.is_sync_in_progress());
 
     let events = tokio::spawn(effects.remove(0)).await.unwrap();
     assert_eq!(events.len(), 1);
@@ -130,6 +133,9 @@ async fn fetch_request_without_peers_is_canceled() {
     // Since the request does not have any peers, it should be canceled.
     assert_eq!(effects.len(), 1);
     assert!(global_state_synchronizer.request_state.is_none());
+    // The partially synchronized state root should no longer be tracked.
+    assert!(!global_state_syncs.is_sync_in_progress());
+    assert_eq!(global_state_syncs.last_completed(), None);
     // Fetch should be always 0 as long as we're below parallel_fetch_limit
     assert_eq!(global_state_synchronizer.fetch_queue.queue.len(), 0);
     assert_eq!(global_state_synchronizer.in_flight.len(), 0);
@@ -145,7 +151,8 @@ async fn sync_global_state_request_starts_maximum_trie_fetches() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, Default::default());
 
     let mut progress = Timestamp::now();
 
@@ -251,7 +258,7 @@ async fn trie_accumulator_error_cancels_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     // Set the parallel fetch limit to allow only 1 fetch
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(1);
+    let mut global_state_synchronizer = GlobalStateSynchronizer::new(1, Default::default());
 
     // Create and register one request
     let (sender, receiver1) = oneshot::channel();
@@ -332,7 +339,8 @@ async fn trie_accumulator_error_cancels_request() {
 async fn successful_trie_fetch_puts_trie_to_store() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), Default::default());
 
     // Create a request
     let (request, trie) = random_sync_global_state_request(
@@ -386,7 +394,8 @@ async fn successful_trie_fetch_puts_trie_to_store() {
 async fn trie_store_error_cancels_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(rng.gen_range(2..10));
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(rng.gen_range(2..10), Default::default());
 
     // Create a request
     let (sender, receiver) = oneshot::channel();
@@ -441,7 +450,8 @@ async fn missing_trie_node_children_triggers_fetch() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, Default::default());
 
     // Create a request
     let (request, request_trie) = random_sync_global_state_request(
@@ -639,7 +649,9 @@ async fn stored_trie_finalizes_request() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
     let parallel_fetch_limit = rng.gen_range(2..10);
-    let mut global_state_synchronizer = GlobalStateSynchronizer::new(parallel_fetch_limit);
+    let global_state_syncs = GlobalStateSyncTracker::default();
+    let mut global_state_synchronizer =
+        GlobalStateSynchronizer::new(parallel_fetch_limit, global_state_syncs.clone());
 
     // Create a request
     let (sender, receiver) = oneshot::channel();
@@ -651,6 +663,7 @@ async fn stored_trie_finalizes_request() {
     assert_eq!(effects.len(), 1);
     assert!(global_state_synchronizer.request_state.is_some());
     assert_eq!(global_state_synchronizer.in_flight.len(), 0);
+    assert!(global_state_syncs.is_sync_in_progress());
 
     let events = tokio::spawn(effects.remove(0)).await.unwrap();
     assert_eq!(events.len(), 1);
@@ -700,6 +713,9 @@ async fn stored_trie_finalizes_request() {
     assert!(global_state_synchronizer.request_state.is_none());
     assert_eq!(global_state_synchronizer.in_flight.len(), 0);
     assert_eq!(global_state_synchronizer.fetch_queue.queue.len(), 0);
+    // The synchronized state root should be retained by the trie garbage collector.
+    assert!(!global_state_syncs.is_sync_in_progress());
+    assert_eq!(global_state_syncs.last_completed(), Some(state_root_hash));
     tokio::spawn(effects.remove(0));
     let result = receiver.await.unwrap();
     assert!(result.is_ok());
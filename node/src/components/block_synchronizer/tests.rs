@@ -268,6 +268,7 @@ impl BlockSynchronizer {
             Arc::new(Chainspec::random(rng)),
             MAX_SIMULTANEOUS_PEERS,
             validator_matrix,
+            Default::default(),
             &prometheus::Registry::new(),
         )
         .expect("Failed to create BlockSynchronizer");
@@ -6,6 +6,7 @@ mod metrics;
 mod operations;
 #[cfg(test)]
 mod tests;
+mod trie_gc;
 mod types;
 
 use std::{
@@ -32,7 +33,7 @@ use casper_execution_engine::{
         self,
         engine_config::{FeeHandling, RefundHandling},
         genesis::GenesisError,
-        ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
+        ChainspecRegistry, EngineConfigBuilder, EngineState, GenesisSuccess,
         SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
     },
     shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
@@ -67,7 +68,9 @@ pub(crate) use config::Config;
 pub(crate) use error::{BlockExecutionError, ConfigError};
 use metrics::Metrics;
 pub use operations::execute_finalized_block;
-use operations::execute_only;
+use operations::{execute_only, execute_only_with_trace};
+pub(crate) use trie_gc::GlobalStateSyncTracker;
+use trie_gc::TrieGarbageCollector;
 pub(crate) use types::{
     BlockAndExecutionResults, EraValidatorsRequest, StepEffectAndUpcomingEraValidators,
 };
@@ -212,6 +215,14 @@ pub(crate) struct ContractRuntime {
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    /// The number of threads used to execute the deploys in a block in parallel.
+    parallel_execution_threads: usize,
+    /// Deletes the tries unreachable from recent state roots, if enabled.
+    #[data_size(skip)]
+    trie_gc: Option<Arc<TrieGarbageCollector>>,
+    /// The global state synchronizations whose tries must not be garbage collected.
+    #[data_size(skip)]
+    global_state_syncs: GlobalStateSyncTracker,
 }
 
 impl Debug for ContractRuntime {
@@ -494,6 +505,8 @@ impl ContractRuntime {
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
+                        let parallel_execution_threads = self.parallel_execution_threads;
+                        let trie_gc = self.trie_gc.clone();
                         effects.extend(
                             Self::execute_finalized_block_or_requeue(
                                 engine_state,
@@ -508,6 +521,8 @@ impl ContractRuntime {
                                 activation_point,
                                 key_block_height_for_activation_point,
                                 prune_batch_size,
+                                parallel_execution_threads,
+                                trie_gc,
                                 meta_block_state,
                             )
                             .ignore(),
@@ -551,6 +566,23 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::ListKeys {
+                list_keys_request,
+                responder,
+            } => {
+                trace!(?list_keys_request, "list keys request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let start = Instant::now();
+                    let result = engine_state.list_keys(correlation_id, list_keys_request);
+                    metrics.list_keys.observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "list keys result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash,
                 responder,
@@ -568,16 +600,31 @@ impl ContractRuntime {
             }
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
-                deploy,
+                deploy_item,
                 responder,
             } => {
                 let engine_state = Arc::clone(&self.engine_state);
                 async move {
                     let result = run_intensive_task(move || {
-                        execute_only(
+                        execute_only(engine_state.as_ref(), execution_prestate, *deploy_item)
+                    })
+                    .await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::SpeculativeDeployExecutionWithTrace {
+                execution_prestate,
+                deploy_item,
+                responder,
+            } => {
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let result = run_intensive_task(move || {
+                        execute_only_with_trace(
                             engine_state.as_ref(),
                             execution_prestate,
-                            DeployItem::from((*deploy).clone()),
+                            *deploy_item,
                         )
                     })
                     .await;
@@ -654,6 +701,7 @@ impl ContractRuntime {
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
 
         let metrics = Arc::new(Metrics::new(registry)?);
+        let global_state_syncs = GlobalStateSyncTracker::default();
 
         Ok(ContractRuntime {
             state: ComponentState::Initialized,
@@ -665,9 +713,22 @@ impl ContractRuntime {
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            parallel_execution_threads: contract_runtime_config
+                .parallel_execution_threads_or_default(),
+            trie_gc: TrieGarbageCollector::new(
+                contract_runtime_config.trie_gc_retained_state_roots_or_default(),
+                global_state_syncs.clone(),
+            ),
+            global_state_syncs,
         })
     }
 
+    /// Returns the tracker through which global state synchronizations are reported to the trie
+    /// garbage collector.
+    pub(crate) fn global_state_sync_tracker(&self) -> GlobalStateSyncTracker {
+        self.global_state_syncs.clone()
+    }
+
     /// Commits a genesis request.
     pub(crate) fn commit_genesis(
         &self,
@@ -756,6 +817,8 @@ impl ContractRuntime {
         activation_point: ActivationPoint,
         key_block_height_for_activation_point: u64,
         prune_batch_size: u64,
+        parallel_execution_threads: usize,
+        trie_gc: Option<Arc<TrieGarbageCollector>>,
         mut meta_block_state: MetaBlockState,
     ) where
         REv: From<ContractRuntimeRequest>
@@ -767,6 +830,7 @@ impl ContractRuntime {
     {
         debug!("ContractRuntime: execute_finalized_block_or_requeue");
         let contract_runtime_metrics = metrics.clone();
+        let gc_engine_state = Arc::clone(&engine_state);
         let BlockAndExecutionResults {
             block,
             approvals_hashes,
@@ -784,6 +848,7 @@ impl ContractRuntime {
                 activation_point.era_id(),
                 key_block_height_for_activation_point,
                 prune_batch_size,
+                parallel_execution_threads,
             )
         })
         .await
@@ -796,6 +861,15 @@ impl ContractRuntime {
         };
 
         let new_execution_pre_state = ExecutionPreState::from_block_header(block.header());
+        if let Some(trie_gc) = trie_gc {
+            trie_gc
+                .record_state_root(
+                    &gc_engine_state,
+                    &shared_pre_state,
+                    *block.header().state_root_hash(),
+                )
+                .await;
+        }
         {
             // The `shared_pre_state` could have been set to a block we just fully synced after
             // doing a sync leap (via a call to `set_initial_state`).  We should not allow a block
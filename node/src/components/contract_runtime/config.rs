@@ -7,6 +7,8 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_PARALLEL_EXECUTION_THREADS: usize = 1;
+const DEFAULT_TRIE_GC_RETAINED_STATE_ROOTS: u64 = 0;
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -31,6 +33,27 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// The number of threads used to execute the deploys in a block optimistically in parallel.
+    ///
+    /// Deploys are executed concurrently, then committed in order, with any deploy which read
+    /// state changed by an earlier deploy in the block being re-executed.  The resulting state is
+    /// identical to that of sequential execution.  A value of 1 disables parallel execution.
+    ///
+    /// Defaults to 1.
+    pub parallel_execution_threads: Option<usize>,
+    /// The number of most recently executed blocks whose global state is retained by trie garbage
+    /// collection.
+    ///
+    /// When non-zero, each time this many blocks have been executed the tries which are not
+    /// reachable from the post-state roots of the most recent blocks are deleted in the
+    /// background.  Global state under older state roots can no longer be queried, so this should
+    /// only be enabled on non-archival nodes.  A value of 0 disables garbage collection.
+    ///
+    /// Each collection holds the hashes of all reachable tries in memory, roughly 40 to 80 bytes
+    /// per trie, and is abandoned if more than 50 million tries are reachable.
+    ///
+    /// Defaults to 0.
+    pub trie_gc_retained_state_roots: Option<u64>,
 }
 
 impl Config {
@@ -58,6 +81,18 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Number of threads used to execute deploys in parallel.
+    pub fn parallel_execution_threads_or_default(&self) -> usize {
+        self.parallel_execution_threads
+            .unwrap_or(DEFAULT_PARALLEL_EXECUTION_THREADS)
+    }
+
+    /// Number of recent state roots retained by trie garbage collection.
+    pub fn trie_gc_retained_state_roots_or_default(&self) -> u64 {
+        self.trie_gc_retained_state_roots
+            .unwrap_or(DEFAULT_TRIE_GC_RETAINED_STATE_ROOTS)
+    }
 }
 
 impl Default for Config {
@@ -67,6 +102,8 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            parallel_execution_threads: Some(DEFAULT_PARALLEL_EXECUTION_THREADS),
+            trie_gc_retained_state_roots: Some(DEFAULT_TRIE_GC_RETAINED_STATE_ROOTS),
         }
     }
 }
@@ -1,4 +1,4 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use crate::{unregister_metric, utils};
 
@@ -39,6 +39,9 @@ const GET_ERA_VALIDATORS_HELP: &str =
 const GET_BIDS_NAME: &str = "contract_runtime_get_bids";
 const GET_BIDS_HELP: &str = "time in seconds to get bids from global state";
 
+const LIST_KEYS_NAME: &str = "contract_runtime_list_keys";
+const LIST_KEYS_HELP: &str = "time in seconds to list a page of keys from global state";
+
 const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "time in seconds to put a trie";
 
@@ -55,6 +58,10 @@ const EXEC_QUEUE_SIZE_NAME: &str = "execution_queue_size";
 const EXEC_QUEUE_SIZE_HELP: &str =
     "number of blocks that are currently enqueued and waiting for execution";
 
+const REEXECUTED_DEPLOYS_NAME: &str = "contract_runtime_reexecuted_deploys";
+const REEXECUTED_DEPLOYS_HELP: &str =
+    "number of deploys re-executed as their parallel execution conflicted with earlier deploys";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -66,11 +73,13 @@ pub struct Metrics {
     pub(super) get_balance: Histogram,
     pub(super) get_era_validators: Histogram,
     pub(super) get_bids: Histogram,
+    pub(super) list_keys: Histogram,
     pub(super) put_trie: Histogram,
     pub(super) get_trie: Histogram,
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
     pub(super) exec_queue_size: IntGauge,
+    pub(super) reexecuted_deploys: IntCounter,
     registry: Registry,
 }
 
@@ -95,6 +104,9 @@ impl Metrics {
         let exec_queue_size = IntGauge::new(EXEC_QUEUE_SIZE_NAME, EXEC_QUEUE_SIZE_HELP)?;
         registry.register(Box::new(exec_queue_size.clone()))?;
 
+        let reexecuted_deploys = IntCounter::new(REEXECUTED_DEPLOYS_NAME, REEXECUTED_DEPLOYS_HELP)?;
+        registry.register(Box::new(reexecuted_deploys.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -144,6 +156,12 @@ impl Metrics {
                 GET_BIDS_HELP,
                 common_buckets.clone(),
             )?,
+            list_keys: utils::register_histogram_metric(
+                registry,
+                LIST_KEYS_NAME,
+                LIST_KEYS_HELP,
+                common_buckets.clone(),
+            )?,
             get_trie: utils::register_histogram_metric(
                 registry,
                 GET_TRIE_NAME,
@@ -164,6 +182,7 @@ impl Metrics {
             )?,
             latest_commit_step,
             exec_queue_size,
+            reexecuted_deploys,
             registry: registry.clone(),
         })
     }
@@ -179,10 +198,12 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.get_balance);
         unregister_metric!(self.registry, self.get_era_validators);
         unregister_metric!(self.registry, self.get_bids);
+        unregister_metric!(self.registry, self.list_keys);
         unregister_metric!(self.registry, self.put_trie);
         unregister_metric!(self.registry, self.get_trie);
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
         unregister_metric!(self.registry, self.exec_queue_size);
+        unregister_metric!(self.registry, self.reexecuted_deploys);
     }
 }
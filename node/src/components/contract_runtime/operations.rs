@@ -1,4 +1,14 @@
-use std::{cmp, collections::BTreeMap, ops::Range, sync::Arc, time::Instant};
+use std::{
+    cmp,
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Instant,
+};
 
 use itertools::Itertools;
 use tracing::{debug, error, info, trace, warn};
@@ -12,9 +22,13 @@ use casper_execution_engine::{
             StepSuccess,
         },
         execution,
+        runtime::trace::ExecutionTrace,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
-    storage::global_state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
+    storage::global_state::{
+        lmdb::LmdbGlobalState, recording::ReadSet, scratch::ScratchGlobalState, CommitProvider,
+        StateProvider,
+    },
 };
 use casper_hashing::Digest;
 use casper_types::{
@@ -99,6 +113,7 @@ pub fn execute_finalized_block(
     activation_point_era_id: EraId,
     key_block_height_for_activation_point: u64,
     prune_batch_size: u64,
+    parallel_execution_threads: usize,
 ) -> Result<BlockAndExecutionResults, BlockExecutionError> {
     if finalized_block.height() != execution_pre_state.next_block_height {
         return Err(BlockExecutionError::WrongBlockHeight {
@@ -125,26 +140,105 @@ pub fn execute_finalized_block(
     // Create a new EngineState that reads from LMDB but only caches changes in memory.
     let scratch_state = engine_state.get_scratch_engine_state();
 
-    // WARNING: Do not change the order of `deploys` as it will result in a different root hash.
-    for deploy in deploys {
-        let deploy_hash = *deploy.hash();
-        let deploy_header = deploy.header().clone();
-        let execute_request = ExecuteRequest::new(
+    let create_execute_request = |state_root_hash: Digest, deploy: Deploy| {
+        ExecuteRequest::new(
             state_root_hash,
             block_time,
             vec![DeployItem::from(deploy)],
             protocol_version,
             *finalized_block.proposer(),
-        );
+        )
+    };
+
+    // If enabled, execute all the deploys concurrently against the pre-state first.  Each
+    // speculative result is then only used below if it doesn't conflict with the deploys committed
+    // before it, otherwise the deploy is re-executed.
+    let is_parallel = parallel_execution_threads > 1 && deploys.len() > 1;
+    let proposer = *finalized_block.proposer();
+    let fee_purse_balance_key = if is_parallel {
+        engine_state.get_fee_purse_balance_key(
+            CorrelationId::new(),
+            pre_state_root_hash,
+            proposer.clone(),
+        )?
+    } else {
+        None
+    };
+    let mut speculative_results = if is_parallel {
+        execute_speculatively(
+            &scratch_state,
+            metrics.clone(),
+            parallel_execution_threads,
+            &deploys,
+            |deploy| create_execute_request(state_root_hash, deploy.clone()),
+        )
+    } else {
+        vec![]
+    }
+    .into_iter();
+    // The keys written by the deploys committed so far, each with whether it was only added to.
+    let mut written_keys: HashMap<Key, bool> = HashMap::new();
+    let mut reexecuted_count = 0_usize;
+
+    // WARNING: Do not change the order of `deploys` as it will result in a different root hash.
+    for deploy in deploys {
+        let deploy_hash = *deploy.hash();
+        let deploy_header = deploy.header().clone();
 
         // TODO: this is currently working coincidentally because we are passing only one
         // deploy_item per exec. The execution results coming back from the EE lack the
         // mapping between deploy_hash and execution result, and this outer logic is
         // enriching it with the deploy hash. If we were passing multiple deploys per exec
         // the relation between the deploy and the execution results would be lost.
-        let result = execute(&scratch_state, metrics.clone(), execute_request)?;
+        let speculative_result = match speculative_results.next().flatten() {
+            Some((result, read_set)) => {
+                // Only the owner of the fee purse could observe its balance, so additions to it by
+                // other accounts' deploys commute.
+                let commutative_key = fee_purse_balance_key
+                    .as_ref()
+                    .filter(|_| *deploy_header.account() != proposer);
+                let conflicts = is_conflicting(
+                    engine_state,
+                    &scratch_state,
+                    ConflictCheck {
+                        pre_state_root_hash,
+                        state_root_hash,
+                        written_keys: &written_keys,
+                        commutative_key,
+                    },
+                    &result,
+                    &read_set,
+                )?;
+                if conflicts {
+                    trace!(?deploy_hash, "re-executing conflicting deploy");
+                    reexecuted_count += 1;
+                    None
+                } else {
+                    Some(result)
+                }
+            }
+            None => None,
+        };
+        let result = match speculative_result {
+            Some(result) => result,
+            None => execute(
+                &scratch_state,
+                metrics.clone(),
+                create_execute_request(state_root_hash, deploy),
+            )?,
+        };
 
         trace!(?deploy_hash, ?result, "deploy execution result");
+        if is_parallel {
+            for (key, transform) in result
+                .iter()
+                .flat_map(|result| result.execution_journal().iter())
+                .filter(|(_, transform)| *transform != Transform::Identity)
+            {
+                let is_addition = matches!(transform, Transform::AddUInt512(_));
+                *written_keys.entry(*key).or_insert(true) &= is_addition;
+            }
+        }
         // As for now a given state is expected to exist.
         let (state_hash, execution_result) = commit_execution_results(
             &scratch_state,
@@ -157,6 +251,16 @@ pub fn execute_finalized_block(
         state_root_hash = state_hash;
     }
 
+    if is_parallel {
+        debug!(
+            deploy_count = execution_results.len(),
+            reexecuted_count, "executed deploys in parallel"
+        );
+        if let Some(metrics) = metrics.as_ref() {
+            metrics.reexecuted_deploys.inc_by(reexecuted_count as u64);
+        }
+    }
+
     // Write the deploy approvals and execution results Merkle root hashes to global state if there
     // were any deploys.
     let execution_results_checksum = compute_execution_results_checksum(
@@ -449,6 +553,44 @@ where
     })
 }
 
+/// Executes the given deploy as per [`execute_only`], additionally returning a trace of the gas
+/// charged while executing it.
+pub fn execute_only_with_trace<S>(
+    engine_state: &EngineState<S>,
+    execution_state: SpeculativeExecutionState,
+    deploy: DeployItem,
+) -> Result<Option<(ExecutionResult, ExecutionTrace)>, engine_state::Error>
+where
+    S: StateProvider + CommitProvider,
+    S::Error: Into<execution::Error>,
+{
+    let SpeculativeExecutionState {
+        state_root_hash,
+        block_time,
+        protocol_version,
+    } = execution_state;
+    let deploy_hash = deploy.deploy_hash;
+    let execute_request = ExecuteRequest::new(
+        state_root_hash,
+        block_time.millis(),
+        vec![deploy],
+        protocol_version,
+        PublicKey::System,
+    );
+    let mut results = engine_state.run_execute_traced(CorrelationId::new(), execute_request)?;
+    let len = results.len();
+    if len != 1 {
+        warn!(
+            ?deploy_hash,
+            "got more ({}) execution results from a single transaction", len
+        );
+        return Ok(None);
+    }
+    Ok(results
+        .pop()
+        .map(|(execution_result, trace)| (execution_result.into(), trace)))
+}
+
 fn execute<S>(
     engine_state: &EngineState<S>,
     metrics: Option<Arc<Metrics>>,
@@ -469,6 +611,153 @@ where
     result
 }
 
+/// The state of block execution against which a speculative result is checked for conflicts.
+struct ConflictCheck<'a> {
+    pre_state_root_hash: Digest,
+    state_root_hash: Digest,
+    /// The keys written by the deploys committed so far, each with whether it was only added to.
+    written_keys: &'a HashMap<Key, bool>,
+    /// A key whose additions commute, such that it doesn't conflict if it was only added to.
+    commutative_key: Option<&'a Key>,
+}
+
+/// Returns `true` if the speculative `result` of a deploy, executed against the pre-state while
+/// reading `read_set`, might differ from the result of executing it after the deploys committed
+/// so far.
+///
+/// A key read by the deploy and since written doesn't conflict if its value is unchanged from the
+/// pre-state, as with the payment purse balance which every deploy leaves empty.  Nor does the
+/// commutative key, i.e. the fee purse balance, if every deploy including this one only added to
+/// it.
+fn is_conflicting(
+    engine_state: &EngineState<LmdbGlobalState>,
+    scratch_state: &EngineState<ScratchGlobalState>,
+    check: ConflictCheck,
+    result: &ExecutionResults,
+    read_set: &ReadSet,
+) -> Result<bool, BlockExecutionError> {
+    if read_set.is_listing_affected_by(check.written_keys.keys()) {
+        return Ok(true);
+    }
+    let changed_keys: Vec<&Key> = read_set
+        .keys()
+        .filter(|key| match check.written_keys.get(*key) {
+            None => false,
+            Some(true) if Some(*key) == check.commutative_key => !is_only_added_to(result, key),
+            Some(_) => true,
+        })
+        .collect();
+    if changed_keys.is_empty() {
+        return Ok(false);
+    }
+    let (mut pre_state, mut current_state) = match (
+        engine_state.tracking_copy(check.pre_state_root_hash)?,
+        scratch_state.tracking_copy(check.state_root_hash)?,
+    ) {
+        (Some(pre_state), Some(current_state)) => (pre_state, current_state),
+        _ => return Ok(true),
+    };
+    let correlation_id = CorrelationId::new();
+    for key in changed_keys {
+        let pre_state_value = pre_state
+            .read(correlation_id, key)
+            .map_err(engine_state::Error::from)?;
+        let current_value = current_state
+            .read(correlation_id, key)
+            .map_err(engine_state::Error::from)?;
+        if pre_state_value != current_value {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns `true` if `result` adds to `key` and doesn't otherwise write to it.
+fn is_only_added_to(result: &ExecutionResults, key: &Key) -> bool {
+    let mut is_added_to = false;
+    for (_, transform) in result
+        .iter()
+        .flat_map(|result| result.execution_journal().iter())
+        .filter(|(journal_key, _)| journal_key == key)
+    {
+        match transform {
+            Transform::AddUInt512(_) => is_added_to = true,
+            Transform::Identity => {}
+            _ => return false,
+        }
+    }
+    is_added_to
+}
+
+/// Executes each of the given deploys on its own against the same pre-state, spread across
+/// `thread_count` threads, recording the keys each one reads.
+///
+/// Returns the results in the same order as `deploys`, with `None` for any deploy whose execution
+/// failed; such deploys are simply executed again sequentially by the caller.
+fn execute_speculatively<S, F>(
+    engine_state: &EngineState<S>,
+    metrics: Option<Arc<Metrics>>,
+    thread_count: usize,
+    deploys: &[Deploy],
+    create_execute_request: F,
+) -> Vec<Option<(ExecutionResults, ReadSet)>>
+where
+    S: StateProvider + CommitProvider + Sync,
+    S::Error: Into<execution::Error>,
+    F: Fn(&Deploy) -> ExecuteRequest + Sync,
+{
+    let next_index = &AtomicUsize::new(0);
+    let metrics = metrics.as_ref();
+    let create_execute_request = &create_execute_request;
+    let mut results: Vec<_> = deploys.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..thread_count.min(deploys.len()))
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut worker_results = vec![];
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let deploy = match deploys.get(index) {
+                            Some(deploy) => deploy,
+                            None => break worker_results,
+                        };
+                        let start = Instant::now();
+                        let result = engine_state.run_execute_recording_reads(
+                            CorrelationId::new(),
+                            create_execute_request(deploy),
+                        );
+                        if let Some(metrics) = metrics {
+                            metrics.run_execute.observe(start.elapsed().as_secs_f64());
+                        }
+                        match result {
+                            Ok(result) => worker_results.push((index, result)),
+                            Err(error) => {
+                                debug!(
+                                    deploy_hash = %deploy.hash(),
+                                    %error,
+                                    "speculative execution failed"
+                                );
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            match worker.join() {
+                Ok(worker_results) => {
+                    for (index, result) in worker_results {
+                        results[index] = Some(result);
+                    }
+                }
+                // A panic during execution would also occur when executing sequentially.
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+    });
+    results
+}
+
 fn commit_step<S>(
     engine_state: &EngineState<S>,
     maybe_metrics: Option<Arc<Metrics>>,
@@ -2,7 +2,7 @@ use std::{sync::Arc, time::Duration};
 
 use derive_more::{Display, From};
 use prometheus::Registry;
-use rand::RngCore;
+use rand::{Rng, RngCore};
 use serde::Serialize;
 use tempfile::TempDir;
 
@@ -392,3 +392,233 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         next_block_height
     );
 }
+
+/// Creates a native transfer of the minimum amount from the account of `secret_key` to `target`.
+fn new_transfer(chainspec: &Chainspec, secret_key: &SecretKey, target: PublicKey) -> Deploy {
+    let payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! {
+          "amount" => U512::from(chainspec.system_costs_config.wasmless_transfer_cost()),
+        },
+    };
+    let session = ExecutableDeployItem::Transfer {
+        args: runtime_args! {
+          "amount" => U512::from(chainspec.deploy_config.native_transfer_minimum_motes),
+          "target" => target,
+          "id" => Some(9_u64),
+        },
+    };
+    Deploy::new(
+        Timestamp::now(),
+        TimeDiff::from_seconds(100),
+        1,
+        vec![],
+        chainspec.network_config.name.clone(),
+        payment,
+        session,
+        secret_key,
+        None,
+    )
+}
+
+/// Commits genesis to a new contract runtime, then executes `finalized_block` as the first block
+/// using the given number of parallel execution threads.
+fn execute_first_block(
+    chainspec: &Chainspec,
+    chainspec_raw_bytes: &ChainspecRawBytes,
+    parallel_execution_threads: usize,
+    metrics: Option<Arc<Metrics>>,
+    finalized_block: FinalizedBlock,
+    deploys: Vec<Deploy>,
+) -> BlockAndExecutionResults {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        max_global_state_size: Some(100 * 1024 * 1024),
+        parallel_execution_threads: Some(parallel_execution_threads),
+        ..Config::default()
+    };
+    let contract_runtime = ContractRuntime::new(
+        chainspec.protocol_version(),
+        temp_dir.path(),
+        &config,
+        chainspec.wasm_config,
+        chainspec.system_costs_config,
+        chainspec.core_config.max_associated_keys,
+        chainspec.core_config.max_runtime_call_stack_height,
+        chainspec.core_config.minimum_delegation_amount,
+        chainspec.protocol_config.activation_point,
+        chainspec.core_config.prune_batch_size,
+        chainspec.core_config.strict_argument_checking,
+        chainspec.core_config.vesting_schedule_period.millis(),
+        Some(chainspec.core_config.max_delegators_per_validator),
+        &Registry::new(),
+        chainspec.core_config.administrators.clone(),
+        chainspec.core_config.allow_auction_bids,
+        chainspec.core_config.allow_unrestricted_transfers,
+        chainspec.core_config.refund_handling,
+        chainspec.core_config.fee_handling,
+    )
+    .unwrap();
+    let post_commit_genesis_state_hash = contract_runtime
+        .commit_genesis(chainspec, chainspec_raw_bytes)
+        .unwrap()
+        .post_state_hash;
+
+    execute_finalized_block(
+        contract_runtime.engine_state(),
+        metrics,
+        chainspec.protocol_version(),
+        ExecutionPreState::new(
+            0,
+            post_commit_genesis_state_hash,
+            BlockHash::default(),
+            Digest::default(),
+        ),
+        finalized_block,
+        deploys,
+        chainspec.protocol_config.activation_point.era_id(),
+        0,
+        chainspec.core_config.prune_batch_size,
+        parallel_execution_threads,
+    )
+    .unwrap()
+}
+
+#[test]
+fn parallel_execution_should_match_sequential_execution() {
+    testing::init_logging();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut rng = crate::new_rng();
+
+    let secret_keys: Vec<SecretKey> = (1..=5)
+        .map(|index| {
+            SecretKey::from_file(
+                RESOURCES_PATH
+                    .join("local")
+                    .join("secret_keys")
+                    .join(format!("node-{}.pem", index)),
+            )
+            .unwrap()
+        })
+        .collect();
+    let public_keys: Vec<PublicKey> = secret_keys.iter().map(PublicKey::from).collect();
+
+    // A mix of transfers between the genesis accounts, which conflict with each other, and to new
+    // accounts.
+    let deploys: Vec<Deploy> = (0..50)
+        .map(|_| {
+            let secret_key = &secret_keys[rng.gen_range(0..secret_keys.len())];
+            let target = if rng.gen() {
+                public_keys[rng.gen_range(0..public_keys.len())].clone()
+            } else {
+                PublicKey::random(&mut rng)
+            };
+            new_transfer(&chainspec, secret_key, target)
+        })
+        .collect();
+    let finalized_block = FinalizedBlock::new(
+        BlockPayload::new(
+            vec![],
+            deploys.iter().map(DeployHashWithApprovals::from).collect(),
+            vec![],
+            true,
+        ),
+        None,
+        Timestamp::now(),
+        EraId::new(0),
+        0,
+        PublicKey::System,
+    );
+
+    let sequential = execute_first_block(
+        &chainspec,
+        &chainspec_raw_bytes,
+        1,
+        None,
+        finalized_block.clone(),
+        deploys.clone(),
+    );
+    for parallel_execution_threads in [2, 8].iter().copied() {
+        let parallel = execute_first_block(
+            &chainspec,
+            &chainspec_raw_bytes,
+            parallel_execution_threads,
+            None,
+            finalized_block.clone(),
+            deploys.clone(),
+        );
+        assert_eq!(
+            parallel.block.state_root_hash(),
+            sequential.block.state_root_hash(),
+            "state root differs with {} threads",
+            parallel_execution_threads
+        );
+        assert_eq!(parallel.block, sequential.block);
+        assert_eq!(parallel.execution_results, sequential.execution_results);
+    }
+}
+
+#[test]
+fn parallel_execution_should_not_reexecute_transfers_between_disjoint_accounts() {
+    testing::init_logging();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut rng = crate::new_rng();
+
+    // Each genesis account makes a single transfer to a new account, so the deploys only share the
+    // payment and fee purses.
+    let deploys: Vec<Deploy> = (1..=5)
+        .map(|index| {
+            let secret_key = SecretKey::from_file(
+                RESOURCES_PATH
+                    .join("local")
+                    .join("secret_keys")
+                    .join(format!("node-{}.pem", index)),
+            )
+            .unwrap();
+            new_transfer(&chainspec, &secret_key, PublicKey::random(&mut rng))
+        })
+        .collect();
+    let finalized_block = FinalizedBlock::new(
+        BlockPayload::new(
+            vec![],
+            deploys.iter().map(DeployHashWithApprovals::from).collect(),
+            vec![],
+            true,
+        ),
+        None,
+        Timestamp::now(),
+        EraId::new(0),
+        0,
+        PublicKey::System,
+    );
+
+    let sequential = execute_first_block(
+        &chainspec,
+        &chainspec_raw_bytes,
+        1,
+        None,
+        finalized_block.clone(),
+        deploys.clone(),
+    );
+    let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+    let parallel = execute_first_block(
+        &chainspec,
+        &chainspec_raw_bytes,
+        4,
+        Some(Arc::clone(&metrics)),
+        finalized_block,
+        deploys.clone(),
+    );
+    assert_eq!(parallel.block, sequential.block);
+    assert_eq!(parallel.execution_results, sequential.execution_results);
+    assert!(
+        (metrics.reexecuted_deploys.get() as usize) < deploys.len(),
+        "re-executed {} of {} deploys",
+        metrics.reexecuted_deploys.get(),
+        deploys.len()
+    );
+}
@@ -0,0 +1,191 @@
+//! Background garbage collection of the tries in global state.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Instant,
+};
+
+use tokio::sync::oneshot;
+use tracing::{debug, error, info};
+
+use casper_execution_engine::{
+    core::engine_state::EngineState, storage::global_state::lmdb::LmdbGlobalState,
+};
+use casper_hashing::Digest;
+
+use super::ExecutionPreState;
+
+/// The maximum number of tries read or deleted in a single transaction.
+const TRIE_GC_BATCH_SIZE: usize = 10_000;
+/// The maximum number of reachable tries, whose hashes are held in memory while marking, beyond
+/// which a collection is abandoned.
+const TRIE_GC_MAX_REACHABLE_TRIES: usize = 50_000_000;
+
+/// The state root whose global state is being synchronized from peers, if any, and the one most
+/// recently synchronized in full.
+///
+/// Shared between the global state synchronizer and the trie garbage collector, so that the tries
+/// of a partially synchronized state, which are not yet reachable from any state root, are not
+/// deleted.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GlobalStateSyncTracker {
+    inner: Arc<Mutex<GlobalStateSyncs>>,
+}
+
+#[derive(Debug, Default)]
+struct GlobalStateSyncs {
+    in_progress: Option<Digest>,
+    last_completed: Option<Digest>,
+}
+
+impl GlobalStateSyncTracker {
+    /// Records that synchronization of the global state under `state_root_hash` has started.
+    pub(crate) fn sync_started(&self, state_root_hash: Digest) {
+        self.lock().in_progress = Some(state_root_hash);
+    }
+
+    /// Records that synchronization of the global state under `state_root_hash` has finished,
+    /// either in full or by being cancelled.
+    pub(crate) fn sync_finished(&self, state_root_hash: Digest, completed: bool) {
+        let mut syncs = self.lock();
+        if syncs.in_progress == Some(state_root_hash) {
+            syncs.in_progress = None;
+        }
+        if completed {
+            syncs.last_completed = Some(state_root_hash);
+        }
+    }
+
+    pub(crate) fn is_sync_in_progress(&self) -> bool {
+        self.lock().in_progress.is_some()
+    }
+
+    pub(crate) fn last_completed(&self) -> Option<Digest> {
+        self.lock().last_completed
+    }
+
+    fn lock(&self) -> MutexGuard<'_, GlobalStateSyncs> {
+        self.inner
+            .lock()
+            .expect("GlobalStateSyncTracker: poisoned mutex")
+    }
+}
+
+/// Tracks the state roots of recently executed blocks and periodically deletes the tries which are
+/// unreachable from them.
+#[derive(Debug)]
+pub(super) struct TrieGarbageCollector {
+    /// The number of most recent post-state roots to retain.
+    retained_state_roots: usize,
+    /// The most recent post-state roots, oldest first, along with the number of blocks executed
+    /// since the last collection started.
+    recent_state_roots: Mutex<(VecDeque<Digest>, usize)>,
+    /// Whether a collection is currently running.
+    running: AtomicBool,
+    /// The global state synchronization in progress, during which no collection is started, and
+    /// the last completed one, whose state root is retained.
+    global_state_syncs: GlobalStateSyncTracker,
+}
+
+impl TrieGarbageCollector {
+    /// Returns a new collector, or `None` if `retained_state_roots` is 0.
+    pub(super) fn new(
+        retained_state_roots: u64,
+        global_state_syncs: GlobalStateSyncTracker,
+    ) -> Option<Arc<Self>> {
+        if retained_state_roots == 0 {
+            return None;
+        }
+        let retained_state_roots = retained_state_roots as usize;
+        Some(Arc::new(TrieGarbageCollector {
+            retained_state_roots,
+            recent_state_roots: Mutex::new((VecDeque::with_capacity(retained_state_roots), 0)),
+            running: AtomicBool::new(false),
+            global_state_syncs,
+        }))
+    }
+
+    /// Records the post-state root of an executed block, starting a collection in the background
+    /// once `retained_state_roots` blocks have been executed since the last one started. While a
+    /// global state synchronization is in progress, the collection is postponed until the first
+    /// block executed after it finishes.
+    ///
+    /// Must be called before the shared pre-state is updated to the executed block, so that no
+    /// later block can be committed before the started collection is tracking writes.
+    pub(super) async fn record_state_root(
+        self: &Arc<Self>,
+        engine_state: &Arc<EngineState<LmdbGlobalState>>,
+        shared_pre_state: &Arc<Mutex<ExecutionPreState>>,
+        state_root_hash: Digest,
+    ) {
+        {
+            let mut recent_state_roots = self
+                .recent_state_roots
+                .lock()
+                .expect("TrieGarbageCollector: recent_state_roots poisoned mutex");
+            let (roots, blocks_since_last_run) = &mut *recent_state_roots;
+            roots.push_back(state_root_hash);
+            while roots.len() > self.retained_state_roots {
+                roots.pop_front();
+            }
+            *blocks_since_last_run += 1;
+            if *blocks_since_last_run < self.retained_state_roots
+                || self.global_state_syncs.is_sync_in_progress()
+                || self.running.swap(true, Ordering::SeqCst)
+            {
+                return;
+            }
+            *blocks_since_last_run = 0;
+        }
+
+        let (roots_taken_sender, roots_taken_receiver) = oneshot::channel();
+        let collector = Arc::clone(self);
+        let engine_state = Arc::clone(engine_state);
+        let shared_pre_state = Arc::clone(shared_pre_state);
+        tokio::task::spawn_blocking(move || {
+            let start = Instant::now();
+            let result = engine_state.get_state().collect_garbage(
+                || {
+                    let mut roots: Vec<Digest> = collector
+                        .recent_state_roots
+                        .lock()
+                        .expect("TrieGarbageCollector: recent_state_roots poisoned mutex")
+                        .0
+                        .iter()
+                        .copied()
+                        .collect();
+                    roots.push(
+                        shared_pre_state
+                            .lock()
+                            .expect("TrieGarbageCollector: shared_pre_state poisoned mutex")
+                            .pre_state_root_hash,
+                    );
+                    roots.extend(collector.global_state_syncs.last_completed());
+                    let _ = roots_taken_sender.send(());
+                    roots
+                },
+                TRIE_GC_BATCH_SIZE,
+                TRIE_GC_MAX_REACHABLE_TRIES,
+            );
+            match result {
+                Ok(result) => info!(
+                    reachable_tries = result.reachable_tries,
+                    deleted_tries = result.deleted_tries,
+                    duration_secs = start.elapsed().as_secs_f64(),
+                    "collected garbage tries"
+                ),
+                Err(error) => error!(%error, "failed to collect garbage tries"),
+            }
+            collector.running.store(false, Ordering::SeqCst);
+        });
+
+        // If the collection fails before taking the retained roots, the sender is dropped.
+        if roots_taken_receiver.await.is_err() {
+            debug!("trie garbage collection did not start");
+        }
+    }
+}
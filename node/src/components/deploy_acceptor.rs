@@ -27,16 +27,20 @@ use casper_types::{
 };
 
 use crate::{
-    components::Component,
+    components::{
+        deploy_buffer::{PendingDeploy, PendingDeployState, PendingLimitExceeded},
+        Component,
+    },
     effect::{
         announcements::{DeployAcceptorAnnouncement, FatalAnnouncement},
-        requests::{ContractRuntimeRequest, StorageRequest},
+        requests::{ContractRuntimeRequest, DeployBufferRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects, Responder,
     },
     fatal,
     types::{
         chainspec::{CoreConfig, DeployConfig},
-        BlockHash, BlockHeader, Chainspec, Deploy, DeployConfigurationFailure, FinalizedApprovals,
+        BlockHash, BlockHeader, Chainspec, Deploy, DeployConfigurationFailure, DeployHash,
+        FinalizedApprovals,
     },
     utils::Source,
     NodeRng,
@@ -82,6 +86,14 @@ pub(crate) enum Error {
         /// The timestamp when the node validated the expiry timestamp.
         current_node_timestamp: Timestamp,
     },
+
+    /// The deploy is invalid as a replacement for a previously-received deploy.
+    #[error("invalid replacement deploy: {0}")]
+    InvalidReplacement(ReplacementFailure),
+
+    /// The deploy's account has too many deploys pending in the deploy buffer.
+    #[error("pending deploy limit exceeded: {0}")]
+    PendingLimitExceeded(PendingLimitExceeded),
 }
 
 impl Error {
@@ -144,12 +156,32 @@ pub(crate) enum DeployParameterFailure {
     MissingModuleBytes,
 }
 
+/// A representation of the way in which a deploy failed the checks applied to replacements of
+/// previously-received deploys.
+#[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Error, Serialize)]
+pub(crate) enum ReplacementFailure {
+    /// A cancellation doesn't name any pending deploy from the same account.
+    #[error("cancellation doesn't name any pending deploy from the same account")]
+    NothingToCancel,
+    /// The replacement doesn't have a higher gas price than the deploy it replaces.
+    #[error(
+        "gas price {gas_price} must be higher than gas price {replaced_gas_price} of replaced \
+        deploy {replaced_deploy_hash}"
+    )]
+    GasPriceTooLow {
+        replaced_deploy_hash: DeployHash,
+        replaced_gas_price: u64,
+        gas_price: u64,
+    },
+}
+
 /// A helper trait constraining `DeployAcceptor` compatible reactor events.
 pub(crate) trait ReactorEventT:
     From<Event>
     + From<DeployAcceptorAnnouncement>
     + From<StorageRequest>
     + From<ContractRuntimeRequest>
+    + From<DeployBufferRequest>
     + From<FatalAnnouncement>
     + Send
 {
@@ -160,6 +192,7 @@ impl<REv> ReactorEventT for REv where
         + From<DeployAcceptorAnnouncement>
         + From<StorageRequest>
         + From<ContractRuntimeRequest>
+        + From<DeployBufferRequest>
         + From<FatalAnnouncement>
         + Send
 {
@@ -184,7 +217,11 @@ impl<REv> ReactorEventT for REv where
 ///
 ///     Start --> A{has valid size?}
 ///     A -->|Yes| B{"is compliant with config?<br/>(size, chain name, ttl, etc.)"}
-///     G -->|Yes| ZZ[Accept]
+///     G -->|Yes| R{"is valid<br/>replacement?<br/>(if from client<br/>or gossiped)"}
+///     R -->|Yes| L{"is within account's<br/>pending limits?<br/>(if from client<br/>or gossiped)"}
+///     R -->|No| Z
+///     L -->|Yes| ZZ[Accept]
+///     L -->|No| Z
 ///     B -->|Yes| C{is from<br/>client?}
 ///     C -->|Yes| CLIENT{has expired?}
 ///     B -->|No| Z[Reject]
@@ -648,7 +685,10 @@ impl DeployAcceptor {
                 }
             }
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                if module_bytes.is_empty() {
+                // empty module bytes are only permitted for cancellations.
+                let is_cancellation = self.deploy_config.allow_deploy_replacement
+                    && event_metadata.deploy.is_cancellation();
+                if module_bytes.is_empty() && !is_cancellation {
                     let error = Error::parameter_failure(
                         &block_header,
                         DeployParameterFailure::MissingModuleBytes,
@@ -913,6 +953,107 @@ impl DeployAcceptor {
             return effects;
         }
 
+        // We check replacements of deploys still pending in the deploy buffer for deploys received
+        // from clients or gossiped by peers.  Deploys fetched from peers may be needed to execute a
+        // block, so are exempt.
+        let header = event_metadata.deploy.header();
+        if self.deploy_config.allow_deploy_replacement
+            && matches!(
+                event_metadata.source,
+                Source::Client | Source::PeerGossiped(_)
+            )
+            && !header.dependencies().is_empty()
+        {
+            let dependencies = header.dependencies().clone();
+            return effect_builder
+                .get_pending_deploys(Some(header.account().clone()))
+                .event(move |pending_deploys| Event::GetReplacedDeploysResult {
+                    event_metadata,
+                    replaced_deploys: pending_deploys
+                        .into_iter()
+                        .filter(|pending_deploy| {
+                            pending_deploy.state != PendingDeployState::Dead
+                                && dependencies.contains(&pending_deploy.deploy_hash)
+                        })
+                        .collect(),
+                    verification_start_timestamp,
+                });
+        }
+
+        self.check_pending_limits(effect_builder, event_metadata, verification_start_timestamp)
+    }
+
+    fn handle_get_replaced_deploys_result<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        replaced_deploys: Vec<PendingDeploy>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        if let Err(failure) = check_replacement(&event_metadata.deploy, &replaced_deploys) {
+            debug!(%failure, "invalid replacement deploy");
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                Error::InvalidReplacement(failure),
+                verification_start_timestamp,
+            );
+        }
+        self.check_pending_limits(effect_builder, event_metadata, verification_start_timestamp)
+    }
+
+    fn check_pending_limits<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        // We apply the per-account limits to deploys received from clients or gossiped by peers.
+        // Deploys fetched from peers may be needed to execute a block, so are exempt.
+        if !matches!(
+            event_metadata.source,
+            Source::Client | Source::PeerGossiped(_)
+        ) {
+            return self.put_to_storage(
+                effect_builder,
+                event_metadata,
+                verification_start_timestamp,
+            );
+        }
+        effect_builder
+            .check_pending_deploy_limits(Arc::clone(&event_metadata.deploy))
+            .event(move |result| Event::CheckPendingLimitsResult {
+                event_metadata,
+                result,
+                verification_start_timestamp,
+            })
+    }
+
+    fn handle_check_pending_limits_result<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        result: Result<(), PendingLimitExceeded>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        if let Err(limit_exceeded) = result {
+            debug!(%limit_exceeded, "deploy exceeds pending limits");
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                Error::PendingLimitExceeded(limit_exceeded),
+                verification_start_timestamp,
+            );
+        }
+        self.put_to_storage(effect_builder, event_metadata, verification_start_timestamp)
+    }
+
+    fn put_to_storage<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
         effect_builder
             .put_deploy_to_storage(event_metadata.deploy.clone())
             .event(move |is_new| Event::PutToStorageResult {
@@ -937,6 +1078,7 @@ impl DeployAcceptor {
         if !matches!(source, Source::SpeculativeExec(_)) {
             self.metrics.observe_rejected(verification_start_timestamp);
         }
+        let reason = error.to_string();
         let mut effects = Effects::new();
         if let Some(responder) = maybe_responder {
             // The client has submitted an invalid deploy
@@ -948,7 +1090,7 @@ impl DeployAcceptor {
         if !matches!(source, Source::SpeculativeExec(_)) {
             effects.extend(
                 effect_builder
-                    .announce_invalid_deploy(deploy, source)
+                    .announce_invalid_deploy(deploy, source, reason)
                     .ignore(),
             );
         }
@@ -1026,6 +1168,38 @@ impl DeployAcceptor {
     }
 }
 
+/// Checks that `deploy` is a valid replacement for those of `replaced_deploys` which are from the
+/// same account, where `replaced_deploys` are the deploys pending in the deploy buffer named in its
+/// dependencies.
+///
+/// Deploys from other accounts aren't replaced, so are ignored.
+fn check_replacement(
+    deploy: &Deploy,
+    replaced_deploys: &[PendingDeploy],
+) -> Result<(), ReplacementFailure> {
+    let account = deploy.header().account();
+    let mut replaced_deploys = replaced_deploys
+        .iter()
+        .filter(|replaced_deploy| replaced_deploy.account == *account)
+        .peekable();
+    if deploy.is_cancellation() {
+        return match replaced_deploys.peek() {
+            Some(_) => Ok(()),
+            None => Err(ReplacementFailure::NothingToCancel),
+        };
+    }
+    for replaced_deploy in replaced_deploys {
+        if deploy.header().gas_price() <= replaced_deploy.gas_price {
+            return Err(ReplacementFailure::GasPriceTooLow {
+                replaced_deploy_hash: replaced_deploy.deploy_hash,
+                replaced_gas_price: replaced_deploy.gas_price,
+                gas_price: deploy.header().gas_price(),
+            });
+        }
+    }
+    Ok(())
+}
+
 impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
     type Event = Event;
 
@@ -1121,6 +1295,26 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 maybe_contract_package,
                 verification_start_timestamp,
             ),
+            Event::GetReplacedDeploysResult {
+                event_metadata,
+                replaced_deploys,
+                verification_start_timestamp,
+            } => self.handle_get_replaced_deploys_result(
+                effect_builder,
+                event_metadata,
+                replaced_deploys,
+                verification_start_timestamp,
+            ),
+            Event::CheckPendingLimitsResult {
+                event_metadata,
+                result,
+                verification_start_timestamp,
+            } => self.handle_check_pending_limits_result(
+                effect_builder,
+                event_metadata,
+                result,
+                verification_start_timestamp,
+            ),
             Event::PutToStorageResult {
                 event_metadata,
                 is_new,
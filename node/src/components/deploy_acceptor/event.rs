@@ -12,7 +12,10 @@ use casper_types::{
 
 use super::Source;
 use crate::{
-    components::deploy_acceptor::Error,
+    components::{
+        deploy_acceptor::Error,
+        deploy_buffer::{PendingDeploy, PendingLimitExceeded},
+    },
     effect::Responder,
     types::{BlockHeader, Deploy},
 };
@@ -48,6 +51,19 @@ pub(crate) enum Event {
         source: Source,
         maybe_responder: Option<Responder<Result<(), Error>>>,
     },
+    /// The result of querying the deploy buffer for the pending deploys which a `Deploy` replaces.
+    GetReplacedDeploysResult {
+        event_metadata: Box<EventMetadata>,
+        replaced_deploys: Vec<PendingDeploy>,
+        verification_start_timestamp: Timestamp,
+    },
+    /// The result of checking with the deploy buffer that a `Deploy` is within the limits on
+    /// pending deploys for its account.
+    CheckPendingLimitsResult {
+        event_metadata: Box<EventMetadata>,
+        result: Result<(), PendingLimitExceeded>,
+        verification_start_timestamp: Timestamp,
+    },
     /// The result of the `DeployAcceptor` putting a `Deploy` to the storage component.
     PutToStorageResult {
         event_metadata: Box<EventMetadata>,
@@ -154,6 +170,20 @@ impl Display for Event {
                     event_metadata.deploy.hash()
                 )
             }
+            Event::GetReplacedDeploysResult { event_metadata, .. } => {
+                write!(
+                    formatter,
+                    "verifying replaced deploys to validate deploy with hash {}",
+                    event_metadata.deploy.hash()
+                )
+            }
+            Event::CheckPendingLimitsResult { event_metadata, .. } => {
+                write!(
+                    formatter,
+                    "verifying pending limits to validate deploy with hash {}",
+                    event_metadata.deploy.hash()
+                )
+            }
             Event::GetAccountResult { event_metadata, .. } => {
                 write!(
                     formatter,
@@ -39,8 +39,8 @@ use crate::{
     effect::{
         announcements::{ControlAnnouncement, DeployAcceptorAnnouncement},
         requests::{
-            ContractRuntimeRequest, MakeBlockExecutableRequest, MarkBlockCompletedRequest,
-            NetworkRequest,
+            ContractRuntimeRequest, DeployBufferRequest, MakeBlockExecutableRequest,
+            MarkBlockCompletedRequest, NetworkRequest,
         },
         Responder,
     },
@@ -78,6 +78,8 @@ enum Event {
     #[from]
     StorageRequest(StorageRequest),
     #[from]
+    DeployBufferRequest(#[serde(skip_serializing)] DeployBufferRequest),
+    #[from]
     NetworkRequest(NetworkRequest<Message>),
 }
 
@@ -124,6 +126,9 @@ impl Display for Event {
                 write!(formatter, "contract-runtime event: {:?}", event)
             }
             Event::StorageRequest(request) => write!(formatter, "storage request: {:?}", request),
+            Event::DeployBufferRequest(request) => {
+                write!(formatter, "deploy buffer request: {}", request)
+            }
             Event::NetworkRequest(request) => write!(formatter, "network request: {:?}", request),
         }
     }
@@ -169,6 +174,7 @@ enum TestScenario {
     FromClientInsufficientBalance,
     FromClientValidDeploy,
     FromClientRepeatedValidDeploy,
+    FromClientExceedingPendingLimit,
     FromClientAccountWithInsufficientWeight,
     FromClientAccountWithInvalidAssociatedKeys,
     AccountWithUnknownBalance,
@@ -218,6 +224,7 @@ impl TestScenario {
             | TestScenario::FromClientInsufficientBalance
             | TestScenario::FromClientValidDeploy
             | TestScenario::FromClientRepeatedValidDeploy
+            | TestScenario::FromClientExceedingPendingLimit
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::AccountWithUnknownBalance
@@ -254,6 +261,7 @@ impl TestScenario {
             | TestScenario::FromClientInsufficientBalance
             | TestScenario::FromClientValidDeploy
             | TestScenario::FromClientRepeatedValidDeploy
+            | TestScenario::FromClientExceedingPendingLimit
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::AccountWithUnknownBalance
@@ -371,6 +379,7 @@ impl TestScenario {
             | TestScenario::FromClientInsufficientBalance
             | TestScenario::FromClientMissingAccount
             | TestScenario::FromClientInvalidDeploy
+            | TestScenario::FromClientExceedingPendingLimit
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::AccountWithUnknownBalance
@@ -690,6 +699,19 @@ impl reactor::Reactor for Reactor {
                 }
                 _ => panic!("should not receive {:?}", event),
             },
+            Event::DeployBufferRequest(DeployBufferRequest::CheckPendingLimits {
+                responder,
+                ..
+            }) => {
+                let result = if self.test_scenario == TestScenario::FromClientExceedingPendingLimit
+                {
+                    Err(PendingLimitExceeded::AccountDeployCount { max: 0 })
+                } else {
+                    Ok(())
+                };
+                responder.respond(result).ignore()
+            }
+            Event::DeployBufferRequest(request) => panic!("should not receive {}", request),
             Event::NetworkRequest(_) => panic!("test does not handle network requests"),
         }
     }
@@ -868,6 +890,7 @@ async fn run_deploy_acceptor_without_timeout(
             TestScenario::FromClientInvalidDeploy
             | TestScenario::FromClientMissingAccount
             | TestScenario::FromClientInsufficientBalance
+            | TestScenario::FromClientExceedingPendingLimit
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::DeployWithEmptySessionModuleBytes
@@ -1143,6 +1166,18 @@ async fn should_reject_valid_deploy_from_client_for_account_with_insufficient_we
     ))
 }
 
+#[tokio::test]
+async fn should_reject_valid_deploy_from_client_exceeding_pending_limit() {
+    let test_scenario = TestScenario::FromClientExceedingPendingLimit;
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::PendingLimitExceeded(
+            PendingLimitExceeded::AccountDeployCount { .. }
+        ))
+    ))
+}
+
 #[tokio::test]
 async fn should_reject_valid_deploy_from_client_for_insufficient_balance() {
     let result = run_deploy_acceptor(TestScenario::FromClientInsufficientBalance).await;
@@ -1671,3 +1706,80 @@ async fn should_reject_valid_deploy_from_peer_signed_by_non_administrator() {
     let result = run_deploy_acceptor(test_scenario).await;
     assert!(result.is_err())
 }
+
+fn pending_deploy(deploy: &Deploy) -> PendingDeploy {
+    PendingDeploy::new(
+        *deploy.hash(),
+        &deploy.footprint().unwrap(),
+        deploy.approvals().len(),
+        PendingDeployState::Proposable,
+    )
+}
+
+#[test]
+fn should_check_replacement_gas_price() {
+    let mut rng = crate::new_rng();
+    let secret_key = SecretKey::random(&mut rng);
+    let replaced =
+        Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 2, vec![]);
+    // deploys from other accounts aren't replaced, so don't constrain the gas price
+    let other_secret_key = SecretKey::random(&mut rng);
+    let other_account_deploy = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &other_secret_key,
+        5,
+        vec![],
+    );
+    let dependencies = vec![*replaced.hash(), *other_account_deploy.hash()];
+    let pending_deploys = vec![
+        pending_deploy(&replaced),
+        pending_deploy(&other_account_deploy),
+    ];
+
+    let replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &secret_key,
+        3,
+        dependencies.clone(),
+    );
+    assert!(check_replacement(&replacement, &pending_deploys).is_ok());
+
+    let underpriced_replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &secret_key,
+        2,
+        dependencies,
+    );
+    assert_eq!(
+        check_replacement(&underpriced_replacement, &pending_deploys),
+        Err(ReplacementFailure::GasPriceTooLow {
+            replaced_deploy_hash: *replaced.hash(),
+            replaced_gas_price: 2,
+            gas_price: 2,
+        })
+    );
+}
+
+#[test]
+fn should_check_cancellation_names_deploy_from_same_account() {
+    let mut rng = crate::new_rng();
+    let secret_key = SecretKey::random(&mut rng);
+    let cancelled =
+        Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 2, vec![]);
+
+    let cancellation = Deploy::random_cancellation(&mut rng, &secret_key, vec![*cancelled.hash()]);
+    assert!(cancellation.is_cancellation());
+    assert!(check_replacement(&cancellation, &[pending_deploy(&cancelled)]).is_ok());
+    assert_eq!(
+        check_replacement(&cancellation, &[]),
+        Err(ReplacementFailure::NothingToCancel)
+    );
+
+    let other_secret_key = SecretKey::random(&mut rng);
+    let other_account_cancellation =
+        Deploy::random_cancellation(&mut rng, &other_secret_key, vec![*cancelled.hash()]);
+    assert_eq!(
+        check_replacement(&other_account_cancellation, &[pending_deploy(&cancelled)]),
+        Err(ReplacementFailure::NothingToCancel)
+    );
+}
@@ -1,10 +1,13 @@
 mod config;
 mod event;
 mod metrics;
+mod pending_deploy;
+mod selection_policy;
 #[cfg(test)]
 mod tests;
 
 use std::{
+    cmp::Reverse,
     collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryInto,
     iter::FromIterator,
@@ -18,7 +21,7 @@ use prometheus::Registry;
 use smallvec::smallvec;
 use tracing::{debug, error, info, warn};
 
-use casper_types::Timestamp;
+use casper_types::{bytesrepr::ToBytes, PublicKey, Timestamp};
 
 use crate::{
     components::{
@@ -36,14 +39,16 @@ use crate::{
     types::{
         appendable_block::{AddError, AppendableBlock},
         chainspec::DeployConfig,
-        Approval, Block, Deploy, DeployFootprint, DeployHash, DeployHashWithApprovals, DeployId,
-        FinalizedBlock,
+        Approval, Block, BlockHash, Deploy, DeployFootprint, DeployHash, DeployHashWithApprovals,
+        DeployId, DeployStatus, FinalizedBlock,
     },
     utils::DisplayIter,
     NodeRng,
 };
 pub(crate) use config::Config;
 pub(crate) use event::Event;
+pub(crate) use pending_deploy::{PendingDeploy, PendingDeployState, PendingLimitExceeded};
+pub(crate) use selection_policy::SelectionPolicy;
 
 use metrics::Metrics;
 
@@ -72,6 +77,19 @@ pub(crate) struct DeployBuffer {
     hold: BTreeMap<Timestamp, HashSet<DeployHash>>,
     // deploy_hashes that should not be proposed, ever
     dead: HashSet<DeployHash>,
+    // The deploys in `buffer` with known footprints, indexed by account.  Used to check the limits
+    // on pending deploys without scanning the whole buffer.
+    deploys_by_account: HashMap<PublicKey, HashSet<DeployHash>>,
+    // Cancellations in `buffer`.  These are dead as soon as buffered, but still count towards the
+    // limits on pending deploys for their account until they expire.
+    cancellations: HashSet<DeployHash>,
+    // The statuses of recently-seen deploys, along with the time each last changed.
+    //
+    // Entries are removed once unchanged for longer than the configured retention period.  Beyond
+    // the configured maximum, new statuses of deploys not held in `buffer` are only announced.
+    statuses: HashMap<DeployHash, (Timestamp, DeployStatus)>,
+    // status changes not yet announced
+    pending_status_changes: Vec<(DeployHash, DeployStatus)>,
     // deploy buffer metrics
     #[data_size(skip)]
     metrics: Metrics,
@@ -91,6 +109,10 @@ impl DeployBuffer {
             buffer: HashMap::new(),
             hold: BTreeMap::new(),
             dead: HashSet::new(),
+            deploys_by_account: HashMap::new(),
+            cancellations: HashSet::new(),
+            statuses: HashMap::new(),
+            pending_status_changes: vec![],
             metrics: Metrics::new(registry)?,
         })
     }
@@ -166,6 +188,13 @@ impl DeployBuffer {
         });
         self.hold.retain(|_, remaining| !remaining.is_empty());
 
+        for (deploy_hash, (_, maybe_data)) in &freed {
+            if let Some((footprint, _)) = maybe_data {
+                self.remove_from_account_index(deploy_hash, footprint.header.account());
+            }
+            self.cancellations.remove(deploy_hash);
+        }
+
         // retain all those in `dead` which are not in `freed`, at the same time reducing `freed` to
         // only those entries not also in `dead` - i.e. deploys which expired without being included
         // in a block
@@ -173,6 +202,17 @@ impl DeployBuffer {
             .retain(|deploy_hash| freed.remove(deploy_hash).is_none());
         self.buffer = buffer;
 
+        for deploy_hash in freed.keys() {
+            self.set_status(*deploy_hash, DeployStatus::Expired);
+        }
+        // statuses of deploys still pending are retained regardless of age
+        let retain_after = now.saturating_sub(self.cfg.status_retention_period());
+        let (buffer, dead) = (&self.buffer, &self.dead);
+        self.statuses.retain(|deploy_hash, (last_changed, _)| {
+            *last_changed >= retain_after
+                || (buffer.contains_key(deploy_hash) && !dead.contains(deploy_hash))
+        });
+
         if !freed.is_empty() {
             info!(
                 "DeployBuffer: expiring without executing {} deploy(s)",
@@ -232,8 +272,27 @@ impl DeployBuffer {
                 return;
             }
         };
+        if !self.buffer.contains_key(deploy_hash) {
+            if let Err(limit_exceeded) = self.check_pending_limits(&deploy) {
+                info!(%deploy_hash, %limit_exceeded, "DeployBuffer: deploy not buffered");
+                self.set_status(*deploy_hash, DeployStatus::Evicted);
+                return;
+            }
+            if self.deploy_config.allow_deploy_replacement
+                && deploy.is_cancellation()
+                && !self.replaces_pending_deploy(&deploy)
+            {
+                info!(%deploy_hash, "DeployBuffer: cancellation of no pending deploy not buffered");
+                self.set_status(*deploy_hash, DeployStatus::Evicted);
+                return;
+            }
+        }
         let expiry_time = deploy.header().expires();
         let approvals = deploy.approvals().clone();
+        self.deploys_by_account
+            .entry(deploy.header().account().clone())
+            .or_default()
+            .insert(*deploy_hash);
         match self
             .buffer
             .insert(*deploy_hash, (expiry_time, Some((footprint, approvals))))
@@ -246,11 +305,179 @@ impl DeployBuffer {
                 self.metrics.total_deploys.inc();
             }
         }
+        self.set_status(*deploy_hash, DeployStatus::Buffered);
+        if self.deploy_config.allow_deploy_replacement {
+            self.register_replacement(&deploy);
+        }
+        self.evict_excess_deploys();
+    }
+
+    /// Checks that buffering the given deploy wouldn't take its account over the configured limits
+    /// on pending deploys.
+    ///
+    /// Deploys which the given one would replace don't count towards the limits, while buffered
+    /// cancellations do until they expire.
+    fn check_pending_limits(&self, deploy: &Deploy) -> Result<(), PendingLimitExceeded> {
+        let mut count: u32 = 1;
+        let mut bytes = deploy.serialized_length() as u64;
+        let account_deploys = self
+            .deploys_by_account
+            .get(deploy.header().account())
+            .into_iter()
+            .flatten();
+        for deploy_hash in account_deploys {
+            if deploy_hash == deploy.hash()
+                || (self.dead.contains(deploy_hash) && !self.cancellations.contains(deploy_hash))
+            {
+                continue;
+            }
+            let footprint = match self.buffer.get(deploy_hash) {
+                Some((_, Some((footprint, _)))) => footprint,
+                Some((_, None)) | None => continue,
+            };
+            let is_replaced = self.deploy_config.allow_deploy_replacement
+                && deploy.header().dependencies().contains(deploy_hash)
+                && deploy.can_replace(&footprint.header);
+            if is_replaced {
+                continue;
+            }
+            count = count.saturating_add(1);
+            bytes = bytes.saturating_add(footprint.size_estimate as u64);
+        }
+        if count > self.cfg.max_pending_deploys_per_account() {
+            return Err(PendingLimitExceeded::AccountDeployCount {
+                max: self.cfg.max_pending_deploys_per_account(),
+            });
+        }
+        if bytes > self.cfg.max_pending_bytes_per_account() {
+            return Err(PendingLimitExceeded::AccountDeployBytes {
+                max: self.cfg.max_pending_bytes_per_account(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the given deploy names in its dependencies a pending deploy which it can
+    /// replace.
+    fn replaces_pending_deploy(&self, deploy: &Deploy) -> bool {
+        deploy.header().dependencies().iter().any(|replaced_hash| {
+            match self.buffer.get(replaced_hash) {
+                Some((_, Some((footprint, _)))) => {
+                    !self.dead.contains(replaced_hash) && deploy.can_replace(&footprint.header)
+                }
+                Some((_, None)) | None => false,
+            }
+        })
+    }
+
+    /// Removes the given deploy from the index of buffered deploys by account.
+    fn remove_from_account_index(&mut self, deploy_hash: &DeployHash, account: &PublicKey) {
+        if let Some(account_deploys) = self.deploys_by_account.get_mut(account) {
+            account_deploys.remove(deploy_hash);
+            if account_deploys.is_empty() {
+                self.deploys_by_account.remove(account);
+            }
+        }
+    }
+
+    /// Evicts proposable deploys while the number of pending deploys exceeds the configured
+    /// maximum.
+    ///
+    /// Deploys with the lowest gas price are evicted first, and the newest first among those with
+    /// equal gas price.  Held deploys are never evicted.
+    fn evict_excess_deploys(&mut self) {
+        let max_buffered_deploys = self.cfg.max_buffered_deploys() as usize;
+        // `buffer` includes dead deploys, so this is a cheap upper bound on pending deploys
+        if self.buffer.len() <= max_buffered_deploys {
+            return;
+        }
+        let pending_count = self
+            .buffer
+            .iter()
+            .filter(|(deploy_hash, (_, maybe_data))| {
+                maybe_data.is_some() && !self.dead.contains(deploy_hash)
+            })
+            .count();
+        if pending_count <= max_buffered_deploys {
+            return;
+        }
+        let evicted: Vec<DeployHash> = self
+            .proposable()
+            .into_iter()
+            .map(|(with_approvals, footprint)| {
+                (
+                    footprint.header.gas_price(),
+                    Reverse(footprint.header.timestamp()),
+                    *with_approvals.deploy_hash(),
+                )
+            })
+            .sorted()
+            .take(pending_count - max_buffered_deploys)
+            .map(|(_, _, deploy_hash)| deploy_hash)
+            .collect();
+        info!(
+            "DeployBuffer: evicting {} deploy(s) as the buffer is full",
+            evicted.len()
+        );
+        for deploy_hash in evicted {
+            debug!(%deploy_hash, "DeployBuffer: deploy evicted");
+            if let Some((_, Some((footprint, _)))) = self.buffer.remove(&deploy_hash) {
+                self.remove_from_account_index(&deploy_hash, footprint.header.account());
+            }
+            self.set_status(deploy_hash, DeployStatus::Evicted);
+        }
+        self.update_all_metrics();
+    }
+
+    /// Marks as dead any pending deploys replaced by the given newly-buffered deploy.
+    ///
+    /// A deploy replaces each pending deploy named in its dependencies which is from the same
+    /// account, provided it is either a cancellation or has a higher gas price.  Cancellations are
+    /// never proposed themselves.
+    ///
+    /// Replacement is best-effort: it only affects this node's proposals, so a replaced deploy can
+    /// still be included in a block proposed by a node which hasn't yet seen its replacement.
+    fn register_replacement(&mut self, deploy: &Deploy) {
+        let deploy_hash = deploy.hash();
+        for replaced_hash in deploy.header().dependencies() {
+            if self.dead.contains(replaced_hash) {
+                continue;
+            }
+            let can_replace = match self.buffer.get(replaced_hash) {
+                Some((_, Some((footprint, _)))) => deploy.can_replace(&footprint.header),
+                Some((_, None)) | None => false,
+            };
+            if !can_replace {
+                continue;
+            }
+            info!(%replaced_hash, replacement = %deploy_hash, "DeployBuffer: deploy replaced");
+            self.dead.insert(*replaced_hash);
+            self.set_status(
+                *replaced_hash,
+                DeployStatus::Replaced {
+                    replacement: *deploy_hash,
+                },
+            );
+        }
+        if deploy.is_cancellation() {
+            debug!(%deploy_hash, "DeployBuffer: cancellation will not be proposed");
+            self.dead.insert(*deploy_hash);
+            self.cancellations.insert(*deploy_hash);
+        }
+        self.update_all_metrics();
     }
 
     /// Update holds considering new proposed block.
     fn register_block_proposed(&mut self, proposed_block: ProposedBlock<ClContext>) {
         let timestamp = &proposed_block.context().timestamp();
+        for deploy_hash in proposed_block.value().deploy_and_transfer_hashes() {
+            self.set_status(
+                *deploy_hash,
+                DeployStatus::Proposed {
+                    timestamp: *timestamp,
+                },
+            );
+        }
         if let Some(hold_set) = self.hold.get_mut(timestamp) {
             debug!(%timestamp, "DeployBuffer: existing hold timestamp extended");
             hold_set.extend(proposed_block.value().deploy_and_transfer_hashes());
@@ -285,10 +512,15 @@ impl DeployBuffer {
             self.dead.insert(*deploy_hash);
         }
         // deploys held for proposed blocks which did not get finalized in time are eligible again
-        let (hold, _) = mem::take(&mut self.hold)
+        let (hold, released): (BTreeMap<_, _>, BTreeMap<_, _>) = mem::take(&mut self.hold)
             .into_iter()
             .partition(|(ts, _)| *ts > timestamp);
         self.hold = hold;
+        for deploy_hash in released.into_values().flatten() {
+            if self.buffer.contains_key(&deploy_hash) && !self.dead.contains(&deploy_hash) {
+                self.set_status(deploy_hash, DeployStatus::Buffered);
+            }
+        }
         self.update_all_metrics();
     }
 
@@ -306,12 +538,76 @@ impl DeployBuffer {
         let timestamp = finalized_block.timestamp();
         debug!(%timestamp, "DeployBuffer: register_block_finalized({}) timestamp finalized", block_height);
         self.register_deploys(timestamp, finalized_block.deploy_and_transfer_hashes());
+        for deploy_hash in finalized_block.deploy_and_transfer_hashes() {
+            self.set_status(*deploy_hash, DeployStatus::Included { block_height });
+        }
+    }
+
+    /// Update statuses considering deploys executed in the given block.
+    fn register_deploys_executed(
+        &mut self,
+        block_hash: BlockHash,
+        executed: Vec<(DeployHash, bool)>,
+    ) {
+        for (deploy_hash, success) in executed {
+            self.set_status(
+                deploy_hash,
+                DeployStatus::Executed {
+                    block_hash,
+                    success,
+                },
+            );
+        }
     }
 
-    /// Returns eligible deploys that are buffered and not held or dead.
+    /// Records the given status of the deploy if it supersedes its current status.
+    ///
+    /// Once the configured maximum number of statuses is reached, the status of a deploy which is
+    /// neither already tracked nor held in the buffer is announced but not recorded.
+    fn set_status(&mut self, deploy_hash: DeployHash, status: DeployStatus) {
+        let current = self.statuses.get(&deploy_hash).map(|(_, status)| status);
+        if !status.supersedes(current) {
+            return;
+        }
+        debug!(%deploy_hash, %status, "DeployBuffer: deploy status changed");
+        if current.is_some()
+            || self.buffer.contains_key(&deploy_hash)
+            || self.statuses.len() < self.cfg.max_tracked_statuses() as usize
+        {
+            self.statuses
+                .insert(deploy_hash, (Timestamp::now(), status.clone()));
+        } else {
+            debug!(%deploy_hash, "DeployBuffer: too many tracked statuses, not recording");
+        }
+        self.pending_status_changes.push((deploy_hash, status));
+    }
+
+    /// Returns the status of the given deploy, if known.
+    fn status(&self, deploy_hash: &DeployHash) -> Option<DeployStatus> {
+        self.statuses
+            .get(deploy_hash)
+            .map(|(_, status)| status.clone())
+    }
+
+    /// Announces any status changes recorded since the last announcement.
+    fn announce_status_changes<REv>(&mut self, effect_builder: EffectBuilder<REv>) -> Effects<Event>
+    where
+        REv: From<DeployBufferAnnouncement> + Send,
+    {
+        if self.pending_status_changes.is_empty() {
+            return Effects::new();
+        }
+        effect_builder
+            .announce_deploy_statuses_changed(mem::take(&mut self.pending_status_changes))
+            .ignore()
+    }
+
+    /// Returns eligible deploys that are buffered and not held or dead, in the order given by the
+    /// configured selection policy.
     fn proposable(&self) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
         debug!("DeployBuffer: getting proposable deploys");
-        self.buffer
+        let proposable = self
+            .buffer
             .iter()
             .filter(|(dh, _)| !self.hold.values().any(|hs| hs.contains(dh)))
             .filter(|(dh, _)| !self.dead.contains(dh))
@@ -323,7 +619,47 @@ impl DeployBuffer {
                     )
                 })
             })
-            .collect()
+            .collect();
+        self.cfg.selection_policy().order(proposable)
+    }
+
+    /// Returns the deploys known to the buffer, optionally restricted to those from the given
+    /// account, sorted by expiry time and then deploy hash.
+    ///
+    /// Deploys known only by hash, i.e. which were included in a block but never received
+    /// directly, are omitted.
+    fn pending_deploys(&self, account: Option<&PublicKey>) -> Vec<PendingDeploy> {
+        let deploy_hashes: Vec<&DeployHash> = match account {
+            Some(account) => self
+                .deploys_by_account
+                .get(account)
+                .into_iter()
+                .flatten()
+                .collect(),
+            None => self.buffer.keys().collect(),
+        };
+        let mut pending_deploys: Vec<_> = deploy_hashes
+            .into_iter()
+            .filter_map(|deploy_hash| {
+                let (footprint, approvals) = self.buffer.get(deploy_hash)?.1.as_ref()?;
+                let state = if self.dead.contains(deploy_hash) {
+                    PendingDeployState::Dead
+                } else if self.hold.values().any(|hs| hs.contains(deploy_hash)) {
+                    PendingDeployState::Held
+                } else {
+                    PendingDeployState::Proposable
+                };
+                Some(PendingDeploy::new(
+                    *deploy_hash,
+                    footprint,
+                    approvals.len(),
+                    state,
+                ))
+            })
+            .collect();
+        pending_deploys
+            .sort_by_key(|pending_deploy| (pending_deploy.expires, pending_deploy.deploy_hash));
+        pending_deploys
     }
 
     /// Returns a right-sized payload of deploys that can be proposed.
@@ -345,6 +681,7 @@ impl DeployBuffer {
                 Ok(_) => {
                     debug!(%deploy_hash, "DeployBuffer: proposing deploy");
                     holds.insert(deploy_hash);
+                    self.set_status(deploy_hash, DeployStatus::Proposed { timestamp });
                 }
                 Err(error) => {
                     match error {
@@ -497,14 +834,20 @@ where
                 );
                 Effects::new()
             }
-            ComponentState::Uninitialized => {
-                warn!(
-                    ?event,
-                    name = <Self as Component<MainEvent>>::name(self),
-                    "should not handle this event when component is uninitialized"
-                );
-                Effects::new()
-            }
+            ComponentState::Uninitialized => match event {
+                // nothing is buffered yet, so no limits can be exceeded
+                Event::Request(DeployBufferRequest::CheckPendingLimits { responder, .. }) => {
+                    responder.respond(Ok(())).ignore()
+                }
+                event => {
+                    warn!(
+                        ?event,
+                        name = <Self as Component<MainEvent>>::name(self),
+                        "should not handle this event when component is uninitialized"
+                    );
+                    Effects::new()
+                }
+            },
             ComponentState::Initializing => {
                 match event {
                     Event::Initialize(blocks) => {
@@ -520,12 +863,19 @@ where
                             .set_timeout(self.cfg.expiry_check_interval().into())
                             .event(move |_| Event::Expire)
                     }
+                    // nothing is buffered yet, so no limits can be exceeded
+                    Event::Request(DeployBufferRequest::CheckPendingLimits {
+                        responder, ..
+                    }) => responder.respond(Ok(())).ignore(),
                     Event::Request(_)
                     | Event::ReceiveDeployGossiped(_)
+                    | Event::DeployAccepted(_)
+                    | Event::DeployRejected { .. }
                     | Event::StoredDeploy(_, _)
                     | Event::BlockProposed(_)
                     | Event::Block(_)
                     | Event::BlockFinalized(_)
+                    | Event::DeploysExecuted { .. }
                     | Event::Expire => {
                         warn!(
                             ?event,
@@ -536,47 +886,85 @@ where
                     }
                 }
             }
-            ComponentState::Initialized => match event {
-                Event::Initialize(_) => {
-                    error!(
-                        ?event,
-                        name = <Self as Component<MainEvent>>::name(self),
-                        "component already initialized"
-                    );
-                    Effects::new()
-                }
-                Event::Request(DeployBufferRequest::GetAppendableBlock {
-                    timestamp,
-                    responder,
-                }) => responder.respond(self.appendable_block(timestamp)).ignore(),
-                Event::BlockFinalized(finalized_block) => {
-                    self.register_block_finalized(&finalized_block);
-                    Effects::new()
-                }
-                Event::Block(block) => {
-                    self.register_block(&block);
-                    Effects::new()
-                }
-                Event::BlockProposed(proposed) => {
-                    self.register_block_proposed(*proposed);
-                    Effects::new()
-                }
-                Event::ReceiveDeployGossiped(deploy_id) => {
-                    self.register_deploy_gossiped(deploy_id, effect_builder)
-                }
-                Event::StoredDeploy(deploy_id, maybe_deploy) => {
-                    match maybe_deploy {
-                        Some(deploy) => {
-                            self.register_deploy(*deploy);
-                        }
-                        None => {
-                            warn!("cannot register un-stored deploy({})", deploy_id);
+            ComponentState::Initialized => {
+                let mut effects = match event {
+                    Event::Initialize(_) => {
+                        error!(
+                            ?event,
+                            name = <Self as Component<MainEvent>>::name(self),
+                            "component already initialized"
+                        );
+                        Effects::new()
+                    }
+                    Event::Request(DeployBufferRequest::GetAppendableBlock {
+                        timestamp,
+                        responder,
+                    }) => responder.respond(self.appendable_block(timestamp)).ignore(),
+                    Event::Request(DeployBufferRequest::GetDeployStatus {
+                        deploy_hash,
+                        responder,
+                    }) => responder.respond(self.status(&deploy_hash)).ignore(),
+                    Event::Request(DeployBufferRequest::GetPendingDeploys {
+                        account,
+                        responder,
+                    }) => responder
+                        .respond(self.pending_deploys(account.as_deref()))
+                        .ignore(),
+                    Event::Request(DeployBufferRequest::CheckPendingLimits {
+                        deploy,
+                        responder,
+                    }) => responder
+                        .respond(self.check_pending_limits(&deploy))
+                        .ignore(),
+                    Event::BlockFinalized(finalized_block) => {
+                        self.register_block_finalized(&finalized_block);
+                        Effects::new()
+                    }
+                    Event::Block(block) => {
+                        self.register_block(&block);
+                        Effects::new()
+                    }
+                    Event::BlockProposed(proposed) => {
+                        self.register_block_proposed(*proposed);
+                        Effects::new()
+                    }
+                    Event::ReceiveDeployGossiped(deploy_id) => {
+                        self.register_deploy_gossiped(deploy_id, effect_builder)
+                    }
+                    Event::DeployAccepted(deploy_hash) => {
+                        self.set_status(deploy_hash, DeployStatus::Received);
+                        Effects::new()
+                    }
+                    Event::DeployRejected {
+                        deploy_hash,
+                        reason,
+                    } => {
+                        self.set_status(deploy_hash, DeployStatus::Rejected { reason });
+                        Effects::new()
+                    }
+                    Event::DeploysExecuted {
+                        block_hash,
+                        executed,
+                    } => {
+                        self.register_deploys_executed(block_hash, executed);
+                        Effects::new()
+                    }
+                    Event::StoredDeploy(deploy_id, maybe_deploy) => {
+                        match maybe_deploy {
+                            Some(deploy) => {
+                                self.register_deploy(*deploy);
+                            }
+                            None => {
+                                warn!("cannot register un-stored deploy({})", deploy_id);
+                            }
                         }
+                        Effects::new()
                     }
-                    Effects::new()
-                }
-                Event::Expire => self.expire(effect_builder),
-            },
+                    Event::Expire => self.expire(effect_builder),
+                };
+                effects.extend(self.announce_status_changes(effect_builder));
+                effects
+            }
         }
     }
 
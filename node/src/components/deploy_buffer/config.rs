@@ -3,13 +3,34 @@ use serde::{Deserialize, Serialize};
 
 use casper_types::TimeDiff;
 
+use super::SelectionPolicy;
+
 const DEFAULT_EXPIRY_CHECK_INTERVAL: &str = "1min";
+const DEFAULT_STATUS_RETENTION_PERIOD: &str = "1hour";
+const DEFAULT_MAX_PENDING_DEPLOYS_PER_ACCOUNT: u32 = 100;
+const DEFAULT_MAX_PENDING_BYTES_PER_ACCOUNT: u64 = 16 * 1024 * 1024;
+const DEFAULT_MAX_BUFFERED_DEPLOYS: u32 = 50_000;
+const DEFAULT_MAX_TRACKED_STATUSES: u32 = 100_000;
 
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// The interval of checking for expired deploys.
     pub expiry_check_interval: TimeDiff,
+    /// How long the status of a deploy is retained after it last changed.
+    pub status_retention_period: TimeDiff,
+    /// The order in which deploys are selected for proposed blocks.
+    pub selection_policy: SelectionPolicy,
+    /// The maximum number of pending deploys a single account may have in the buffer.
+    pub max_pending_deploys_per_account: u32,
+    /// The maximum total size in bytes of the pending deploys a single account may have in the
+    /// buffer.
+    pub max_pending_bytes_per_account: u64,
+    /// The maximum number of pending deploys held in the buffer, beyond which the lowest-priority
+    /// deploys are evicted.
+    pub max_buffered_deploys: u32,
+    /// The maximum number of statuses of deploys no longer held in the buffer which are retained.
+    pub max_tracked_statuses: u32,
 }
 
 impl Config {
@@ -17,12 +38,49 @@ impl Config {
     pub fn expiry_check_interval(&self) -> TimeDiff {
         self.expiry_check_interval
     }
+
+    /// Returns how long the status of a deploy is retained after it last changed.
+    pub fn status_retention_period(&self) -> TimeDiff {
+        self.status_retention_period
+    }
+
+    /// Returns the order in which deploys are selected for proposed blocks.
+    pub fn selection_policy(&self) -> SelectionPolicy {
+        self.selection_policy
+    }
+
+    /// Returns the maximum number of pending deploys a single account may have in the buffer.
+    pub fn max_pending_deploys_per_account(&self) -> u32 {
+        self.max_pending_deploys_per_account
+    }
+
+    /// Returns the maximum total size in bytes of the pending deploys a single account may have in
+    /// the buffer.
+    pub fn max_pending_bytes_per_account(&self) -> u64 {
+        self.max_pending_bytes_per_account
+    }
+
+    /// Returns the maximum number of pending deploys held in the buffer.
+    pub fn max_buffered_deploys(&self) -> u32 {
+        self.max_buffered_deploys
+    }
+
+    /// Returns the maximum number of retained statuses of deploys no longer held in the buffer.
+    pub fn max_tracked_statuses(&self) -> u32 {
+        self.max_tracked_statuses
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             expiry_check_interval: DEFAULT_EXPIRY_CHECK_INTERVAL.parse().unwrap(),
+            status_retention_period: DEFAULT_STATUS_RETENTION_PERIOD.parse().unwrap(),
+            selection_policy: SelectionPolicy::default(),
+            max_pending_deploys_per_account: DEFAULT_MAX_PENDING_DEPLOYS_PER_ACCOUNT,
+            max_pending_bytes_per_account: DEFAULT_MAX_PENDING_BYTES_PER_ACCOUNT,
+            max_buffered_deploys: DEFAULT_MAX_BUFFERED_DEPLOYS,
+            max_tracked_statuses: DEFAULT_MAX_TRACKED_STATUSES,
         }
     }
 }
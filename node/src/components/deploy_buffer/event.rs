@@ -9,7 +9,7 @@ use derive_more::From;
 use crate::{
     components::consensus::{ClContext, ProposedBlock},
     effect::requests::DeployBufferRequest,
-    types::{Block, Deploy, DeployId, FinalizedBlock},
+    types::{Block, BlockHash, Deploy, DeployHash, DeployId, FinalizedBlock},
 };
 
 #[derive(Debug, From, DataSize)]
@@ -18,10 +18,21 @@ pub(crate) enum Event {
     #[from]
     Request(DeployBufferRequest),
     ReceiveDeployGossiped(DeployId),
+    DeployAccepted(DeployHash),
+    DeployRejected {
+        deploy_hash: DeployHash,
+        reason: String,
+    },
     StoredDeploy(DeployId, Option<Box<Deploy>>),
     BlockProposed(Box<ProposedBlock<ClContext>>),
     Block(Arc<Block>),
     BlockFinalized(Box<FinalizedBlock>),
+    DeploysExecuted {
+        block_hash: BlockHash,
+        /// The hashes of the executed deploys, each with a flag indicating whether execution
+        /// succeeded.
+        executed: Vec<(DeployHash, bool)>,
+    },
     Expire,
 }
 
@@ -34,9 +45,28 @@ impl Display for Event {
             Event::Request(DeployBufferRequest::GetAppendableBlock { .. }) => {
                 write!(formatter, "get appendable block request")
             }
+            Event::Request(DeployBufferRequest::GetDeployStatus { deploy_hash, .. }) => {
+                write!(formatter, "get deploy status request for {}", deploy_hash)
+            }
+            Event::Request(DeployBufferRequest::GetPendingDeploys { .. }) => {
+                write!(formatter, "get pending deploys request")
+            }
+            Event::Request(DeployBufferRequest::CheckPendingLimits { deploy, .. }) => {
+                write!(
+                    formatter,
+                    "check pending limits request for {}",
+                    deploy.hash()
+                )
+            }
             Event::ReceiveDeployGossiped(deploy_id) => {
                 write!(formatter, "receive deploy gossiped {}", deploy_id)
             }
+            Event::DeployAccepted(deploy_hash) => {
+                write!(formatter, "deploy accepted {}", deploy_hash)
+            }
+            Event::DeployRejected { deploy_hash, .. } => {
+                write!(formatter, "deploy rejected {}", deploy_hash)
+            }
             Event::StoredDeploy(deploy_id, maybe_deploy) => {
                 write!(
                     formatter,
@@ -58,6 +88,17 @@ impl Display for Event {
             Event::Block(_) => {
                 write!(formatter, "block")
             }
+            Event::DeploysExecuted {
+                block_hash,
+                executed,
+            } => {
+                write!(
+                    formatter,
+                    "{} deploys executed in block {}",
+                    executed.len(),
+                    block_hash
+                )
+            }
             Event::Expire => {
                 write!(formatter, "expire deploys")
             }
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use casper_types::{Gas, PublicKey, TimeDiff, Timestamp};
+
+use crate::types::{DeployFootprint, DeployHash};
+
+/// Where a deploy known to the deploy buffer is in the proposal process.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub(crate) enum PendingDeployState {
+    /// The deploy is eligible to be included in the next proposed block.
+    Proposable,
+    /// The deploy is held by a proposed block which has not yet been finalized.
+    Held,
+    /// The deploy has been included in a block, or is otherwise ineligible to be proposed.
+    Dead,
+}
+
+/// Information about a deploy known to the deploy buffer.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct PendingDeploy {
+    pub(crate) deploy_hash: DeployHash,
+    pub(crate) account: PublicKey,
+    pub(crate) timestamp: Timestamp,
+    pub(crate) ttl: TimeDiff,
+    pub(crate) expires: Timestamp,
+    pub(crate) gas_price: u64,
+    pub(crate) gas_estimate: Gas,
+    pub(crate) size_estimate: usize,
+    pub(crate) is_transfer: bool,
+    pub(crate) approval_count: usize,
+    pub(crate) state: PendingDeployState,
+}
+
+impl PendingDeploy {
+    pub(crate) fn new(
+        deploy_hash: DeployHash,
+        footprint: &DeployFootprint,
+        approval_count: usize,
+        state: PendingDeployState,
+    ) -> Self {
+        let header = &footprint.header;
+        PendingDeploy {
+            deploy_hash,
+            account: header.account().clone(),
+            timestamp: header.timestamp(),
+            ttl: header.ttl(),
+            expires: header.expires(),
+            gas_price: header.gas_price(),
+            gas_estimate: footprint.gas_estimate,
+            size_estimate: footprint.size_estimate,
+            is_transfer: footprint.is_transfer,
+            approval_count,
+            state,
+        }
+    }
+}
+
+/// A limit on the deploys pending in the deploy buffer which would be exceeded by buffering a new
+/// deploy.
+#[derive(Clone, Eq, PartialEq, Debug, Error, Serialize)]
+pub(crate) enum PendingLimitExceeded {
+    /// The account already has the maximum number of pending deploys.
+    #[error("account already has the maximum of {max} pending deploys")]
+    AccountDeployCount { max: u32 },
+    /// The account's pending deploys would exceed the maximum total size.
+    #[error("account's pending deploys would exceed the maximum total of {max} bytes")]
+    AccountDeployBytes { max: u64 },
+}
@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::PublicKey;
+
+use crate::types::{DeployFootprint, DeployHashWithApprovals};
+
+/// The order in which proposable deploys are offered for inclusion in a proposed block.
+///
+/// Whichever policy is used, deploys are only included while they fit within the block's limits
+/// on transfers, deploys, approvals, gas and size.
+#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionPolicy {
+    /// Oldest deploys first, by deploy timestamp.
+    Fifo,
+    /// Deploys with the highest gas price first, and oldest first among those with equal gas
+    /// price.
+    GasPrice,
+    /// Deploys from each account in turn, with each account's deploys taken oldest first.
+    /// Accounts are visited in the order of their oldest deploy.
+    FairShare,
+}
+
+impl Default for SelectionPolicy {
+    fn default() -> Self {
+        SelectionPolicy::Fifo
+    }
+}
+
+impl SelectionPolicy {
+    /// Sorts the given proposable deploys into the order in which they should be proposed.
+    pub(super) fn order(
+        self,
+        mut deploys: Vec<(DeployHashWithApprovals, DeployFootprint)>,
+    ) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
+        // sorting by hash last makes the order deterministic
+        deploys.sort_by(
+            |(with_approvals1, footprint1), (with_approvals2, footprint2)| {
+                footprint1
+                    .header
+                    .timestamp()
+                    .cmp(&footprint2.header.timestamp())
+                    .then_with(|| {
+                        with_approvals1
+                            .deploy_hash()
+                            .cmp(with_approvals2.deploy_hash())
+                    })
+            },
+        );
+        match self {
+            SelectionPolicy::Fifo => deploys,
+            SelectionPolicy::GasPrice => {
+                // stable sort, so retains FIFO order for equal gas prices
+                deploys.sort_by(|(_, footprint1), (_, footprint2)| {
+                    footprint2
+                        .header
+                        .gas_price()
+                        .cmp(&footprint1.header.gas_price())
+                });
+                deploys
+            }
+            SelectionPolicy::FairShare => {
+                // each account's queue is keyed by the position of its oldest deploy
+                let mut account_positions: BTreeMap<PublicKey, usize> = BTreeMap::new();
+                let mut queues: Vec<VecDeque<_>> = vec![];
+                for (with_approvals, footprint) in deploys {
+                    let next_position = queues.len();
+                    let position = *account_positions
+                        .entry(footprint.header.account().clone())
+                        .or_insert(next_position);
+                    if position == next_position {
+                        queues.push(VecDeque::new());
+                    }
+                    queues[position].push_back((with_approvals, footprint));
+                }
+                let mut ordered = vec![];
+                while !queues.is_empty() {
+                    for queue in queues.iter_mut() {
+                        ordered.extend(queue.pop_front());
+                    }
+                    queues.retain(|queue| !queue.is_empty());
+                }
+                ordered
+            }
+        }
+    }
+}
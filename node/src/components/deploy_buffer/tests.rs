@@ -5,7 +5,7 @@ use crate::{
     types::{Block, FinalizedBlock},
     utils,
 };
-use casper_types::{testing::TestRng, EraId, TimeDiff};
+use casper_types::{testing::TestRng, EraId, SecretKey, TimeDiff};
 use prometheus::Registry;
 use rand::Rng;
 
@@ -394,6 +394,145 @@ fn register_deploys_and_blocks() {
     );
 }
 
+#[test]
+fn track_deploy_statuses() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let deploy = create_valid_deploys(&mut rng, 1, DeployType::Transfer, None, None)
+        .pop()
+        .unwrap();
+    let deploy_hash = *deploy.hash();
+    assert_eq!(deploy_buffer.status(&deploy_hash), None);
+
+    deploy_buffer.set_status(deploy_hash, DeployStatus::Received);
+    assert_eq!(
+        deploy_buffer.status(&deploy_hash),
+        Some(DeployStatus::Received)
+    );
+
+    deploy_buffer.register_deploy(deploy.clone());
+    assert_eq!(
+        deploy_buffer.status(&deploy_hash),
+        Some(DeployStatus::Buffered)
+    );
+
+    let timestamp = Timestamp::now();
+    let appendable_block = deploy_buffer.appendable_block(timestamp);
+    assert_eq!(appendable_block.deploy_and_transfer_set().len(), 1);
+    assert_eq!(
+        deploy_buffer.status(&deploy_hash),
+        Some(DeployStatus::Proposed { timestamp })
+    );
+
+    // a rejection of the same deploy received from elsewhere shouldn't affect its status
+    deploy_buffer.set_status(
+        deploy_hash,
+        DeployStatus::Rejected {
+            reason: "invalid approvals".to_string(),
+        },
+    );
+    assert_eq!(
+        deploy_buffer.status(&deploy_hash),
+        Some(DeployStatus::Proposed { timestamp })
+    );
+
+    let block = FinalizedBlock::random_with_deploys(&mut rng, Some(&deploy));
+    deploy_buffer.register_block_finalized(&block);
+    assert_eq!(
+        deploy_buffer.status(&deploy_hash),
+        Some(DeployStatus::Included {
+            block_height: block.height()
+        })
+    );
+
+    let block_hash = BlockHash::random(&mut rng);
+    deploy_buffer.register_deploys_executed(block_hash, vec![(deploy_hash, true)]);
+    let executed = DeployStatus::Executed {
+        block_hash,
+        success: true,
+    };
+    assert_eq!(deploy_buffer.status(&deploy_hash), Some(executed.clone()));
+
+    // registering the executed block shouldn't regress the status
+    deploy_buffer.register_block_finalized(&block);
+    assert_eq!(deploy_buffer.status(&deploy_hash), Some(executed.clone()));
+
+    let changes: Vec<_> = deploy_buffer
+        .pending_status_changes
+        .iter()
+        .map(|(_, status)| status.clone())
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            DeployStatus::Received,
+            DeployStatus::Buffered,
+            DeployStatus::Proposed { timestamp },
+            DeployStatus::Included {
+                block_height: block.height()
+            },
+            executed,
+        ]
+    );
+}
+
+#[test]
+fn should_bound_tracked_statuses() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        max_tracked_statuses: 2,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+
+    let mut deploys = create_valid_deploys(&mut rng, 4, DeployType::Transfer, None, None);
+    let rejected = DeployStatus::Rejected {
+        reason: "invalid approvals".to_string(),
+    };
+    for deploy in &deploys[..3] {
+        deploy_buffer.set_status(*deploy.hash(), rejected.clone());
+    }
+    // the third rejection is announced, but not recorded
+    assert_eq!(deploy_buffer.statuses.len(), 2);
+    assert_eq!(deploy_buffer.pending_status_changes.len(), 3);
+    assert_eq!(deploy_buffer.status(deploys[2].hash()), None);
+
+    // tracked statuses are still updated
+    deploy_buffer.set_status(*deploys[0].hash(), DeployStatus::Received);
+    assert_eq!(
+        deploy_buffer.status(deploys[0].hash()),
+        Some(DeployStatus::Received)
+    );
+
+    // statuses of buffered deploys are always recorded
+    let deploy = deploys.pop().unwrap();
+    deploy_buffer.register_deploy(deploy.clone());
+    assert_eq!(
+        deploy_buffer.status(deploy.hash()),
+        Some(DeployStatus::Buffered)
+    );
+}
+
+#[test]
+fn rejected_deploy_status_can_be_superseded() {
+    let rejected = DeployStatus::Rejected {
+        reason: "account not found".to_string(),
+    };
+    assert!(rejected.supersedes(None));
+    assert!(DeployStatus::Received.supersedes(Some(&rejected)));
+    assert!(!rejected.supersedes(Some(&DeployStatus::Received)));
+    assert!(!DeployStatus::Received.supersedes(Some(&DeployStatus::Buffered)));
+    assert!(!DeployStatus::Buffered.supersedes(Some(&DeployStatus::Expired)));
+    assert!(
+        DeployStatus::Buffered.supersedes(Some(&DeployStatus::Proposed {
+            timestamp: Timestamp::now()
+        }))
+    );
+}
+
 /// Event for the mock reactor.
 enum ReactorEvent {
     DeployBufferAnnouncement(DeployBufferAnnouncement),
@@ -440,6 +579,449 @@ impl MockReactor {
     }
 }
 
+#[test]
+fn list_pending_deploys() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let deploys = create_valid_deploys(&mut rng, 3, DeployType::Random, None, None);
+    for deploy in &deploys {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+    let pending_deploys = deploy_buffer.pending_deploys(None);
+    assert_eq!(pending_deploys.len(), 3);
+    assert!(pending_deploys
+        .windows(2)
+        .all(|pair| pair[0].expires <= pair[1].expires));
+    assert!(pending_deploys
+        .iter()
+        .all(|pending_deploy| pending_deploy.state == PendingDeployState::Proposable));
+
+    // filtering by account only lists that account's deploys
+    let account = deploys[0].header().account();
+    let pending_deploys = deploy_buffer.pending_deploys(Some(account));
+    assert_eq!(pending_deploys.len(), 1);
+    assert_eq!(pending_deploys[0].deploy_hash, *deploys[0].hash());
+    assert_eq!(
+        pending_deploys[0].approval_count,
+        deploys[0].approvals().len()
+    );
+
+    // proposed deploys are held, and finalized ones are dead
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    assert_eq!(appendable_block.deploy_and_transfer_set().len(), 3);
+    assert!(deploy_buffer
+        .pending_deploys(None)
+        .iter()
+        .all(|pending_deploy| pending_deploy.state == PendingDeployState::Held));
+
+    let block = FinalizedBlock::random_with_deploys(&mut rng, Some(&deploys[0]));
+    deploy_buffer.register_block_finalized(&block);
+    let pending_deploys = deploy_buffer.pending_deploys(Some(account));
+    assert_eq!(pending_deploys[0].state, PendingDeployState::Dead);
+}
+
+#[test]
+fn replace_deploys() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        allow_deploy_replacement: true,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+    let secret_key = SecretKey::random(&mut rng);
+
+    let deploy =
+        Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 2, vec![]);
+    deploy_buffer.register_deploy(deploy.clone());
+
+    // neither a replacement from another account nor one with an equal gas price has any effect
+    let other_secret_key = SecretKey::random(&mut rng);
+    let other_account_replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &other_secret_key,
+        3,
+        vec![*deploy.hash()],
+    );
+    let underpriced_replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &secret_key,
+        2,
+        vec![*deploy.hash()],
+    );
+    deploy_buffer.register_deploy(other_account_replacement);
+    deploy_buffer.register_deploy(underpriced_replacement);
+    assert_eq!(
+        deploy_buffer.status(deploy.hash()),
+        Some(DeployStatus::Buffered)
+    );
+    assert_eq!(deploy_buffer.proposable().len(), 3);
+
+    let replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &secret_key,
+        3,
+        vec![*deploy.hash()],
+    );
+    deploy_buffer.register_deploy(replacement.clone());
+    assert_eq!(
+        deploy_buffer.status(deploy.hash()),
+        Some(DeployStatus::Replaced {
+            replacement: *replacement.hash()
+        })
+    );
+    assert!(deploy_buffer.dead.contains(deploy.hash()));
+    let proposable = deploy_buffer.proposable();
+    assert_eq!(proposable.len(), 3);
+    assert!(proposable
+        .iter()
+        .all(|(with_approvals, _)| with_approvals.deploy_hash() != deploy.hash()));
+
+    // a cancellation removes the deploy it cancels, and is never proposed itself
+    let cancellation =
+        Deploy::random_cancellation(&mut rng, &secret_key, vec![*replacement.hash()]);
+    deploy_buffer.register_deploy(cancellation.clone());
+    assert_eq!(
+        deploy_buffer.status(replacement.hash()),
+        Some(DeployStatus::Replaced {
+            replacement: *cancellation.hash()
+        })
+    );
+    assert!(deploy_buffer.dead.contains(cancellation.hash()));
+    assert_eq!(deploy_buffer.proposable().len(), 2);
+
+    // a cancellation naming no pending deploy is not buffered
+    let orphan_cancellation =
+        Deploy::random_cancellation(&mut rng, &secret_key, vec![*replacement.hash()]);
+    deploy_buffer.register_deploy(orphan_cancellation.clone());
+    assert!(!deploy_buffer
+        .buffer
+        .contains_key(orphan_cancellation.hash()));
+    assert_eq!(
+        deploy_buffer.status(orphan_cancellation.hash()),
+        Some(DeployStatus::Evicted)
+    );
+}
+
+#[test]
+fn cancellations_count_towards_pending_limits() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        max_pending_deploys_per_account: 2,
+        ..Default::default()
+    };
+    let deploy_config = DeployConfig {
+        allow_deploy_replacement: true,
+        ..Default::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+    let secret_key = SecretKey::random(&mut rng);
+    let mut new_deploy =
+        || Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 1, vec![]);
+    let first_deploy = new_deploy();
+    let second_deploy = new_deploy();
+    let third_deploy = new_deploy();
+    deploy_buffer.register_deploy(first_deploy.clone());
+
+    // the cancelled deploy no longer counts, but the cancellation does
+    let cancellation =
+        Deploy::random_cancellation(&mut rng, &secret_key, vec![*first_deploy.hash()]);
+    assert_eq!(deploy_buffer.check_pending_limits(&cancellation), Ok(()));
+    deploy_buffer.register_deploy(cancellation.clone());
+    assert!(deploy_buffer.dead.contains(first_deploy.hash()));
+    assert!(deploy_buffer.dead.contains(cancellation.hash()));
+
+    deploy_buffer.register_deploy(second_deploy);
+    assert_eq!(
+        deploy_buffer.check_pending_limits(&third_deploy),
+        Err(PendingLimitExceeded::AccountDeployCount { max: 2 })
+    );
+    deploy_buffer.register_deploy(third_deploy.clone());
+    assert!(!deploy_buffer.buffer.contains_key(third_deploy.hash()));
+}
+
+#[test]
+fn should_not_replace_deploys_if_disabled() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let secret_key = SecretKey::random(&mut rng);
+
+    let deploy =
+        Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 2, vec![]);
+    let replacement = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &secret_key,
+        3,
+        vec![*deploy.hash()],
+    );
+    deploy_buffer.register_deploy(deploy.clone());
+    deploy_buffer.register_deploy(replacement);
+    assert_eq!(
+        deploy_buffer.status(deploy.hash()),
+        Some(DeployStatus::Buffered)
+    );
+    assert_eq!(deploy_buffer.proposable().len(), 2);
+}
+
+fn deploy_buffer_with_selection_policy(selection_policy: SelectionPolicy) -> DeployBuffer {
+    let deploy_config = DeployConfig {
+        block_max_transfer_count: 2,
+        block_max_approval_count: 12,
+        ..Default::default()
+    };
+    let config = Config {
+        selection_policy,
+        ..Default::default()
+    };
+    DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap()
+}
+
+// Returns the hashes of the given deploys, ordered oldest first.
+fn oldest_first<'a>(deploys: impl IntoIterator<Item = &'a Deploy>) -> Vec<DeployHash> {
+    deploys
+        .into_iter()
+        .sorted_by_key(|deploy| (deploy.header().timestamp(), *deploy.hash()))
+        .map(|deploy| *deploy.hash())
+        .collect()
+}
+
+#[test]
+fn select_deploys_oldest_first() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer = deploy_buffer_with_selection_policy(SelectionPolicy::Fifo);
+    let deploys = create_valid_deploys(&mut rng, 5, DeployType::Transfer, None, None);
+    for deploy in &deploys {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    let expected: HashSet<_> = oldest_first(&deploys).into_iter().take(2).collect();
+    assert_eq!(*appendable_block.deploy_and_transfer_set(), expected);
+}
+
+#[test]
+fn select_deploys_by_gas_price() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer = deploy_buffer_with_selection_policy(SelectionPolicy::GasPrice);
+    let deploys: Vec<_> = (1..=5)
+        .map(|gas_price| {
+            let secret_key = SecretKey::random(&mut rng);
+            Deploy::random_valid_native_transfer_with_dependencies(
+                &mut rng,
+                &secret_key,
+                gas_price,
+                vec![],
+            )
+        })
+        .collect();
+    for deploy in &deploys {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    let expected: HashSet<_> = deploys[3..].iter().map(|deploy| *deploy.hash()).collect();
+    assert_eq!(*appendable_block.deploy_and_transfer_set(), expected);
+}
+
+#[test]
+fn select_deploys_fairly_across_accounts() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer = deploy_buffer_with_selection_policy(SelectionPolicy::FairShare);
+    let busy_secret_key = SecretKey::random(&mut rng);
+    let busy_account_deploys: Vec<_> = (0..4)
+        .map(|_| {
+            Deploy::random_valid_native_transfer_with_dependencies(
+                &mut rng,
+                &busy_secret_key,
+                1,
+                vec![],
+            )
+        })
+        .collect();
+    let quiet_secret_key = SecretKey::random(&mut rng);
+    let quiet_account_deploy = Deploy::random_valid_native_transfer_with_dependencies(
+        &mut rng,
+        &quiet_secret_key,
+        1,
+        vec![],
+    );
+    for deploy in busy_account_deploys
+        .iter()
+        .chain(Some(&quiet_account_deploy))
+    {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+
+    // each account gets one of the two transfer slots, taking the busy account's oldest deploy
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    let expected: HashSet<_> = [
+        oldest_first(&busy_account_deploys)[0],
+        *quiet_account_deploy.hash(),
+    ]
+    .iter()
+    .copied()
+    .collect();
+    assert_eq!(*appendable_block.deploy_and_transfer_set(), expected);
+}
+
+#[test]
+fn check_pending_limits_per_account() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        max_pending_deploys_per_account: 2,
+        ..Default::default()
+    };
+    let deploy_config = DeployConfig {
+        allow_deploy_replacement: true,
+        ..Default::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+    let secret_key = SecretKey::random(&mut rng);
+    let mut new_deploy = |gas_price, dependencies| {
+        Deploy::random_valid_native_transfer_with_dependencies(
+            &mut rng,
+            &secret_key,
+            gas_price,
+            dependencies,
+        )
+    };
+
+    let first_deploy = new_deploy(1, vec![]);
+    let second_deploy = new_deploy(1, vec![]);
+    let third_deploy = new_deploy(1, vec![]);
+    assert_eq!(deploy_buffer.check_pending_limits(&first_deploy), Ok(()));
+    deploy_buffer.register_deploy(first_deploy.clone());
+    deploy_buffer.register_deploy(second_deploy.clone());
+
+    assert_eq!(
+        deploy_buffer.check_pending_limits(&third_deploy),
+        Err(PendingLimitExceeded::AccountDeployCount { max: 2 })
+    );
+    // a deploy already buffered doesn't count against itself
+    assert_eq!(deploy_buffer.check_pending_limits(&second_deploy), Ok(()));
+    // nor does a deploy which would be replaced
+    let replacement = new_deploy(2, vec![*first_deploy.hash()]);
+    assert_eq!(deploy_buffer.check_pending_limits(&replacement), Ok(()));
+
+    // other accounts are unaffected
+    let other_account_deploy = create_valid_deploys(&mut rng, 1, DeployType::Transfer, None, None)
+        .pop()
+        .unwrap();
+    assert_eq!(
+        deploy_buffer.check_pending_limits(&other_account_deploy),
+        Ok(())
+    );
+
+    // included deploys are no longer pending
+    let block = FinalizedBlock::random_with_deploys(&mut rng, Some(&first_deploy));
+    deploy_buffer.register_block_finalized(&block);
+    assert_eq!(deploy_buffer.check_pending_limits(&third_deploy), Ok(()));
+}
+
+#[test]
+fn enforce_pending_limits_when_buffering() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        max_pending_deploys_per_account: 2,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+    let secret_key = SecretKey::random(&mut rng);
+    let account = PublicKey::from(&secret_key);
+    let deploys: Vec<_> = (0..3)
+        .map(|_| {
+            Deploy::random_valid_native_transfer_with_dependencies(&mut rng, &secret_key, 1, vec![])
+        })
+        .collect();
+
+    // deploys beyond the limit aren't buffered, regardless of their source
+    for deploy in &deploys {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+    assert_container_sizes(&deploy_buffer, 2, 0, 0);
+    assert!(!deploy_buffer.buffer.contains_key(deploys[2].hash()));
+    assert_eq!(
+        deploy_buffer.status(deploys[2].hash()),
+        Some(DeployStatus::Evicted)
+    );
+    assert_eq!(deploy_buffer.deploys_by_account[&account].len(), 2);
+
+    // re-registering an already buffered deploy is unaffected by the limit
+    deploy_buffer.register_deploy(deploys[0].clone());
+    assert_container_sizes(&deploy_buffer, 2, 0, 0);
+
+    // once a buffered deploy is included in a block, another can be buffered
+    let block = FinalizedBlock::random_with_deploys(&mut rng, Some(&deploys[0]));
+    deploy_buffer.register_block_finalized(&block);
+    deploy_buffer.register_deploy(deploys[2].clone());
+    assert!(deploy_buffer.buffer.contains_key(deploys[2].hash()));
+    assert_eq!(deploy_buffer.deploys_by_account[&account].len(), 3);
+}
+
+#[test]
+fn check_pending_bytes_per_account() {
+    let mut rng = TestRng::new();
+    let deploy = create_valid_deploys(&mut rng, 1, DeployType::Transfer, None, None)
+        .pop()
+        .unwrap();
+    let config = Config {
+        max_pending_bytes_per_account: deploy.serialized_length() as u64 - 1,
+        ..Default::default()
+    };
+    let deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+    assert_eq!(
+        deploy_buffer.check_pending_limits(&deploy),
+        Err(PendingLimitExceeded::AccountDeployBytes {
+            max: deploy.serialized_length() as u64 - 1
+        })
+    );
+}
+
+#[test]
+fn evict_lowest_gas_price_deploys_when_full() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        max_buffered_deploys: 3,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+    let deploys: Vec<_> = [3, 1, 4, 2]
+        .iter()
+        .map(|gas_price| {
+            let secret_key = SecretKey::random(&mut rng);
+            Deploy::random_valid_native_transfer_with_dependencies(
+                &mut rng,
+                &secret_key,
+                *gas_price,
+                vec![],
+            )
+        })
+        .collect();
+    for deploy in &deploys[..3] {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+    // the buffer is full, but nothing has been evicted yet
+    assert_container_sizes(&deploy_buffer, 3, 0, 0);
+
+    deploy_buffer.register_deploy(deploys[3].clone());
+    assert_container_sizes(&deploy_buffer, 3, 0, 0);
+    assert_eq!(
+        deploy_buffer.status(deploys[1].hash()),
+        Some(DeployStatus::Evicted)
+    );
+    assert!(!deploy_buffer.buffer.contains_key(deploys[1].hash()));
+    assert_eq!(
+        deploy_buffer.status(deploys[3].hash()),
+        Some(DeployStatus::Buffered)
+    );
+}
+
 #[tokio::test]
 async fn expire_deploys_and_check_announcement() {
     let mut rng = TestRng::new();
@@ -15,11 +15,13 @@
 //!
 //! This component uses a ring buffer for outbound events providing some robustness against
 //! unintended subscriber disconnects, if a disconnected subscriber re-subscribes before the buffer
-//! has advanced past their last received event.
+//! has advanced past their last received event.  Optionally, events are also written to an
+//! on-disk log, from which subscribers can be sent events older than those held in the buffer.
 
 mod config;
 mod event;
 mod event_indexer;
+mod event_log;
 mod http_server;
 mod sse_server;
 #[cfg(test)]
@@ -29,6 +31,7 @@ use std::{fmt::Debug, net::SocketAddr, path::PathBuf};
 
 use datasize::DataSize;
 use tokio::sync::{
+    broadcast,
     mpsc::{self, UnboundedSender},
     oneshot,
 };
@@ -42,18 +45,25 @@ use crate::{
     components::{ComponentState, InitializedComponent, PortBoundComponent},
     effect::{EffectBuilder, Effects},
     reactor::main_reactor::MainEvent,
-    types::JsonBlock,
+    types::{JsonBlock, JsonEraEnd},
     utils::{self, ListeningError},
     NodeRng,
 };
 pub use config::Config;
+pub(crate) use config::ConfigError;
 pub(crate) use event::Event;
 use event_indexer::{EventIndex, EventIndexer};
-use sse_server::ChannelsAndFilter;
-pub(crate) use sse_server::SseData;
+use event_log::{EventLog, SharedEventLog};
+use sse_server::{ChannelsAndFilter, Id, ServerSentEvent};
+pub(crate) use sse_server::{
+    SseData, SSE_API_DEPLOYS_PATH, SSE_API_MAIN_PATH, SSE_API_NODE_PATH, SSE_API_SIGNATURES_PATH,
+};
 
 const COMPONENT_NAME: &str = "event_stream_server";
 
+/// The name of the directory in the storage path holding the on-disk event log.
+const EVENT_LOG_DIR_NAME: &str = "sse_event_log";
+
 /// This is used to define the number of events to buffer in the tokio broadcast channel to help
 /// slower clients to try to avoid missing events (See
 /// <https://docs.rs/tokio/1.4.0/tokio/sync/broadcast/index.html#lagging> for further details).  The
@@ -87,17 +97,37 @@ pub(crate) struct EventStreamServer {
     storage_path: PathBuf,
     api_version: ProtocolVersion,
     sse_server: Option<InnerServer>,
+    /// Channel sender to pass event-stream data to other in-process consumers, e.g. the JSON-RPC
+    /// server's WebSocket subscriptions.  This is used regardless of whether the SSE HTTP server
+    /// is enabled.
+    #[data_size(skip)]
+    sse_data_broadcaster: broadcast::Sender<SseData>,
 }
 
 impl EventStreamServer {
-    pub(crate) fn new(config: Config, storage_path: PathBuf, api_version: ProtocolVersion) -> Self {
-        EventStreamServer {
+    pub(crate) fn new(
+        config: Config,
+        storage_path: PathBuf,
+        api_version: ProtocolVersion,
+    ) -> Result<Self, ConfigError> {
+        if config.event_stream_buffer_length == 0 {
+            return Err(ConfigError::ZeroEventStreamBufferLength);
+        }
+        let (sse_data_broadcaster, _) =
+            broadcast::channel(config.event_stream_buffer_length as usize);
+        Ok(EventStreamServer {
             state: ComponentState::Uninitialized,
             config,
             storage_path,
             api_version,
             sse_server: None,
-        }
+            sse_data_broadcaster,
+        })
+    }
+
+    /// Returns a sender via which a receiver of all subsequent event-stream data can be created.
+    pub(crate) fn sse_data_broadcaster(&self) -> broadcast::Sender<SseData> {
+        self.sse_data_broadcaster.clone()
     }
 
     fn listen(&mut self) -> Result<(), ListeningError> {
@@ -128,6 +158,30 @@ impl EventStreamServer {
 
         let (sse_data_sender, sse_data_receiver) = mpsc::unbounded_channel();
 
+        let mut event_indexer = EventIndexer::new(self.storage_path.clone());
+
+        let event_log = if self.config.enable_event_log {
+            match EventLog::open(
+                self.storage_path.join(EVENT_LOG_DIR_NAME),
+                self.config.event_log_max_bytes,
+                self.config.event_log_max_age,
+            )
+            .and_then(|event_log| {
+                if let Some(last_id) = event_log.last_id() {
+                    event_indexer.advance_past(last_id);
+                }
+                SharedEventLog::spawn(event_log)
+            }) {
+                Ok(event_log) => Some(event_log),
+                Err(error) => {
+                    warn!(%error, "failed to open sse event log: continuing without it");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         let listening_address = match self.config.cors_origin.as_str() {
             "" => {
                 let (listening_address, server_with_shutdown) = warp::serve(sse_filter)
@@ -147,6 +201,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
@@ -169,6 +224,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
@@ -192,6 +248,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
@@ -199,8 +256,6 @@ impl EventStreamServer {
 
         info!(address=%listening_address, "started event stream server");
 
-        let event_indexer = EventIndexer::new(self.storage_path.clone());
-
         self.sse_server = Some(InnerServer {
             sse_data_sender,
             event_indexer,
@@ -209,8 +264,14 @@ impl EventStreamServer {
         Ok(())
     }
 
-    /// Broadcasts the SSE data to all clients connected to the event stream.
+    /// Broadcasts the SSE data to all clients connected to the event stream, and to any in-process
+    /// consumers.
     fn broadcast(&mut self, sse_data: SseData) -> Effects<Event> {
+        if self.sse_data_broadcaster.receiver_count() > 0 {
+            // This can validly fail if the last receiver is dropped concurrently, so don't log the
+            // error.
+            let _ = self.sse_data_broadcaster.send(sse_data.clone());
+        }
         if let Some(server) = self.sse_server.as_mut() {
             let event_index = server.event_indexer.next_index();
             let _ = server.sse_data_sender.send((event_index, sse_data));
@@ -265,9 +326,13 @@ where
                 | Event::DeployAccepted(_)
                 | Event::DeployProcessed { .. }
                 | Event::DeploysExpired(_)
+                | Event::DeployStatusesChanged(_)
                 | Event::Fault { .. }
                 | Event::FinalitySignature(_)
-                | Event::Step { .. } => {
+                | Event::Step { .. }
+                | Event::EraEnd { .. }
+                | Event::BlockFinalized { .. }
+                | Event::NodeStateChanged { .. } => {
                     warn!(
                         ?event,
                         name = <Self as Component<MainEvent>>::name(self),
@@ -308,6 +373,15 @@ where
                     .into_iter()
                     .flat_map(|deploy_hash| self.broadcast(SseData::DeployExpired { deploy_hash }))
                     .collect(),
+                Event::DeployStatusesChanged(statuses) => statuses
+                    .into_iter()
+                    .flat_map(|(deploy_hash, status)| {
+                        self.broadcast(SseData::DeployStatusChanged {
+                            deploy_hash,
+                            status,
+                        })
+                    })
+                    .collect(),
                 Event::Fault {
                     era_id,
                     public_key,
@@ -325,6 +399,31 @@ where
                     era_id,
                     execution_effect,
                 }),
+                Event::EraEnd {
+                    era_id,
+                    block_hash,
+                    era_end,
+                } => self.broadcast(SseData::EraEnd {
+                    era_id,
+                    block_hash,
+                    era_end: Box::new(JsonEraEnd::from(*era_end)),
+                }),
+                Event::BlockFinalized {
+                    block_hash,
+                    era_id,
+                    height,
+                } => self.broadcast(SseData::BlockFinalized {
+                    block_hash,
+                    era_id,
+                    height,
+                }),
+                Event::NodeStateChanged {
+                    previous_state,
+                    new_state,
+                } => self.broadcast(SseData::NodeStateChanged {
+                    previous_state,
+                    new_state,
+                }),
             },
         }
     }
@@ -1,5 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_types::TimeDiff;
 
 /// Default binding address for the SSE HTTP server.
 ///
@@ -12,6 +15,12 @@ const DEFAULT_EVENT_STREAM_BUFFER_LENGTH: u32 = 5000;
 /// Default maximum number of subscribers.
 const DEFAULT_MAX_CONCURRENT_SUBSCRIBERS: u32 = 100;
 
+/// Default maximum size in bytes of the on-disk event log (1 GiB).
+const DEFAULT_EVENT_LOG_MAX_BYTES: u64 = 1_073_741_824;
+
+/// Default maximum age of events in the on-disk event log (7 days).
+const DEFAULT_EVENT_LOG_MAX_AGE_SECS: u32 = 7 * 24 * 60 * 60;
+
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
 
@@ -34,6 +43,16 @@ pub struct Config {
 
     /// CORS origin.
     pub cors_origin: String,
+
+    /// Setting to enable the on-disk event log, from which events older than those buffered in
+    /// memory can be replayed to clients, including across node restarts.
+    pub enable_event_log: bool,
+
+    /// Maximum size in bytes of the on-disk event log.
+    pub event_log_max_bytes: u64,
+
+    /// Maximum age of events in the on-disk event log.
+    pub event_log_max_age: TimeDiff,
 }
 
 impl Config {
@@ -45,6 +64,9 @@ impl Config {
             event_stream_buffer_length: DEFAULT_EVENT_STREAM_BUFFER_LENGTH,
             max_concurrent_subscribers: DEFAULT_MAX_CONCURRENT_SUBSCRIBERS,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            enable_event_log: false,
+            event_log_max_bytes: DEFAULT_EVENT_LOG_MAX_BYTES,
+            event_log_max_age: TimeDiff::from_seconds(DEFAULT_EVENT_LOG_MAX_AGE_SECS),
         }
     }
 }
@@ -54,3 +76,11 @@ impl Default for Config {
         Config::new()
     }
 }
+
+/// An error returned from mis-configuring the event stream server.
+#[derive(Debug, Error)]
+pub(crate) enum ConfigError {
+    /// The event stream buffer length is zero.
+    #[error("event_stream_buffer_length must be greater than zero")]
+    ZeroEventStreamBufferLength,
+}
@@ -6,7 +6,12 @@ use std::{
 use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp};
 use itertools::Itertools;
 
-use crate::types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature};
+use crate::{
+    reactor::main_reactor::ReactorState,
+    types::{
+        Block, BlockHash, Deploy, DeployHash, DeployHeader, DeployStatus, EraEnd, FinalitySignature,
+    },
+};
 
 #[derive(Debug)]
 pub enum Event {
@@ -20,6 +25,7 @@ pub enum Event {
         execution_result: Box<ExecutionResult>,
     },
     DeploysExpired(Vec<DeployHash>),
+    DeployStatusesChanged(Vec<(DeployHash, DeployStatus)>),
     Fault {
         era_id: EraId,
         public_key: Box<PublicKey>,
@@ -30,6 +36,20 @@ pub enum Event {
         era_id: EraId,
         execution_effect: ExecutionEffect,
     },
+    EraEnd {
+        era_id: EraId,
+        block_hash: BlockHash,
+        era_end: Box<EraEnd>,
+    },
+    BlockFinalized {
+        block_hash: BlockHash,
+        era_id: EraId,
+        height: u64,
+    },
+    NodeStateChanged {
+        previous_state: ReactorState,
+        new_state: ReactorState,
+    },
 }
 
 impl Display for Event {
@@ -47,6 +67,9 @@ impl Display for Event {
                     deploy_hashes.iter().join(", ")
                 )
             }
+            Event::DeployStatusesChanged(statuses) => {
+                write!(formatter, "{} deploy statuses changed", statuses.len())
+            }
             Event::DeployProcessed { deploy_hash, .. } => {
                 write!(formatter, "deploy processed {}", deploy_hash)
             }
@@ -61,6 +84,18 @@ impl Display for Event {
             ),
             Event::FinalitySignature(fs) => write!(formatter, "finality signature {}", fs),
             Event::Step { era_id, .. } => write!(formatter, "step committed for {}", era_id),
+            Event::EraEnd { era_id, .. } => write!(formatter, "era end for {}", era_id),
+            Event::BlockFinalized { block_hash, .. } => {
+                write!(formatter, "block finalized {}", block_hash)
+            }
+            Event::NodeStateChanged {
+                previous_state,
+                new_state,
+            } => write!(
+                formatter,
+                "node state changed from {} to {}",
+                previous_state, new_state
+            ),
         }
     }
 }
@@ -55,6 +55,15 @@ impl EventIndexer {
         index
     }
 
+    /// Ensures the next index is greater than the given one, e.g. where the cached index is stale
+    /// following an unclean shutdown.
+    pub(super) fn advance_past(&mut self, index: EventIndex) {
+        if self.index <= index {
+            debug!(cached_index = %self.index, %index, "advancing sse index");
+            self.index = index.wrapping_add(1);
+        }
+    }
+
     #[cfg(test)]
     pub(super) fn current_index(&self) -> EventIndex {
         self.index
@@ -109,6 +118,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_advance_past_given_index() {
+        let _ = logging::init();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let mut event_indexer = EventIndexer::new(tempdir.path().to_path_buf());
+        event_indexer.advance_past(9);
+        assert_eq!(event_indexer.next_index(), 10);
+
+        // Should not go backwards.
+        event_indexer.advance_past(3);
+        assert_eq!(event_indexer.next_index(), 11);
+    }
+
     #[test]
     fn should_wrap() {
         let _ = logging::init();
@@ -0,0 +1,507 @@
+//! An optional on-disk log of the events emitted by the event stream server.
+//!
+//! The in-memory buffer only holds the most recent `event_stream_buffer_length` events, so a client
+//! which has been disconnected for a while may not be able to resume from its last received event.
+//! Where enabled, every event is also appended to this log, from which older events can be
+//! replayed, including across node restarts.
+//!
+//! The log is split into segment files, each named after the ID of the first event it contains and
+//! holding one JSON-encoded event per line.  Whole segments are deleted once the log exceeds its
+//! maximum size, or once they have not been written to for longer than the maximum age.
+//!
+//! The server appends to the log via a `SharedEventLog`, which does the file I/O on a dedicated
+//! thread rather than on the async executor.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use casper_types::TimeDiff;
+
+use super::{Id, ServerSentEvent, SseData};
+
+/// The file extension of segment files.
+const SEGMENT_EXTENSION: &str = "log";
+/// The number of segments into which the maximum size of the log is divided.
+const TARGET_SEGMENT_COUNT: u64 = 16;
+
+/// A single event as persisted in the log.
+#[derive(Serialize, Deserialize)]
+struct LoggedEvent {
+    id: Id,
+    data: SseData,
+}
+
+/// A segment file of the log.
+#[derive(Clone, Debug)]
+struct Segment {
+    first_id: Id,
+    path: PathBuf,
+    size: u64,
+}
+
+impl Segment {
+    fn new(dir: &Path, first_id: Id) -> Self {
+        Segment {
+            first_id,
+            path: dir.join(format!("{:010}.{}", first_id, SEGMENT_EXTENSION)),
+            size: 0,
+        }
+    }
+
+    /// Parses the first event ID from a segment file's path.
+    fn first_id_from_path(path: &Path) -> Option<Id> {
+        if path.extension()? != SEGMENT_EXTENSION {
+            return None;
+        }
+        path.file_stem()?.to_str()?.parse().ok()
+    }
+
+    /// Returns the events in the segment, skipping any line which cannot be parsed.
+    ///
+    /// The final line may validly fail to parse if it is being written concurrently.
+    fn read_events(&self) -> io::Result<impl Iterator<Item = LoggedEvent>> {
+        let path = self.path.clone();
+        let lines = BufReader::new(File::open(&self.path)?).lines();
+        Ok(lines.filter_map(move |maybe_line| {
+            let line = match maybe_line {
+                Ok(line) => line,
+                Err(error) => {
+                    warn!(file = %path.display(), %error, "failed to read sse event log");
+                    return None;
+                }
+            };
+            match serde_json::from_str(&line) {
+                Ok(event) => Some(event),
+                Err(error) => {
+                    debug!(file = %path.display(), %error, "failed to parse sse event log entry");
+                    None
+                }
+            }
+        }))
+    }
+}
+
+/// An append-only, size- and age-bounded log of events.
+#[derive(Debug)]
+pub(super) struct EventLog {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    max_segment_bytes: u64,
+    /// The segments, oldest first.  The last one is the one being appended to.
+    segments: VecDeque<Segment>,
+    /// The file of the last segment, opened for appending.
+    current_file: Option<File>,
+    /// The ID of the most recently logged event.
+    last_id: Option<Id>,
+}
+
+impl EventLog {
+    /// Opens the log in the given directory, creating the directory if required.
+    pub(super) fn open(dir: PathBuf, max_bytes: u64, max_age: TimeDiff) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let mut segments = fs::read_dir(&dir)?
+            .map(|maybe_entry| {
+                let entry = maybe_entry?;
+                let path = entry.path();
+                Ok::<_, io::Error>(Segment::first_id_from_path(&path).map(|first_id| Segment {
+                    first_id,
+                    path,
+                    size: 0,
+                }))
+            })
+            .filter_map(Result::transpose)
+            .collect::<io::Result<Vec<_>>>()?;
+        segments.sort_by_key(|segment| segment.first_id);
+        for segment in segments.iter_mut() {
+            segment.size = fs::metadata(&segment.path)?.len();
+        }
+
+        let last_id = match segments.last() {
+            Some(segment) => segment.read_events()?.last().map(|event| event.id),
+            None => None,
+        };
+
+        let mut event_log = EventLog {
+            dir,
+            max_bytes,
+            max_age: Duration::from_millis(max_age.millis()),
+            max_segment_bytes: (max_bytes / TARGET_SEGMENT_COUNT).max(1),
+            segments: segments.into(),
+            current_file: None,
+            last_id,
+        };
+        event_log.prune()?;
+        info!(
+            dir = %event_log.dir.display(),
+            segment_count = event_log.segments.len(),
+            ?last_id,
+            "opened sse event log"
+        );
+        Ok(event_log)
+    }
+
+    /// Returns the ID of the most recently logged event, if any.
+    pub(super) fn last_id(&self) -> Option<Id> {
+        self.last_id
+    }
+
+    /// Appends the given event to the log.
+    ///
+    /// If the event's ID is not greater than that of the previously logged event, e.g. due to the
+    /// event IDs wrapping, the log is cleared first so that logged IDs are always ascending.
+    pub(super) fn append(&mut self, event: &ServerSentEvent) -> io::Result<()> {
+        let id = match (event.id, &event.data) {
+            (None, _) | (_, SseData::ApiVersion(_)) | (_, SseData::Shutdown) => return Ok(()),
+            (Some(id), _) => id,
+        };
+        if self.last_id.map_or(false, |last_id| id <= last_id) {
+            info!(%id, "sse event ID not ascending: clearing sse event log");
+            self.clear()?;
+        }
+
+        let needs_new_segment = self
+            .segments
+            .back()
+            .map_or(true, |segment| segment.size >= self.max_segment_bytes);
+        if needs_new_segment {
+            self.segments.push_back(Segment::new(&self.dir, id));
+            self.current_file = None;
+            self.prune()?;
+        }
+
+        let segment = self.segments.back_mut().expect("should have a segment");
+        if self.current_file.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&segment.path)?;
+            self.current_file = Some(file);
+        }
+        let file = self.current_file.as_mut().expect("should have a file");
+
+        let mut line = serde_json::to_vec(&LoggedEvent {
+            id,
+            data: event.data.clone(),
+        })?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        file.flush()?;
+        segment.size += line.len() as u64;
+        self.last_id = Some(id);
+        Ok(())
+    }
+
+    /// Returns a reader of the log as it currently stands, which can be used from another thread.
+    pub(super) fn reader(&self) -> EventLogReader {
+        EventLogReader {
+            segments: self.segments.iter().cloned().collect(),
+        }
+    }
+
+    /// Deletes the oldest segments while the log exceeds its maximum size, or while they have not
+    /// been modified for longer than the maximum age.  The segment being appended to is never
+    /// deleted.
+    fn prune(&mut self) -> io::Result<()> {
+        let now = SystemTime::now();
+        while self.segments.len() > 1 {
+            let total_bytes: u64 = self.segments.iter().map(|segment| segment.size).sum();
+            let oldest = &self.segments[0];
+            let age = fs::metadata(&oldest.path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if total_bytes <= self.max_bytes && age <= self.max_age {
+                break;
+            }
+            debug!(file = %oldest.path.display(), "removing sse event log segment");
+            remove_file_if_exists(&oldest.path)?;
+            let _ = self.segments.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Deletes all segments.
+    fn clear(&mut self) -> io::Result<()> {
+        self.current_file = None;
+        for segment in self.segments.drain(..) {
+            remove_file_if_exists(&segment.path)?;
+        }
+        self.last_id = None;
+        Ok(())
+    }
+}
+
+/// A handle to an `EventLog` which is appended to on a dedicated thread.
+#[derive(Clone, Debug)]
+pub(super) struct SharedEventLog {
+    event_log: Arc<Mutex<EventLog>>,
+    event_sender: mpsc::UnboundedSender<ServerSentEvent>,
+}
+
+impl SharedEventLog {
+    /// Spawns the thread appending to the given log.  The thread exits once all handles have been
+    /// dropped.
+    pub(super) fn spawn(event_log: EventLog) -> io::Result<Self> {
+        let event_log = Arc::new(Mutex::new(event_log));
+        let (event_sender, mut event_receiver) = mpsc::unbounded_channel::<ServerSentEvent>();
+        let cloned_event_log = Arc::clone(&event_log);
+        thread::Builder::new()
+            .name("sse-event-log".to_string())
+            .spawn(move || {
+                while let Some(event) = event_receiver.blocking_recv() {
+                    let result = cloned_event_log
+                        .lock()
+                        .expect("sse event log lock poisoned")
+                        .append(&event);
+                    if let Err(error) = result {
+                        warn!(%error, "failed to write to sse event log");
+                    }
+                }
+            })?;
+        Ok(SharedEventLog {
+            event_log,
+            event_sender,
+        })
+    }
+
+    /// Queues the given event to be appended to the log.
+    pub(super) fn append(&self, event: ServerSentEvent) {
+        if self.event_sender.send(event).is_err() {
+            warn!("sse event log thread has exited");
+        }
+    }
+
+    /// Returns a reader of the log as it currently stands.
+    ///
+    /// This blocks while an event is being appended, so shouldn't be called on the async executor.
+    pub(super) fn reader(&self) -> EventLogReader {
+        self.event_log
+            .lock()
+            .expect("sse event log lock poisoned")
+            .reader()
+    }
+}
+
+/// A snapshot of the segments of an `EventLog`, used to read events from the log without blocking
+/// the event stream server.
+#[derive(Debug)]
+pub(super) struct EventLogReader {
+    segments: Vec<Segment>,
+}
+
+impl EventLogReader {
+    /// Returns the logged events with IDs in the range `[start_from, end_before)`, or with IDs of
+    /// at least `start_from` if `end_before` is `None`.
+    ///
+    /// Segments which are deleted while being read are treated as empty.
+    pub(super) fn events(
+        &self,
+        start_from: Id,
+        end_before: Option<Id>,
+    ) -> impl Iterator<Item = ServerSentEvent> + '_ {
+        // Skip segments which are wholly before `start_from`, i.e. where the next segment starts
+        // at or before `start_from`.
+        let first_relevant = self
+            .segments
+            .iter()
+            .rposition(|segment| segment.first_id <= start_from)
+            .unwrap_or_default();
+        self.segments[first_relevant..]
+            .iter()
+            .take_while(move |segment| end_before.map_or(true, |end| segment.first_id < end))
+            .flat_map(|segment| match segment.read_events() {
+                Ok(events) => Some(events),
+                Err(error) => {
+                    if error.kind() != io::ErrorKind::NotFound {
+                        warn!(
+                            file = %segment.path.display(),
+                            %error,
+                            "failed to open sse event log segment"
+                        );
+                    }
+                    None
+                }
+            })
+            .flatten()
+            .skip_while(move |event| event.id < start_from)
+            .take_while(move |event| end_before.map_or(true, |end| event.id < end))
+            .map(|event| ServerSentEvent {
+                id: Some(event.id),
+                data: event.data,
+            })
+    }
+}
+
+fn remove_file_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    fn event(id: Id, rng: &mut TestRng) -> ServerSentEvent {
+        ServerSentEvent {
+            id: Some(id),
+            data: SseData::random_deploy_expired(rng),
+        }
+    }
+
+    fn ids(reader: &EventLogReader, start_from: Id, end_before: Option<Id>) -> Vec<Id> {
+        reader
+            .events(start_from, end_before)
+            .map(|event| event.id.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_read_requested_range() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        // Small enough that each segment only holds a few events, but large enough to hold them
+        // all.
+        let mut event_log = EventLog::open(
+            tempdir.path().to_path_buf(),
+            16_000,
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        for id in 10..40 {
+            event_log.append(&event(id, rng)).unwrap();
+        }
+        assert!(event_log.segments.len() > 1);
+
+        let reader = event_log.reader();
+        assert_eq!(ids(&reader, 0, None), (10..40).collect::<Vec<_>>());
+        assert_eq!(ids(&reader, 25, Some(30)), (25..30).collect::<Vec<_>>());
+        assert_eq!(ids(&reader, 39, None), vec![39]);
+        assert!(ids(&reader, 40, None).is_empty());
+    }
+
+    #[test]
+    fn should_persist_across_reopening() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let max_age = TimeDiff::from_seconds(60);
+        let events: Vec<_> = (0..5).map(|id| event(id, rng)).collect();
+        {
+            let mut event_log =
+                EventLog::open(tempdir.path().to_path_buf(), 1_000_000, max_age).unwrap();
+            for event in &events {
+                event_log.append(event).unwrap();
+            }
+        }
+
+        let event_log = EventLog::open(tempdir.path().to_path_buf(), 1_000_000, max_age).unwrap();
+        assert_eq!(event_log.last_id(), Some(4));
+        let read_events: Vec<_> = event_log.reader().events(0, None).collect();
+        assert_eq!(read_events, events);
+    }
+
+    #[test]
+    fn should_not_log_shutdown() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut event_log = EventLog::open(
+            tempdir.path().to_path_buf(),
+            1_000_000,
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        let shutdown = ServerSentEvent {
+            id: Some(0),
+            data: SseData::Shutdown,
+        };
+        event_log.append(&shutdown).unwrap();
+        assert_eq!(event_log.last_id(), None);
+        assert_eq!(event_log.reader().events(0, None).count(), 0);
+    }
+
+    #[test]
+    fn should_prune_oldest_segments_when_too_large() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let max_bytes = 3_200;
+        let mut event_log = EventLog::open(
+            tempdir.path().to_path_buf(),
+            max_bytes,
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        for id in 0..200 {
+            event_log.append(&event(id, rng)).unwrap();
+        }
+
+        let total_bytes: u64 = event_log.segments.iter().map(|segment| segment.size).sum();
+        assert!(total_bytes <= max_bytes + event_log.max_segment_bytes * 2);
+        let read_ids = ids(&event_log.reader(), 0, None);
+        assert_eq!(read_ids.last(), Some(&199));
+        assert!(read_ids[0] > 0);
+        assert_eq!(
+            read_ids,
+            (read_ids[0]..200).collect::<Vec<_>>(),
+            "remaining events should be contiguous"
+        );
+    }
+
+    #[test]
+    fn should_clear_if_ids_not_ascending() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut event_log = EventLog::open(
+            tempdir.path().to_path_buf(),
+            1_000_000,
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        for id in [5, 6, 7, 2, 3] {
+            event_log.append(&event(id, rng)).unwrap();
+        }
+        assert_eq!(ids(&event_log.reader(), 0, None), vec![2, 3]);
+    }
+
+    #[test]
+    fn should_append_via_shared_log() {
+        let rng = &mut TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let event_log = EventLog::open(
+            tempdir.path().to_path_buf(),
+            1_000_000,
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        let shared_event_log = SharedEventLog::spawn(event_log).unwrap();
+        for id in 0..5 {
+            shared_event_log.append(event(id, rng));
+        }
+
+        // The events are appended asynchronously.
+        for _ in 0..1_000 {
+            if ids(&shared_event_log.reader(), 0, None).len() == 5 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            ids(&shared_event_log.reader(), 0, None),
+            (0..5).collect::<Vec<_>>()
+        );
+    }
+}
@@ -10,6 +10,7 @@ use wheelbuf::WheelBuf;
 use casper_types::ProtocolVersion;
 
 use super::{
+    event_log::SharedEventLog,
     sse_server::{BroadcastChannelMessage, Id, NewSubscriberInfo, ServerSentEvent},
     Config, EventIndex, SseData,
 };
@@ -25,6 +26,10 @@ use super::{
 /// * `new_subscriber_info_receiver` is used to notify the server of the details of a new client
 ///   having subscribed to the event stream.  It allows the server to populate that client's stream
 ///   with the requested number of historical events.
+/// * `event_log`, if provided, has every event appended to it, and is used to provide clients with
+///   requested historical events which are older than those in the in-memory buffer.  These are
+///   sent from a blocking task, which waits for the client to keep up with them.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     config: Config,
     api_version: ProtocolVersion,
@@ -33,6 +38,7 @@ pub(super) async fn run(
     mut data_receiver: mpsc::UnboundedReceiver<(EventIndex, SseData)>,
     broadcaster: broadcast::Sender<BroadcastChannelMessage>,
     mut new_subscriber_info_receiver: mpsc::UnboundedReceiver<NewSubscriberInfo>,
+    event_log: Option<SharedEventLog>,
 ) {
     let server_joiner = task::spawn(server_with_shutdown);
 
@@ -53,7 +59,7 @@ pub(super) async fn run(
                         // errors - the client may have disconnected already.
                         let _ = subscriber
                             .initial_events_sender
-                            .send(ServerSentEvent::initial_event(api_version));
+                            .try_send(ServerSentEvent::initial_event(api_version));
                         // If the client supplied a "start_from" index, provide the buffered events.
                         // If they requested more than is buffered, just provide the whole buffer.
                         if let Some(start_index) = subscriber.start_from {
@@ -73,16 +79,55 @@ pub(super) async fn run(
                                     id > Id::MAX - buffer_size || id < buffer_size
                                 })
                                 .unwrap_or_default();
-                            for event in buffer.iter().skip_while(|event| {
-                                if in_wraparound_zone {
-                                    event.id.unwrap().wrapping_add(buffer_size)
-                                        < start_index.wrapping_add(buffer_size)
-                                } else {
-                                    event.id.unwrap() < start_index
+                            let buffered_events: Vec<ServerSentEvent> = buffer
+                                .iter()
+                                .skip_while(|event| {
+                                    if in_wraparound_zone {
+                                        event.id.unwrap().wrapping_add(buffer_size)
+                                            < start_index.wrapping_add(buffer_size)
+                                    } else {
+                                        event.id.unwrap() < start_index
+                                    }
+                                })
+                                .cloned()
+                                .collect();
+
+                            // If the client requested events older than the oldest buffered one,
+                            // read those from the event log, if enabled.  As this can be slow, it
+                            // is done in a separate task, which then sends the buffered events.
+                            // The event log isn't used while the buffered IDs are wrapping, as the
+                            // log is cleared when they do.
+                            let first_buffered_id = buffer.iter().next().and_then(|event| event.id);
+                            let last_buffered_id = buffer.iter().last().and_then(|event| event.id);
+                            let needs_logged_events = match (first_buffered_id, last_buffered_id) {
+                                (Some(first_id), Some(last_id)) => {
+                                    first_id <= last_id && start_index < first_id
+                                }
+                                _ => true,
+                            };
+                            match event_log.as_ref() {
+                                Some(event_log) if needs_logged_events => {
+                                    let event_log = event_log.clone();
+                                    let initial_events_sender = subscriber.initial_events_sender;
+                                    task::spawn_blocking(move || {
+                                        let reader = event_log.reader();
+                                        let logged_events =
+                                            reader.events(start_index, first_buffered_id);
+                                        for event in logged_events.chain(buffered_events) {
+                                            if initial_events_sender.blocking_send(event).is_err() {
+                                                // The client has disconnected.
+                                                break;
+                                            }
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    for event in buffered_events {
+                                        // As per sending `SSE_INITIAL_EVENT`, we don't care if this
+                                        // errors.  The channel has capacity for the whole buffer.
+                                        let _ = subscriber.initial_events_sender.try_send(event);
+                                    }
                                 }
-                            }) {
-                                // As per sending `SSE_INITIAL_EVENT`, we don't care if this errors.
-                                let _ = subscriber.initial_events_sender.send(event.clone());
                             }
                         }
                     }
@@ -94,6 +139,9 @@ pub(super) async fn run(
                             // Buffer the data and broadcast it to subscribed clients.
                             trace!("Event stream server received {:?}", data);
                             let event = ServerSentEvent { id: Some(event_index), data };
+                            if let Some(log) = event_log.as_ref() {
+                                log.append(event.clone());
+                            }
                             buffer.push(event.clone());
                             let message = BroadcastChannelMessage::ServerSentEvent(event);
                             // This can validly fail if there are no connected clients, so don't log
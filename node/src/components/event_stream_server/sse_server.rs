@@ -2,6 +2,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    hash::Hash,
     net::SocketAddr,
     sync::{Arc, RwLock},
 };
@@ -18,9 +19,7 @@ use tokio::sync::{
     broadcast::{self, error::RecvError},
     mpsc,
 };
-use tokio_stream::wrappers::{
-    errors::BroadcastStreamRecvError, BroadcastStream, UnboundedReceiverStream,
-};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 use tracing::{debug, error, info, warn};
 use warp::{
     addr,
@@ -32,13 +31,21 @@ use warp::{
     Filter, Reply,
 };
 
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_hashing::Digest;
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
-    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
+    AsymmetricType, EraId, ExecutionEffect, ExecutionResult, HashAddr, Key, ProtocolVersion,
+    PublicKey, TimeDiff, Timestamp,
 };
 
-use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
+use crate::{
+    reactor::main_reactor::ReactorState,
+    types::{
+        BlockHash, Deploy, DeployHash, DeployStatus, FinalitySignature, JsonBlock, JsonEraEnd,
+    },
+};
 #[cfg(test)]
 use crate::{testing, types::Block};
 
@@ -47,12 +54,30 @@ pub const SSE_API_ROOT_PATH: &str = "events";
 /// The URL path part to subscribe to all events other than `DeployAccepted`s and
 /// `FinalitySignature`s.
 pub const SSE_API_MAIN_PATH: &str = "main";
-/// The URL path part to subscribe to only `DeployAccepted` events.
+/// The URL path part to subscribe to only `DeployAccepted` and `DeployStatusChanged` events.
 pub const SSE_API_DEPLOYS_PATH: &str = "deploys";
 /// The URL path part to subscribe to only `FinalitySignature` events.
 pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
+/// The URL path part to subscribe to only `EraEnd`, `BlockFinalized` and `NodeStateChanged`
+/// events.
+pub const SSE_API_NODE_PATH: &str = "node";
 /// The URL query string field name.
 pub const QUERY_FIELD: &str = "start_from";
+/// The URL query string field name for restricting `DeployAccepted` and `DeployProcessed` events to
+/// those of the given comma-separated account public keys.
+pub const ACCOUNT_QUERY_FIELD: &str = "account";
+/// The URL query string field name for restricting `DeployAccepted` and `DeployProcessed` events to
+/// those involving the given comma-separated contract or contract package hashes.
+pub const CONTRACT_HASH_QUERY_FIELD: &str = "contract_hash";
+/// The URL query string field name for restricting `DeployAccepted`, `DeployProcessed` and
+/// `DeployStatusChanged` events to those of the given comma-separated deploy hashes.
+pub const DEPLOY_HASH_QUERY_FIELD: &str = "deploy_hash";
+/// The URL query string field name for restricting `FinalitySignature` events to those of the
+/// given comma-separated validator public keys.
+pub const VALIDATOR_QUERY_FIELD: &str = "validator";
+/// The URL query string field name for stripping the execution effect from `DeployProcessed`
+/// events.
+pub const EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD: &str = "exclude_execution_effect";
 
 /// The filter associated with `/events/main` path.
 const MAIN_FILTER: [EventFilter; 5] = [
@@ -63,9 +88,18 @@ const MAIN_FILTER: [EventFilter; 5] = [
     EventFilter::Step,
 ];
 /// The filter associated with `/events/deploys` path.
-const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
+const DEPLOYS_FILTER: [EventFilter; 2] = [
+    EventFilter::DeployAccepted,
+    EventFilter::DeployStatusChanged,
+];
 /// The filter associated with `/events/sigs` path.
 const SIGNATURES_FILTER: [EventFilter; 1] = [EventFilter::FinalitySignature];
+/// The filter associated with `/events/node` path.
+const NODE_FILTER: [EventFilter; 3] = [
+    EventFilter::EraEnd,
+    EventFilter::BlockFinalized,
+    EventFilter::NodeStateChanged,
+];
 
 /// The "id" field of the events sent on the event stream to clients.
 pub type Id = u32;
@@ -100,6 +134,11 @@ pub enum SseData {
     },
     /// The given deploy has expired.
     DeployExpired { deploy_hash: DeployHash },
+    /// The lifecycle status of the given deploy has changed.
+    DeployStatusChanged {
+        deploy_hash: DeployHash,
+        status: DeployStatus,
+    },
     /// Generic representation of validator's fault in an era.
     Fault {
         era_id: EraId,
@@ -114,6 +153,24 @@ pub enum SseData {
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
+    /// The given switch block has been added to the linear chain, ending the given era.
+    EraEnd {
+        era_id: EraId,
+        block_hash: BlockHash,
+        era_end: Box<JsonEraEnd>,
+    },
+    /// The given block has been signed by validators with sufficient combined weight to be
+    /// considered finalized.
+    BlockFinalized {
+        block_hash: BlockHash,
+        era_id: EraId,
+        height: u64,
+    },
+    /// The node's reactor has changed state, e.g. from catching up to keeping up with the network.
+    NodeStateChanged {
+        previous_state: ReactorState,
+        new_state: ReactorState,
+    },
     /// The node is about to shut down.
     Shutdown,
 }
@@ -126,17 +183,29 @@ impl SseData {
             SseData::DeployAccepted { .. } => filter.contains(&EventFilter::DeployAccepted),
             SseData::DeployProcessed { .. } => filter.contains(&EventFilter::DeployProcessed),
             SseData::DeployExpired { .. } => filter.contains(&EventFilter::DeployExpired),
+            SseData::DeployStatusChanged { .. } => {
+                filter.contains(&EventFilter::DeployStatusChanged)
+            }
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
             SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
             SseData::Step { .. } => filter.contains(&EventFilter::Step),
+            SseData::EraEnd { .. } => filter.contains(&EventFilter::EraEnd),
+            SseData::BlockFinalized { .. } => filter.contains(&EventFilter::BlockFinalized),
+            SseData::NodeStateChanged { .. } => filter.contains(&EventFilter::NodeStateChanged),
         }
     }
+
+    /// Returns `true` if this event is published on the feed with the given final URL path element,
+    /// e.g. `SSE_API_MAIN_PATH`.
+    pub(crate) fn is_published_on(&self, path_param: &str) -> bool {
+        get_filter(path_param).map_or(false, |filter| self.should_include(filter))
+    }
 }
 
 #[cfg(test)]
 impl SseData {
     /// Returns a random `SseData::ApiVersion`.
-    pub(super) fn random_api_version(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_api_version(rng: &mut TestRng) -> Self {
         let protocol_version = ProtocolVersion::from_parts(
             rng.gen_range(0..10),
             rng.gen::<u8>() as u32,
@@ -146,7 +215,7 @@ impl SseData {
     }
 
     /// Returns a random `SseData::BlockAdded`.
-    pub(super) fn random_block_added(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_block_added(rng: &mut TestRng) -> Self {
         let block = Block::random(rng);
         SseData::BlockAdded {
             block_hash: *block.hash(),
@@ -155,7 +224,7 @@ impl SseData {
     }
 
     /// Returns a random `SseData::DeployAccepted`, along with the random `Deploy`.
-    pub(super) fn random_deploy_accepted(rng: &mut TestRng) -> (Self, Deploy) {
+    pub(crate) fn random_deploy_accepted(rng: &mut TestRng) -> (Self, Deploy) {
         let deploy = Deploy::random(rng);
         let event = SseData::DeployAccepted {
             deploy: Arc::new(deploy.clone()),
@@ -164,7 +233,7 @@ impl SseData {
     }
 
     /// Returns a random `SseData::DeployProcessed`.
-    pub(super) fn random_deploy_processed(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_deploy_processed(rng: &mut TestRng) -> Self {
         let deploy = Deploy::random(rng);
         SseData::DeployProcessed {
             deploy_hash: Box::new(*deploy.hash()),
@@ -178,15 +247,45 @@ impl SseData {
     }
 
     /// Returns a random `SseData::DeployExpired`
-    pub(super) fn random_deploy_expired(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_deploy_expired(rng: &mut TestRng) -> Self {
         let deploy = testing::create_expired_deploy(Timestamp::now(), rng);
         SseData::DeployExpired {
             deploy_hash: *deploy.hash(),
         }
     }
 
+    /// Returns a random `SseData::DeployStatusChanged`.
+    pub(crate) fn random_deploy_status_changed(rng: &mut TestRng) -> Self {
+        let status = match rng.gen_range(0..9) {
+            0 => DeployStatus::Received,
+            1 => DeployStatus::Buffered,
+            2 => DeployStatus::Proposed {
+                timestamp: Timestamp::random(rng),
+            },
+            3 => DeployStatus::Included {
+                block_height: rng.gen(),
+            },
+            4 => DeployStatus::Executed {
+                block_hash: BlockHash::random(rng),
+                success: rng.gen(),
+            },
+            5 => DeployStatus::Replaced {
+                replacement: DeployHash::random(rng),
+            },
+            6 => DeployStatus::Evicted,
+            7 => DeployStatus::Expired,
+            _ => DeployStatus::Rejected {
+                reason: "invalid deploy".to_string(),
+            },
+        };
+        SseData::DeployStatusChanged {
+            deploy_hash: DeployHash::random(rng),
+            status,
+        }
+    }
+
     /// Returns a random `SseData::Fault`.
-    pub(super) fn random_fault(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_fault(rng: &mut TestRng) -> Self {
         SseData::Fault {
             era_id: EraId::new(rng.gen()),
             public_key: Box::new(PublicKey::random(rng)),
@@ -195,7 +294,7 @@ impl SseData {
     }
 
     /// Returns a random `SseData::FinalitySignature`.
-    pub(super) fn random_finality_signature(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_finality_signature(rng: &mut TestRng) -> Self {
         SseData::FinalitySignature(Box::new(FinalitySignature::random_for_block(
             BlockHash::random(rng),
             rng.gen(),
@@ -203,7 +302,7 @@ impl SseData {
     }
 
     /// Returns a random `SseData::Step`.
-    pub(super) fn random_step(rng: &mut TestRng) -> Self {
+    pub(crate) fn random_step(rng: &mut TestRng) -> Self {
         let execution_effect = match rng.gen::<ExecutionResult>() {
             ExecutionResult::Success { effect, .. } | ExecutionResult::Failure { effect, .. } => {
                 effect
@@ -214,6 +313,46 @@ impl SseData {
             execution_effect,
         }
     }
+
+    /// Returns a random `SseData::EraEnd`.
+    pub(crate) fn random_era_end(rng: &mut TestRng) -> Self {
+        let block = Block::random_switch_block(rng);
+        let era_end = block
+            .header()
+            .era_end()
+            .cloned()
+            .expect("should be a switch block");
+        SseData::EraEnd {
+            era_id: block.header().era_id(),
+            block_hash: *block.hash(),
+            era_end: Box::new(JsonEraEnd::from(era_end)),
+        }
+    }
+
+    /// Returns a random `SseData::BlockFinalized`.
+    pub(crate) fn random_block_finalized(rng: &mut TestRng) -> Self {
+        SseData::BlockFinalized {
+            block_hash: BlockHash::random(rng),
+            era_id: EraId::new(rng.gen()),
+            height: rng.gen(),
+        }
+    }
+
+    /// Returns a random `SseData::NodeStateChanged`.
+    pub(crate) fn random_node_state_changed(rng: &mut TestRng) -> Self {
+        let states = [
+            ReactorState::Initialize,
+            ReactorState::CatchUp,
+            ReactorState::Upgrading,
+            ReactorState::KeepUp,
+            ReactorState::Validate,
+            ReactorState::ShutdownForUpgrade,
+        ];
+        SseData::NodeStateChanged {
+            previous_state: states[rng.gen_range(0..states.len())],
+            new_state: states[rng.gen_range(0..states.len())],
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -260,7 +399,9 @@ pub(super) struct NewSubscriberInfo {
     pub(super) start_from: Option<Id>,
     /// A channel to send the initial events to the client's handler.  This will always send the
     /// ApiVersion as the first event, and then any buffered events as indicated by `start_from`.
-    pub(super) initial_events_sender: mpsc::UnboundedSender<ServerSentEvent>,
+    ///
+    /// The channel can hold the ApiVersion event and a full buffer of events.
+    pub(super) initial_events_sender: mpsc::Sender<ServerSentEvent>,
 }
 
 /// A filter for event types a client has subscribed to receive.
@@ -270,17 +411,173 @@ pub(super) enum EventFilter {
     DeployAccepted,
     DeployProcessed,
     DeployExpired,
+    DeployStatusChanged,
     Fault,
     FinalitySignature,
     Step,
+    EraEnd,
+    BlockFinalized,
+    NodeStateChanged,
+}
+
+/// Restrictions on the content of events a client has subscribed to receive, as specified in the
+/// URL query string.
+///
+/// Each restriction only applies to the event types to which it is relevant; other event types are
+/// unaffected.  An event must satisfy all restrictions, and satisfies a restriction if it matches
+/// any of that restriction's values.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub(super) struct ContentFilter {
+    accounts: Option<HashSet<PublicKey>>,
+    contract_hashes: Option<HashSet<HashAddr>>,
+    deploy_hashes: Option<HashSet<DeployHash>>,
+    validators: Option<HashSet<PublicKey>>,
+    exclude_execution_effect: bool,
+}
+
+impl ContentFilter {
+    /// Returns `true` if `data` satisfies all restrictions of this filter.
+    fn matches(&self, data: &SseData) -> bool {
+        match data {
+            SseData::DeployAccepted { deploy } => {
+                matches_any(&self.accounts, deploy.header().account())
+                    && matches_any(&self.deploy_hashes, Deploy::hash(deploy))
+                    && self
+                        .contract_hashes
+                        .as_ref()
+                        .map_or(true, |contract_hashes| {
+                            [deploy.session(), deploy.payment()]
+                                .iter()
+                                .copied()
+                                .filter_map(called_contract_hash)
+                                .any(|contract_hash| contract_hashes.contains(&contract_hash))
+                        })
+            }
+            SseData::DeployProcessed {
+                deploy_hash,
+                account,
+                execution_result,
+                ..
+            } => {
+                matches_any(&self.accounts, &**account)
+                    && matches_any(&self.deploy_hashes, &**deploy_hash)
+                    && self
+                        .contract_hashes
+                        .as_ref()
+                        .map_or(true, |contract_hashes| {
+                            let effect = match execution_result.as_ref() {
+                                ExecutionResult::Success { effect, .. }
+                                | ExecutionResult::Failure { effect, .. } => effect,
+                            };
+                            contract_hashes.iter().any(|contract_hash| {
+                                let key = Key::Hash(*contract_hash).to_formatted_string();
+                                effect
+                                    .transforms
+                                    .iter()
+                                    .any(|transform_entry| transform_entry.key == key)
+                            })
+                        })
+            }
+            SseData::DeployStatusChanged { deploy_hash, .. } => {
+                matches_any(&self.deploy_hashes, deploy_hash)
+            }
+            SseData::FinalitySignature(finality_signature) => {
+                matches_any(&self.validators, &finality_signature.public_key)
+            }
+            SseData::ApiVersion(_)
+            | SseData::BlockAdded { .. }
+            | SseData::DeployExpired { .. }
+            | SseData::Fault { .. }
+            | SseData::Step { .. }
+            | SseData::EraEnd { .. }
+            | SseData::BlockFinalized { .. }
+            | SseData::NodeStateChanged { .. }
+            | SseData::Shutdown => true,
+        }
+    }
+}
+
+/// Returns `true` if `values` is `None` or contains `value`.
+fn matches_any<T: Eq + Hash>(values: &Option<HashSet<T>>, value: &T) -> bool {
+    values
+        .as_ref()
+        .map_or(true, |values| values.contains(value))
+}
+
+/// Returns the hash of the stored contract or contract package called by `item`, if any.
+fn called_contract_hash(item: &ExecutableDeployItem) -> Option<HashAddr> {
+    match item {
+        ExecutableDeployItem::StoredContractByHash { hash, .. } => Some(hash.value()),
+        ExecutableDeployItem::StoredVersionedContractByHash { hash, .. } => Some(hash.value()),
+        ExecutableDeployItem::ModuleBytes { .. }
+        | ExecutableDeployItem::StoredContractByName { .. }
+        | ExecutableDeployItem::StoredVersionedContractByName { .. }
+        | ExecutableDeployItem::Transfer { .. } => None,
+    }
+}
+
+/// Returns a copy of the given `DeployProcessed` data with an empty execution effect, or `None` if
+/// `data` is a different variant.
+fn without_execution_effect(data: &SseData) -> Option<SseData> {
+    let (deploy_hash, account, timestamp, ttl, dependencies, block_hash, execution_result) =
+        match data {
+            SseData::DeployProcessed {
+                deploy_hash,
+                account,
+                timestamp,
+                ttl,
+                dependencies,
+                block_hash,
+                execution_result,
+            } => (
+                deploy_hash,
+                account,
+                timestamp,
+                ttl,
+                dependencies,
+                block_hash,
+                execution_result,
+            ),
+            _ => return None,
+        };
+    let execution_result = match execution_result.as_ref() {
+        ExecutionResult::Success {
+            transfers, cost, ..
+        } => ExecutionResult::Success {
+            effect: ExecutionEffect::default(),
+            transfers: transfers.clone(),
+            cost: *cost,
+        },
+        ExecutionResult::Failure {
+            transfers,
+            cost,
+            error_message,
+            ..
+        } => ExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: transfers.clone(),
+            cost: *cost,
+            error_message: error_message.clone(),
+        },
+    };
+    Some(SseData::DeployProcessed {
+        deploy_hash: deploy_hash.clone(),
+        account: account.clone(),
+        timestamp: *timestamp,
+        ttl: *ttl,
+        dependencies: dependencies.clone(),
+        block_hash: block_hash.clone(),
+        execution_result: Box::new(execution_result),
+    })
 }
 
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
 async fn filter_map_server_sent_event(
     event: &ServerSentEvent,
     event_filter: &[EventFilter],
+    content_filter: &ContentFilter,
 ) -> Option<Result<WarpServerSentEvent, RecvError>> {
-    if !event.data.should_include(event_filter) {
+    if !event.data.should_include(event_filter) || !content_filter.matches(&event.data) {
         return None;
     }
 
@@ -309,12 +606,27 @@ async fn filter_map_server_sent_event(
                 WarpServerSentEvent::default()
             }))),
 
+        &SseData::DeployProcessed { .. } if content_filter.exclude_execution_effect => {
+            let data = without_execution_effect(&event.data)?;
+            Some(Ok(WarpServerSentEvent::default()
+                .json_data(&data)
+                .unwrap_or_else(|error| {
+                    warn!(%error, ?event, "failed to jsonify sse event");
+                    WarpServerSentEvent::default()
+                })
+                .id(id)))
+        }
+
         &SseData::BlockAdded { .. }
         | &SseData::DeployProcessed { .. }
         | &SseData::DeployExpired { .. }
+        | &SseData::DeployStatusChanged { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
         | &SseData::FinalitySignature(_)
+        | &SseData::EraEnd { .. }
+        | &SseData::BlockFinalized { .. }
+        | &SseData::NodeStateChanged { .. }
         | &SseData::Shutdown => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
@@ -341,40 +653,71 @@ pub(super) fn get_filter(path_param: &str) -> Option<&'static [EventFilter]> {
         SSE_API_MAIN_PATH => Some(&MAIN_FILTER[..]),
         SSE_API_DEPLOYS_PATH => Some(&DEPLOYS_FILTER[..]),
         SSE_API_SIGNATURES_PATH => Some(&SIGNATURES_FILTER[..]),
+        SSE_API_NODE_PATH => Some(&NODE_FILTER[..]),
         _ => None,
     }
 }
 
-/// Extracts the starting event ID from the provided query, or `None` if `query` is empty.
+/// Extracts the starting event ID (or `None` if not specified) and the content filter from the
+/// provided query.
 ///
-/// If `query` is not empty, returns a 422 response if `query` doesn't have exactly one entry,
-/// "starts_from" mapped to a value representing an event ID.
-fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
-    if query.is_empty() {
-        return Ok(None);
-    }
-
-    if query.len() > 1 {
-        return Err(create_422());
+/// Returns a 422 response if `query` has an unknown field or a field with an invalid value.
+fn parse_query(query: HashMap<String, String>) -> Result<(Option<Id>, ContentFilter), Response> {
+    let mut start_from = None;
+    let mut content_filter = ContentFilter::default();
+    for (field, value) in query {
+        match field.as_str() {
+            QUERY_FIELD => start_from = Some(value.parse::<Id>().map_err(|_| create_422())?),
+            ACCOUNT_QUERY_FIELD => {
+                content_filter.accounts =
+                    Some(parse_list(&value, |item| PublicKey::from_hex(item).ok())?)
+            }
+            CONTRACT_HASH_QUERY_FIELD => {
+                content_filter.contract_hashes = Some(parse_list(&value, |item| {
+                    Digest::from_hex(item).ok().map(|digest| digest.value())
+                })?)
+            }
+            DEPLOY_HASH_QUERY_FIELD => {
+                content_filter.deploy_hashes = Some(parse_list(&value, |item| {
+                    Digest::from_hex(item).ok().map(DeployHash::new)
+                })?)
+            }
+            VALIDATOR_QUERY_FIELD => {
+                content_filter.validators =
+                    Some(parse_list(&value, |item| PublicKey::from_hex(item).ok())?)
+            }
+            EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD => {
+                content_filter.exclude_execution_effect =
+                    value.parse::<bool>().map_err(|_| create_422())?
+            }
+            _ => return Err(create_422()),
+        }
     }
+    Ok((start_from, content_filter))
+}
 
-    match query
-        .get(QUERY_FIELD)
-        .and_then(|id_str| id_str.parse::<Id>().ok())
-    {
-        Some(id) => Ok(Some(id)),
-        None => Err(create_422()),
-    }
+/// Parses a comma-separated list of values, returning a 422 response if any fails to parse.
+fn parse_list<T, F>(value: &str, parse_item: F) -> Result<HashSet<T>, Response>
+where
+    T: Eq + Hash,
+    F: Fn(&str) -> Option<T>,
+{
+    value
+        .split(',')
+        .map(|item| parse_item(item).ok_or_else(create_422))
+        .collect()
 }
 
 /// Creates a 404 response with a useful error message in the body.
 fn create_404() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}' or '/{root}/{sigs}'\n",
+        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}', '/{root}/{sigs}' or \
+        '/{root}/{node}'\n",
         root = SSE_API_ROOT_PATH,
         main = SSE_API_MAIN_PATH,
         deploys = SSE_API_DEPLOYS_PATH,
-        sigs = SSE_API_SIGNATURES_PATH
+        sigs = SSE_API_SIGNATURES_PATH,
+        node = SSE_API_NODE_PATH
     )));
     *response.status_mut() = StatusCode::NOT_FOUND;
     response
@@ -384,8 +727,14 @@ fn create_404() -> Response {
 /// string.
 fn create_422() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid query: expected single field '{}=<EVENT ID>'\n",
-        QUERY_FIELD
+        "invalid query: expected only optional fields '{}=<EVENT ID>', '{}=<PUBLIC KEYS>', \
+        '{}=<HASHES>', '{}=<HASHES>', '{}=<PUBLIC KEYS>' and '{}=<true|false>'\n",
+        QUERY_FIELD,
+        ACCOUNT_QUERY_FIELD,
+        CONTRACT_HASH_QUERY_FIELD,
+        DEPLOY_HASH_QUERY_FIELD,
+        VALIDATOR_QUERY_FIELD,
+        EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD
     )));
     *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
     response
@@ -441,13 +790,15 @@ impl ChannelsAndFilter {
                 None => return create_404(),
             };
 
-            let start_from = match parse_query(query) {
-                Ok(maybe_id) => maybe_id,
+            let (start_from, content_filter) = match parse_query(query) {
+                Ok(parsed_query) => parsed_query,
                 Err(error_response) => return error_response,
             };
 
-            // Create a channel for the client's handler to receive the stream of initial events.
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            // Create a channel for the client's handler to receive the stream of initial events,
+            // large enough to hold the `ApiVersion` event and all buffered events.
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(broadcast_channel_size + 1);
 
             // Supply the server with the sender part of the channel along with the client's
             // requested starting point.
@@ -469,6 +820,7 @@ impl ChannelsAndFilter {
                 initial_events_receiver,
                 ongoing_events_receiver,
                 event_filter,
+                Arc::new(content_filter),
                 remote_address,
             )))
             .into_response()
@@ -505,11 +857,12 @@ impl ChannelsAndFilter {
 /// subscribed to the server's event stream.
 ///
 /// It also takes an `EventFilter` which causes events to which the client didn't subscribe to be
-/// skipped.
+/// skipped, and a `ContentFilter` which further restricts and trims the events sent.
 fn stream_to_client(
-    initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
+    initial_events: mpsc::Receiver<ServerSentEvent>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
     event_filter: &'static [EventFilter],
+    content_filter: Arc<ContentFilter>,
     remote_address: String,
 ) -> impl Stream<Item = Result<WarpServerSentEvent, RecvError>> + 'static {
     // Keep a record of the IDs of the events delivered via the `initial_events` receiver.
@@ -549,7 +902,7 @@ fn stream_to_client(
 
     // Serve the initial events followed by the ongoing ones, filtering as dictated by the
     // `event_filter`.
-    UnboundedReceiverStream::new(initial_events)
+    ReceiverStream::new(initial_events)
         .map(move |event| {
             if let Some(id) = event.id {
                 let _ = initial_stream_ids.write().unwrap().insert(id);
@@ -557,10 +910,15 @@ fn stream_to_client(
             Ok(event)
         })
         .chain(ongoing_stream)
-        .filter_map(move |result| async move {
-            match result {
-                Ok(event) => filter_map_server_sent_event(&event, event_filter).await,
-                Err(error) => Some(Err(error)),
+        .filter_map(move |result| {
+            let content_filter = Arc::clone(&content_filter);
+            async move {
+                match result {
+                    Ok(event) => {
+                        filter_map_server_sent_event(&event, event_filter, &content_filter).await
+                    }
+                    Err(error) => Some(Err(error)),
+                }
             }
         })
 }
@@ -569,14 +927,16 @@ fn stream_to_client(
 mod tests {
     use std::iter;
 
-    use casper_types::testing::TestRng;
+    use casper_types::{testing::TestRng, Transform, TransformEntry};
 
     use super::*;
     use crate::logging;
 
     async fn should_filter_out(event: &ServerSentEvent, filter: &'static [EventFilter]) {
         assert!(
-            filter_map_server_sent_event(event, filter).await.is_none(),
+            filter_map_server_sent_event(event, filter, &ContentFilter::default())
+                .await
+                .is_none(),
             "should filter out {:?} with {:?}",
             event,
             filter
@@ -585,7 +945,9 @@ mod tests {
 
     async fn should_not_filter_out(event: &ServerSentEvent, filter: &'static [EventFilter]) {
         assert!(
-            filter_map_server_sent_event(event, filter).await.is_some(),
+            filter_map_server_sent_event(event, filter, &ContentFilter::default())
+                .await
+                .is_some(),
             "should not filter out {:?} with {:?}",
             event,
             filter
@@ -622,6 +984,10 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_deploy_expired(&mut rng),
         };
+        let deploy_status_changed = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_status_changed(&mut rng),
+        };
         let fault = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_fault(&mut rng),
@@ -634,6 +1000,18 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_step(&mut rng),
         };
+        let era_end = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_era_end(&mut rng),
+        };
+        let block_finalized = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_block_finalized(&mut rng),
+        };
+        let node_state_changed = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_node_state_changed(&mut rng),
+        };
         let shutdown = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::Shutdown,
@@ -649,12 +1027,17 @@ mod tests {
         should_not_filter_out(&shutdown, &MAIN_FILTER).await;
 
         should_filter_out(&deploy_accepted, &MAIN_FILTER[..]).await;
+        should_filter_out(&deploy_status_changed, &MAIN_FILTER[..]).await;
         should_filter_out(&finality_signature, &MAIN_FILTER[..]).await;
+        should_filter_out(&era_end, &MAIN_FILTER[..]).await;
+        should_filter_out(&block_finalized, &MAIN_FILTER[..]).await;
+        should_filter_out(&node_state_changed, &MAIN_FILTER[..]).await;
 
-        // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s and
-        // `DeployAccepted`s.
+        // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s,
+        // `DeployAccepted`s and `DeployStatusChanged`s.
         should_not_filter_out(&api_version, &DEPLOYS_FILTER[..]).await;
         should_not_filter_out(&deploy_accepted, &DEPLOYS_FILTER[..]).await;
+        should_not_filter_out(&deploy_status_changed, &DEPLOYS_FILTER[..]).await;
         should_not_filter_out(&shutdown, &DEPLOYS_FILTER[..]).await;
 
         should_filter_out(&block_added, &DEPLOYS_FILTER[..]).await;
@@ -663,6 +1046,9 @@ mod tests {
         should_filter_out(&fault, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&step, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&era_end, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&block_finalized, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&node_state_changed, &DEPLOYS_FILTER[..]).await;
 
         // `EventFilter::Signatures` should filter out everything except `ApiVersion`s and
         // `FinalitySignature`s.
@@ -674,8 +1060,29 @@ mod tests {
         should_filter_out(&deploy_accepted, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&deploy_processed, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&deploy_expired, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&deploy_status_changed, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&step, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&era_end, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&block_finalized, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&node_state_changed, &SIGNATURES_FILTER[..]).await;
+
+        // `EventFilter::Node` should filter out everything except `ApiVersion`s, `EraEnd`s,
+        // `BlockFinalized`s and `NodeStateChanged`s.
+        should_not_filter_out(&api_version, &NODE_FILTER[..]).await;
+        should_not_filter_out(&era_end, &NODE_FILTER[..]).await;
+        should_not_filter_out(&block_finalized, &NODE_FILTER[..]).await;
+        should_not_filter_out(&node_state_changed, &NODE_FILTER[..]).await;
+        should_not_filter_out(&shutdown, &NODE_FILTER[..]).await;
+
+        should_filter_out(&block_added, &NODE_FILTER[..]).await;
+        should_filter_out(&deploy_accepted, &NODE_FILTER[..]).await;
+        should_filter_out(&deploy_processed, &NODE_FILTER[..]).await;
+        should_filter_out(&deploy_expired, &NODE_FILTER[..]).await;
+        should_filter_out(&deploy_status_changed, &NODE_FILTER[..]).await;
+        should_filter_out(&fault, &NODE_FILTER[..]).await;
+        should_filter_out(&finality_signature, &NODE_FILTER[..]).await;
+        should_filter_out(&step, &NODE_FILTER[..]).await;
     }
 
     /// This test checks that events with incorrect IDs (i.e. no types have an ID except for
@@ -708,6 +1115,10 @@ mod tests {
             id: None,
             data: SseData::random_deploy_expired(&mut rng),
         };
+        let malformed_deploy_status_changed = ServerSentEvent {
+            id: None,
+            data: SseData::random_deploy_status_changed(&mut rng),
+        };
         let malformed_fault = ServerSentEvent {
             id: None,
             data: SseData::random_fault(&mut rng),
@@ -720,6 +1131,18 @@ mod tests {
             id: None,
             data: SseData::random_step(&mut rng),
         };
+        let malformed_era_end = ServerSentEvent {
+            id: None,
+            data: SseData::random_era_end(&mut rng),
+        };
+        let malformed_block_finalized = ServerSentEvent {
+            id: None,
+            data: SseData::random_block_finalized(&mut rng),
+        };
+        let malformed_node_state_changed = ServerSentEvent {
+            id: None,
+            data: SseData::random_node_state_changed(&mut rng),
+        };
         let malformed_shutdown = ServerSentEvent {
             id: None,
             data: SseData::Shutdown,
@@ -729,15 +1152,20 @@ mod tests {
             &MAIN_FILTER[..],
             &DEPLOYS_FILTER[..],
             &SIGNATURES_FILTER[..],
+            &NODE_FILTER[..],
         ] {
             should_filter_out(&malformed_api_version, filter).await;
             should_filter_out(&malformed_block_added, filter).await;
             should_filter_out(&malformed_deploy_accepted, filter).await;
             should_filter_out(&malformed_deploy_processed, filter).await;
             should_filter_out(&malformed_deploy_expired, filter).await;
+            should_filter_out(&malformed_deploy_status_changed, filter).await;
             should_filter_out(&malformed_fault, filter).await;
             should_filter_out(&malformed_finality_signature, filter).await;
             should_filter_out(&malformed_step, filter).await;
+            should_filter_out(&malformed_era_end, filter).await;
+            should_filter_out(&malformed_block_finalized, filter).await;
+            should_filter_out(&malformed_node_state_changed, filter).await;
             should_filter_out(&malformed_shutdown, filter).await;
         }
     }
@@ -764,6 +1192,7 @@ mod tests {
                             event
                         }
                         SSE_API_SIGNATURES_PATH => SseData::random_finality_signature(rng),
+                        SSE_API_NODE_PATH => SseData::random_block_finalized(rng),
                         _ => unreachable!(),
                     };
                     ServerSentEvent { id: Some(id), data }
@@ -833,13 +1262,14 @@ mod tests {
                 &mut deploys,
             );
 
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(initial_events.len());
             let (ongoing_events_sender, ongoing_events_receiver) =
                 broadcast::channel(NUM_INITIAL_EVENTS + NUM_ONGOING_EVENTS + 1);
 
             // Send all the events.
             for event in initial_events.iter().cloned() {
-                initial_events_sender.send(event).unwrap();
+                initial_events_sender.try_send(event).unwrap();
             }
             for event in ongoing_events.iter().cloned() {
                 let _ = ongoing_events_sender
@@ -855,6 +1285,7 @@ mod tests {
                 initial_events_receiver,
                 ongoing_events_receiver,
                 get_filter(path_filter).unwrap(),
+                Arc::new(ContentFilter::default()),
                 "127.0.0.1:3456".to_string(),
             )
             .collect()
@@ -920,4 +1351,202 @@ mod tests {
     async fn should_filter_duplicate_signature_events() {
         should_filter_duplicate_events(SSE_API_SIGNATURES_PATH).await
     }
+
+    /// This test checks that node events from the initial stream which are duplicated in the
+    /// ongoing stream are filtered out.
+    #[tokio::test]
+    async fn should_filter_duplicate_node_events() {
+        should_filter_duplicate_events(SSE_API_NODE_PATH).await
+    }
+
+    #[test]
+    fn should_parse_content_filter_from_query() {
+        let mut rng = crate::new_rng();
+        let account = PublicKey::random(&mut rng);
+        let validator = PublicKey::random(&mut rng);
+        let deploy_hashes = [DeployHash::random(&mut rng), DeployHash::random(&mut rng)];
+
+        let query: HashMap<String, String> = [
+            (QUERY_FIELD, "3".to_string()),
+            (ACCOUNT_QUERY_FIELD, account.to_hex()),
+            (CONTRACT_HASH_QUERY_FIELD, base16::encode_lower(&[19; 32])),
+            (
+                DEPLOY_HASH_QUERY_FIELD,
+                format!(
+                    "{},{}",
+                    base16::encode_lower(&deploy_hashes[0]),
+                    base16::encode_lower(&deploy_hashes[1])
+                ),
+            ),
+            (VALIDATOR_QUERY_FIELD, validator.to_hex()),
+            (EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD, "true".to_string()),
+        ]
+        .iter()
+        .map(|(field, value)| (field.to_string(), value.clone()))
+        .collect();
+
+        let (start_from, content_filter) = parse_query(query).unwrap();
+        assert_eq!(start_from, Some(3));
+        assert_eq!(
+            content_filter,
+            ContentFilter {
+                accounts: Some(iter::once(account).collect()),
+                contract_hashes: Some(iter::once([19; 32]).collect()),
+                deploy_hashes: Some(deploy_hashes.iter().copied().collect()),
+                validators: Some(iter::once(validator).collect()),
+                exclude_execution_effect: true,
+            }
+        );
+
+        assert_eq!(
+            parse_query(HashMap::new()).unwrap(),
+            (None, ContentFilter::default())
+        );
+    }
+
+    #[test]
+    fn should_filter_deploy_events_by_content() {
+        let mut rng = crate::new_rng();
+        // This deploy's payment is stored contract with hash `[19; 32]`.
+        let deploy = Deploy::random_with_missing_payment_contract_by_hash(&mut rng);
+        let accepted = SseData::DeployAccepted {
+            deploy: Arc::new(deploy.clone()),
+        };
+        let effect = ExecutionEffect::new(vec![TransformEntry {
+            key: Key::Hash([19; 32]).to_formatted_string(),
+            transform: Transform::Identity,
+        }]);
+        let processed = SseData::DeployProcessed {
+            deploy_hash: Box::new(*deploy.hash()),
+            account: Box::new(deploy.header().account().clone()),
+            timestamp: deploy.header().timestamp(),
+            ttl: deploy.header().ttl(),
+            dependencies: vec![],
+            block_hash: Box::new(BlockHash::random(&mut rng)),
+            execution_result: Box::new(ExecutionResult::Success {
+                effect,
+                transfers: vec![],
+                cost: 1.into(),
+            }),
+        };
+
+        let matching_filters = [
+            ContentFilter::default(),
+            ContentFilter {
+                accounts: Some(iter::once(deploy.header().account().clone()).collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                contract_hashes: Some([[19; 32], [20; 32]].iter().copied().collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                deploy_hashes: Some(iter::once(*deploy.hash()).collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                validators: Some(iter::once(PublicKey::random(&mut rng)).collect()),
+                ..Default::default()
+            },
+        ];
+        for content_filter in &matching_filters {
+            assert!(content_filter.matches(&accepted), "{:?}", content_filter);
+            assert!(content_filter.matches(&processed), "{:?}", content_filter);
+        }
+
+        let non_matching_filters = [
+            ContentFilter {
+                accounts: Some(iter::once(PublicKey::random(&mut rng)).collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                contract_hashes: Some(iter::once([20; 32]).collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                deploy_hashes: Some(iter::once(DeployHash::random(&mut rng)).collect()),
+                ..Default::default()
+            },
+            ContentFilter {
+                accounts: Some(iter::once(deploy.header().account().clone()).collect()),
+                deploy_hashes: Some(iter::once(DeployHash::random(&mut rng)).collect()),
+                ..Default::default()
+            },
+        ];
+        for content_filter in &non_matching_filters {
+            assert!(!content_filter.matches(&accepted), "{:?}", content_filter);
+            assert!(!content_filter.matches(&processed), "{:?}", content_filter);
+        }
+    }
+
+    #[test]
+    fn should_filter_finality_signatures_by_validator() {
+        let mut rng = crate::new_rng();
+        let signature = SseData::random_finality_signature(&mut rng);
+        let validator = match &signature {
+            SseData::FinalitySignature(finality_signature) => finality_signature.public_key.clone(),
+            _ => unreachable!(),
+        };
+
+        let mut content_filter = ContentFilter {
+            validators: Some(iter::once(validator).collect()),
+            ..Default::default()
+        };
+        assert!(content_filter.matches(&signature));
+        assert!(content_filter.matches(&SseData::random_block_added(&mut rng)));
+
+        content_filter.validators = Some(iter::once(PublicKey::random(&mut rng)).collect());
+        assert!(!content_filter.matches(&signature));
+    }
+
+    #[test]
+    fn should_strip_execution_effect() {
+        let mut rng = crate::new_rng();
+        let processed = SseData::random_deploy_processed(&mut rng);
+        let stripped = without_execution_effect(&processed).unwrap();
+        match (&processed, &stripped) {
+            (
+                SseData::DeployProcessed {
+                    deploy_hash,
+                    execution_result,
+                    ..
+                },
+                SseData::DeployProcessed {
+                    deploy_hash: stripped_deploy_hash,
+                    execution_result: stripped_execution_result,
+                    ..
+                },
+            ) => {
+                assert_eq!(deploy_hash, stripped_deploy_hash);
+                match (
+                    execution_result.as_ref(),
+                    stripped_execution_result.as_ref(),
+                ) {
+                    (
+                        ExecutionResult::Success { cost, .. },
+                        ExecutionResult::Success {
+                            effect,
+                            cost: stripped_cost,
+                            ..
+                        },
+                    )
+                    | (
+                        ExecutionResult::Failure { cost, .. },
+                        ExecutionResult::Failure {
+                            effect,
+                            cost: stripped_cost,
+                            ..
+                        },
+                    ) => {
+                        assert_eq!(cost, stripped_cost);
+                        assert_eq!(effect, &ExecutionEffect::default());
+                    }
+                    _ => panic!("execution result variant should be unchanged"),
+                }
+            }
+            _ => panic!("should be DeployProcessed"),
+        }
+
+        assert!(without_execution_effect(&SseData::random_deploy_expired(&mut rng)).is_none());
+    }
 }
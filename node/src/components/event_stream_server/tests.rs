@@ -27,9 +27,10 @@ use casper_types::testing::TestRng;
 use super::*;
 use crate::{logging, testing::assert_schema};
 use sse_server::{
-    DeployAccepted, Id, QUERY_FIELD, SSE_API_DEPLOYS_PATH as DEPLOYS_PATH,
-    SSE_API_MAIN_PATH as MAIN_PATH, SSE_API_ROOT_PATH as ROOT_PATH,
-    SSE_API_SIGNATURES_PATH as SIGS_PATH,
+    DeployAccepted, Id, ACCOUNT_QUERY_FIELD, CONTRACT_HASH_QUERY_FIELD, DEPLOY_HASH_QUERY_FIELD,
+    EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD, QUERY_FIELD, SSE_API_DEPLOYS_PATH as DEPLOYS_PATH,
+    SSE_API_MAIN_PATH as MAIN_PATH, SSE_API_NODE_PATH as NODE_PATH, SSE_API_ROOT_PATH as ROOT_PATH,
+    SSE_API_SIGNATURES_PATH as SIGS_PATH, VALIDATOR_QUERY_FIELD,
 };
 
 /// The total number of random events each `EventStreamServer` will emit by default, excluding the
@@ -82,6 +83,8 @@ struct ServerBehavior {
     /// If `Some`, sets the `max_concurrent_subscribers` server config value, otherwise uses the
     /// config default.
     max_concurrent_subscribers: Option<u32>,
+    /// Whether the server should write events to the on-disk event log.
+    enable_event_log: bool,
     clients: Vec<ClientSyncBehavior>,
 }
 
@@ -94,6 +97,7 @@ impl ServerBehavior {
             has_delay_between_events: true,
             repeat_events: false,
             max_concurrent_subscribers: None,
+            enable_event_log: false,
             clients: Vec::new(),
         }
     }
@@ -107,6 +111,7 @@ impl ServerBehavior {
             has_delay_between_events: false,
             repeat_events: true,
             max_concurrent_subscribers: None,
+            enable_event_log: false,
             clients: Vec::new(),
         }
     }
@@ -124,6 +129,11 @@ impl ServerBehavior {
         self.max_concurrent_subscribers = Some(count);
     }
 
+    /// Enables the on-disk event log.
+    fn enable_event_log(&mut self) {
+        self.enable_event_log = true;
+    }
+
     /// Waits for all clients which specified they wanted to join just before the given event ID.
     async fn wait_for_clients(&self, id: Id) {
         for client_behavior in &self.clients {
@@ -199,7 +209,7 @@ struct TestFixture {
 impl TestFixture {
     /// Constructs a new `TestFixture` including `EVENT_COUNT` random events ready to be served.
     fn new(rng: &mut TestRng) -> Self {
-        const DISTINCT_EVENTS_COUNT: u32 = 7;
+        const DISTINCT_EVENTS_COUNT: u32 = 11;
 
         let _ = logging::init();
         let storage_dir = tempfile::tempdir().unwrap();
@@ -220,6 +230,10 @@ impl TestFixture {
                 4 => SseData::random_fault(rng),
                 5 => SseData::random_step(rng),
                 6 => SseData::random_finality_signature(rng),
+                7 => SseData::random_era_end(rng),
+                8 => SseData::random_block_finalized(rng),
+                9 => SseData::random_node_state_changed(rng),
+                10 => SseData::random_deploy_status_changed(rng),
                 _ => unreachable!(),
             })
             .collect();
@@ -262,13 +276,15 @@ impl TestFixture {
             max_concurrent_subscribers: server_behavior
                 .max_concurrent_subscribers
                 .unwrap_or(Config::default().max_concurrent_subscribers),
+            enable_event_log: server_behavior.enable_event_log,
             ..Default::default()
         };
         let mut server = EventStreamServer::new(
             config,
             self.storage_dir.path().to_path_buf(),
             self.protocol_version,
-        );
+        )
+        .unwrap();
         server.listen().unwrap();
         assert!(server.sse_server.is_some());
 
@@ -619,6 +635,11 @@ async fn should_serve_signature_events_with_no_query() {
     should_serve_events_with_no_query(SIGS_PATH).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_node_events_with_no_query() {
+    should_serve_events_with_no_query(NODE_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=25`
 ///   * connected just before event ID 50
@@ -659,6 +680,11 @@ async fn should_serve_signature_events_with_query() {
     should_serve_events_with_query(SIGS_PATH).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_node_events_with_query() {
+    should_serve_events_with_query(NODE_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=0`
 ///   * connected just before event ID 75
@@ -700,6 +726,49 @@ async fn should_serve_remaining_signature_events_with_query() {
     should_serve_remaining_events_with_query(SIGS_PATH).await;
 }
 
+/// Client setup:
+///   * `<IP:port>/events/<path>?start_from=0`
+///   * connected just before event ID 75
+///   * server has the event log enabled
+///
+/// Expected to receive all main, deploy-accepted or signature events (depending on `path`), as
+/// events 0 to 24 should be replayed from the event log despite having been purged from the server
+/// buffer.
+async fn should_serve_logged_events_with_query(path: &str) {
+    let mut rng = crate::new_rng();
+    let mut fixture = TestFixture::new(&mut rng);
+
+    let connect_at_event_id = BUFFER_LENGTH * 3 / 2;
+    let start_from_event_id = 0;
+
+    let mut server_behavior = ServerBehavior::new();
+    server_behavior.enable_event_log();
+    let barrier = server_behavior.add_client_sync_before_event(connect_at_event_id);
+    let server_address = fixture.run_server(server_behavior).await;
+
+    let url = url(server_address, path, Some(start_from_event_id));
+    let (expected_events, final_id) = fixture.filtered_events(path, start_from_event_id);
+    let received_events = subscribe(&url, barrier, final_id, "client").await.unwrap();
+    fixture.stop_server().await;
+
+    assert_eq!(received_events, expected_events);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_logged_main_events_with_query() {
+    should_serve_logged_events_with_query(MAIN_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_logged_deploy_accepted_events_with_query() {
+    should_serve_logged_events_with_query(DEPLOYS_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_logged_signature_events_with_query() {
+    should_serve_logged_events_with_query(SIGS_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=25`
 ///   * connected before first event
@@ -910,8 +979,8 @@ async fn should_handle_bad_url_path() {
     ];
 
     let expected_body = format!(
-        "invalid path: expected '/{0}/{1}', '/{0}/{2}' or '/{0}/{3}'",
-        ROOT_PATH, MAIN_PATH, DEPLOYS_PATH, SIGS_PATH
+        "invalid path: expected '/{0}/{1}', '/{0}/{2}', '/{0}/{3}' or '/{0}/{4}'",
+        ROOT_PATH, MAIN_PATH, DEPLOYS_PATH, SIGS_PATH, NODE_PATH
     );
     for url in &urls {
         let response = reqwest::get(url).await.unwrap();
@@ -955,11 +1024,23 @@ async fn should_handle_bad_url_query() {
         format!("{}?{}=0&extra=1", main_url, QUERY_FIELD),
         format!("{}?{}=0&extra=1", deploys_url, QUERY_FIELD),
         format!("{}?{}=0&extra=1", sigs_url, QUERY_FIELD),
+        format!("{}?{}=not-a-key", main_url, ACCOUNT_QUERY_FIELD),
+        format!("{}?{}=", deploys_url, ACCOUNT_QUERY_FIELD),
+        format!("{}?{}=abc", main_url, CONTRACT_HASH_QUERY_FIELD),
+        format!("{}?{}=abc", deploys_url, DEPLOY_HASH_QUERY_FIELD),
+        format!("{}?{}=not-a-key", sigs_url, VALIDATOR_QUERY_FIELD),
+        format!("{}?{}=1", main_url, EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD),
     ];
 
     let expected_body = format!(
-        "invalid query: expected single field '{}=<EVENT ID>'",
-        QUERY_FIELD
+        "invalid query: expected only optional fields '{}=<EVENT ID>', '{}=<PUBLIC KEYS>', \
+        '{}=<HASHES>', '{}=<HASHES>', '{}=<PUBLIC KEYS>' and '{}=<true|false>'",
+        QUERY_FIELD,
+        ACCOUNT_QUERY_FIELD,
+        CONTRACT_HASH_QUERY_FIELD,
+        DEPLOY_HASH_QUERY_FIELD,
+        VALIDATOR_QUERY_FIELD,
+        EXCLUDE_EXECUTION_EFFECT_QUERY_FIELD
     );
     for url in &urls {
         let response = reqwest::get(url).await.unwrap();
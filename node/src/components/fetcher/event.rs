@@ -77,7 +77,7 @@ impl From<DeployAcceptorAnnouncement> for Event<Deploy> {
                     source,
                 }
             }
-            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source } => {
+            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source, .. } => {
                 Event::GotInvalidRemotely {
                     id: deploy.fetch_id(),
                     source,
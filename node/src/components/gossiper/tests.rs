@@ -286,8 +286,7 @@ impl reactor::Reactor for Reactor {
                 self.dispatch_event(effect_builder, rng, Event::DeployGossiper(event))
             }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
-                deploy: _,
-                source: _,
+                ..
             }) => Effects::new(),
             Event::DeployGossiperAnnouncement(GossiperAnnouncement::NewItemBody {
                 item,
@@ -0,0 +1,266 @@
+//! gRPC server
+//!
+//! The gRPC server provides clients with a protobuf-based alternative to the JSON-RPC server.  It
+//! exposes the same read methods (status, peers, chainspec, blocks, transfers, state root hashes,
+//! deploys, global state queries, balances, auction info, era summaries, accounts and dictionary
+//! items), plus server-streaming equivalents of the event stream server's feeds.  The API is
+//! defined in `proto/casper/node/v1/node.proto`.
+//!
+//! The server is disabled by default.  Requests are subject to the same global and per-client rate
+//! limits as those of the JSON-RPC server, with gRPC method names such as `QueryGlobalState` used
+//! to identify expensive methods.
+//!
+//! The actual server is run in a backgrounded task.  gRPC requests are translated into reactor
+//! requests to various components.
+
+mod config;
+mod event;
+mod http_server;
+mod proto;
+mod service;
+#[cfg(test)]
+mod tests;
+
+use std::{fmt::Debug, sync::Arc, time::Instant};
+
+use datasize::DataSize;
+use futures::{future::BoxFuture, FutureExt};
+use tokio::{
+    sync::{broadcast, oneshot},
+    task::JoinHandle,
+};
+use tracing::{debug, error, info, warn};
+
+use casper_types::ProtocolVersion;
+
+use super::Component;
+use crate::{
+    components::{
+        event_stream_server::SseData, ComponentState, InitializedComponent, PortBoundComponent,
+    },
+    effect::{
+        requests::{
+            ChainspecRawBytesRequest, ContractRuntimeRequest, NetworkInfoRequest,
+            ReactorStatusRequest, StorageRequest,
+        },
+        EffectBuilder, Effects,
+    },
+    reactor::{main_reactor::MainEvent, Finalize},
+    utils::{self, rate_limiter::RateLimiter, ListeningError},
+    NodeRng,
+};
+pub use config::Config;
+pub(crate) use event::Event;
+use service::GrpcService;
+
+const COMPONENT_NAME: &str = "grpc_server";
+
+/// A helper trait capturing all of this components Request type dependencies.
+pub(crate) trait ReactorEventT:
+    From<Event>
+    + From<StorageRequest>
+    + From<ContractRuntimeRequest>
+    + From<NetworkInfoRequest>
+    + From<ReactorStatusRequest>
+    + From<ChainspecRawBytesRequest>
+    + Send
+{
+}
+
+impl<REv> ReactorEventT for REv where
+    REv: From<Event>
+        + From<StorageRequest>
+        + From<ContractRuntimeRequest>
+        + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
+        + From<ChainspecRawBytesRequest>
+        + Send
+        + 'static
+{
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct InnerGrpcServer {
+    /// When the message is sent, it signals the server loop to exit cleanly.
+    #[data_size(skip)]
+    shutdown_sender: oneshot::Sender<()>,
+    /// The task handle which will only join once the server loop has exited.
+    #[data_size(skip)]
+    server_join_handle: Option<JoinHandle<()>>,
+}
+
+#[derive(DataSize, Debug)]
+pub(crate) struct GrpcServer {
+    /// The component state.
+    state: ComponentState,
+    config: Config,
+    api_version: ProtocolVersion,
+    network_name: String,
+    node_startup_instant: Instant,
+    /// Sender via which each event stream subscribes to the event-stream data.
+    #[data_size(skip)]
+    sse_data_broadcaster: broadcast::Sender<SseData>,
+    /// Inner server is present only when enabled in the config.
+    inner_grpc: Option<InnerGrpcServer>,
+}
+
+impl GrpcServer {
+    pub(crate) fn new(
+        config: Config,
+        api_version: ProtocolVersion,
+        network_name: String,
+        node_startup_instant: Instant,
+        sse_data_broadcaster: broadcast::Sender<SseData>,
+    ) -> Self {
+        GrpcServer {
+            state: ComponentState::Uninitialized,
+            config,
+            api_version,
+            network_name,
+            node_startup_instant,
+            sse_data_broadcaster,
+            inner_grpc: None,
+        }
+    }
+}
+
+impl<REv> Component<REv> for GrpcServer
+where
+    REv: ReactorEventT,
+{
+    type Event = Event;
+
+    fn handle_event(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        _rng: &mut NodeRng,
+        event: Self::Event,
+    ) -> Effects<Self::Event> {
+        match &self.state {
+            ComponentState::Fatal(msg) => {
+                error!(
+                    msg,
+                    ?event,
+                    name = <Self as Component<MainEvent>>::name(self),
+                    "should not handle this event when this component has fatal error"
+                );
+                Effects::new()
+            }
+            ComponentState::Uninitialized => {
+                warn!(
+                    ?event,
+                    name = <Self as Component<MainEvent>>::name(self),
+                    "should not handle this event when component is uninitialized"
+                );
+                Effects::new()
+            }
+            ComponentState::Initializing => match event {
+                Event::Initialize => {
+                    let (effects, state) = self.bind(self.config.enable_server, effect_builder);
+                    <Self as InitializedComponent<MainEvent>>::set_state(self, state);
+                    effects
+                }
+            },
+            ComponentState::Initialized => match event {
+                Event::Initialize => {
+                    error!(
+                        ?event,
+                        name = <Self as Component<MainEvent>>::name(self),
+                        "component already initialized"
+                    );
+                    Effects::new()
+                }
+            },
+        }
+    }
+
+    fn name(&self) -> &str {
+        COMPONENT_NAME
+    }
+}
+
+impl<REv> InitializedComponent<REv> for GrpcServer
+where
+    REv: ReactorEventT,
+{
+    fn state(&self) -> &ComponentState {
+        &self.state
+    }
+
+    fn set_state(&mut self, new_state: ComponentState) {
+        info!(
+            ?new_state,
+            name = <Self as Component<MainEvent>>::name(self),
+            "component state changed"
+        );
+
+        self.state = new_state;
+    }
+}
+
+impl<REv> PortBoundComponent<REv> for GrpcServer
+where
+    REv: ReactorEventT,
+{
+    type Error = ListeningError;
+    type ComponentEvent = Event;
+
+    fn listen(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Result<Effects<Self::ComponentEvent>, Self::Error> {
+        let cfg = &self.config;
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+
+        let builder = utils::start_listening(&cfg.address)?
+            .http2_only(true)
+            .http2_max_concurrent_streams(cfg.max_concurrent_streams)
+            .tcp_nodelay(true);
+        let rate_limiter = RateLimiter::new(&cfg.rate_limits).map(Arc::new);
+        let service = GrpcService::new(
+            effect_builder,
+            self.api_version,
+            self.network_name.clone(),
+            self.node_startup_instant,
+            self.sse_data_broadcaster.clone(),
+        );
+        let server_join_handle = tokio::spawn(http_server::run(
+            builder,
+            service,
+            shutdown_receiver,
+            cfg.qps_limit,
+            rate_limiter,
+            cfg.concurrency_limit_per_connection,
+        ));
+
+        self.inner_grpc = Some(InnerGrpcServer {
+            shutdown_sender,
+            server_join_handle: Some(server_join_handle),
+        });
+
+        Ok(Effects::new())
+    }
+}
+
+impl Finalize for GrpcServer {
+    fn finalize(self) -> BoxFuture<'static, ()> {
+        async {
+            if let Some(mut grpc_server) = self.inner_grpc {
+                let _ = grpc_server.shutdown_sender.send(());
+
+                // Wait for the server to exit cleanly.
+                if let Some(join_handle) = grpc_server.server_join_handle.take() {
+                    match join_handle.await {
+                        Ok(_) => debug!("gRPC server exited cleanly"),
+                        Err(error) => error!(%error, "could not join gRPC server task cleanly"),
+                    }
+                } else {
+                    warn!("gRPC server shutdown while already shut down")
+                }
+            } else {
+                info!("gRPC server was disabled in config, no shutdown performed")
+            }
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,69 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::rate_limiter::RateLimitConfig;
+
+/// Default binding address for the gRPC server.
+///
+/// Uses a fixed port per node, but binds on any interface.
+const DEFAULT_ADDRESS: &str = "0.0.0.0:0";
+/// Default rate limit in qps.
+const DEFAULT_QPS_LIMIT: u64 = 100;
+/// Default maximum number of concurrent requests per connection.
+const DEFAULT_CONCURRENCY_LIMIT_PER_CONNECTION: usize = 32;
+/// Default maximum number of concurrent event streams per connection.
+const DEFAULT_MAX_CONCURRENT_STREAMS: u32 = 8;
+
+/// gRPC server configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Setting to enable the gRPC server.
+    pub enable_server: bool,
+
+    /// Address to bind gRPC server to.
+    pub address: String,
+
+    /// Maximum rate limit in queries per second.
+    pub qps_limit: u64,
+
+    /// Per-client rate limiting configuration.  Expensive methods are identified by their gRPC
+    /// method names, e.g. `QueryGlobalState`.
+    pub rate_limits: RateLimitConfig,
+
+    /// Maximum number of requests handled concurrently on a single connection.
+    pub concurrency_limit_per_connection: usize,
+
+    /// Maximum number of HTTP/2 streams, and hence of event streams, open concurrently on a single
+    /// connection.
+    pub max_concurrent_streams: u32,
+}
+
+impl Config {
+    /// Creates a default instance for `GrpcServer`.
+    pub fn new() -> Self {
+        Config {
+            enable_server: false,
+            address: DEFAULT_ADDRESS.to_string(),
+            qps_limit: DEFAULT_QPS_LIMIT,
+            rate_limits: RateLimitConfig {
+                expensive_methods: vec![
+                    "QueryGlobalState".to_string(),
+                    "GetBalance".to_string(),
+                    "GetAuctionInfo".to_string(),
+                    "GetDictionaryItem".to_string(),
+                ],
+                ..RateLimitConfig::default()
+            },
+            concurrency_limit_per_connection: DEFAULT_CONCURRENCY_LIMIT_PER_CONNECTION,
+            max_concurrent_streams: DEFAULT_MAX_CONCURRENT_STREAMS,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::new()
+    }
+}
@@ -0,0 +1,14 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug)]
+pub(crate) enum Event {
+    Initialize,
+}
+
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Event::Initialize => write!(formatter, "initialize"),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::{convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, TryFutureExt,
+};
+use http::{Request, Response};
+use hyper::{
+    server::{
+        conn::{AddrIncoming, AddrStream},
+        Builder,
+    },
+    Body,
+};
+use tokio::sync::oneshot;
+use tonic::{body::BoxBody, Status};
+use tower::{builder::ServiceBuilder, Service};
+use tracing::{debug, info, warn};
+
+use super::{proto::casper_node_server::CasperNodeServer, service::GrpcService, ReactorEventT};
+use crate::utils::rate_limiter::RateLimiter;
+
+/// Handles the given request via the given service, unless the requesting client has exceeded its
+/// rate limit for the gRPC method, in which case a `RESOURCE_EXHAUSTED` status is returned.
+///
+/// The last segment of the URL path, i.e. the unqualified gRPC method name, is used as the method
+/// name when checking the rate limit.
+fn call_rate_limited<S>(
+    service: &S,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+    remote_addr: SocketAddr,
+    request: Request<Body>,
+) -> BoxFuture<'static, Result<Response<BoxBody>, Infallible>>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible> + Clone,
+    S::Future: Send + 'static,
+{
+    if let Some(rate_limiter) = rate_limiter {
+        let client_id = rate_limiter.client_id(remote_addr, request.headers());
+        let path = request.uri().path();
+        let method = path.rsplit('/').next().unwrap_or_default();
+        if let Err(error) = rate_limiter.check(&client_id, method) {
+            debug!(%client_id, %error, path, "rejecting gRPC request");
+            return future::ok(Status::resource_exhausted(error.to_string()).to_http()).boxed();
+        }
+    }
+    service.clone().call(request).boxed()
+}
+
+/// Run the gRPC server.
+///
+/// A message received on `shutdown_receiver` will cause the server to exit cleanly.
+pub(super) async fn run<REv: ReactorEventT>(
+    builder: Builder<AddrIncoming>,
+    service: GrpcService<REv>,
+    shutdown_receiver: oneshot::Receiver<()>,
+    qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    concurrency_limit_per_connection: usize,
+) {
+    let service = CasperNodeServer::new(service);
+
+    // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
+    let make_svc = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let remote_addr = connection.remote_addr();
+        let service = service.clone();
+        let rate_limiter = rate_limiter.clone();
+        future::ok::<_, Infallible>(
+            ServiceBuilder::new()
+                .concurrency_limit(concurrency_limit_per_connection)
+                .service(hyper::service::service_fn(move |request| {
+                    call_rate_limited(&service, &rate_limiter, remote_addr, request)
+                })),
+        )
+    });
+
+    let rate_limited_service = ServiceBuilder::new()
+        .rate_limit(qps_limit, Duration::from_secs(1))
+        .service(make_svc);
+
+    let server = builder.serve(rate_limited_service);
+    info!(address = %server.local_addr(), "started gRPC server");
+
+    // Shutdown the server gracefully.
+    let _ = server
+        .with_graceful_shutdown(async {
+            shutdown_receiver.await.ok();
+        })
+        .map_err(|error| {
+            warn!(%error, "error running gRPC server");
+        })
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use tower::service_fn;
+
+    use super::*;
+    use crate::utils::rate_limiter::RateLimitConfig;
+
+    #[tokio::test]
+    async fn should_reject_rate_limited_requests() {
+        let config = RateLimitConfig {
+            enable: true,
+            qps_limit: 1,
+            burst_limit: 1,
+            expensive_methods: vec![],
+            expensive_qps_limit: 1,
+            expensive_burst_limit: 1,
+            api_key_header: String::new(),
+            api_keys: vec![],
+        };
+        let rate_limiter = RateLimiter::new(&config).map(Arc::new);
+        let service = service_fn(|_request: Request<Body>| {
+            future::ok::<_, Infallible>(Response::new(tonic::body::empty_body()))
+        });
+        let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 12345);
+        let request = || {
+            Request::builder()
+                .uri("/casper.node.v1.CasperNode/GetStatus")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = call_rate_limited(&service, &rate_limiter, remote_addr, request())
+            .await
+            .unwrap();
+        assert!(response.headers().get("grpc-status").is_none());
+
+        let response = call_rate_limited(&service, &rate_limiter, remote_addr, request())
+            .await
+            .unwrap();
+        let grpc_status = response.headers().get("grpc-status").unwrap();
+        assert_eq!(
+            grpc_status,
+            &(tonic::Code::ResourceExhausted as i32).to_string()
+        );
+
+        // Without a rate limiter, requests are never rejected.
+        for _ in 0..3 {
+            let response = call_rate_limited(&service, &None, remote_addr, request())
+                .await
+                .unwrap();
+            assert!(response.headers().get("grpc-status").is_none());
+        }
+    }
+}
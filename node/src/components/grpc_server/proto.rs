@@ -0,0 +1,1097 @@
+//! The protobuf types of the gRPC server's API, generated from `proto/casper/node/v1/node.proto`,
+//! and conversions to them from the node's own types.
+//!
+//! The generated code in `proto/casper.node.v1.rs` is checked in.  After changing the protobuf
+//! definitions, run the tests with the `grpc-codegen` feature enabled: the test checking the
+//! generated code fails, giving the path of the freshly generated code to copy over it.
+
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem as ExecutableItem;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    system::auction,
+    AccessRights, EntryPointAccess, EntryPointType, EraId, OpKind, PublicKey, RuntimeArgs,
+    TransferAddr, U512,
+};
+
+use crate::{
+    components::{consensus, event_stream_server::SseData},
+    types::{self, BlockHash},
+};
+
+#[allow(clippy::all, missing_docs, trivial_casts, unused_qualifications)]
+#[path = "proto/casper.node.v1.rs"]
+#[rustfmt::skip]
+mod generated;
+
+pub(super) use generated::*;
+
+impl TryFrom<&types::Block> for Block {
+    type Error = bytesrepr::Error;
+
+    fn try_from(block: &types::Block) -> Result<Self, Self::Error> {
+        Ok(Block {
+            hash: block.hash().inner().into_vec(),
+            header: Some(BlockHeader::try_from(block.header())?),
+            body: Some(BlockBody::try_from(block.body())?),
+        })
+    }
+}
+
+impl TryFrom<&types::BlockHeader> for BlockHeader {
+    type Error = bytesrepr::Error;
+
+    fn try_from(header: &types::BlockHeader) -> Result<Self, Self::Error> {
+        Ok(BlockHeader {
+            parent_hash: header.parent_hash().inner().into_vec(),
+            state_root_hash: header.state_root_hash().into_vec(),
+            body_hash: header.body_hash().into_vec(),
+            random_bit: header.random_bit(),
+            accumulated_seed: header.accumulated_seed().into_vec(),
+            era_end: header.era_end().map(EraEnd::try_from).transpose()?,
+            timestamp: header.timestamp().millis(),
+            era_id: header.era_id().value(),
+            height: header.height(),
+            protocol_version: header.protocol_version().to_string(),
+        })
+    }
+}
+
+impl TryFrom<&types::EraEnd> for EraEnd {
+    type Error = bytesrepr::Error;
+
+    fn try_from(era_end: &types::EraEnd) -> Result<Self, Self::Error> {
+        let next_era_validator_weights = era_end
+            .next_era_validator_weights()
+            .iter()
+            .map(|(validator, weight)| {
+                Ok(ValidatorWeight {
+                    validator: validator.to_bytes()?,
+                    weight: weight.to_string(),
+                })
+            })
+            .collect::<Result<_, bytesrepr::Error>>()?;
+        Ok(EraEnd {
+            era_report: Some(EraReport::try_from(era_end.era_report())?),
+            next_era_validator_weights,
+        })
+    }
+}
+
+impl TryFrom<&consensus::EraReport<PublicKey>> for EraReport {
+    type Error = bytesrepr::Error;
+
+    fn try_from(era_report: &consensus::EraReport<PublicKey>) -> Result<Self, Self::Error> {
+        let consensus::EraReport {
+            equivocators,
+            rewards,
+            inactive_validators,
+        } = era_report;
+        Ok(EraReport {
+            equivocators: equivocators
+                .iter()
+                .map(ToBytes::to_bytes)
+                .collect::<Result<_, _>>()?,
+            rewards: rewards
+                .iter()
+                .map(|(validator, amount)| {
+                    Ok(Reward {
+                        validator: validator.to_bytes()?,
+                        amount: *amount,
+                    })
+                })
+                .collect::<Result<_, bytesrepr::Error>>()?,
+            inactive_validators: inactive_validators
+                .iter()
+                .map(ToBytes::to_bytes)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&types::BlockBody> for BlockBody {
+    type Error = bytesrepr::Error;
+
+    fn try_from(body: &types::BlockBody) -> Result<Self, Self::Error> {
+        Ok(BlockBody {
+            proposer: body.proposer().to_bytes()?,
+            deploy_hashes: body
+                .deploy_hashes()
+                .iter()
+                .map(|deploy_hash| deploy_hash.inner().into_vec())
+                .collect(),
+            transfer_hashes: body
+                .transfer_hashes()
+                .iter()
+                .map(|deploy_hash| deploy_hash.inner().into_vec())
+                .collect(),
+        })
+    }
+}
+
+/// Converts the finality signatures of a block.
+pub(super) fn finality_signatures(
+    block_signatures: &types::BlockSignatures,
+) -> Result<Vec<FinalitySignature>, bytesrepr::Error> {
+    block_signatures
+        .proofs
+        .iter()
+        .map(|(public_key, signature)| {
+            Ok(FinalitySignature {
+                public_key: public_key.to_bytes()?,
+                signature: signature.to_bytes()?,
+            })
+        })
+        .collect()
+}
+
+impl TryFrom<&types::Deploy> for Deploy {
+    type Error = bytesrepr::Error;
+
+    fn try_from(deploy: &types::Deploy) -> Result<Self, Self::Error> {
+        Ok(Deploy {
+            hash: deploy.hash().inner().into_vec(),
+            header: Some(DeployHeader::try_from(deploy.header())?),
+            payment: Some(deploy.payment().into()),
+            session: Some(deploy.session().into()),
+            approvals: deploy
+                .approvals()
+                .iter()
+                .map(|approval| {
+                    Ok(Approval {
+                        signer: approval.signer().to_bytes()?,
+                        signature: approval.signature().to_bytes()?,
+                    })
+                })
+                .collect::<Result<_, bytesrepr::Error>>()?,
+        })
+    }
+}
+
+impl TryFrom<&types::DeployHeader> for DeployHeader {
+    type Error = bytesrepr::Error;
+
+    fn try_from(header: &types::DeployHeader) -> Result<Self, Self::Error> {
+        Ok(DeployHeader {
+            account: header.account().to_bytes()?,
+            timestamp: header.timestamp().millis(),
+            ttl: header.ttl().millis(),
+            gas_price: header.gas_price(),
+            body_hash: header.body_hash().into_vec(),
+            dependencies: header
+                .dependencies()
+                .iter()
+                .map(|deploy_hash| deploy_hash.inner().into_vec())
+                .collect(),
+            chain_name: header.chain_name().to_string(),
+        })
+    }
+}
+
+impl From<&ExecutableItem> for ExecutableDeployItem {
+    fn from(item: &ExecutableItem) -> Self {
+        let item = match item {
+            ExecutableItem::ModuleBytes { module_bytes, args } => {
+                executable_deploy_item::Item::ModuleBytes(executable_deploy_item::ModuleBytes {
+                    module_bytes: module_bytes.to_vec(),
+                    args: named_args(args),
+                })
+            }
+            ExecutableItem::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            } => executable_deploy_item::Item::StoredContractByHash(
+                executable_deploy_item::StoredContractByHash {
+                    hash: hash.value().to_vec(),
+                    entry_point: entry_point.clone(),
+                    args: named_args(args),
+                },
+            ),
+            ExecutableItem::StoredContractByName {
+                name,
+                entry_point,
+                args,
+            } => executable_deploy_item::Item::StoredContractByName(
+                executable_deploy_item::StoredContractByName {
+                    name: name.clone(),
+                    entry_point: entry_point.clone(),
+                    args: named_args(args),
+                },
+            ),
+            ExecutableItem::StoredVersionedContractByHash {
+                hash,
+                version,
+                entry_point,
+                args,
+            } => executable_deploy_item::Item::StoredVersionedContractByHash(
+                executable_deploy_item::StoredVersionedContractByHash {
+                    hash: hash.value().to_vec(),
+                    version: *version,
+                    entry_point: entry_point.clone(),
+                    args: named_args(args),
+                },
+            ),
+            ExecutableItem::StoredVersionedContractByName {
+                name,
+                version,
+                entry_point,
+                args,
+            } => executable_deploy_item::Item::StoredVersionedContractByName(
+                executable_deploy_item::StoredVersionedContractByName {
+                    name: name.clone(),
+                    version: *version,
+                    entry_point: entry_point.clone(),
+                    args: named_args(args),
+                },
+            ),
+            ExecutableItem::Transfer { args } => {
+                executable_deploy_item::Item::Transfer(executable_deploy_item::Transfer {
+                    args: named_args(args),
+                })
+            }
+        };
+        ExecutableDeployItem { item: Some(item) }
+    }
+}
+
+fn named_args(args: &RuntimeArgs) -> Vec<NamedArg> {
+    args.named_args()
+        .map(|named_arg| NamedArg {
+            name: named_arg.name().to_string(),
+            value: Some(named_arg.cl_value().into()),
+        })
+        .collect()
+}
+
+impl From<&casper_types::CLType> for ClType {
+    fn from(cl_type: &casper_types::CLType) -> Self {
+        let simple = |simple: cl_type::Simple| cl_type::ClType::Simple(simple as i32);
+        let boxed = |inner: &casper_types::CLType| Box::new(ClType::from(inner));
+        let tuple = |types: &[Box<casper_types::CLType>]| {
+            cl_type::ClType::Tuple(cl_type::TupleType {
+                types: types.iter().map(|inner| ClType::from(&**inner)).collect(),
+            })
+        };
+        let cl_type = match cl_type {
+            casper_types::CLType::Bool => simple(cl_type::Simple::Bool),
+            casper_types::CLType::I32 => simple(cl_type::Simple::I32),
+            casper_types::CLType::I64 => simple(cl_type::Simple::I64),
+            casper_types::CLType::U8 => simple(cl_type::Simple::U8),
+            casper_types::CLType::U32 => simple(cl_type::Simple::U32),
+            casper_types::CLType::U64 => simple(cl_type::Simple::U64),
+            casper_types::CLType::U128 => simple(cl_type::Simple::U128),
+            casper_types::CLType::U256 => simple(cl_type::Simple::U256),
+            casper_types::CLType::U512 => simple(cl_type::Simple::U512),
+            casper_types::CLType::Unit => simple(cl_type::Simple::Unit),
+            casper_types::CLType::String => simple(cl_type::Simple::String),
+            casper_types::CLType::Key => simple(cl_type::Simple::Key),
+            casper_types::CLType::URef => simple(cl_type::Simple::Uref),
+            casper_types::CLType::PublicKey => simple(cl_type::Simple::PublicKey),
+            casper_types::CLType::Any => simple(cl_type::Simple::Any),
+            casper_types::CLType::Option(inner) => cl_type::ClType::Option(boxed(inner)),
+            casper_types::CLType::List(inner) => cl_type::ClType::List(boxed(inner)),
+            casper_types::CLType::ByteArray(length) => cl_type::ClType::ByteArray(*length),
+            casper_types::CLType::Result { ok, err } => {
+                cl_type::ClType::Result(Box::new(cl_type::ResultType {
+                    ok: Some(boxed(ok)),
+                    err: Some(boxed(err)),
+                }))
+            }
+            casper_types::CLType::Map { key, value } => {
+                cl_type::ClType::Map(Box::new(cl_type::MapType {
+                    key: Some(boxed(key)),
+                    value: Some(boxed(value)),
+                }))
+            }
+            casper_types::CLType::Tuple1(types) => tuple(types),
+            casper_types::CLType::Tuple2(types) => tuple(types),
+            casper_types::CLType::Tuple3(types) => tuple(types),
+        };
+        ClType {
+            cl_type: Some(cl_type),
+        }
+    }
+}
+
+impl From<&casper_types::CLValue> for ClValue {
+    fn from(cl_value: &casper_types::CLValue) -> Self {
+        ClValue {
+            cl_type: Some(cl_value.cl_type().into()),
+            bytes: cl_value.inner_bytes().clone(),
+        }
+    }
+}
+
+impl From<casper_types::URef> for URef {
+    fn from(uref: casper_types::URef) -> Self {
+        URef {
+            addr: uref.addr().to_vec(),
+            access_rights: uref.access_rights().bits().into(),
+        }
+    }
+}
+
+impl TryFrom<URef> for casper_types::URef {
+    type Error = String;
+
+    fn try_from(uref: URef) -> Result<Self, Self::Error> {
+        let access_rights = u8::try_from(uref.access_rights)
+            .ok()
+            .and_then(AccessRights::from_bits)
+            .ok_or_else(|| format!("invalid access rights: {}", uref.access_rights))?;
+        Ok(casper_types::URef::new(
+            parse_addr(&uref.addr, "URef address")?,
+            access_rights,
+        ))
+    }
+}
+
+impl From<&casper_types::Key> for Key {
+    fn from(key: &casper_types::Key) -> Self {
+        let key = match *key {
+            casper_types::Key::Account(account_hash) => {
+                key::Key::Account(account_hash.value().to_vec())
+            }
+            casper_types::Key::Hash(addr) => key::Key::Hash(addr.to_vec()),
+            casper_types::Key::URef(uref) => key::Key::Uref(uref.into()),
+            casper_types::Key::Transfer(addr) => key::Key::Transfer(addr.value().to_vec()),
+            casper_types::Key::DeployInfo(deploy_hash) => {
+                key::Key::DeployInfo(deploy_hash.value().to_vec())
+            }
+            casper_types::Key::EraInfo(era_id) => key::Key::EraInfo(era_id.value()),
+            casper_types::Key::Balance(addr) => key::Key::Balance(addr.to_vec()),
+            casper_types::Key::Bid(account_hash) => key::Key::Bid(account_hash.value().to_vec()),
+            casper_types::Key::Withdraw(account_hash) => {
+                key::Key::Withdraw(account_hash.value().to_vec())
+            }
+            casper_types::Key::Dictionary(addr) => key::Key::Dictionary(addr.to_vec()),
+            casper_types::Key::SystemContractRegistry => key::Key::SystemContractRegistry(Unit {}),
+            casper_types::Key::EraSummary => key::Key::EraSummary(Unit {}),
+            casper_types::Key::Unbond(account_hash) => {
+                key::Key::Unbond(account_hash.value().to_vec())
+            }
+            casper_types::Key::ChainspecRegistry => key::Key::ChainspecRegistry(Unit {}),
+            casper_types::Key::ChecksumRegistry => key::Key::ChecksumRegistry(Unit {}),
+        };
+        Key { key: Some(key) }
+    }
+}
+
+impl TryFrom<Key> for casper_types::Key {
+    type Error = String;
+
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        let account_hash = |bytes: &[u8]| parse_addr(bytes, "account hash").map(AccountHash::new);
+        let key = match key.key.ok_or_else(|| "missing key variant".to_string())? {
+            key::Key::Account(bytes) => casper_types::Key::Account(account_hash(&bytes)?),
+            key::Key::Hash(bytes) => casper_types::Key::Hash(parse_addr(&bytes, "hash")?),
+            key::Key::Uref(uref) => casper_types::Key::URef(uref.try_into()?),
+            key::Key::Transfer(bytes) => {
+                casper_types::Key::Transfer(TransferAddr::new(parse_addr(&bytes, "transfer")?))
+            }
+            key::Key::DeployInfo(bytes) => casper_types::Key::DeployInfo(
+                casper_types::DeployHash::new(parse_addr(&bytes, "deploy hash")?),
+            ),
+            key::Key::EraInfo(era_id) => casper_types::Key::EraInfo(EraId::new(era_id)),
+            key::Key::Balance(bytes) => {
+                casper_types::Key::Balance(parse_addr(&bytes, "purse address")?)
+            }
+            key::Key::Bid(bytes) => casper_types::Key::Bid(account_hash(&bytes)?),
+            key::Key::Withdraw(bytes) => casper_types::Key::Withdraw(account_hash(&bytes)?),
+            key::Key::Dictionary(bytes) => {
+                casper_types::Key::Dictionary(parse_addr(&bytes, "dictionary address")?)
+            }
+            key::Key::SystemContractRegistry(Unit {}) => casper_types::Key::SystemContractRegistry,
+            key::Key::EraSummary(Unit {}) => casper_types::Key::EraSummary,
+            key::Key::Unbond(bytes) => casper_types::Key::Unbond(account_hash(&bytes)?),
+            key::Key::ChainspecRegistry(Unit {}) => casper_types::Key::ChainspecRegistry,
+            key::Key::ChecksumRegistry(Unit {}) => casper_types::Key::ChecksumRegistry,
+        };
+        Ok(key)
+    }
+}
+
+/// Parses a 32-byte hash or address.
+fn parse_addr(bytes: &[u8], name: &str) -> Result<[u8; 32], String> {
+    <[u8; 32]>::try_from(bytes)
+        .map_err(|_| format!("{} must be 32 bytes, got {}", name, bytes.len()))
+}
+
+fn named_keys(named_keys: &BTreeMap<String, casper_types::Key>) -> Vec<NamedKey> {
+    named_keys
+        .iter()
+        .map(|(name, key)| NamedKey {
+            name: name.clone(),
+            key: Some(key.into()),
+        })
+        .collect()
+}
+
+impl TryFrom<&casper_types::StoredValue> for StoredValue {
+    type Error = bytesrepr::Error;
+
+    fn try_from(value: &casper_types::StoredValue) -> Result<Self, Self::Error> {
+        let value = match value {
+            casper_types::StoredValue::CLValue(cl_value) => {
+                stored_value::Value::ClValue(cl_value.into())
+            }
+            casper_types::StoredValue::Account(account) => {
+                stored_value::Value::Account(account.into())
+            }
+            casper_types::StoredValue::ContractWasm(contract_wasm) => {
+                stored_value::Value::ContractWasm(ContractWasm {
+                    bytes: contract_wasm.bytes().to_vec(),
+                })
+            }
+            casper_types::StoredValue::Contract(contract) => {
+                stored_value::Value::Contract(contract.into())
+            }
+            casper_types::StoredValue::ContractPackage(contract_package) => {
+                stored_value::Value::ContractPackage(contract_package.into())
+            }
+            casper_types::StoredValue::Transfer(transfer) => {
+                stored_value::Value::Transfer(transfer.into())
+            }
+            casper_types::StoredValue::DeployInfo(deploy_info) => {
+                stored_value::Value::DeployInfo(deploy_info.into())
+            }
+            casper_types::StoredValue::EraInfo(era_info) => {
+                stored_value::Value::EraInfo(era_info.try_into()?)
+            }
+            casper_types::StoredValue::Bid(bid) => stored_value::Value::Bid((&**bid).try_into()?),
+            casper_types::StoredValue::Withdraw(purses) => {
+                stored_value::Value::Withdraw(unbonding_purses(purses)?)
+            }
+            casper_types::StoredValue::Unbonding(purses) => {
+                stored_value::Value::Unbonding(unbonding_purses(purses)?)
+            }
+        };
+        Ok(StoredValue { value: Some(value) })
+    }
+}
+
+impl From<&casper_types::account::Account> for Account {
+    fn from(account: &casper_types::account::Account) -> Self {
+        let action_thresholds = account.action_thresholds();
+        Account {
+            account_hash: account.account_hash().value().to_vec(),
+            named_keys: named_keys(account.named_keys()),
+            main_purse: Some(account.main_purse().into()),
+            associated_keys: account
+                .associated_keys()
+                .iter()
+                .map(|(account_hash, weight)| AssociatedKey {
+                    account_hash: account_hash.value().to_vec(),
+                    weight: weight.value().into(),
+                })
+                .collect(),
+            action_thresholds: Some(ActionThresholds {
+                deployment: action_thresholds.deployment().value().into(),
+                key_management: action_thresholds.key_management().value().into(),
+            }),
+        }
+    }
+}
+
+impl From<&casper_types::Contract> for Contract {
+    fn from(contract: &casper_types::Contract) -> Self {
+        Contract {
+            contract_package_hash: contract.contract_package_hash().value().to_vec(),
+            contract_wasm_hash: contract.contract_wasm_hash().value().to_vec(),
+            named_keys: named_keys(contract.named_keys()),
+            entry_points: contract
+                .entry_points()
+                .clone()
+                .take_entry_points()
+                .iter()
+                .map(EntryPoint::from)
+                .collect(),
+            protocol_version: contract.protocol_version().to_string(),
+        }
+    }
+}
+
+impl From<&casper_types::EntryPoint> for EntryPoint {
+    fn from(entry_point: &casper_types::EntryPoint) -> Self {
+        let access = match entry_point.access() {
+            EntryPointAccess::Public => entry_point::Access::Public(Unit {}),
+            EntryPointAccess::Groups(groups) => entry_point::Access::Groups(entry_point::Groups {
+                names: groups
+                    .iter()
+                    .map(|group| group.value().to_string())
+                    .collect(),
+            }),
+        };
+        let entry_point_type = match entry_point.entry_point_type() {
+            EntryPointType::Session => entry_point::Type::Session,
+            EntryPointType::Contract => entry_point::Type::Contract,
+        };
+        EntryPoint {
+            name: entry_point.name().to_string(),
+            args: entry_point
+                .args()
+                .iter()
+                .map(|parameter| entry_point::Parameter {
+                    name: parameter.name().to_string(),
+                    cl_type: Some(parameter.cl_type().into()),
+                })
+                .collect(),
+            ret: Some(entry_point.ret().into()),
+            access: Some(access),
+            entry_point_type: entry_point_type as i32,
+        }
+    }
+}
+
+impl From<&casper_types::ContractPackage> for ContractPackage {
+    fn from(contract_package: &casper_types::ContractPackage) -> Self {
+        let version = |version_key: &casper_types::ContractVersionKey, contract_hash: Vec<u8>| {
+            contract_package::Version {
+                protocol_version_major: version_key.protocol_version_major(),
+                contract_version: version_key.contract_version(),
+                contract_hash,
+            }
+        };
+        ContractPackage {
+            access_key: Some(contract_package.access_key().into()),
+            versions: contract_package
+                .versions()
+                .iter()
+                .map(|(version_key, contract_hash)| {
+                    version(version_key, contract_hash.value().to_vec())
+                })
+                .collect(),
+            disabled_versions: contract_package
+                .disabled_versions()
+                .iter()
+                .map(|version_key| version(version_key, vec![]))
+                .collect(),
+            groups: contract_package
+                .groups()
+                .iter()
+                .map(|(group, urefs)| contract_package::Group {
+                    name: group.value().to_string(),
+                    urefs: urefs.iter().map(|uref| URef::from(*uref)).collect(),
+                })
+                .collect(),
+            locked: contract_package.is_locked(),
+        }
+    }
+}
+
+impl From<&casper_types::Transfer> for Transfer {
+    fn from(transfer: &casper_types::Transfer) -> Self {
+        Transfer {
+            deploy_hash: transfer.deploy_hash.value().to_vec(),
+            from: transfer.from.value().to_vec(),
+            to: transfer
+                .to
+                .map(|account_hash| account_hash.value().to_vec()),
+            source: Some(transfer.source.into()),
+            target: Some(transfer.target.into()),
+            amount: transfer.amount.to_string(),
+            gas: transfer.gas.to_string(),
+            id: transfer.id,
+        }
+    }
+}
+
+impl From<&casper_types::DeployInfo> for DeployInfo {
+    fn from(deploy_info: &casper_types::DeployInfo) -> Self {
+        DeployInfo {
+            deploy_hash: deploy_info.deploy_hash.value().to_vec(),
+            transfers: deploy_info
+                .transfers
+                .iter()
+                .map(|transfer_addr| transfer_addr.value().to_vec())
+                .collect(),
+            from: deploy_info.from.value().to_vec(),
+            source: Some(deploy_info.source.into()),
+            gas: deploy_info.gas.to_string(),
+        }
+    }
+}
+
+impl TryFrom<&auction::EraInfo> for EraInfo {
+    type Error = bytesrepr::Error;
+
+    fn try_from(era_info: &auction::EraInfo) -> Result<Self, Self::Error> {
+        let seigniorage_allocations = era_info
+            .seigniorage_allocations()
+            .iter()
+            .map(|allocation| match allocation {
+                auction::SeigniorageAllocation::Validator {
+                    validator_public_key,
+                    amount,
+                } => Ok(SeigniorageAllocation {
+                    validator_public_key: validator_public_key.to_bytes()?,
+                    delegator_public_key: None,
+                    amount: amount.to_string(),
+                }),
+                auction::SeigniorageAllocation::Delegator {
+                    delegator_public_key,
+                    validator_public_key,
+                    amount,
+                } => Ok(SeigniorageAllocation {
+                    validator_public_key: validator_public_key.to_bytes()?,
+                    delegator_public_key: Some(delegator_public_key.to_bytes()?),
+                    amount: amount.to_string(),
+                }),
+            })
+            .collect::<Result<_, bytesrepr::Error>>()?;
+        Ok(EraInfo {
+            seigniorage_allocations,
+        })
+    }
+}
+
+impl TryFrom<&auction::Bid> for Bid {
+    type Error = bytesrepr::Error;
+
+    fn try_from(bid: &auction::Bid) -> Result<Self, Self::Error> {
+        Ok(Bid {
+            validator_public_key: bid.validator_public_key().to_bytes()?,
+            bonding_purse: Some((*bid.bonding_purse()).into()),
+            staked_amount: bid.staked_amount().to_string(),
+            delegation_rate: (*bid.delegation_rate()).into(),
+            vesting_schedule: bid.vesting_schedule().map(|schedule| {
+                vesting_schedule(
+                    schedule.initial_release_timestamp_millis(),
+                    schedule.locked_amounts(),
+                )
+            }),
+            delegators: bid
+                .delegators()
+                .values()
+                .map(Delegator::try_from)
+                .collect::<Result<_, _>>()?,
+            inactive: bid.inactive(),
+        })
+    }
+}
+
+impl TryFrom<&auction::Delegator> for Delegator {
+    type Error = bytesrepr::Error;
+
+    fn try_from(delegator: &auction::Delegator) -> Result<Self, Self::Error> {
+        Ok(Delegator {
+            delegator_public_key: delegator.delegator_public_key().to_bytes()?,
+            staked_amount: delegator.staked_amount().to_string(),
+            bonding_purse: Some((*delegator.bonding_purse()).into()),
+            validator_public_key: delegator.validator_public_key().to_bytes()?,
+            vesting_schedule: delegator.vesting_schedule().map(|schedule| {
+                vesting_schedule(
+                    schedule.initial_release_timestamp_millis(),
+                    schedule.locked_amounts(),
+                )
+            }),
+        })
+    }
+}
+
+fn vesting_schedule(
+    initial_release_timestamp: u64,
+    locked_amounts: Option<&[U512]>,
+) -> VestingSchedule {
+    VestingSchedule {
+        initial_release_timestamp,
+        locked_amounts: locked_amounts
+            .unwrap_or_default()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    }
+}
+
+impl TryFrom<&auction::UnbondingPurse> for UnbondingPurse {
+    type Error = bytesrepr::Error;
+
+    fn try_from(purse: &auction::UnbondingPurse) -> Result<Self, Self::Error> {
+        Ok(UnbondingPurse {
+            bonding_purse: Some((*purse.bonding_purse()).into()),
+            validator_public_key: purse.validator_public_key().to_bytes()?,
+            unbonder_public_key: purse.unbonder_public_key().to_bytes()?,
+            era_of_creation: purse.era_of_creation().value(),
+            amount: purse.amount().to_string(),
+            new_validator: purse
+                .new_validator()
+                .as_ref()
+                .map(ToBytes::to_bytes)
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<&auction::WithdrawPurse> for UnbondingPurse {
+    type Error = bytesrepr::Error;
+
+    fn try_from(purse: &auction::WithdrawPurse) -> Result<Self, Self::Error> {
+        Ok(UnbondingPurse {
+            bonding_purse: Some((*purse.bonding_purse()).into()),
+            validator_public_key: purse.validator_public_key().to_bytes()?,
+            unbonder_public_key: purse.unbonder_public_key().to_bytes()?,
+            era_of_creation: purse.era_of_creation().value(),
+            amount: purse.amount().to_string(),
+            new_validator: None,
+        })
+    }
+}
+
+fn unbonding_purses<'a, T>(purses: &'a [T]) -> Result<UnbondingPurses, bytesrepr::Error>
+where
+    UnbondingPurse: TryFrom<&'a T, Error = bytesrepr::Error>,
+{
+    Ok(UnbondingPurses {
+        purses: purses
+            .iter()
+            .map(UnbondingPurse::try_from)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Converts the result of executing a deploy in the given block.
+pub(super) fn execution_result(
+    block_hash: &BlockHash,
+    execution_result: &casper_types::ExecutionResult,
+) -> Result<ExecutionResult, bytesrepr::Error> {
+    let (effect, transfers, cost, error_message) = match execution_result {
+        casper_types::ExecutionResult::Failure {
+            effect,
+            transfers,
+            cost,
+            error_message,
+        } => (effect, transfers, cost, Some(error_message.clone())),
+        casper_types::ExecutionResult::Success {
+            effect,
+            transfers,
+            cost,
+        } => (effect, transfers, cost, None),
+    };
+    Ok(ExecutionResult {
+        block_hash: block_hash.inner().into_vec(),
+        effect: Some(effect.try_into()?),
+        transfers: transfers
+            .iter()
+            .map(|transfer_addr| transfer_addr.value().to_vec())
+            .collect(),
+        cost: cost.to_string(),
+        error_message,
+    })
+}
+
+impl TryFrom<&casper_types::ExecutionEffect> for ExecutionEffect {
+    type Error = bytesrepr::Error;
+
+    fn try_from(effect: &casper_types::ExecutionEffect) -> Result<Self, Self::Error> {
+        Ok(ExecutionEffect {
+            operations: effect
+                .operations
+                .iter()
+                .map(|operation| {
+                    let kind = match operation.kind {
+                        OpKind::Read => operation::Kind::Read,
+                        OpKind::Write => operation::Kind::Write,
+                        OpKind::Add => operation::Kind::Add,
+                        OpKind::NoOp => operation::Kind::NoOp,
+                    };
+                    Operation {
+                        key: operation.key.clone(),
+                        kind: kind as i32,
+                    }
+                })
+                .collect(),
+            transforms: effect
+                .transforms
+                .iter()
+                .map(|entry| {
+                    Ok(TransformEntry {
+                        key: entry.key.clone(),
+                        transform: Some((&entry.transform).try_into()?),
+                    })
+                })
+                .collect::<Result<_, bytesrepr::Error>>()?,
+        })
+    }
+}
+
+impl TryFrom<&casper_types::Transform> for Transform {
+    type Error = bytesrepr::Error;
+
+    fn try_from(transform: &casper_types::Transform) -> Result<Self, Self::Error> {
+        let transform = match transform {
+            casper_types::Transform::Identity => transform::Transform::Identity(Unit {}),
+            casper_types::Transform::WriteCLValue(cl_value) => {
+                transform::Transform::WriteClValue(cl_value.into())
+            }
+            casper_types::Transform::WriteAccount(account_hash) => {
+                transform::Transform::WriteAccount(account_hash.value().to_vec())
+            }
+            casper_types::Transform::WriteContractWasm => {
+                transform::Transform::WriteContractWasm(Unit {})
+            }
+            casper_types::Transform::WriteContract => transform::Transform::WriteContract(Unit {}),
+            casper_types::Transform::WriteContractPackage => {
+                transform::Transform::WriteContractPackage(Unit {})
+            }
+            casper_types::Transform::WriteDeployInfo(deploy_info) => {
+                transform::Transform::WriteDeployInfo(deploy_info.into())
+            }
+            casper_types::Transform::WriteEraInfo(era_info) => {
+                transform::Transform::WriteEraInfo(era_info.try_into()?)
+            }
+            casper_types::Transform::WriteTransfer(transfer) => {
+                transform::Transform::WriteTransfer(transfer.into())
+            }
+            casper_types::Transform::WriteBid(bid) => {
+                transform::Transform::WriteBid((&**bid).try_into()?)
+            }
+            casper_types::Transform::WriteWithdraw(purses) => {
+                transform::Transform::WriteWithdraw(unbonding_purses(purses)?)
+            }
+            casper_types::Transform::AddInt32(value) => transform::Transform::AddInt32(*value),
+            casper_types::Transform::AddUInt64(value) => transform::Transform::AddUint64(*value),
+            casper_types::Transform::AddUInt128(value) => {
+                transform::Transform::AddUint128(value.to_string())
+            }
+            casper_types::Transform::AddUInt256(value) => {
+                transform::Transform::AddUint256(value.to_string())
+            }
+            casper_types::Transform::AddUInt512(value) => {
+                transform::Transform::AddUint512(value.to_string())
+            }
+            casper_types::Transform::AddKeys(named_keys) => {
+                transform::Transform::AddKeys(transform::NamedKeys {
+                    named_keys: named_keys
+                        .iter()
+                        .map(|named_key| transform::NamedKey {
+                            name: named_key.name.clone(),
+                            key: named_key.key.clone(),
+                        })
+                        .collect(),
+                })
+            }
+            casper_types::Transform::Failure(error) => transform::Transform::Failure(error.clone()),
+            casper_types::Transform::WriteUnbonding(purses) => {
+                transform::Transform::WriteUnbonding(unbonding_purses(purses)?)
+            }
+        };
+        Ok(Transform {
+            transform: Some(transform),
+        })
+    }
+}
+
+impl TryFrom<&SseData> for Event {
+    type Error = serde_json::Error;
+
+    fn try_from(sse_data: &SseData) -> Result<Self, Self::Error> {
+        let kind = match sse_data {
+            SseData::ApiVersion(_) => "ApiVersion",
+            SseData::BlockAdded { .. } => "BlockAdded",
+            SseData::DeployAccepted { .. } => "DeployAccepted",
+            SseData::DeployProcessed { .. } => "DeployProcessed",
+            SseData::DeployExpired { .. } => "DeployExpired",
+            SseData::DeployStatusChanged { .. } => "DeployStatusChanged",
+            SseData::Fault { .. } => "Fault",
+            SseData::FinalitySignature(_) => "FinalitySignature",
+            SseData::Step { .. } => "Step",
+            SseData::EraEnd { .. } => "EraEnd",
+            SseData::BlockFinalized { .. } => "BlockFinalized",
+            SseData::NodeStateChanged { .. } => "NodeStateChanged",
+            SseData::Shutdown => "Shutdown",
+        };
+        Ok(Event {
+            kind: kind.to_string(),
+            json: serde_json::to_string(sse_data)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use casper_types::{testing::TestRng, CLValue, SecretKey};
+
+    use super::*;
+
+    #[test]
+    fn should_convert_block() {
+        let rng = &mut TestRng::new();
+        let block = types::Block::random(rng);
+
+        let converted = Block::try_from(&block).unwrap();
+        assert_eq!(converted.hash, block.hash().inner().into_vec());
+        let header = converted.header.unwrap();
+        assert_eq!(header.height, block.height());
+        assert_eq!(header.era_id, block.header().era_id().value());
+        assert_eq!(header.era_end.is_some(), block.header().is_switch_block());
+        let body = converted.body.unwrap();
+        assert_eq!(body.proposer, block.body().proposer().to_bytes().unwrap());
+        assert_eq!(body.deploy_hashes.len(), block.deploy_hashes().len());
+    }
+
+    #[test]
+    fn should_convert_deploy() {
+        let rng = &mut TestRng::new();
+        let deploy = types::Deploy::random(rng);
+
+        let converted = Deploy::try_from(&deploy).unwrap();
+        assert_eq!(converted.hash, deploy.hash().inner().into_vec());
+        assert_eq!(converted.approvals.len(), deploy.approvals().len());
+        assert_eq!(
+            converted.header.unwrap().chain_name,
+            deploy.header().chain_name()
+        );
+        let session_args = match converted.session.unwrap().item.unwrap() {
+            executable_deploy_item::Item::ModuleBytes(item) => item.args,
+            executable_deploy_item::Item::StoredContractByHash(item) => item.args,
+            executable_deploy_item::Item::StoredContractByName(item) => item.args,
+            executable_deploy_item::Item::StoredVersionedContractByHash(item) => item.args,
+            executable_deploy_item::Item::StoredVersionedContractByName(item) => item.args,
+            executable_deploy_item::Item::Transfer(item) => item.args,
+        };
+        let arg_names: Vec<_> = session_args.iter().map(|arg| arg.name.as_str()).collect();
+        let expected_arg_names: Vec<_> = deploy
+            .session()
+            .args()
+            .named_args()
+            .map(|arg| arg.name())
+            .collect();
+        assert_eq!(arg_names, expected_arg_names);
+    }
+
+    #[test]
+    fn should_roundtrip_key() {
+        let rng = &mut TestRng::new();
+        for _ in 0..100 {
+            let key: casper_types::Key = rng.gen();
+            let converted = Key::from(&key);
+            assert_eq!(casper_types::Key::try_from(converted).unwrap(), key);
+        }
+
+        let too_short = Key {
+            key: Some(key::Key::Hash(vec![7; 31])),
+        };
+        assert!(casper_types::Key::try_from(too_short).is_err());
+
+        let invalid_access_rights = Key {
+            key: Some(key::Key::Uref(URef {
+                addr: vec![7; 32],
+                access_rights: 8,
+            })),
+        };
+        assert!(casper_types::Key::try_from(invalid_access_rights).is_err());
+
+        assert!(casper_types::Key::try_from(Key { key: None }).is_err());
+    }
+
+    #[test]
+    fn should_convert_cl_value() {
+        let cl_value = CLValue::from_t((1_u64, vec!["a".to_string()])).unwrap();
+        let converted = ClValue::from(&cl_value);
+        assert_eq!(&converted.bytes, cl_value.inner_bytes());
+
+        let simple = |simple: cl_type::Simple| ClType {
+            cl_type: Some(cl_type::ClType::Simple(simple as i32)),
+        };
+        let expected_cl_type = ClType {
+            cl_type: Some(cl_type::ClType::Tuple(cl_type::TupleType {
+                types: vec![
+                    simple(cl_type::Simple::U64),
+                    ClType {
+                        cl_type: Some(cl_type::ClType::List(Box::new(simple(
+                            cl_type::Simple::String,
+                        )))),
+                    },
+                ],
+            })),
+        };
+        assert_eq!(converted.cl_type.unwrap(), expected_cl_type);
+    }
+
+    #[test]
+    fn should_convert_stored_bid() {
+        let rng = &mut TestRng::new();
+        let public_key = PublicKey::from(&SecretKey::random(rng));
+        let bonding_purse = casper_types::URef::new(rng.gen(), AccessRights::READ_ADD_WRITE);
+        let bid = auction::Bid::locked(
+            public_key.clone(),
+            bonding_purse,
+            U512::from(1_000),
+            10,
+            u64::MAX,
+        );
+
+        let converted = StoredValue::try_from(&casper_types::StoredValue::Bid(Box::new(bid)))
+            .unwrap()
+            .value;
+        let converted_bid = match converted {
+            Some(stored_value::Value::Bid(bid)) => bid,
+            value => panic!("expected a bid, got {:?}", value),
+        };
+        assert_eq!(
+            converted_bid.validator_public_key,
+            public_key.to_bytes().unwrap()
+        );
+        assert_eq!(converted_bid.bonding_purse, Some(bonding_purse.into()));
+        assert_eq!(converted_bid.staked_amount, "1000");
+        assert_eq!(converted_bid.delegation_rate, 10);
+        let vesting_schedule = converted_bid.vesting_schedule.unwrap();
+        assert_eq!(vesting_schedule.initial_release_timestamp, u64::MAX);
+        assert!(vesting_schedule.locked_amounts.is_empty());
+    }
+
+    #[test]
+    fn should_convert_execution_result() {
+        let rng = &mut TestRng::new();
+        for _ in 0..10 {
+            let block_hash = BlockHash::random(rng);
+            let result: casper_types::ExecutionResult = rng.gen();
+
+            let converted = execution_result(&block_hash, &result).unwrap();
+            assert_eq!(converted.block_hash, block_hash.inner().into_vec());
+            let (effect, transfers, cost, error_message) = match &result {
+                casper_types::ExecutionResult::Failure {
+                    effect,
+                    transfers,
+                    cost,
+                    error_message,
+                } => (effect, transfers, cost, Some(error_message.clone())),
+                casper_types::ExecutionResult::Success {
+                    effect,
+                    transfers,
+                    cost,
+                } => (effect, transfers, cost, None),
+            };
+            assert_eq!(converted.cost, cost.to_string());
+            assert_eq!(converted.error_message, error_message);
+            assert_eq!(converted.transfers.len(), transfers.len());
+            let converted_effect = converted.effect.unwrap();
+            assert_eq!(converted_effect.operations.len(), effect.operations.len());
+            let keys: Vec<_> = converted_effect
+                .transforms
+                .iter()
+                .map(|entry| entry.key.as_str())
+                .collect();
+            let expected_keys: Vec<_> = effect
+                .transforms
+                .iter()
+                .map(|entry| entry.key.as_str())
+                .collect();
+            assert_eq!(keys, expected_keys);
+            assert!(converted_effect.transforms.iter().all(|entry| entry
+                .transform
+                .as_ref()
+                .unwrap()
+                .transform
+                .is_some()));
+        }
+    }
+}
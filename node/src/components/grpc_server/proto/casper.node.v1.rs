@@ -0,0 +1,2113 @@
+/// Identifies a block.  If neither field is set, the highest complete block is identified.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockIdentifier {
+    #[prost(oneof = "block_identifier::Identifier", tags = "1, 2")]
+    pub identifier: ::core::option::Option<block_identifier::Identifier>,
+}
+/// Nested message and enum types in `BlockIdentifier`.
+pub mod block_identifier {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Identifier {
+        #[prost(bytes, tag = "1")]
+        Hash(::prost::alloc::vec::Vec<u8>),
+        #[prost(uint64, tag = "2")]
+        Height(u64),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Block {
+    #[prost(bytes = "vec", tag = "1")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub header: ::core::option::Option<BlockHeader>,
+    #[prost(message, optional, tag = "3")]
+    pub body: ::core::option::Option<BlockBody>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockHeader {
+    #[prost(bytes = "vec", tag = "1")]
+    pub parent_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub body_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "4")]
+    pub random_bit: bool,
+    #[prost(bytes = "vec", tag = "5")]
+    pub accumulated_seed: ::prost::alloc::vec::Vec<u8>,
+    /// Only set for switch blocks.
+    #[prost(message, optional, tag = "6")]
+    pub era_end: ::core::option::Option<EraEnd>,
+    #[prost(uint64, tag = "7")]
+    pub timestamp: u64,
+    #[prost(uint64, tag = "8")]
+    pub era_id: u64,
+    #[prost(uint64, tag = "9")]
+    pub height: u64,
+    #[prost(string, tag = "10")]
+    pub protocol_version: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EraEnd {
+    #[prost(message, optional, tag = "1")]
+    pub era_report: ::core::option::Option<EraReport>,
+    #[prost(message, repeated, tag = "2")]
+    pub next_era_validator_weights: ::prost::alloc::vec::Vec<ValidatorWeight>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EraReport {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub equivocators: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, repeated, tag = "2")]
+    pub rewards: ::prost::alloc::vec::Vec<Reward>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub inactive_validators: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Reward {
+    #[prost(bytes = "vec", tag = "1")]
+    pub validator: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub amount: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorWeight {
+    #[prost(bytes = "vec", tag = "1")]
+    pub validator: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub weight: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockBody {
+    #[prost(bytes = "vec", tag = "1")]
+    pub proposer: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub deploy_hashes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub transfer_hashes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FinalitySignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Deploy {
+    #[prost(bytes = "vec", tag = "1")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub header: ::core::option::Option<DeployHeader>,
+    #[prost(message, optional, tag = "3")]
+    pub payment: ::core::option::Option<ExecutableDeployItem>,
+    #[prost(message, optional, tag = "4")]
+    pub session: ::core::option::Option<ExecutableDeployItem>,
+    #[prost(message, repeated, tag = "5")]
+    pub approvals: ::prost::alloc::vec::Vec<Approval>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeployHeader {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    #[prost(uint64, tag = "3")]
+    pub ttl: u64,
+    #[prost(uint64, tag = "4")]
+    pub gas_price: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub body_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "6")]
+    pub dependencies: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(string, tag = "7")]
+    pub chain_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Approval {
+    #[prost(bytes = "vec", tag = "1")]
+    pub signer: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutableDeployItem {
+    #[prost(oneof = "executable_deploy_item::Item", tags = "1, 2, 3, 4, 5, 6")]
+    pub item: ::core::option::Option<executable_deploy_item::Item>,
+}
+/// Nested message and enum types in `ExecutableDeployItem`.
+pub mod executable_deploy_item {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ModuleBytes {
+        #[prost(bytes = "vec", tag = "1")]
+        pub module_bytes: ::prost::alloc::vec::Vec<u8>,
+        #[prost(message, repeated, tag = "2")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct StoredContractByHash {
+        #[prost(bytes = "vec", tag = "1")]
+        pub hash: ::prost::alloc::vec::Vec<u8>,
+        #[prost(string, tag = "2")]
+        pub entry_point: ::prost::alloc::string::String,
+        #[prost(message, repeated, tag = "3")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct StoredContractByName {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        #[prost(string, tag = "2")]
+        pub entry_point: ::prost::alloc::string::String,
+        #[prost(message, repeated, tag = "3")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct StoredVersionedContractByHash {
+        #[prost(bytes = "vec", tag = "1")]
+        pub hash: ::prost::alloc::vec::Vec<u8>,
+        /// If not set, the latest enabled version is used.
+        #[prost(uint32, optional, tag = "2")]
+        pub version: ::core::option::Option<u32>,
+        #[prost(string, tag = "3")]
+        pub entry_point: ::prost::alloc::string::String,
+        #[prost(message, repeated, tag = "4")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct StoredVersionedContractByName {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        /// If not set, the latest enabled version is used.
+        #[prost(uint32, optional, tag = "2")]
+        pub version: ::core::option::Option<u32>,
+        #[prost(string, tag = "3")]
+        pub entry_point: ::prost::alloc::string::String,
+        #[prost(message, repeated, tag = "4")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Transfer {
+        #[prost(message, repeated, tag = "1")]
+        pub args: ::prost::alloc::vec::Vec<super::NamedArg>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Item {
+        #[prost(message, tag = "1")]
+        ModuleBytes(ModuleBytes),
+        #[prost(message, tag = "2")]
+        StoredContractByHash(StoredContractByHash),
+        #[prost(message, tag = "3")]
+        StoredContractByName(StoredContractByName),
+        #[prost(message, tag = "4")]
+        StoredVersionedContractByHash(StoredVersionedContractByHash),
+        #[prost(message, tag = "5")]
+        StoredVersionedContractByName(StoredVersionedContractByName),
+        #[prost(message, tag = "6")]
+        Transfer(Transfer),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NamedArg {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub value: ::core::option::Option<ClValue>,
+}
+/// Marks a variant of a `oneof` which carries no data.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Unit {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClType {
+    #[prost(oneof = "cl_type::ClType", tags = "1, 2, 3, 4, 5, 6, 7")]
+    pub cl_type: ::core::option::Option<cl_type::ClType>,
+}
+/// Nested message and enum types in `CLType`.
+pub mod cl_type {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ResultType {
+        #[prost(message, optional, boxed, tag = "1")]
+        pub ok: ::core::option::Option<::prost::alloc::boxed::Box<super::ClType>>,
+        #[prost(message, optional, boxed, tag = "2")]
+        pub err: ::core::option::Option<::prost::alloc::boxed::Box<super::ClType>>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MapType {
+        #[prost(message, optional, boxed, tag = "1")]
+        pub key: ::core::option::Option<::prost::alloc::boxed::Box<super::ClType>>,
+        #[prost(message, optional, boxed, tag = "2")]
+        pub value: ::core::option::Option<::prost::alloc::boxed::Box<super::ClType>>,
+    }
+    /// The element types of a tuple of one, two or three elements.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TupleType {
+        #[prost(message, repeated, tag = "1")]
+        pub types: ::prost::alloc::vec::Vec<super::ClType>,
+    }
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum Simple {
+        Bool = 0,
+        I32 = 1,
+        I64 = 2,
+        U8 = 3,
+        U32 = 4,
+        U64 = 5,
+        U128 = 6,
+        U256 = 7,
+        U512 = 8,
+        Unit = 9,
+        String = 10,
+        Key = 11,
+        Uref = 12,
+        PublicKey = 13,
+        Any = 14,
+    }
+    impl Simple {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Simple::Bool => "SIMPLE_BOOL",
+                Simple::I32 => "SIMPLE_I32",
+                Simple::I64 => "SIMPLE_I64",
+                Simple::U8 => "SIMPLE_U8",
+                Simple::U32 => "SIMPLE_U32",
+                Simple::U64 => "SIMPLE_U64",
+                Simple::U128 => "SIMPLE_U128",
+                Simple::U256 => "SIMPLE_U256",
+                Simple::U512 => "SIMPLE_U512",
+                Simple::Unit => "SIMPLE_UNIT",
+                Simple::String => "SIMPLE_STRING",
+                Simple::Key => "SIMPLE_KEY",
+                Simple::Uref => "SIMPLE_UREF",
+                Simple::PublicKey => "SIMPLE_PUBLIC_KEY",
+                Simple::Any => "SIMPLE_ANY",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "SIMPLE_BOOL" => Some(Self::Bool),
+                "SIMPLE_I32" => Some(Self::I32),
+                "SIMPLE_I64" => Some(Self::I64),
+                "SIMPLE_U8" => Some(Self::U8),
+                "SIMPLE_U32" => Some(Self::U32),
+                "SIMPLE_U64" => Some(Self::U64),
+                "SIMPLE_U128" => Some(Self::U128),
+                "SIMPLE_U256" => Some(Self::U256),
+                "SIMPLE_U512" => Some(Self::U512),
+                "SIMPLE_UNIT" => Some(Self::Unit),
+                "SIMPLE_STRING" => Some(Self::String),
+                "SIMPLE_KEY" => Some(Self::Key),
+                "SIMPLE_UREF" => Some(Self::Uref),
+                "SIMPLE_PUBLIC_KEY" => Some(Self::PublicKey),
+                "SIMPLE_ANY" => Some(Self::Any),
+                _ => None,
+            }
+        }
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum ClType {
+        #[prost(enumeration = "Simple", tag = "1")]
+        Simple(i32),
+        #[prost(message, tag = "2")]
+        Option(::prost::alloc::boxed::Box<super::ClType>),
+        #[prost(message, tag = "3")]
+        List(::prost::alloc::boxed::Box<super::ClType>),
+        /// The length of the byte array.
+        #[prost(uint32, tag = "4")]
+        ByteArray(u32),
+        #[prost(message, tag = "5")]
+        Result(::prost::alloc::boxed::Box<ResultType>),
+        #[prost(message, tag = "6")]
+        Map(::prost::alloc::boxed::Box<MapType>),
+        #[prost(message, tag = "7")]
+        Tuple(TupleType),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClValue {
+    #[prost(message, optional, tag = "1")]
+    pub cl_type: ::core::option::Option<ClType>,
+    /// The `bytesrepr`-serialized value.
+    #[prost(bytes = "vec", tag = "2")]
+    pub bytes: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct URef {
+    #[prost(bytes = "vec", tag = "1")]
+    pub addr: ::prost::alloc::vec::Vec<u8>,
+    /// The access rights as bit flags: 1 for read, 2 for write and 4 for add.
+    #[prost(uint32, tag = "2")]
+    pub access_rights: u32,
+}
+/// A global state key.  Account hashes, hashes and addresses are given as their raw 32 bytes.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Key {
+    #[prost(
+        oneof = "key::Key",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15"
+    )]
+    pub key: ::core::option::Option<key::Key>,
+}
+/// Nested message and enum types in `Key`.
+pub mod key {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Key {
+        #[prost(bytes, tag = "1")]
+        Account(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "2")]
+        Hash(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "3")]
+        Uref(super::URef),
+        #[prost(bytes, tag = "4")]
+        Transfer(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "5")]
+        DeployInfo(::prost::alloc::vec::Vec<u8>),
+        #[prost(uint64, tag = "6")]
+        EraInfo(u64),
+        /// The address of the purse.
+        #[prost(bytes, tag = "7")]
+        Balance(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "8")]
+        Bid(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "9")]
+        Withdraw(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "10")]
+        Dictionary(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "11")]
+        SystemContractRegistry(super::Unit),
+        #[prost(message, tag = "12")]
+        EraSummary(super::Unit),
+        #[prost(bytes, tag = "13")]
+        Unbond(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "14")]
+        ChainspecRegistry(super::Unit),
+        #[prost(message, tag = "15")]
+        ChecksumRegistry(super::Unit),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NamedKey {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub key: ::core::option::Option<Key>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoredValue {
+    #[prost(oneof = "stored_value::Value", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
+    pub value: ::core::option::Option<stored_value::Value>,
+}
+/// Nested message and enum types in `StoredValue`.
+pub mod stored_value {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        ClValue(super::ClValue),
+        #[prost(message, tag = "2")]
+        Account(super::Account),
+        #[prost(message, tag = "3")]
+        ContractWasm(super::ContractWasm),
+        #[prost(message, tag = "4")]
+        Contract(super::Contract),
+        #[prost(message, tag = "5")]
+        ContractPackage(super::ContractPackage),
+        #[prost(message, tag = "6")]
+        Transfer(super::Transfer),
+        #[prost(message, tag = "7")]
+        DeployInfo(super::DeployInfo),
+        #[prost(message, tag = "8")]
+        EraInfo(super::EraInfo),
+        #[prost(message, tag = "9")]
+        Bid(super::Bid),
+        #[prost(message, tag = "10")]
+        Withdraw(super::UnbondingPurses),
+        #[prost(message, tag = "11")]
+        Unbonding(super::UnbondingPurses),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Account {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "2")]
+    pub named_keys: ::prost::alloc::vec::Vec<NamedKey>,
+    #[prost(message, optional, tag = "3")]
+    pub main_purse: ::core::option::Option<URef>,
+    #[prost(message, repeated, tag = "4")]
+    pub associated_keys: ::prost::alloc::vec::Vec<AssociatedKey>,
+    #[prost(message, optional, tag = "5")]
+    pub action_thresholds: ::core::option::Option<ActionThresholds>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssociatedKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub weight: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActionThresholds {
+    #[prost(uint32, tag = "1")]
+    pub deployment: u32,
+    #[prost(uint32, tag = "2")]
+    pub key_management: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContractWasm {
+    #[prost(bytes = "vec", tag = "1")]
+    pub bytes: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Contract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub contract_package_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract_wasm_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "3")]
+    pub named_keys: ::prost::alloc::vec::Vec<NamedKey>,
+    #[prost(message, repeated, tag = "4")]
+    pub entry_points: ::prost::alloc::vec::Vec<EntryPoint>,
+    #[prost(string, tag = "5")]
+    pub protocol_version: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EntryPoint {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub args: ::prost::alloc::vec::Vec<entry_point::Parameter>,
+    #[prost(message, optional, tag = "3")]
+    pub ret: ::core::option::Option<ClType>,
+    #[prost(enumeration = "entry_point::Type", tag = "6")]
+    pub entry_point_type: i32,
+    #[prost(oneof = "entry_point::Access", tags = "4, 5")]
+    pub access: ::core::option::Option<entry_point::Access>,
+}
+/// Nested message and enum types in `EntryPoint`.
+pub mod entry_point {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Parameter {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        #[prost(message, optional, tag = "2")]
+        pub cl_type: ::core::option::Option<super::ClType>,
+    }
+    /// The names of the groups permitted to call the entry point.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Groups {
+        #[prost(string, repeated, tag = "1")]
+        pub names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    }
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum Type {
+        Session = 0,
+        Contract = 1,
+    }
+    impl Type {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Type::Session => "TYPE_SESSION",
+                Type::Contract => "TYPE_CONTRACT",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "TYPE_SESSION" => Some(Self::Session),
+                "TYPE_CONTRACT" => Some(Self::Contract),
+                _ => None,
+            }
+        }
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Access {
+        #[prost(message, tag = "4")]
+        Public(super::Unit),
+        #[prost(message, tag = "5")]
+        Groups(Groups),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContractPackage {
+    #[prost(message, optional, tag = "1")]
+    pub access_key: ::core::option::Option<URef>,
+    #[prost(message, repeated, tag = "2")]
+    pub versions: ::prost::alloc::vec::Vec<contract_package::Version>,
+    #[prost(message, repeated, tag = "3")]
+    pub disabled_versions: ::prost::alloc::vec::Vec<contract_package::Version>,
+    #[prost(message, repeated, tag = "4")]
+    pub groups: ::prost::alloc::vec::Vec<contract_package::Group>,
+    #[prost(bool, tag = "5")]
+    pub locked: bool,
+}
+/// Nested message and enum types in `ContractPackage`.
+pub mod contract_package {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Version {
+        #[prost(uint32, tag = "1")]
+        pub protocol_version_major: u32,
+        #[prost(uint32, tag = "2")]
+        pub contract_version: u32,
+        /// Not set for disabled versions.
+        #[prost(bytes = "vec", tag = "3")]
+        pub contract_hash: ::prost::alloc::vec::Vec<u8>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Group {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        #[prost(message, repeated, tag = "2")]
+        pub urefs: ::prost::alloc::vec::Vec<super::URef>,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transfer {
+    #[prost(bytes = "vec", tag = "1")]
+    pub deploy_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The account hash of the sender.
+    #[prost(bytes = "vec", tag = "2")]
+    pub from: ::prost::alloc::vec::Vec<u8>,
+    /// The account hash of the recipient, if known.
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub to: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag = "4")]
+    pub source: ::core::option::Option<URef>,
+    #[prost(message, optional, tag = "5")]
+    pub target: ::core::option::Option<URef>,
+    #[prost(string, tag = "6")]
+    pub amount: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub gas: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag = "8")]
+    pub id: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeployInfo {
+    #[prost(bytes = "vec", tag = "1")]
+    pub deploy_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The addresses of the transfers made by the deploy.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub transfers: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// The account hash of the deploy's account.
+    #[prost(bytes = "vec", tag = "3")]
+    pub from: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub source: ::core::option::Option<URef>,
+    #[prost(string, tag = "5")]
+    pub gas: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EraInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub seigniorage_allocations: ::prost::alloc::vec::Vec<SeigniorageAllocation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SeigniorageAllocation {
+    #[prost(bytes = "vec", tag = "1")]
+    pub validator_public_key: ::prost::alloc::vec::Vec<u8>,
+    /// Only set for allocations to delegators.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub delegator_public_key: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(string, tag = "3")]
+    pub amount: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bid {
+    #[prost(bytes = "vec", tag = "1")]
+    pub validator_public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub bonding_purse: ::core::option::Option<URef>,
+    #[prost(string, tag = "3")]
+    pub staked_amount: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub delegation_rate: u32,
+    /// Only set for genesis validators.
+    #[prost(message, optional, tag = "5")]
+    pub vesting_schedule: ::core::option::Option<VestingSchedule>,
+    #[prost(message, repeated, tag = "6")]
+    pub delegators: ::prost::alloc::vec::Vec<Delegator>,
+    #[prost(bool, tag = "7")]
+    pub inactive: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Delegator {
+    #[prost(bytes = "vec", tag = "1")]
+    pub delegator_public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub staked_amount: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub bonding_purse: ::core::option::Option<URef>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub validator_public_key: ::prost::alloc::vec::Vec<u8>,
+    /// Only set for genesis delegators.
+    #[prost(message, optional, tag = "5")]
+    pub vesting_schedule: ::core::option::Option<VestingSchedule>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VestingSchedule {
+    #[prost(uint64, tag = "1")]
+    pub initial_release_timestamp: u64,
+    /// The amounts locked at each week of the vesting period.  Empty until initialized.
+    #[prost(string, repeated, tag = "2")]
+    pub locked_amounts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnbondingPurse {
+    #[prost(message, optional, tag = "1")]
+    pub bonding_purse: ::core::option::Option<URef>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub validator_public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub unbonder_public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "4")]
+    pub era_of_creation: u64,
+    #[prost(string, tag = "5")]
+    pub amount: ::prost::alloc::string::String,
+    /// Only set for unbonds which redelegate to a new validator.
+    #[prost(bytes = "vec", optional, tag = "6")]
+    pub new_validator: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnbondingPurses {
+    #[prost(message, repeated, tag = "1")]
+    pub purses: ::prost::alloc::vec::Vec<UnbondingPurse>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStatusRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStatusResponse {
+    #[prost(string, tag = "1")]
+    pub api_version: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub chainspec_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub reactor_state: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub last_progress: u64,
+    #[prost(uint64, tag = "5")]
+    pub uptime: u64,
+    /// Not set if the node has no complete blocks yet.
+    #[prost(message, optional, tag = "6")]
+    pub last_added_block_header: ::core::option::Option<BlockHeader>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub last_added_block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "8")]
+    pub available_block_range_low: u64,
+    #[prost(uint64, tag = "9")]
+    pub available_block_range_high: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeersRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Peer {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub address: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub peers: ::prost::alloc::vec::Vec<Peer>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainspecRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainspecResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub chainspec_bytes: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub genesis_accounts_bytes: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub global_state_bytes: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockRequest {
+    #[prost(message, optional, tag = "1")]
+    pub block_identifier: ::core::option::Option<BlockIdentifier>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub block: ::core::option::Option<Block>,
+    #[prost(message, repeated, tag = "2")]
+    pub finality_signatures: ::prost::alloc::vec::Vec<FinalitySignature>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockTransfersResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "2")]
+    pub transfers: ::prost::alloc::vec::Vec<Transfer>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStateRootHashResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDeployRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub deploy_hash: ::prost::alloc::vec::Vec<u8>,
+    /// Whether to return the deploy with the finalized approvals substituted.
+    #[prost(bool, tag = "2")]
+    pub finalized_approvals: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutionResult {
+    #[prost(bytes = "vec", tag = "1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub effect: ::core::option::Option<ExecutionEffect>,
+    /// The addresses of the transfers made by the deploy.
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub transfers: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(string, tag = "4")]
+    pub cost: ::prost::alloc::string::String,
+    /// Only set if execution failed.
+    #[prost(string, optional, tag = "5")]
+    pub error_message: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// The effects of executing a deploy on global state.  Keys are given in their formatted string
+/// form, e.g. "hash-<hex>".
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutionEffect {
+    #[prost(message, repeated, tag = "1")]
+    pub operations: ::prost::alloc::vec::Vec<Operation>,
+    #[prost(message, repeated, tag = "2")]
+    pub transforms: ::prost::alloc::vec::Vec<TransformEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Operation {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(enumeration = "operation::Kind", tag = "2")]
+    pub kind: i32,
+}
+/// Nested message and enum types in `Operation`.
+pub mod operation {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum Kind {
+        Read = 0,
+        Write = 1,
+        Add = 2,
+        NoOp = 3,
+    }
+    impl Kind {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Kind::Read => "KIND_READ",
+                Kind::Write => "KIND_WRITE",
+                Kind::Add => "KIND_ADD",
+                Kind::NoOp => "KIND_NO_OP",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "KIND_READ" => Some(Self::Read),
+                "KIND_WRITE" => Some(Self::Write),
+                "KIND_ADD" => Some(Self::Add),
+                "KIND_NO_OP" => Some(Self::NoOp),
+                _ => None,
+            }
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransformEntry {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub transform: ::core::option::Option<Transform>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transform {
+    #[prost(
+        oneof = "transform::Transform",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19"
+    )]
+    pub transform: ::core::option::Option<transform::Transform>,
+}
+/// Nested message and enum types in `Transform`.
+pub mod transform {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct NamedKey {
+        #[prost(string, tag = "1")]
+        pub name: ::prost::alloc::string::String,
+        /// The key in its formatted string form.
+        #[prost(string, tag = "2")]
+        pub key: ::prost::alloc::string::String,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct NamedKeys {
+        #[prost(message, repeated, tag = "1")]
+        pub named_keys: ::prost::alloc::vec::Vec<NamedKey>,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Transform {
+        #[prost(message, tag = "1")]
+        Identity(super::Unit),
+        #[prost(message, tag = "2")]
+        WriteClValue(super::ClValue),
+        /// The account hash of the written account.
+        #[prost(bytes, tag = "3")]
+        WriteAccount(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "4")]
+        WriteContractWasm(super::Unit),
+        #[prost(message, tag = "5")]
+        WriteContract(super::Unit),
+        #[prost(message, tag = "6")]
+        WriteContractPackage(super::Unit),
+        #[prost(message, tag = "7")]
+        WriteDeployInfo(super::DeployInfo),
+        #[prost(message, tag = "8")]
+        WriteEraInfo(super::EraInfo),
+        #[prost(message, tag = "9")]
+        WriteTransfer(super::Transfer),
+        #[prost(message, tag = "10")]
+        WriteBid(super::Bid),
+        #[prost(message, tag = "11")]
+        WriteWithdraw(super::UnbondingPurses),
+        #[prost(int32, tag = "12")]
+        AddInt32(i32),
+        #[prost(uint64, tag = "13")]
+        AddUint64(u64),
+        #[prost(string, tag = "14")]
+        AddUint128(::prost::alloc::string::String),
+        #[prost(string, tag = "15")]
+        AddUint256(::prost::alloc::string::String),
+        #[prost(string, tag = "16")]
+        AddUint512(::prost::alloc::string::String),
+        #[prost(message, tag = "17")]
+        AddKeys(NamedKeys),
+        /// The error message of the failed transform.
+        #[prost(string, tag = "18")]
+        Failure(::prost::alloc::string::String),
+        #[prost(message, tag = "19")]
+        WriteUnbonding(super::UnbondingPurses),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDeployResponse {
+    #[prost(message, optional, tag = "1")]
+    pub deploy: ::core::option::Option<Deploy>,
+    #[prost(message, repeated, tag = "2")]
+    pub execution_results: ::prost::alloc::vec::Vec<ExecutionResult>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGlobalStateRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub key: ::core::option::Option<Key>,
+    #[prost(string, repeated, tag = "3")]
+    pub path: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGlobalStateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub stored_value: ::core::option::Option<StoredValue>,
+    /// The `bytesrepr`-serialized Merkle proof.
+    #[prost(bytes = "vec", tag = "2")]
+    pub merkle_proof: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    /// Must be a `URef` key.
+    #[prost(message, optional, tag = "2")]
+    pub purse_uref: ::core::option::Option<Key>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceResponse {
+    #[prost(string, tag = "1")]
+    pub balance_value: ::prost::alloc::string::String,
+    /// The `bytesrepr`-serialized Merkle proof.
+    #[prost(bytes = "vec", tag = "2")]
+    pub merkle_proof: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAuctionInfoResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub block_height: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub era_validators: ::prost::alloc::vec::Vec<EraValidators>,
+    #[prost(message, repeated, tag = "4")]
+    pub bids: ::prost::alloc::vec::Vec<Bid>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EraValidators {
+    #[prost(uint64, tag = "1")]
+    pub era_id: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub validator_weights: ::prost::alloc::vec::Vec<ValidatorWeight>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEraSummaryResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub era_id: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub era_info: ::core::option::Option<EraInfo>,
+    /// The `bytesrepr`-serialized Merkle proof.
+    #[prost(bytes = "vec", tag = "5")]
+    pub merkle_proof: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountInfoRequest {
+    #[prost(message, optional, tag = "3")]
+    pub block_identifier: ::core::option::Option<BlockIdentifier>,
+    #[prost(oneof = "get_account_info_request::AccountIdentifier", tags = "1, 2")]
+    pub account_identifier: ::core::option::Option<
+        get_account_info_request::AccountIdentifier,
+    >,
+}
+/// Nested message and enum types in `GetAccountInfoRequest`.
+pub mod get_account_info_request {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum AccountIdentifier {
+        #[prost(bytes, tag = "1")]
+        PublicKey(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "2")]
+        AccountHash(::prost::alloc::vec::Vec<u8>),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountInfoResponse {
+    #[prost(message, optional, tag = "1")]
+    pub account: ::core::option::Option<Account>,
+    /// The `bytesrepr`-serialized Merkle proof.
+    #[prost(bytes = "vec", tag = "2")]
+    pub merkle_proof: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DictionaryIdentifier {
+    #[prost(oneof = "dictionary_identifier::Identifier", tags = "1, 2, 3")]
+    pub identifier: ::core::option::Option<dictionary_identifier::Identifier>,
+}
+/// Nested message and enum types in `DictionaryIdentifier`.
+pub mod dictionary_identifier {
+    /// Identifies a dictionary by its name in the named keys of an account or contract.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ByNamedKey {
+        /// The key of the account or contract.
+        #[prost(message, optional, tag = "1")]
+        pub key: ::core::option::Option<super::Key>,
+        #[prost(string, tag = "2")]
+        pub dictionary_name: ::prost::alloc::string::String,
+        #[prost(string, tag = "3")]
+        pub dictionary_item_key: ::prost::alloc::string::String,
+    }
+    /// Identifies a dictionary by its seed `URef`.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct BySeedURef {
+        #[prost(message, optional, tag = "1")]
+        pub seed_uref: ::core::option::Option<super::URef>,
+        #[prost(string, tag = "2")]
+        pub dictionary_item_key: ::prost::alloc::string::String,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Identifier {
+        #[prost(message, tag = "1")]
+        NamedKey(ByNamedKey),
+        #[prost(message, tag = "2")]
+        SeedUref(BySeedURef),
+        /// The address of the dictionary item.
+        #[prost(bytes, tag = "3")]
+        DictionaryAddr(::prost::alloc::vec::Vec<u8>),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDictionaryItemRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub state_root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub dictionary_identifier: ::core::option::Option<DictionaryIdentifier>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDictionaryItemResponse {
+    #[prost(message, optional, tag = "1")]
+    pub dictionary_key: ::core::option::Option<Key>,
+    #[prost(message, optional, tag = "2")]
+    pub stored_value: ::core::option::Option<StoredValue>,
+    /// The `bytesrepr`-serialized Merkle proof.
+    #[prost(bytes = "vec", tag = "3")]
+    pub merkle_proof: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamEventsRequest {
+    #[prost(enumeration = "Feed", tag = "1")]
+    pub feed: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Event {
+    /// The name of the variant, e.g. "BlockAdded".
+    #[prost(string, tag = "1")]
+    pub kind: ::prost::alloc::string::String,
+    /// The event's data as JSON, identical to the "data" field of the corresponding server-sent event.
+    #[prost(string, tag = "2")]
+    pub json: ::prost::alloc::string::String,
+}
+/// The feeds of the event stream server, i.e. the "/events/<feed>" paths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Feed {
+    Main = 0,
+    Deploys = 1,
+    Signatures = 2,
+    Node = 3,
+}
+impl Feed {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Feed::Main => "FEED_MAIN",
+            Feed::Deploys => "FEED_DEPLOYS",
+            Feed::Signatures => "FEED_SIGNATURES",
+            Feed::Node => "FEED_NODE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FEED_MAIN" => Some(Self::Main),
+            "FEED_DEPLOYS" => Some(Self::Deploys),
+            "FEED_SIGNATURES" => Some(Self::Signatures),
+            "FEED_NODE" => Some(Self::Node),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod casper_node_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with CasperNodeServer.
+    #[async_trait]
+    pub trait CasperNode: Send + Sync + 'static {
+        /// Returns the current status of the node.
+        async fn get_status(
+            &self,
+            request: tonic::Request<super::GetStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetStatusResponse>,
+            tonic::Status,
+        >;
+        /// Returns the node's connected peers.
+        async fn get_peers(
+            &self,
+            request: tonic::Request<super::GetPeersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPeersResponse>,
+            tonic::Status,
+        >;
+        /// Returns the raw bytes of the chainspec files.
+        async fn get_chainspec(
+            &self,
+            request: tonic::Request<super::GetChainspecRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetChainspecResponse>,
+            tonic::Status,
+        >;
+        /// Returns a block and its finality signatures.
+        async fn get_block(
+            &self,
+            request: tonic::Request<super::GetBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlockResponse>,
+            tonic::Status,
+        >;
+        /// Returns the transfers executed in a block.
+        async fn get_block_transfers(
+            &self,
+            request: tonic::Request<super::GetBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlockTransfersResponse>,
+            tonic::Status,
+        >;
+        /// Returns the state root hash of a block.
+        async fn get_state_root_hash(
+            &self,
+            request: tonic::Request<super::GetBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetStateRootHashResponse>,
+            tonic::Status,
+        >;
+        /// Returns a deploy and its execution results.
+        async fn get_deploy(
+            &self,
+            request: tonic::Request<super::GetDeployRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDeployResponse>,
+            tonic::Status,
+        >;
+        /// Queries global state.
+        async fn query_global_state(
+            &self,
+            request: tonic::Request<super::QueryGlobalStateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryGlobalStateResponse>,
+            tonic::Status,
+        >;
+        /// Returns the balance of a purse.
+        async fn get_balance(
+            &self,
+            request: tonic::Request<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        >;
+        /// Returns the bids and era validators as of a block.
+        async fn get_auction_info(
+            &self,
+            request: tonic::Request<super::GetBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAuctionInfoResponse>,
+            tonic::Status,
+        >;
+        /// Returns the seigniorage allocations of the most recent switch block as of a block.
+        async fn get_era_summary(
+            &self,
+            request: tonic::Request<super::GetBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEraSummaryResponse>,
+            tonic::Status,
+        >;
+        /// Returns an account as of a block.
+        async fn get_account_info(
+            &self,
+            request: tonic::Request<super::GetAccountInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAccountInfoResponse>,
+            tonic::Status,
+        >;
+        /// Returns an item of a dictionary.
+        async fn get_dictionary_item(
+            &self,
+            request: tonic::Request<super::GetDictionaryItemRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDictionaryItemResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamEvents method.
+        type StreamEventsStream: futures_core::Stream<
+                Item = std::result::Result<super::Event, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Streams the events of one of the event stream server's feeds, starting with the next event.
+        async fn stream_events(
+            &self,
+            request: tonic::Request<super::StreamEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamEventsStream>,
+            tonic::Status,
+        >;
+    }
+    /// The read methods of the JSON-RPC server, plus streams of the events published by the event stream
+    /// server.
+    #[derive(Debug)]
+    pub struct CasperNodeServer<T: CasperNode> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: CasperNode> CasperNodeServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CasperNodeServer<T>
+    where
+        T: CasperNode,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/casper.node.v1.CasperNode/GetStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStatusSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetStatusRequest>
+                    for GetStatusSvc<T> {
+                        type Response = super::GetStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetStatusRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_status(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetPeers" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPeersSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetPeersRequest>
+                    for GetPeersSvc<T> {
+                        type Response = super::GetPeersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPeersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_peers(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPeersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetChainspec" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetChainspecSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetChainspecRequest>
+                    for GetChainspecSvc<T> {
+                        type Response = super::GetChainspecResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetChainspecRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_chainspec(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetChainspecSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetBlock" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlockSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBlockRequest>
+                    for GetBlockSvc<T> {
+                        type Response = super::GetBlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_block(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetBlockTransfers" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlockTransfersSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBlockRequest>
+                    for GetBlockTransfersSvc<T> {
+                        type Response = super::GetBlockTransfersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_block_transfers(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlockTransfersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetStateRootHash" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStateRootHashSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBlockRequest>
+                    for GetStateRootHashSvc<T> {
+                        type Response = super::GetStateRootHashResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_state_root_hash(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetStateRootHashSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetDeploy" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetDeploySvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetDeployRequest>
+                    for GetDeploySvc<T> {
+                        type Response = super::GetDeployResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetDeployRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_deploy(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetDeploySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/QueryGlobalState" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryGlobalStateSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::QueryGlobalStateRequest>
+                    for QueryGlobalStateSvc<T> {
+                        type Response = super::QueryGlobalStateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryGlobalStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).query_global_state(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryGlobalStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBalanceSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBalanceRequest>
+                    for GetBalanceSvc<T> {
+                        type Response = super::GetBalanceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_balance(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBalanceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetAuctionInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAuctionInfoSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBlockRequest>
+                    for GetAuctionInfoSvc<T> {
+                        type Response = super::GetAuctionInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_auction_info(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAuctionInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetEraSummary" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEraSummarySvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetBlockRequest>
+                    for GetEraSummarySvc<T> {
+                        type Response = super::GetEraSummaryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_era_summary(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetEraSummarySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetAccountInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAccountInfoSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetAccountInfoRequest>
+                    for GetAccountInfoSvc<T> {
+                        type Response = super::GetAccountInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAccountInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_account_info(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAccountInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/GetDictionaryItem" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetDictionaryItemSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::UnaryService<super::GetDictionaryItemRequest>
+                    for GetDictionaryItemSvc<T> {
+                        type Response = super::GetDictionaryItemResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetDictionaryItemRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).get_dictionary_item(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetDictionaryItemSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/casper.node.v1.CasperNode/StreamEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamEventsSvc<T: CasperNode>(pub Arc<T>);
+                    impl<
+                        T: CasperNode,
+                    > tonic::server::ServerStreamingService<super::StreamEventsRequest>
+                    for StreamEventsSvc<T> {
+                        type Response = super::Event;
+                        type ResponseStream = T::StreamEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                (*inner).stream_events(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: CasperNode> Clone for CasperNodeServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: CasperNode> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: CasperNode> tonic::server::NamedService for CasperNodeServer<T> {
+        const NAME: &'static str = "casper.node.v1.CasperNode";
+    }
+}
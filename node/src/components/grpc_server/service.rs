@@ -0,0 +1,606 @@
+//! The implementation of the `CasperNode` gRPC service.
+//!
+//! Each method is translated into reactor requests via the effect builder, much as the JSON-RPC
+//! server's methods are.  Event streams are fed from the same broadcast channel as the event stream
+//! server's feeds.
+
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt::Display,
+    pin::Pin,
+    time::Instant,
+};
+
+use futures::{join, stream, Stream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tonic::{Request, Response, Status};
+use tracing::debug;
+
+use casper_execution_engine::core::engine_state::{
+    BalanceRequest, BalanceResult, GetBidsRequest, GetBidsResult, QueryRequest, QueryResult,
+};
+use casper_hashing::Digest;
+use casper_types::{
+    account::{AccountHash, ACCOUNT_HASH_LENGTH},
+    bytesrepr::{self, ToBytes},
+    ProtocolVersion, PublicKey, StoredValue,
+};
+
+use super::{
+    proto::{
+        self, block_identifier::Identifier, casper_node_server::CasperNode, dictionary_identifier,
+        get_account_info_request::AccountIdentifier,
+    },
+    ReactorEventT,
+};
+use crate::{
+    components::{
+        contract_runtime::EraValidatorsRequest,
+        event_stream_server::{
+            SseData, SSE_API_DEPLOYS_PATH, SSE_API_MAIN_PATH, SSE_API_NODE_PATH,
+            SSE_API_SIGNATURES_PATH,
+        },
+    },
+    effect::EffectBuilder,
+    types::{BlockHash, BlockWithMetadata, DeployHash, DeployMetadataExt},
+};
+
+/// The `CasperNode` gRPC service.
+pub(super) struct GrpcService<REv: 'static> {
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    network_name: String,
+    node_startup_instant: Instant,
+    sse_data_broadcaster: broadcast::Sender<SseData>,
+}
+
+impl<REv: ReactorEventT> GrpcService<REv> {
+    pub(super) fn new(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        network_name: String,
+        node_startup_instant: Instant,
+        sse_data_broadcaster: broadcast::Sender<SseData>,
+    ) -> Self {
+        GrpcService {
+            effect_builder,
+            api_version,
+            network_name,
+            node_startup_instant,
+            sse_data_broadcaster,
+        }
+    }
+
+    /// Retrieves the identified block, only from the range of fully available blocks.
+    async fn get_block_with_metadata(
+        &self,
+        block_identifier: Option<proto::BlockIdentifier>,
+    ) -> Result<BlockWithMetadata, Status> {
+        let maybe_block_with_metadata = match block_identifier.and_then(|id| id.identifier) {
+            Some(Identifier::Hash(hash)) => {
+                let block_hash = BlockHash::new(parse_digest(&hash, "block hash")?);
+                self.effect_builder
+                    .get_block_with_metadata_from_storage(block_hash, true)
+                    .await
+            }
+            Some(Identifier::Height(height)) => {
+                self.effect_builder
+                    .get_block_at_height_with_metadata_from_storage(height, true)
+                    .await
+            }
+            None => {
+                self.effect_builder
+                    .get_highest_block_with_metadata_from_storage(true)
+                    .await
+            }
+        };
+        maybe_block_with_metadata.ok_or_else(|| Status::not_found("block not known"))
+    }
+
+    /// Queries global state, returning the value found and the `bytesrepr`-serialized Merkle
+    /// proof.
+    async fn query(
+        &self,
+        state_root_hash: Digest,
+        key: casper_types::Key,
+        path: Vec<String>,
+    ) -> Result<(StoredValue, Vec<u8>), Status> {
+        let query_result = self
+            .effect_builder
+            .query_global_state(QueryRequest::new(state_root_hash, key, path))
+            .await
+            .map_err(internal)?;
+        query_success(query_result)
+    }
+}
+
+#[tonic::async_trait]
+impl<REv: ReactorEventT> CasperNode for GrpcService<REv> {
+    async fn get_status(
+        &self,
+        _request: Request<proto::GetStatusRequest>,
+    ) -> Result<Response<proto::GetStatusResponse>, Status> {
+        let (maybe_header, (reactor_state, last_progress), available_block_range) = join!(
+            self.effect_builder
+                .get_highest_complete_block_header_from_storage(),
+            self.effect_builder.get_reactor_status(),
+            self.effect_builder.get_available_block_range_from_storage(),
+        );
+        let (last_added_block_header, last_added_block_hash) = match maybe_header {
+            Some(header) => (
+                Some(proto::BlockHeader::try_from(&header).map_err(internal)?),
+                header.block_hash().inner().into_vec(),
+            ),
+            None => (None, vec![]),
+        };
+        let uptime =
+            u64::try_from(self.node_startup_instant.elapsed().as_millis()).unwrap_or(u64::MAX);
+        Ok(Response::new(proto::GetStatusResponse {
+            api_version: self.api_version.to_string(),
+            chainspec_name: self.network_name.clone(),
+            reactor_state: reactor_state.to_string(),
+            last_progress: last_progress.millis(),
+            uptime,
+            last_added_block_header,
+            last_added_block_hash,
+            available_block_range_low: available_block_range.low(),
+            available_block_range_high: available_block_range.high(),
+        }))
+    }
+
+    async fn get_peers(
+        &self,
+        _request: Request<proto::GetPeersRequest>,
+    ) -> Result<Response<proto::GetPeersResponse>, Status> {
+        let peers = self
+            .effect_builder
+            .network_peers()
+            .await
+            .into_iter()
+            .map(|(node_id, address)| proto::Peer {
+                node_id: node_id.to_string(),
+                address,
+            })
+            .collect();
+        Ok(Response::new(proto::GetPeersResponse { peers }))
+    }
+
+    async fn get_chainspec(
+        &self,
+        _request: Request<proto::GetChainspecRequest>,
+    ) -> Result<Response<proto::GetChainspecResponse>, Status> {
+        let chainspec_raw_bytes = self.effect_builder.get_chainspec_raw_bytes().await;
+        Ok(Response::new(proto::GetChainspecResponse {
+            chainspec_bytes: chainspec_raw_bytes.chainspec_bytes().to_vec(),
+            genesis_accounts_bytes: chainspec_raw_bytes
+                .maybe_genesis_accounts_bytes()
+                .map(<[u8]>::to_vec),
+            global_state_bytes: chainspec_raw_bytes
+                .maybe_global_state_bytes()
+                .map(<[u8]>::to_vec),
+        }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetBlockResponse>, Status> {
+        let BlockWithMetadata {
+            block,
+            block_signatures,
+        } = self
+            .get_block_with_metadata(request.into_inner().block_identifier)
+            .await?;
+        Ok(Response::new(proto::GetBlockResponse {
+            block: Some(proto::Block::try_from(&block).map_err(internal)?),
+            finality_signatures: proto::finality_signatures(&block_signatures).map_err(internal)?,
+        }))
+    }
+
+    async fn get_block_transfers(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetBlockTransfersResponse>, Status> {
+        let block_hash = *self
+            .get_block_with_metadata(request.into_inner().block_identifier)
+            .await?
+            .block
+            .hash();
+        let transfers = self
+            .effect_builder
+            .get_block_transfers_from_storage(block_hash)
+            .await
+            .ok_or_else(|| Status::not_found("block transfers not known"))?
+            .iter()
+            .map(proto::Transfer::from)
+            .collect();
+        Ok(Response::new(proto::GetBlockTransfersResponse {
+            block_hash: block_hash.inner().into_vec(),
+            transfers,
+        }))
+    }
+
+    async fn get_state_root_hash(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetStateRootHashResponse>, Status> {
+        let state_root_hash = *self
+            .get_block_with_metadata(request.into_inner().block_identifier)
+            .await?
+            .block
+            .state_root_hash();
+        Ok(Response::new(proto::GetStateRootHashResponse {
+            state_root_hash: state_root_hash.into_vec(),
+        }))
+    }
+
+    async fn get_deploy(
+        &self,
+        request: Request<proto::GetDeployRequest>,
+    ) -> Result<Response<proto::GetDeployResponse>, Status> {
+        let request = request.into_inner();
+        let deploy_hash = DeployHash::new(parse_digest(&request.deploy_hash, "deploy hash")?);
+        let (deploy_with_finalized_approvals, metadata_ext) = self
+            .effect_builder
+            .get_deploy_and_metadata_from_storage(deploy_hash)
+            .await
+            .ok_or_else(|| Status::not_found("deploy not known"))?;
+        let deploy = if request.finalized_approvals {
+            deploy_with_finalized_approvals.into_naive()
+        } else {
+            deploy_with_finalized_approvals.discard_finalized_approvals()
+        };
+        let execution_results = match metadata_ext {
+            DeployMetadataExt::Metadata(metadata) => metadata
+                .execution_results
+                .iter()
+                .map(|(block_hash, execution_result)| {
+                    proto::execution_result(block_hash, execution_result)
+                })
+                .collect::<Result<_, _>>()
+                .map_err(internal)?,
+            DeployMetadataExt::BlockInfo(_) | DeployMetadataExt::Empty => vec![],
+        };
+        Ok(Response::new(proto::GetDeployResponse {
+            deploy: Some(proto::Deploy::try_from(&deploy).map_err(internal)?),
+            execution_results,
+        }))
+    }
+
+    async fn query_global_state(
+        &self,
+        request: Request<proto::QueryGlobalStateRequest>,
+    ) -> Result<Response<proto::QueryGlobalStateResponse>, Status> {
+        let request = request.into_inner();
+        let state_root_hash = parse_digest(&request.state_root_hash, "state root hash")?;
+        let key = parse_key(request.key)?;
+        let (value, merkle_proof) = self.query(state_root_hash, key, request.path).await?;
+        Ok(Response::new(proto::QueryGlobalStateResponse {
+            stored_value: Some(proto::StoredValue::try_from(&value).map_err(internal)?),
+            merkle_proof,
+        }))
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<proto::GetBalanceRequest>,
+    ) -> Result<Response<proto::GetBalanceResponse>, Status> {
+        let request = request.into_inner();
+        let state_root_hash = parse_digest(&request.state_root_hash, "state root hash")?;
+        let purse_uref = parse_key(request.purse_uref)?
+            .into_uref()
+            .ok_or_else(|| Status::invalid_argument("purse must be a URef key"))?;
+        let balance_result = self
+            .effect_builder
+            .get_balance(BalanceRequest::new(state_root_hash, purse_uref))
+            .await
+            .map_err(internal)?;
+        match balance_result {
+            BalanceResult::Success { motes, proof } => {
+                Ok(Response::new(proto::GetBalanceResponse {
+                    balance_value: motes.to_string(),
+                    merkle_proof: proof.to_bytes().map_err(internal)?,
+                }))
+            }
+            BalanceResult::RootNotFound => Err(Status::not_found("state root hash not known")),
+        }
+    }
+
+    async fn get_auction_info(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetAuctionInfoResponse>, Status> {
+        let block = self
+            .get_block_with_metadata(request.into_inner().block_identifier)
+            .await?
+            .block;
+        let state_root_hash = *block.state_root_hash();
+        let (get_bids_result, era_validators_result) = join!(
+            self.effect_builder
+                .get_bids(GetBidsRequest::new(state_root_hash)),
+            self.effect_builder
+                .get_era_validators_from_contract_runtime(EraValidatorsRequest::new(
+                    state_root_hash,
+                    self.api_version,
+                )),
+        );
+        let bids = match get_bids_result.map_err(internal)? {
+            GetBidsResult::Success { bids } => bids,
+            GetBidsResult::RootNotFound => {
+                return Err(Status::internal("root not found when getting bids"))
+            }
+        };
+        let era_validators = era_validators_result
+            .map_err(internal)?
+            .iter()
+            .map(|(era_id, validator_weights)| {
+                Ok(proto::EraValidators {
+                    era_id: era_id.value(),
+                    validator_weights: validator_weights
+                        .iter()
+                        .map(|(validator, weight)| {
+                            Ok(proto::ValidatorWeight {
+                                validator: validator.to_bytes()?,
+                                weight: weight.to_string(),
+                            })
+                        })
+                        .collect::<Result<_, bytesrepr::Error>>()?,
+                })
+            })
+            .collect::<Result<_, bytesrepr::Error>>()
+            .map_err(internal)?;
+        Ok(Response::new(proto::GetAuctionInfoResponse {
+            state_root_hash: state_root_hash.into_vec(),
+            block_height: block.height(),
+            era_validators,
+            bids: bids
+                .values()
+                .map(proto::Bid::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(internal)?,
+        }))
+    }
+
+    async fn get_era_summary(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetEraSummaryResponse>, Status> {
+        let block = self
+            .get_block_with_metadata(request.into_inner().block_identifier)
+            .await?
+            .block;
+        let state_root_hash = *block.state_root_hash();
+        // As for the JSON-RPC server, fall back to the era info under `Key::EraInfo` on networks
+        // which predate `Key::EraSummary`.
+        let mut query_result = self
+            .effect_builder
+            .query_global_state(QueryRequest::new(
+                state_root_hash,
+                casper_types::Key::EraSummary,
+                vec![],
+            ))
+            .await;
+        if matches!(query_result, Ok(QueryResult::ValueNotFound(_))) {
+            query_result = self
+                .effect_builder
+                .query_global_state(QueryRequest::new(
+                    state_root_hash,
+                    casper_types::Key::EraInfo(block.header().era_id()),
+                    vec![],
+                ))
+                .await;
+        }
+        let (value, merkle_proof) = query_success(query_result.map_err(internal)?)?;
+        let era_info = match value {
+            StoredValue::EraInfo(era_info) => era_info,
+            value => {
+                return Err(Status::internal(format!(
+                    "expected era info, got {}",
+                    value.type_name()
+                )))
+            }
+        };
+        Ok(Response::new(proto::GetEraSummaryResponse {
+            block_hash: block.hash().inner().into_vec(),
+            era_id: block.header().era_id().value(),
+            state_root_hash: state_root_hash.into_vec(),
+            era_info: Some(proto::EraInfo::try_from(&era_info).map_err(internal)?),
+            merkle_proof,
+        }))
+    }
+
+    async fn get_account_info(
+        &self,
+        request: Request<proto::GetAccountInfoRequest>,
+    ) -> Result<Response<proto::GetAccountInfoResponse>, Status> {
+        let request = request.into_inner();
+        let account_hash = match request.account_identifier {
+            Some(AccountIdentifier::PublicKey(bytes)) => {
+                let public_key: PublicKey = bytesrepr::deserialize(bytes).map_err(|error| {
+                    Status::invalid_argument(format!("failed to parse public key: {}", error))
+                })?;
+                public_key.to_account_hash()
+            }
+            Some(AccountIdentifier::AccountHash(bytes)) => AccountHash::try_from(bytes.as_slice())
+                .map_err(|_| {
+                    Status::invalid_argument(format!(
+                        "account hash must be {} bytes, got {}",
+                        ACCOUNT_HASH_LENGTH,
+                        bytes.len()
+                    ))
+                })?,
+            None => return Err(Status::invalid_argument("missing account identifier")),
+        };
+        let state_root_hash = *self
+            .get_block_with_metadata(request.block_identifier)
+            .await?
+            .block
+            .state_root_hash();
+        let (value, merkle_proof) = self
+            .query(
+                state_root_hash,
+                casper_types::Key::Account(account_hash),
+                vec![],
+            )
+            .await?;
+        let account = match value {
+            StoredValue::Account(account) => account,
+            value => {
+                return Err(Status::not_found(format!(
+                    "stored value is not an account, got {}",
+                    value.type_name()
+                )))
+            }
+        };
+        Ok(Response::new(proto::GetAccountInfoResponse {
+            account: Some(proto::Account::from(&account)),
+            merkle_proof,
+        }))
+    }
+
+    async fn get_dictionary_item(
+        &self,
+        request: Request<proto::GetDictionaryItemRequest>,
+    ) -> Result<Response<proto::GetDictionaryItemResponse>, Status> {
+        let request = request.into_inner();
+        let state_root_hash = parse_digest(&request.state_root_hash, "state root hash")?;
+        let dictionary_key = match request
+            .dictionary_identifier
+            .and_then(|identifier| identifier.identifier)
+        {
+            Some(dictionary_identifier::Identifier::NamedKey(by_named_key)) => {
+                let base_key = parse_key(by_named_key.key)?;
+                let (value, _) = self.query(state_root_hash, base_key, vec![]).await?;
+                let seed_uref = dictionary_seed_uref(&value, &by_named_key.dictionary_name)?;
+                casper_types::Key::dictionary(
+                    seed_uref,
+                    by_named_key.dictionary_item_key.as_bytes(),
+                )
+            }
+            Some(dictionary_identifier::Identifier::SeedUref(by_seed_uref)) => {
+                let seed_uref = by_seed_uref
+                    .seed_uref
+                    .ok_or_else(|| Status::invalid_argument("missing seed URef"))?
+                    .try_into()
+                    .map_err(Status::invalid_argument)?;
+                casper_types::Key::dictionary(
+                    seed_uref,
+                    by_seed_uref.dictionary_item_key.as_bytes(),
+                )
+            }
+            Some(dictionary_identifier::Identifier::DictionaryAddr(bytes)) => {
+                casper_types::Key::Dictionary(parse_digest(&bytes, "dictionary address")?.value())
+            }
+            None => return Err(Status::invalid_argument("missing dictionary identifier")),
+        };
+        let (value, merkle_proof) = self.query(state_root_hash, dictionary_key, vec![]).await?;
+        Ok(Response::new(proto::GetDictionaryItemResponse {
+            dictionary_key: Some(proto::Key::from(&dictionary_key)),
+            stored_value: Some(proto::StoredValue::try_from(&value).map_err(internal)?),
+            merkle_proof,
+        }))
+    }
+
+    type StreamEventsStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let sse_path = match proto::Feed::from_i32(request.into_inner().feed) {
+            Some(proto::Feed::Main) => SSE_API_MAIN_PATH,
+            Some(proto::Feed::Deploys) => SSE_API_DEPLOYS_PATH,
+            Some(proto::Feed::Signatures) => SSE_API_SIGNATURES_PATH,
+            Some(proto::Feed::Node) => SSE_API_NODE_PATH,
+            None => return Err(Status::invalid_argument("unknown feed")),
+        };
+        let receiver = self.sse_data_broadcaster.subscribe();
+        let events = stream::unfold(Some(receiver), move |maybe_receiver| async move {
+            let mut receiver = maybe_receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok(sse_data) => {
+                        if !sse_data.is_published_on(sse_path) {
+                            continue;
+                        }
+                        let event = proto::Event::try_from(&sse_data).map_err(internal);
+                        // The stream ends after the node announces it is shutting down.
+                        let next = match (&sse_data, &event) {
+                            (SseData::Shutdown, _) | (_, Err(_)) => None,
+                            _ => Some(receiver),
+                        };
+                        return Some((event, next));
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        let status = Status::data_loss(format!(
+                            "client too slow: {} events were dropped",
+                            skipped
+                        ));
+                        return Some((Err(status), None));
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+/// Returns an "internal" status for the given error.
+fn internal<E: Display>(error: E) -> Status {
+    Status::internal(error.to_string())
+}
+
+fn parse_digest(bytes: &[u8], name: &str) -> Result<Digest, Status> {
+    Digest::try_from(bytes).map_err(|_| {
+        Status::invalid_argument(format!(
+            "{} must be {} bytes, got {}",
+            name,
+            Digest::LENGTH,
+            bytes.len()
+        ))
+    })
+}
+
+/// Returns the value and `bytesrepr`-serialized Merkle proof of a successful query.
+fn query_success(query_result: QueryResult) -> Result<(StoredValue, Vec<u8>), Status> {
+    match query_result {
+        QueryResult::Success { value, proofs } => {
+            Ok((*value, proofs.to_bytes().map_err(internal)?))
+        }
+        QueryResult::RootNotFound => Err(Status::not_found("state root hash not known")),
+        query_result => {
+            debug!(?query_result, "gRPC query failed");
+            Err(Status::not_found(format!("{:?}", query_result)))
+        }
+    }
+}
+
+/// Returns the seed URef of the dictionary stored under `dictionary_name` in the named keys of the
+/// given account or contract.
+fn dictionary_seed_uref(
+    value: &StoredValue,
+    dictionary_name: &str,
+) -> Result<casper_types::URef, Status> {
+    let named_keys = match value {
+        StoredValue::Account(account) => account.named_keys(),
+        StoredValue::Contract(contract) => contract.named_keys(),
+        value => {
+            return Err(Status::invalid_argument(format!(
+                "expected account or contract, got {}",
+                value.type_name()
+            )))
+        }
+    };
+    let key = named_keys
+        .get(dictionary_name)
+        .ok_or_else(|| Status::not_found("seed URef not in named keys"))?;
+    key.as_uref().copied().ok_or_else(|| {
+        Status::invalid_argument(format!("expected URef, got {}", key.to_formatted_string()))
+    })
+}
+
+fn parse_key(maybe_key: Option<proto::Key>) -> Result<casper_types::Key, Status> {
+    let key = maybe_key.ok_or_else(|| Status::invalid_argument("missing key"))?;
+    casper_types::Key::try_from(key).map_err(Status::invalid_argument)
+}
@@ -0,0 +1,354 @@
+use std::time::Instant;
+
+use derive_more::From;
+use tokio::sync::broadcast;
+use tonic::{Code, Request};
+
+use casper_execution_engine::core::engine_state::{self, QueryRequest, QueryResult};
+use casper_types::{
+    account::Account,
+    bytesrepr::ToBytes,
+    contracts::NamedKeys,
+    system::auction::{EraInfo, SeigniorageAllocation},
+    testing::TestRng,
+    AccessRights, CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue, URef, U512,
+};
+
+use super::{proto::casper_node_server::CasperNode, service::GrpcService, *};
+use crate::{
+    effect::Responder,
+    reactor::{EventQueueHandle, QueueKind, Scheduler},
+    types::{Block, BlockSignatures, BlockWithMetadata},
+    utils,
+};
+
+#[derive(Debug, From)]
+enum ReactorEvent {
+    #[from]
+    GrpcServer(Event),
+    #[from]
+    Storage(StorageRequest),
+    #[from]
+    ContractRuntime(ContractRuntimeRequest),
+    #[from]
+    NetworkInfo(NetworkInfoRequest),
+    #[from]
+    ReactorStatus(ReactorStatusRequest),
+    #[from]
+    ChainspecRawBytes(ChainspecRawBytesRequest),
+}
+
+struct MockReactor {
+    scheduler: &'static Scheduler<ReactorEvent>,
+}
+
+impl MockReactor {
+    fn new() -> Self {
+        MockReactor {
+            scheduler: utils::leak(Scheduler::new(QueueKind::weights())),
+        }
+    }
+
+    fn new_service(&self) -> GrpcService<ReactorEvent> {
+        let (sse_data_broadcaster, _) = broadcast::channel(1);
+        GrpcService::new(
+            EffectBuilder::new(EventQueueHandle::without_shutdown(self.scheduler)),
+            ProtocolVersion::V1_0_0,
+            "test-network".to_string(),
+            Instant::now(),
+            sse_data_broadcaster,
+        )
+    }
+
+    async fn expect_storage_request(&self) -> StorageRequest {
+        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        match reactor_event {
+            ReactorEvent::Storage(request) => request,
+            _ => panic!("unexpected event: {:?}", reactor_event),
+        }
+    }
+
+    /// Expects a request for the highest block, responding with the given block.
+    async fn expect_highest_block_request(&self, block: &Block) {
+        match self.expect_storage_request().await {
+            StorageRequest::GetHighestBlockWithMetadata {
+                only_from_available_block_range: true,
+                responder,
+            } => {
+                let block_signatures = BlockSignatures::new(*block.hash(), block.header().era_id());
+                responder
+                    .respond(Some(BlockWithMetadata {
+                        block: block.clone(),
+                        block_signatures,
+                    }))
+                    .await
+            }
+            request => panic!("unexpected storage request: {}", request),
+        }
+    }
+
+    async fn expect_query(
+        &self,
+    ) -> (
+        QueryRequest,
+        Responder<Result<QueryResult, engine_state::Error>>,
+    ) {
+        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        match reactor_event {
+            ReactorEvent::ContractRuntime(ContractRuntimeRequest::Query {
+                query_request,
+                responder,
+            }) => (query_request, responder),
+            _ => panic!("unexpected event: {:?}", reactor_event),
+        }
+    }
+}
+
+fn query_success(value: StoredValue) -> Result<QueryResult, engine_state::Error> {
+    Ok(QueryResult::Success {
+        value: Box::new(value),
+        proofs: vec![],
+    })
+}
+
+#[cfg(feature = "grpc-codegen")]
+#[test]
+fn checked_in_generated_code_should_be_up_to_date() {
+    const GENERATED_PATH: &str = concat!(env!("OUT_DIR"), "/casper.node.v1.rs");
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/casper.node.v1.rs"));
+    let checked_in = include_str!("proto/casper.node.v1.rs");
+    assert!(
+        generated == checked_in,
+        "node/src/components/grpc_server/proto/casper.node.v1.rs is stale: replace it with {}",
+        GENERATED_PATH
+    );
+}
+
+#[tokio::test]
+async fn should_get_block_by_height() {
+    let rng = &mut TestRng::new();
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+    let block = Block::random(rng);
+
+    let height = block.height();
+    let response = tokio::spawn(async move {
+        let request = proto::GetBlockRequest {
+            block_identifier: Some(proto::BlockIdentifier {
+                identifier: Some(proto::block_identifier::Identifier::Height(height)),
+            }),
+        };
+        service.get_block(Request::new(request)).await
+    });
+
+    match reactor.expect_storage_request().await {
+        StorageRequest::GetBlockAndMetadataByHeight {
+            block_height,
+            only_from_available_block_range: true,
+            responder,
+        } => {
+            assert_eq!(block_height, height);
+            let block_signatures = BlockSignatures::new(*block.hash(), block.header().era_id());
+            responder
+                .respond(Some(BlockWithMetadata {
+                    block: block.clone(),
+                    block_signatures,
+                }))
+                .await
+        }
+        request => panic!("unexpected storage request: {}", request),
+    }
+
+    let response = response.await.unwrap().unwrap().into_inner();
+    assert_eq!(
+        response.block.unwrap().hash,
+        block.hash().inner().into_vec()
+    );
+    assert!(response.finality_signatures.is_empty());
+}
+
+#[tokio::test]
+async fn should_return_not_found_for_unknown_block() {
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+
+    let response = tokio::spawn(async move {
+        let request = proto::GetBlockRequest {
+            block_identifier: None,
+        };
+        service.get_state_root_hash(Request::new(request)).await
+    });
+
+    match reactor.expect_storage_request().await {
+        StorageRequest::GetHighestBlockWithMetadata { responder, .. } => {
+            responder.respond(None).await
+        }
+        request => panic!("unexpected storage request: {}", request),
+    }
+
+    let status = response.await.unwrap().unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn should_reject_invalid_arguments() {
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+
+    let request = proto::QueryGlobalStateRequest {
+        state_root_hash: vec![1; 31],
+        key: Some(proto::Key::from(&Key::Hash([2; 32]))),
+        path: vec![],
+    };
+    let status = service
+        .query_global_state(Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let request = proto::GetBalanceRequest {
+        state_root_hash: vec![1; 32],
+        purse_uref: Some(proto::Key::from(&Key::Hash([2; 32]))),
+    };
+    let status = service
+        .get_balance(Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let request = proto::GetAccountInfoRequest {
+        account_identifier: None,
+        block_identifier: None,
+    };
+    let status = service
+        .get_account_info(Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    // None of the requests should have reached the reactor.
+    assert!(reactor.scheduler.item_count() == 0);
+}
+
+#[tokio::test]
+async fn should_get_account_info_by_public_key() {
+    let rng = &mut TestRng::new();
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+    let block = Block::random(rng);
+    let public_key = PublicKey::from(&SecretKey::random(rng));
+    let account_hash = public_key.to_account_hash();
+    let main_purse = URef::new([3; 32], AccessRights::READ_ADD_WRITE);
+
+    let public_key_bytes = public_key.to_bytes().unwrap();
+    let response = tokio::spawn(async move {
+        let request = proto::GetAccountInfoRequest {
+            account_identifier: Some(
+                proto::get_account_info_request::AccountIdentifier::PublicKey(public_key_bytes),
+            ),
+            block_identifier: None,
+        };
+        service.get_account_info(Request::new(request)).await
+    });
+
+    reactor.expect_highest_block_request(&block).await;
+    let (query_request, responder) = reactor.expect_query().await;
+    assert_eq!(query_request.state_hash(), *block.state_root_hash());
+    assert_eq!(query_request.key(), Key::Account(account_hash));
+    let account = Account::create(account_hash, NamedKeys::new(), main_purse);
+    responder
+        .respond(query_success(StoredValue::Account(account)))
+        .await;
+
+    let account = response
+        .await
+        .unwrap()
+        .unwrap()
+        .into_inner()
+        .account
+        .unwrap();
+    assert_eq!(account.account_hash, account_hash.value().to_vec());
+    assert_eq!(account.main_purse, Some(main_purse.into()));
+}
+
+#[tokio::test]
+async fn should_get_dictionary_item_by_seed_uref() {
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+    let seed_uref = URef::new([4; 32], AccessRights::READ_ADD_WRITE);
+    let expected_key = Key::dictionary(seed_uref, b"item");
+
+    let response = tokio::spawn(async move {
+        let request = proto::GetDictionaryItemRequest {
+            state_root_hash: vec![5; 32],
+            dictionary_identifier: Some(proto::DictionaryIdentifier {
+                identifier: Some(proto::dictionary_identifier::Identifier::SeedUref(
+                    proto::dictionary_identifier::BySeedURef {
+                        seed_uref: Some(seed_uref.into()),
+                        dictionary_item_key: "item".to_string(),
+                    },
+                )),
+            }),
+        };
+        service.get_dictionary_item(Request::new(request)).await
+    });
+
+    let (query_request, responder) = reactor.expect_query().await;
+    assert_eq!(query_request.key(), expected_key);
+    let cl_value = CLValue::from_t(7_u64).unwrap();
+    responder
+        .respond(query_success(StoredValue::CLValue(cl_value)))
+        .await;
+
+    let response = response.await.unwrap().unwrap().into_inner();
+    assert_eq!(
+        response.dictionary_key,
+        Some(proto::Key::from(&expected_key))
+    );
+    assert!(matches!(
+        response.stored_value.unwrap().value,
+        Some(proto::stored_value::Value::ClValue(_))
+    ));
+}
+
+#[tokio::test]
+async fn should_fall_back_to_era_info_key_for_era_summary() {
+    let rng = &mut TestRng::new();
+    let reactor = MockReactor::new();
+    let service = reactor.new_service();
+    let block = Block::random(rng);
+
+    let response = tokio::spawn(async move {
+        let request = proto::GetBlockRequest {
+            block_identifier: None,
+        };
+        service.get_era_summary(Request::new(request)).await
+    });
+
+    reactor.expect_highest_block_request(&block).await;
+    let (query_request, responder) = reactor.expect_query().await;
+    assert_eq!(query_request.key(), Key::EraSummary);
+    responder
+        .respond(Ok(QueryResult::ValueNotFound("not found".to_string())))
+        .await;
+
+    let (query_request, responder) = reactor.expect_query().await;
+    assert_eq!(query_request.key(), Key::EraInfo(block.header().era_id()));
+    let mut era_info = EraInfo::new();
+    era_info
+        .seigniorage_allocations_mut()
+        .push(SeigniorageAllocation::validator(
+            PublicKey::from(&SecretKey::random(rng)),
+            U512::from(10),
+        ));
+    responder
+        .respond(query_success(StoredValue::EraInfo(era_info)))
+        .await;
+
+    let response = response.await.unwrap().unwrap().into_inner();
+    assert_eq!(response.era_id, block.header().era_id().value());
+    let allocations = response.era_info.unwrap().seigniorage_allocations;
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations[0].amount, "10");
+    assert!(allocations[0].delegator_public_key.is_none());
+}
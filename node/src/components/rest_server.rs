@@ -17,39 +17,57 @@
 //!     example: curl -X GET 'http://IP:8888/status'
 //! /metrics : time series data collected from the internals of the node being queried.
 //!     example: curl -X GET 'http://IP:8888/metrics'
+//!
+//! It also serves a GraphQL endpoint at /graphql which accepts POSTed queries, allowing clients to
+//! traverse blocks, deploys, execution results, transfers, accounts and named keys in a single
+//! request.  Queries exceeding the configured depth or complexity limits are rejected.
+//!     example: curl -X POST 'http://IP:8888/graphql' -H 'Content-Type: application/json'
+//!         -d '{"query": "{ block(height: 10) { hash deploys { hash } } }"}'
+//!
+//! If an admin API token is configured and the server is bound to a loopback address, it also
+//! serves /deploy-buffer, listing the deploys known to the deploy buffer along with whether each
+//! is proposable, held or dead.  Requests must carry the token as a bearer token, and may restrict
+//! the listing to an account.  Deploys are listed in pages of at most 1000, ordered by expiry; the
+//! `next_cursor` of a page is passed as `cursor_expires` and `cursor_deploy_hash` to get the next.
+//!     example: curl -X GET 'http://127.0.0.1:8888/deploy-buffer?account=<public key hex>'
+//!         -H 'Authorization: Bearer <token>'
 
 mod config;
 mod event;
 mod filters;
+mod graphql;
 mod http_server;
 
-use std::{fmt::Debug, time::Instant};
+use std::{fmt::Debug, sync::Arc, time::Instant};
 
 use datasize::DataSize;
 use futures::{future::BoxFuture, join, FutureExt};
 use tokio::{sync::oneshot, task::JoinHandle};
 use tracing::{debug, error, info, warn};
 
+use schemars::schema_for;
+
 use casper_json_rpc::CorsOrigin;
 use casper_types::ProtocolVersion;
 
 use super::Component;
 use crate::{
     components::{
-        rpc_server::rpcs::docs::OPEN_RPC_SCHEMA, ComponentState, InitializedComponent,
-        PortBoundComponent,
+        event_stream_server::SseData,
+        rpc_server::rpcs::docs::{OPEN_RPC_SCHEMA, SPECULATIVE_OPEN_RPC_SCHEMA},
+        ComponentState, InitializedComponent, PortBoundComponent,
     },
     effect::{
         requests::{
-            BlockSynchronizerRequest, ChainspecRawBytesRequest, ConsensusRequest, MetricsRequest,
-            NetworkInfoRequest, ReactorStatusRequest, RestRequest, StorageRequest,
-            UpgradeWatcherRequest,
+            BlockSynchronizerRequest, ChainspecRawBytesRequest, ConsensusRequest,
+            ContractRuntimeRequest, DeployBufferRequest, MetricsRequest, NetworkInfoRequest,
+            ReactorStatusRequest, RestRequest, StorageRequest, UpgradeWatcherRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     reactor::{main_reactor::MainEvent, Finalize},
     types::{ChainspecInfo, StatusFeed},
-    utils::{self, ListeningError},
+    utils::{self, rate_limiter::RateLimiter, ListeningError},
     NodeRng,
 };
 pub use config::Config;
@@ -69,6 +87,8 @@ pub(crate) trait ReactorEventT:
     + From<MetricsRequest>
     + From<ReactorStatusRequest>
     + From<BlockSynchronizerRequest>
+    + From<ContractRuntimeRequest>
+    + From<DeployBufferRequest>
     + Send
 {
 }
@@ -84,6 +104,9 @@ impl<REv> ReactorEventT for REv where
         + From<MetricsRequest>
         + From<ReactorStatusRequest>
         + From<BlockSynchronizerRequest>
+        + From<ContractRuntimeRequest>
+        + From<ContractRuntimeRequest>
+        + From<DeployBufferRequest>
         + Send
         + 'static
 {
@@ -242,6 +265,14 @@ where
                     let schema = OPEN_RPC_SCHEMA.clone();
                     responder.respond(schema).ignore()
                 }
+                Event::RestRequest(RestRequest::SpeculativeRpcSchema { responder }) => {
+                    let schema = SPECULATIVE_OPEN_RPC_SCHEMA.clone();
+                    responder.respond(schema).ignore()
+                }
+                Event::RestRequest(RestRequest::SseDataSchema { responder }) => {
+                    let schema = schema_for!(SseData);
+                    responder.respond(schema).ignore()
+                }
                 Event::GetMetricsResult {
                     text,
                     main_responder,
@@ -289,6 +320,13 @@ where
         let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
 
         let builder = utils::start_listening(&cfg.address)?;
+        let admin_api_token = cfg.enabled_admin_api_token();
+        let rate_limiter = RateLimiter::new(&cfg.rate_limits).map(Arc::new);
+        let graphql_schema = graphql::new_schema(
+            effect_builder,
+            cfg.graphql_max_depth,
+            cfg.graphql_max_complexity,
+        );
 
         let server_join_handle = match cfg.cors_origin.as_str() {
             "" => Some(tokio::spawn(http_server::run(
@@ -297,6 +335,9 @@ where
                 self.api_version,
                 shutdown_receiver,
                 cfg.qps_limit,
+                rate_limiter,
+                graphql_schema,
+                admin_api_token,
             ))),
             "*" => Some(tokio::spawn(http_server::run_with_cors(
                 builder,
@@ -304,6 +345,9 @@ where
                 self.api_version,
                 shutdown_receiver,
                 cfg.qps_limit,
+                rate_limiter,
+                graphql_schema,
+                admin_api_token,
                 CorsOrigin::Any,
             ))),
             _ => Some(tokio::spawn(http_server::run_with_cors(
@@ -312,6 +356,9 @@ where
                 self.api_version,
                 shutdown_receiver,
                 cfg.qps_limit,
+                rate_limiter,
+                graphql_schema,
+                admin_api_token,
                 CorsOrigin::Specified(cfg.cors_origin.clone()),
             ))),
         };
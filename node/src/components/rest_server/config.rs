@@ -1,5 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::utils::{self, rate_limiter::RateLimitConfig};
 
 /// Default binding address for the REST HTTP server.
 ///
@@ -9,6 +12,10 @@ const DEFAULT_ADDRESS: &str = "0.0.0.0:0";
 const DEFAULT_QPS_LIMIT: u64 = 100;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
+/// Default maximum nesting depth of a GraphQL query.
+const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 10;
+/// Default maximum complexity of a GraphQL query.
+const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 1_000;
 
 /// REST HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -26,6 +33,25 @@ pub struct Config {
 
     /// CORS origin.
     pub cors_origin: String,
+
+    /// Per-client rate limiting configuration.  Expensive methods are identified by the first
+    /// segment of the URL path.
+    pub rate_limits: RateLimitConfig,
+
+    /// Maximum nesting depth of a GraphQL query.
+    pub graphql_max_depth: usize,
+
+    /// Maximum complexity of a GraphQL query.  Each field counts as 1, and each field returning a
+    /// list counts as the requested list length (its `first` argument, 10 by default and at most
+    /// 100) times the complexity of its children.
+    pub graphql_max_complexity: usize,
+
+    /// Bearer token required by admin endpoints such as `/deploy-buffer`.  If empty, admin
+    /// endpoints are disabled.
+    ///
+    /// As the token is sent in plain text, admin endpoints are only enabled if `address` is a
+    /// loopback address.
+    pub admin_api_token: String,
 }
 
 impl Config {
@@ -36,6 +62,28 @@ impl Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            rate_limits: RateLimitConfig::default(),
+            graphql_max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            graphql_max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            admin_api_token: String::new(),
+        }
+    }
+
+    /// Returns the admin API token if set and the server is bound to a loopback address, or else
+    /// an empty string, disabling admin endpoints.
+    pub(super) fn enabled_admin_api_token(&self) -> String {
+        if self.admin_api_token.is_empty() {
+            return String::new();
+        }
+        match utils::resolve_address(&self.address) {
+            Ok(address) if address.ip().is_loopback() => self.admin_api_token.clone(),
+            _ => {
+                warn!(
+                    address = %self.address,
+                    "admin API disabled as the REST server is not bound to a loopback address"
+                );
+                String::new()
+            }
         }
     }
 }
@@ -45,3 +93,27 @@ impl Default for Config {
         Config::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_enable_admin_api_on_loopback_address() {
+        let mut config = Config {
+            address: "127.0.0.1:0".to_string(),
+            ..Config::default()
+        };
+        assert!(config.enabled_admin_api_token().is_empty());
+
+        config.admin_api_token = "s3cr3t".to_string();
+        assert_eq!(config.enabled_admin_api_token(), "s3cr3t");
+        config.address = "[::1]:8888".to_string();
+        assert_eq!(config.enabled_admin_api_token(), "s3cr3t");
+
+        config.address = "0.0.0.0:8888".to_string();
+        assert!(config.enabled_admin_api_token().is_empty());
+        config.address = "not an address".to_string();
+        assert!(config.enabled_admin_api_token().is_empty());
+    }
+}
@@ -1,23 +1,28 @@
 use futures::FutureExt;
 use http::Response;
 use hyper::Body;
+use serde::{Deserialize, Serialize};
 use tracing::warn;
 use warp::{
     filters::BoxedFilter,
-    http::StatusCode,
+    http::{header::AUTHORIZATION, StatusCode},
     reject::Rejection,
     reply::{self, Reply},
     Filter,
 };
 
-use casper_types::ProtocolVersion;
+use casper_types::{AsymmetricType, ProtocolVersion, PublicKey, Timestamp};
 
-use super::ReactorEventT;
+use super::{
+    graphql::{GraphQlSchema, GRAPHQL_API_PATH},
+    ReactorEventT,
+};
 use crate::{
+    components::deploy_buffer::PendingDeploy,
     effect::{requests::RestRequest, EffectBuilder},
     reactor::QueueKind,
     rpcs::info::{GetChainspecResult, GetValidatorChangesResult},
-    types::GetStatusResult,
+    types::{DeployHash, GetStatusResult},
 };
 
 /// The status URL path.
@@ -29,12 +34,30 @@ pub const METRICS_API_PATH: &str = "metrics";
 /// The OpenRPC schema URL path.
 pub const JSON_RPC_SCHEMA_API_PATH: &str = "rpc-schema";
 
+/// The speculative execution server's OpenRPC schema URL path.
+pub const SPECULATIVE_JSON_RPC_SCHEMA_API_PATH: &str = "speculative-rpc-schema";
+
+/// The event stream server's JSON schema URL path.
+pub const SSE_DATA_SCHEMA_API_PATH: &str = "events-schema";
+
 /// The validator information URL path.
 pub const VALIDATOR_CHANGES_API_PATH: &str = "validator-changes";
 
 /// The chainspec file URL path.
 pub const CHAINSPEC_API_PATH: &str = "chainspec";
 
+/// The deploy buffer inspection URL path.
+pub const DEPLOY_BUFFER_API_PATH: &str = "deploy-buffer";
+
+/// The maximum size in bytes of a GraphQL request body.
+const MAX_GRAPHQL_REQUEST_BYTES: u64 = 64 * 1024;
+
+/// The default number of deploys listed per page by the deploy buffer inspection endpoint.
+const DEFAULT_DEPLOY_BUFFER_LIMIT: usize = 100;
+
+/// The maximum number of deploys listed per page by the deploy buffer inspection endpoint.
+const MAX_DEPLOY_BUFFER_LIMIT: usize = 1_000;
+
 pub(super) fn create_status_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
@@ -101,6 +124,42 @@ pub(super) fn create_rpc_schema_filter<REv: ReactorEventT>(
         .boxed()
 }
 
+pub(super) fn create_speculative_rpc_schema_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(SPECULATIVE_JSON_RPC_SCHEMA_API_PATH))
+        .and_then(move || {
+            effect_builder
+                .make_request(
+                    |responder| RestRequest::SpeculativeRpcSchema { responder },
+                    QueueKind::Api,
+                )
+                .map(move |open_rpc_schema| {
+                    Ok::<_, Rejection>(reply::json(&open_rpc_schema).into_response())
+                })
+        })
+        .boxed()
+}
+
+pub(super) fn create_sse_data_schema_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(SSE_DATA_SCHEMA_API_PATH))
+        .and_then(move || {
+            effect_builder
+                .make_request(
+                    |responder| RestRequest::SseDataSchema { responder },
+                    QueueKind::Api,
+                )
+                .map(move |sse_data_schema| {
+                    Ok::<_, Rejection>(reply::json(&sse_data_schema).into_response())
+                })
+        })
+        .boxed()
+}
+
 pub(super) fn create_validator_changes_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
@@ -134,3 +193,214 @@ pub(super) fn create_chainspec_filter<REv: ReactorEventT>(
         })
         .boxed()
 }
+
+pub(super) fn create_graphql_filter(schema: GraphQlSchema) -> BoxedFilter<(Response<Body>,)> {
+    warp::post()
+        .and(warp::path(GRAPHQL_API_PATH))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_GRAPHQL_REQUEST_BYTES))
+        .and(warp::body::json())
+        .and_then(move |request: async_graphql::Request| {
+            let schema = schema.clone();
+            async move {
+                let response = schema.execute(request).await;
+                Ok::<_, Rejection>(reply::json(&response).into_response())
+            }
+        })
+        .boxed()
+}
+
+/// Query parameters for the deploy buffer inspection endpoint.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeployBufferQuery {
+    /// Hex-encoded public key of the account to restrict the listed deploys to.
+    account: Option<String>,
+    /// The `expires` of the previous page's `next_cursor`.
+    cursor_expires: Option<Timestamp>,
+    /// The `deploy_hash` of the previous page's `next_cursor`.
+    cursor_deploy_hash: Option<DeployHash>,
+    /// The maximum number of deploys to list, at most 1000.  Defaults to 100.
+    limit: Option<usize>,
+}
+
+/// The position after which the deploy buffer inspection endpoint continues listing.
+#[derive(Serialize)]
+struct DeployBufferCursor {
+    expires: Timestamp,
+    deploy_hash: DeployHash,
+}
+
+/// Result for the deploy buffer inspection endpoint.
+#[derive(Serialize)]
+struct GetPendingDeploysResult {
+    api_version: ProtocolVersion,
+    deploys: Vec<PendingDeploy>,
+    /// The cursor to pass in the next request to continue listing, or absent if there are no
+    /// further deploys.
+    next_cursor: Option<DeployBufferCursor>,
+}
+
+/// Returns the page of at most `limit` deploys following the given cursor.
+///
+/// `pending_deploys` must be ordered by expiry and then deploy hash.
+fn paginate(
+    pending_deploys: Vec<PendingDeploy>,
+    cursor: Option<(Timestamp, DeployHash)>,
+    limit: usize,
+) -> (Vec<PendingDeploy>, Option<DeployBufferCursor>) {
+    let start = match cursor {
+        Some(cursor) => pending_deploys.partition_point(|pending_deploy| {
+            (pending_deploy.expires, pending_deploy.deploy_hash) <= cursor
+        }),
+        None => 0,
+    };
+    let has_more = pending_deploys.len() - start > limit;
+    let page: Vec<_> = pending_deploys
+        .into_iter()
+        .skip(start)
+        .take(limit)
+        .collect();
+    let next_cursor = if has_more {
+        page.last().map(|pending_deploy| DeployBufferCursor {
+            expires: pending_deploy.expires,
+            deploy_hash: pending_deploy.deploy_hash,
+        })
+    } else {
+        None
+    };
+    (page, next_cursor)
+}
+
+/// Returns `true` if the given "Authorization" header value carries the given bearer token.
+fn is_authorized(admin_api_token: &str, maybe_authorization: Option<&str>) -> bool {
+    let provided = match maybe_authorization.and_then(|value| value.strip_prefix("Bearer ")) {
+        Some(provided) => provided.trim(),
+        None => return false,
+    };
+    // `memcmp::eq` compares in constant time, but requires inputs of equal length.
+    provided.len() == admin_api_token.len()
+        && openssl::memcmp::eq(provided.as_bytes(), admin_api_token.as_bytes())
+}
+
+pub(super) fn create_deploy_buffer_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    admin_api_token: String,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(DEPLOY_BUFFER_API_PATH))
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>(AUTHORIZATION.as_str()))
+        .and(warp::query::<DeployBufferQuery>())
+        .and_then(
+            move |maybe_authorization: Option<String>, query: DeployBufferQuery| {
+                let admin_api_token = admin_api_token.clone();
+                async move {
+                    if admin_api_token.is_empty() {
+                        return Ok::<_, Rejection>(
+                            reply::with_status("admin API disabled", StatusCode::NOT_FOUND)
+                                .into_response(),
+                        );
+                    }
+                    if !is_authorized(&admin_api_token, maybe_authorization.as_deref()) {
+                        return Ok(reply::with_status("unauthorized", StatusCode::UNAUTHORIZED)
+                            .into_response());
+                    }
+                    let account = match query.account.as_deref().map(PublicKey::from_hex) {
+                        Some(Ok(account)) => Some(account),
+                        Some(Err(error)) => {
+                            return Ok(reply::with_status(
+                                format!("invalid account: {}", error),
+                                StatusCode::BAD_REQUEST,
+                            )
+                            .into_response());
+                        }
+                        None => None,
+                    };
+                    let cursor = match (query.cursor_expires, query.cursor_deploy_hash) {
+                        (Some(expires), Some(deploy_hash)) => Some((expires, deploy_hash)),
+                        (None, None) => None,
+                        _ => {
+                            return Ok(reply::with_status(
+                                "cursor_expires and cursor_deploy_hash must be given together",
+                                StatusCode::BAD_REQUEST,
+                            )
+                            .into_response());
+                        }
+                    };
+                    let limit = query
+                        .limit
+                        .unwrap_or(DEFAULT_DEPLOY_BUFFER_LIMIT)
+                        .clamp(1, MAX_DEPLOY_BUFFER_LIMIT);
+                    let pending_deploys = effect_builder.get_pending_deploys(account).await;
+                    let (deploys, next_cursor) = paginate(pending_deploys, cursor, limit);
+                    let result = GetPendingDeploysResult {
+                        api_version,
+                        deploys,
+                        next_cursor,
+                    };
+                    Ok(reply::json(&result).into_response())
+                }
+            },
+        )
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{components::deploy_buffer::PendingDeployState, types::Deploy};
+
+    #[test]
+    fn should_check_bearer_token() {
+        let token = "s3cr3t";
+        assert!(is_authorized(token, Some("Bearer s3cr3t")));
+        assert!(!is_authorized(token, Some("Bearer s3cr3")));
+        assert!(!is_authorized(token, Some("Bearer s3cr3t!")));
+        assert!(!is_authorized(token, Some("Basic s3cr3t")));
+        assert!(!is_authorized(token, Some("s3cr3t")));
+        assert!(!is_authorized(token, None));
+    }
+
+    #[test]
+    fn should_paginate_pending_deploys() {
+        let mut rng = crate::new_rng();
+        let mut pending_deploys: Vec<_> = (0..5)
+            .map(|_| {
+                let deploy = Deploy::random_valid_native_transfer(&mut rng);
+                PendingDeploy::new(
+                    *deploy.hash(),
+                    &deploy.footprint().unwrap(),
+                    deploy.approvals().len(),
+                    PendingDeployState::Proposable,
+                )
+            })
+            .collect();
+        pending_deploys
+            .sort_by_key(|pending_deploy| (pending_deploy.expires, pending_deploy.deploy_hash));
+        let deploy_hashes: Vec<_> = pending_deploys
+            .iter()
+            .map(|pending_deploy| pending_deploy.deploy_hash)
+            .collect();
+
+        let mut listed = vec![];
+        let mut cursor = None;
+        loop {
+            let (page, next_cursor) = paginate(pending_deploys.clone(), cursor, 2);
+            assert!(page.len() <= 2);
+            listed.extend(page.iter().map(|pending_deploy| pending_deploy.deploy_hash));
+            match next_cursor {
+                Some(next_cursor) => {
+                    cursor = Some((next_cursor.expires, next_cursor.deploy_hash));
+                }
+                None => break,
+            }
+        }
+        assert_eq!(listed, deploy_hashes);
+
+        let (page, next_cursor) = paginate(pending_deploys, None, 5);
+        assert_eq!(page.len(), 5);
+        assert!(next_cursor.is_none());
+    }
+}
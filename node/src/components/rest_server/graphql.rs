@@ -0,0 +1,799 @@
+//! GraphQL read API.
+//!
+//! The schema lets clients traverse `Block -> deploys -> execution results -> transfers` and
+//! `Account -> named keys -> stored values` in a single request.  Blocks, deploys and transfers are
+//! read from storage, and stored values by querying global state.
+//!
+//! The depth and complexity of each query are limited via the REST server config.  Every field
+//! counts as 1 towards the complexity, except for lists of objects which are costed at the
+//! requested list length times the complexity of their elements.  Lists take optional `first` and
+//! `offset` arguments; `first` defaults to `DEFAULT_LIST_LENGTH` and is capped at
+//! `MAX_LIST_LENGTH`, so the complexity of a query bounds the number of elements it can return.
+
+use std::{convert::TryFrom, sync::Arc};
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Error, Json, Object, Result, Schema, SimpleObject,
+};
+use async_trait::async_trait;
+
+use casper_execution_engine::core::engine_state::{self, QueryRequest, QueryResult};
+use casper_hashing::Digest;
+use casper_types::{
+    account::{Account, AccountHash},
+    AsymmetricType, ExecutionEffect, ExecutionResult, Key, PublicKey, Transfer,
+};
+
+use super::ReactorEventT;
+use crate::{
+    effect::EffectBuilder,
+    rpcs::chain::BlockIdentifier,
+    types::{
+        json_compatibility::StoredValue as JsonStoredValue, Block, BlockHash, BlockWithMetadata,
+        Deploy, DeployHash, DeployMetadataExt, JsonEraEnd,
+    },
+};
+
+/// The GraphQL URL path.
+pub const GRAPHQL_API_PATH: &str = "graphql";
+
+/// The number of elements returned by a list field if its `first` argument is not given.
+const DEFAULT_LIST_LENGTH: usize = 10;
+/// The maximum number of elements returned by a list field, regardless of its `first` argument.
+const MAX_LIST_LENGTH: usize = 100;
+
+/// The GraphQL schema served by the REST server.
+pub(super) type GraphQlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Creates the GraphQL schema, resolving queries via the given effect builder.
+pub(super) fn new_schema<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    max_depth: usize,
+    max_complexity: usize,
+) -> GraphQlSchema {
+    build_schema(Arc::new(effect_builder), max_depth, max_complexity)
+}
+
+fn build_schema(
+    data_source: Arc<dyn DataSource>,
+    max_depth: usize,
+    max_complexity: usize,
+) -> GraphQlSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(data_source)
+        .limit_depth(max_depth)
+        .limit_complexity(max_complexity)
+        .finish()
+}
+
+/// The source of the data served via GraphQL.
+///
+/// The schema's types cannot be generic over the reactor event, so they access the reactor via
+/// this trait object.
+#[async_trait]
+trait DataSource: Send + Sync {
+    /// Returns the identified block, or the highest complete block if `None`.
+    async fn block(&self, block_identifier: Option<BlockIdentifier>) -> Option<BlockWithMetadata>;
+
+    async fn deploy(&self, deploy_hash: DeployHash) -> Option<(Deploy, DeployMetadataExt)>;
+
+    async fn block_transfers(&self, block_hash: BlockHash) -> Option<Vec<Transfer>>;
+
+    async fn query(
+        &self,
+        state_root_hash: Digest,
+        base_key: Key,
+        path: Vec<String>,
+    ) -> Result<QueryResult, engine_state::Error>;
+}
+
+#[async_trait]
+impl<REv: ReactorEventT> DataSource for EffectBuilder<REv> {
+    async fn block(&self, block_identifier: Option<BlockIdentifier>) -> Option<BlockWithMetadata> {
+        match block_identifier {
+            Some(BlockIdentifier::Hash(block_hash)) => {
+                self.get_block_with_metadata_from_storage(block_hash, true)
+                    .await
+            }
+            Some(BlockIdentifier::Height(height)) => {
+                self.get_block_at_height_with_metadata_from_storage(height, true)
+                    .await
+            }
+            None => {
+                self.get_highest_block_with_metadata_from_storage(true)
+                    .await
+            }
+        }
+    }
+
+    async fn deploy(&self, deploy_hash: DeployHash) -> Option<(Deploy, DeployMetadataExt)> {
+        self.get_deploy_and_metadata_from_storage(deploy_hash)
+            .await
+            .map(|(deploy_with_finalized_approvals, metadata_ext)| {
+                (
+                    deploy_with_finalized_approvals.discard_finalized_approvals(),
+                    metadata_ext,
+                )
+            })
+    }
+
+    async fn block_transfers(&self, block_hash: BlockHash) -> Option<Vec<Transfer>> {
+        self.get_block_transfers_from_storage(block_hash).await
+    }
+
+    async fn query(
+        &self,
+        state_root_hash: Digest,
+        base_key: Key,
+        path: Vec<String>,
+    ) -> Result<QueryResult, engine_state::Error> {
+        self.query_global_state(QueryRequest::new(state_root_hash, base_key, path))
+            .await
+    }
+}
+
+fn data_source<'a>(ctx: &Context<'a>) -> &'a dyn DataSource {
+    ctx.data_unchecked::<Arc<dyn DataSource>>().as_ref()
+}
+
+fn parse_digest(hex: &str, name: &str) -> Result<Digest> {
+    Digest::from_hex(hex).map_err(|error| Error::new(format!("invalid {}: {}", name, error)))
+}
+
+/// Runs a global state query, returning `None` if there is no value under the given key and path.
+async fn query(
+    ctx: &Context<'_>,
+    state_root_hash: Digest,
+    base_key: Key,
+    path: Vec<String>,
+) -> Result<Option<casper_types::StoredValue>> {
+    match data_source(ctx)
+        .query(state_root_hash, base_key, path)
+        .await?
+    {
+        QueryResult::Success { value, .. } => Ok(Some(*value)),
+        QueryResult::ValueNotFound(_) => Ok(None),
+        QueryResult::RootNotFound => Err(Error::new("state root hash not known")),
+        query_result => Err(Error::new(format!("query failed: {:?}", query_result))),
+    }
+}
+
+/// The root of all queries.
+pub(super) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Returns the block with the given hash or height, or the highest complete block if neither
+    /// is given.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        hash: Option<String>,
+        height: Option<u64>,
+    ) -> Result<Option<BlockObject>> {
+        let block_identifier = match (hash, height) {
+            (Some(hash), None) => Some(BlockIdentifier::Hash(BlockHash::new(parse_digest(
+                &hash,
+                "block hash",
+            )?))),
+            (None, Some(height)) => Some(BlockIdentifier::Height(height)),
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                return Err(Error::new("only one of hash or height may be given"))
+            }
+        };
+        Ok(data_source(ctx)
+            .block(block_identifier)
+            .await
+            .map(|block_with_metadata| BlockObject(block_with_metadata.block)))
+    }
+
+    /// Returns the deploy with the given hash.
+    async fn deploy(&self, ctx: &Context<'_>, hash: String) -> Result<Option<DeployObject>> {
+        let deploy_hash = DeployHash::new(parse_digest(&hash, "deploy hash")?);
+        Ok(data_source(ctx)
+            .deploy(deploy_hash)
+            .await
+            .map(|(deploy, metadata_ext)| DeployObject {
+                deploy,
+                metadata_ext,
+            }))
+    }
+
+    /// Returns the account with the given public key or account hash, as of the given state root
+    /// hash or else the state root hash of the highest complete block.
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        public_key: Option<String>,
+        account_hash: Option<String>,
+        state_root_hash: Option<String>,
+    ) -> Result<Option<AccountObject>> {
+        let account_hash = match (public_key, account_hash) {
+            (Some(public_key), None) => PublicKey::from_hex(public_key)
+                .map_err(|error| Error::new(format!("invalid public key: {}", error)))?
+                .to_account_hash(),
+            (None, Some(account_hash)) => AccountHash::from_formatted_str(&account_hash)
+                .map_err(|error| Error::new(format!("invalid account hash: {}", error)))?,
+            _ => {
+                return Err(Error::new(
+                    "exactly one of public key or account hash must be given",
+                ))
+            }
+        };
+        let state_root_hash = state_root_hash_or_highest(ctx, state_root_hash).await?;
+        match query(ctx, state_root_hash, Key::Account(account_hash), vec![]).await? {
+            Some(casper_types::StoredValue::Account(account)) => Ok(Some(AccountObject {
+                account,
+                state_root_hash,
+            })),
+            Some(_) | None => Ok(None),
+        }
+    }
+
+    /// Returns the value stored under the given key and path, as of the given state root hash or
+    /// else the state root hash of the highest complete block.
+    async fn stored_value(
+        &self,
+        ctx: &Context<'_>,
+        key: String,
+        path: Option<Vec<String>>,
+        state_root_hash: Option<String>,
+    ) -> Result<Option<StoredValueObject>> {
+        let key = Key::from_formatted_str(&key)
+            .map_err(|error| Error::new(format!("invalid key: {}", error)))?;
+        let state_root_hash = state_root_hash_or_highest(ctx, state_root_hash).await?;
+        query(ctx, state_root_hash, key, path.unwrap_or_default())
+            .await?
+            .map(StoredValueObject::new)
+            .transpose()
+    }
+}
+
+async fn state_root_hash_or_highest(
+    ctx: &Context<'_>,
+    maybe_state_root_hash: Option<String>,
+) -> Result<Digest> {
+    match maybe_state_root_hash {
+        Some(state_root_hash) => parse_digest(&state_root_hash, "state root hash"),
+        None => data_source(ctx)
+            .block(None)
+            .await
+            .map(|block_with_metadata| *block_with_metadata.block.state_root_hash())
+            .ok_or_else(|| Error::new("no complete blocks available")),
+    }
+}
+
+/// A block.
+pub(super) struct BlockObject(Block);
+
+#[Object(name = "Block")]
+impl BlockObject {
+    async fn hash(&self) -> String {
+        base16::encode_lower(self.0.hash().inner())
+    }
+
+    async fn height(&self) -> u64 {
+        self.0.height()
+    }
+
+    async fn era_id(&self) -> u64 {
+        self.0.header().era_id().value()
+    }
+
+    async fn timestamp(&self) -> String {
+        self.0.timestamp().to_string()
+    }
+
+    async fn protocol_version(&self) -> String {
+        self.0.protocol_version().to_string()
+    }
+
+    async fn state_root_hash(&self) -> String {
+        base16::encode_lower(self.0.state_root_hash())
+    }
+
+    async fn proposer(&self) -> String {
+        self.0.body().proposer().to_hex()
+    }
+
+    /// The era end, only present for switch blocks.
+    async fn era_end(&self) -> Option<Json<JsonEraEnd>> {
+        self.0
+            .header()
+            .era_end()
+            .map(|era_end| Json(JsonEraEnd::from(era_end.clone())))
+    }
+
+    /// The parent block, unless this is the genesis block or the parent is not available.
+    async fn parent(&self, ctx: &Context<'_>) -> Option<BlockObject> {
+        let parent_hash = *self.0.parent()?;
+        data_source(ctx)
+            .block(Some(BlockIdentifier::Hash(parent_hash)))
+            .await
+            .map(|block_with_metadata| BlockObject(block_with_metadata.block))
+    }
+
+    /// The non-transfer deploys included in the block.
+    #[graphql(complexity = "list_length(first) * child_complexity")]
+    async fn deploys(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "DEFAULT_LIST_LENGTH")] first: usize,
+        #[graphql(default)] offset: usize,
+    ) -> Vec<DeployObject> {
+        deploy_objects(
+            ctx,
+            page(self.0.deploy_hashes().iter().copied(), first, offset),
+        )
+        .await
+    }
+
+    /// The transfer deploys included in the block.
+    #[graphql(complexity = "list_length(first) * child_complexity")]
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "DEFAULT_LIST_LENGTH")] first: usize,
+        #[graphql(default)] offset: usize,
+    ) -> Vec<DeployObject> {
+        deploy_objects(
+            ctx,
+            page(self.0.transfer_hashes().iter().copied(), first, offset),
+        )
+        .await
+    }
+}
+
+/// Returns the number of elements to return for a list field given its `first` argument.
+fn list_length(first: usize) -> usize {
+    first.min(MAX_LIST_LENGTH)
+}
+
+/// Skips `offset` items and returns at most `list_length(first)` of the remainder.
+fn page<I: IntoIterator>(items: I, first: usize, offset: usize) -> Vec<I::Item> {
+    items
+        .into_iter()
+        .skip(offset)
+        .take(list_length(first))
+        .collect()
+}
+
+async fn deploy_objects(ctx: &Context<'_>, deploy_hashes: Vec<DeployHash>) -> Vec<DeployObject> {
+    let mut deploys = Vec::with_capacity(deploy_hashes.len());
+    for deploy_hash in deploy_hashes {
+        if let Some((deploy, metadata_ext)) = data_source(ctx).deploy(deploy_hash).await {
+            deploys.push(DeployObject {
+                deploy,
+                metadata_ext,
+            });
+        }
+    }
+    deploys
+}
+
+/// A deploy.
+pub(super) struct DeployObject {
+    deploy: Deploy,
+    metadata_ext: DeployMetadataExt,
+}
+
+#[Object(name = "Deploy")]
+impl DeployObject {
+    async fn hash(&self) -> String {
+        base16::encode_lower(self.deploy.hash().inner())
+    }
+
+    async fn account(&self) -> String {
+        self.deploy.header().account().to_hex()
+    }
+
+    async fn timestamp(&self) -> String {
+        self.deploy.header().timestamp().to_string()
+    }
+
+    async fn ttl(&self) -> String {
+        self.deploy.header().ttl().to_string()
+    }
+
+    async fn gas_price(&self) -> u64 {
+        self.deploy.header().gas_price()
+    }
+
+    async fn chain_name(&self) -> &str {
+        self.deploy.header().chain_name()
+    }
+
+    async fn payment(&self) -> Json<serde_json::Value> {
+        Json(serde_json::to_value(self.deploy.payment()).unwrap_or_default())
+    }
+
+    async fn session(&self) -> Json<serde_json::Value> {
+        Json(serde_json::to_value(self.deploy.session()).unwrap_or_default())
+    }
+
+    /// The results of executing the deploy, one per block in which it was executed.
+    #[graphql(complexity = "list_length(first) * child_complexity")]
+    async fn execution_results(
+        &self,
+        #[graphql(default_with = "DEFAULT_LIST_LENGTH")] first: usize,
+        #[graphql(default)] offset: usize,
+    ) -> Vec<ExecutionResultObject> {
+        match &self.metadata_ext {
+            DeployMetadataExt::Metadata(metadata) => page(
+                metadata
+                    .execution_results
+                    .iter()
+                    .map(|(block_hash, execution_result)| ExecutionResultObject {
+                        deploy_hash: *self.deploy.hash(),
+                        block_hash: *block_hash,
+                        execution_result: execution_result.clone(),
+                    }),
+                first,
+                offset,
+            ),
+            DeployMetadataExt::BlockInfo(_) | DeployMetadataExt::Empty => vec![],
+        }
+    }
+}
+
+/// The result of executing a deploy in a given block.
+pub(super) struct ExecutionResultObject {
+    deploy_hash: DeployHash,
+    block_hash: BlockHash,
+    execution_result: ExecutionResult,
+}
+
+#[Object(name = "ExecutionResult")]
+impl ExecutionResultObject {
+    async fn block_hash(&self) -> String {
+        base16::encode_lower(self.block_hash.inner())
+    }
+
+    async fn block(&self, ctx: &Context<'_>) -> Option<BlockObject> {
+        data_source(ctx)
+            .block(Some(BlockIdentifier::Hash(self.block_hash)))
+            .await
+            .map(|block_with_metadata| BlockObject(block_with_metadata.block))
+    }
+
+    async fn success(&self) -> bool {
+        matches!(self.execution_result, ExecutionResult::Success { .. })
+    }
+
+    async fn error_message(&self) -> Option<&str> {
+        match &self.execution_result {
+            ExecutionResult::Failure { error_message, .. } => Some(error_message),
+            ExecutionResult::Success { .. } => None,
+        }
+    }
+
+    async fn cost(&self) -> String {
+        match &self.execution_result {
+            ExecutionResult::Failure { cost, .. } | ExecutionResult::Success { cost, .. } => {
+                cost.to_string()
+            }
+        }
+    }
+
+    async fn effect(&self) -> Json<ExecutionEffect> {
+        match &self.execution_result {
+            ExecutionResult::Failure { effect, .. } | ExecutionResult::Success { effect, .. } => {
+                Json(effect.clone())
+            }
+        }
+    }
+
+    /// The transfers performed while executing the deploy.
+    #[graphql(complexity = "list_length(first) * child_complexity")]
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "DEFAULT_LIST_LENGTH")] first: usize,
+        #[graphql(default)] offset: usize,
+    ) -> Vec<TransferObject> {
+        let transfers = data_source(ctx)
+            .block_transfers(self.block_hash)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|transfer| transfer.deploy_hash.value() == self.deploy_hash.inner().value())
+            .map(Into::into);
+        page(transfers, first, offset)
+    }
+}
+
+/// A transfer of motes between purses.
+#[derive(SimpleObject)]
+#[graphql(name = "Transfer")]
+pub(super) struct TransferObject {
+    deploy_hash: String,
+    from: String,
+    to: Option<String>,
+    source: String,
+    target: String,
+    amount: String,
+    gas: String,
+    id: Option<u64>,
+}
+
+impl From<Transfer> for TransferObject {
+    fn from(transfer: Transfer) -> Self {
+        TransferObject {
+            deploy_hash: base16::encode_lower(&transfer.deploy_hash.value()),
+            from: transfer.from.to_formatted_string(),
+            to: transfer
+                .to
+                .map(|account_hash| account_hash.to_formatted_string()),
+            source: transfer.source.to_formatted_string(),
+            target: transfer.target.to_formatted_string(),
+            amount: transfer.amount.to_string(),
+            gas: transfer.gas.to_string(),
+            id: transfer.id,
+        }
+    }
+}
+
+/// An account, as of a given state root hash.
+pub(super) struct AccountObject {
+    account: Account,
+    state_root_hash: Digest,
+}
+
+#[Object(name = "Account")]
+impl AccountObject {
+    async fn account_hash(&self) -> String {
+        self.account.account_hash().to_formatted_string()
+    }
+
+    async fn main_purse(&self) -> String {
+        self.account.main_purse().to_formatted_string()
+    }
+
+    async fn state_root_hash(&self) -> String {
+        base16::encode_lower(&self.state_root_hash)
+    }
+
+    /// The account's named keys, in lexicographical order of their names.
+    #[graphql(complexity = "list_length(first) * child_complexity")]
+    async fn named_keys(
+        &self,
+        #[graphql(default_with = "DEFAULT_LIST_LENGTH")] first: usize,
+        #[graphql(default)] offset: usize,
+    ) -> Vec<NamedKeyObject> {
+        let named_keys = self
+            .account
+            .named_keys()
+            .iter()
+            .map(|(name, key)| NamedKeyObject {
+                name: name.clone(),
+                key: *key,
+                state_root_hash: self.state_root_hash,
+            });
+        page(named_keys, first, offset)
+    }
+}
+
+/// A named key of an account.
+pub(super) struct NamedKeyObject {
+    name: String,
+    key: Key,
+    state_root_hash: Digest,
+}
+
+#[Object(name = "NamedKey")]
+impl NamedKeyObject {
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn key(&self) -> String {
+        self.key.to_formatted_string()
+    }
+
+    /// The value stored under the key, as of the same state root hash as the account.
+    async fn value(&self, ctx: &Context<'_>) -> Result<Option<StoredValueObject>> {
+        query(ctx, self.state_root_hash, self.key, vec![])
+            .await?
+            .map(StoredValueObject::new)
+            .transpose()
+    }
+}
+
+/// A value stored in global state.
+pub(super) struct StoredValueObject {
+    type_name: String,
+    value: JsonStoredValue,
+}
+
+impl StoredValueObject {
+    fn new(stored_value: casper_types::StoredValue) -> Result<Self> {
+        let type_name = stored_value.type_name();
+        let value = JsonStoredValue::try_from(stored_value)
+            .map_err(|error| Error::new(format!("failed to encode stored value: {}", error)))?;
+        Ok(StoredValueObject { type_name, value })
+    }
+}
+
+#[Object(name = "StoredValue")]
+impl StoredValueObject {
+    /// The type of the value, e.g. "Account" or "CLValue".
+    async fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The value, in the same JSON form as returned by the JSON-RPC server.
+    async fn value(&self) -> Json<JsonStoredValue> {
+        Json(self.value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use casper_types::testing::TestRng;
+
+    use super::*;
+    use crate::types::{BlockSignatures, DeployMetadata};
+
+    struct MockDataSource {
+        block: Block,
+        deploys: BTreeMap<DeployHash, Deploy>,
+    }
+
+    #[async_trait]
+    impl DataSource for MockDataSource {
+        async fn block(
+            &self,
+            block_identifier: Option<BlockIdentifier>,
+        ) -> Option<BlockWithMetadata> {
+            let matches = match block_identifier {
+                Some(BlockIdentifier::Hash(block_hash)) => block_hash == *self.block.hash(),
+                Some(BlockIdentifier::Height(height)) => height == self.block.height(),
+                None => true,
+            };
+            matches.then(|| BlockWithMetadata {
+                block: self.block.clone(),
+                block_signatures: BlockSignatures::new(
+                    *self.block.hash(),
+                    self.block.header().era_id(),
+                ),
+            })
+        }
+
+        async fn deploy(&self, deploy_hash: DeployHash) -> Option<(Deploy, DeployMetadataExt)> {
+            let deploy = self.deploys.get(&deploy_hash)?.clone();
+            let execution_results: HashMap<_, _> = [(
+                *self.block.hash(),
+                ExecutionResult::Success {
+                    effect: ExecutionEffect::default(),
+                    transfers: vec![],
+                    cost: 100.into(),
+                },
+            )]
+            .iter()
+            .cloned()
+            .collect();
+            Some((
+                deploy,
+                DeployMetadataExt::Metadata(DeployMetadata { execution_results }),
+            ))
+        }
+
+        async fn block_transfers(&self, _block_hash: BlockHash) -> Option<Vec<Transfer>> {
+            Some(vec![])
+        }
+
+        async fn query(
+            &self,
+            _state_root_hash: Digest,
+            _base_key: Key,
+            _path: Vec<String>,
+        ) -> Result<QueryResult, engine_state::Error> {
+            Ok(QueryResult::RootNotFound)
+        }
+    }
+
+    fn new_test_schema(
+        rng: &mut TestRng,
+        max_depth: usize,
+        max_complexity: usize,
+    ) -> (GraphQlSchema, Block) {
+        let deploys: Vec<_> = (0..3).map(|_| Deploy::random(rng)).collect();
+        let block = Block::random_with_deploys(rng, deploys.iter());
+        let data_source = MockDataSource {
+            block: block.clone(),
+            deploys: deploys
+                .into_iter()
+                .map(|deploy| (*deploy.hash(), deploy))
+                .collect(),
+        };
+        (
+            build_schema(Arc::new(data_source), max_depth, max_complexity),
+            block,
+        )
+    }
+
+    #[tokio::test]
+    async fn should_resolve_block_deploys_and_execution_results() {
+        let rng = &mut TestRng::new();
+        let (schema, block) = new_test_schema(rng, 10, 1_000);
+
+        let query = format!(
+            "{{ block(height: {}) {{ hash deploys {{ hash {} }} }} }}",
+            block.height(),
+            "executionResults { success cost }"
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let expected_hash = base16::encode_lower(block.hash().inner());
+        assert_eq!(data["block"]["hash"], expected_hash.as_str());
+        let deploys = data["block"]["deploys"].as_array().unwrap();
+        assert_eq!(deploys.len(), block.deploy_hashes().len());
+        for deploy in deploys {
+            assert_eq!(deploy["executionResults"][0]["success"], true);
+            assert_eq!(deploy["executionResults"][0]["cost"], "100");
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_null_for_unknown_block() {
+        let rng = &mut TestRng::new();
+        let (schema, block) = new_test_schema(rng, 10, 1_000);
+
+        let query = format!("{{ block(height: {}) {{ hash }} }}", block.height() + 1);
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert!(response.data.into_json().unwrap()["block"].is_null());
+    }
+
+    #[tokio::test]
+    async fn should_reject_query_exceeding_limits() {
+        let rng = &mut TestRng::new();
+
+        let deep_query = "{ block { parent { parent { parent { hash } } } } }";
+        let (schema, _) = new_test_schema(rng, 3, 1_000);
+        assert!(!schema.execute(deep_query).await.errors.is_empty());
+        let (schema, _) = new_test_schema(rng, 5, 1_000);
+        assert!(schema.execute(deep_query).await.errors.is_empty());
+
+        let complex_query = "{ block { deploys { executionResults { transfers { amount } } } } }";
+        let (schema, _) = new_test_schema(rng, 10, 100);
+        assert!(!schema.execute(complex_query).await.errors.is_empty());
+        let (schema, _) = new_test_schema(rng, 10, 10_000);
+        assert!(schema.execute(complex_query).await.errors.is_empty());
+
+        // Requesting shorter lists reduces the complexity accordingly.
+        let short_query = "{ block { deploys(first: 2) { executionResults(first: 1) { \
+            transfers(first: 1) { amount } } } } }";
+        let (schema, _) = new_test_schema(rng, 10, 100);
+        assert!(schema.execute(short_query).await.errors.is_empty());
+
+        // The length of a list, and hence its complexity, is capped.
+        let long_query = "{ block { deploys(first: 1000000) { hash } } }";
+        let (schema, _) = new_test_schema(rng, 10, MAX_LIST_LENGTH + 1);
+        assert!(schema.execute(long_query).await.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_page_lists() {
+        let rng = &mut TestRng::new();
+        let (schema, block) = new_test_schema(rng, 10, 1_000);
+
+        let query = format!(
+            "{{ block(height: {}) {{ deploys(first: 1, offset: 1) {{ hash }} }} }}",
+            block.height()
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let deploys = data["block"]["deploys"].as_array().unwrap();
+        assert_eq!(deploys.len(), 1);
+        let expected_hash = base16::encode_lower(block.deploy_hashes()[1].inner());
+        assert_eq!(deploys[0]["hash"], expected_hash.as_str());
+    }
+}
@@ -1,47 +1,108 @@
-use std::{convert::Infallible, time::Duration};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
 
-use futures::{future, TryFutureExt};
-use hyper::server::{conn::AddrIncoming, Builder};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, TryFutureExt,
+};
+use http::{Request, Response, StatusCode};
+use hyper::{
+    server::{
+        conn::{AddrIncoming, AddrStream},
+        Builder,
+    },
+    Body,
+};
+use serde_json::json;
 use tokio::sync::oneshot;
-use tower::builder::ServiceBuilder;
-use tracing::{info, warn};
-use warp::Filter;
+use tower::{builder::ServiceBuilder, Service};
+use tracing::{debug, info, warn};
+use warp::{Filter, Reply};
 
 use casper_json_rpc::CorsOrigin;
 use casper_types::ProtocolVersion;
 
-use super::{filters, ReactorEventT};
-use crate::effect::EffectBuilder;
+use super::{filters, graphql::GraphQlSchema, ReactorEventT};
+use crate::{effect::EffectBuilder, utils::rate_limiter::RateLimiter};
+
+/// Handles the given request via the given service, unless the requesting client has exceeded its
+/// rate limit for the path, in which case a "429 Too Many Requests" response is returned.
+///
+/// The first segment of the URL path is used as the method name when checking the rate limit.
+fn call_rate_limited<S>(
+    service: &S,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+    remote_addr: SocketAddr,
+    request: Request<Body>,
+) -> BoxFuture<'static, Result<Response<Body>, Infallible>>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = Infallible> + Clone,
+    S::Future: Send + 'static,
+{
+    if let Some(rate_limiter) = rate_limiter {
+        let client_id = rate_limiter.client_id(remote_addr, request.headers());
+        let path = request.uri().path().trim_start_matches('/');
+        let method = path.split('/').next().unwrap_or_default();
+        if let Err(error) = rate_limiter.check(&client_id, method) {
+            debug!(%client_id, %error, path, "rejecting REST request");
+            let response = warp::reply::with_status(
+                warp::reply::json(&json!({ "message": error.to_string() })),
+                StatusCode::TOO_MANY_REQUESTS,
+            )
+            .into_response();
+            return future::ok(response).boxed();
+        }
+    }
+    service.clone().call(request).boxed()
+}
 
 /// Run the REST HTTP server.
 ///
 /// A message received on `shutdown_receiver` will cause the server to exit cleanly.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     shutdown_receiver: oneshot::Receiver<()>,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    graphql_schema: GraphQlSchema,
+    admin_api_token: String,
 ) {
     // REST filters.
     let rest_status = filters::create_status_filter(effect_builder, api_version);
     let rest_metrics = filters::create_metrics_filter(effect_builder);
     let rest_open_rpc = filters::create_rpc_schema_filter(effect_builder);
+    let rest_speculative_open_rpc = filters::create_speculative_rpc_schema_filter(effect_builder);
+    let rest_sse_data_schema = filters::create_sse_data_schema_filter(effect_builder);
     let rest_validator_changes =
         filters::create_validator_changes_filter(effect_builder, api_version);
     let rest_chainspec_filter = filters::create_chainspec_filter(effect_builder, api_version);
+    let rest_graphql = filters::create_graphql_filter(graphql_schema);
+    let rest_deploy_buffer =
+        filters::create_deploy_buffer_filter(effect_builder, api_version, admin_api_token);
 
     let service = warp::service(
         rest_status
             .or(rest_metrics)
             .or(rest_open_rpc)
+            .or(rest_speculative_open_rpc)
+            .or(rest_sse_data_schema)
             .or(rest_validator_changes)
-            .or(rest_chainspec_filter),
+            .or(rest_chainspec_filter)
+            .or(rest_graphql)
+            .or(rest_deploy_buffer),
     );
 
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
-    let make_svc =
-        hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
+    let make_svc = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let remote_addr = connection.remote_addr();
+        let service = service.clone();
+        let rate_limiter = rate_limiter.clone();
+        future::ok::<_, Infallible>(hyper::service::service_fn(move |request| {
+            call_rate_limited(&service, &rate_limiter, remote_addr, request)
+        }))
+    });
 
     let rate_limited_service = ServiceBuilder::new()
         .rate_limit(qps_limit, Duration::from_secs(1))
@@ -64,28 +125,41 @@ pub(super) async fn run<REv: ReactorEventT>(
 /// Run the REST HTTP server with CORS enabled.
 ///
 /// A message received on `shutdown_receiver` will cause the server to exit cleanly.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run_with_cors<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     shutdown_receiver: oneshot::Receiver<()>,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    graphql_schema: GraphQlSchema,
+    admin_api_token: String,
     cors_origin: CorsOrigin,
 ) {
     // REST filters.
     let rest_status = filters::create_status_filter(effect_builder, api_version);
     let rest_metrics = filters::create_metrics_filter(effect_builder);
     let rest_open_rpc = filters::create_rpc_schema_filter(effect_builder);
+    let rest_speculative_open_rpc = filters::create_speculative_rpc_schema_filter(effect_builder);
+    let rest_sse_data_schema = filters::create_sse_data_schema_filter(effect_builder);
     let rest_validator_changes =
         filters::create_validator_changes_filter(effect_builder, api_version);
     let rest_chainspec_filter = filters::create_chainspec_filter(effect_builder, api_version);
+    let rest_graphql = filters::create_graphql_filter(graphql_schema);
+    let rest_deploy_buffer =
+        filters::create_deploy_buffer_filter(effect_builder, api_version, admin_api_token);
 
     let service = warp::service(
         rest_status
             .or(rest_metrics)
             .or(rest_open_rpc)
+            .or(rest_speculative_open_rpc)
+            .or(rest_sse_data_schema)
             .or(rest_validator_changes)
             .or(rest_chainspec_filter)
+            .or(rest_graphql)
+            .or(rest_deploy_buffer)
             .with(match cors_origin {
                 CorsOrigin::Any => warp::cors().allow_any_origin(),
                 CorsOrigin::Specified(origin) => warp::cors().allow_origin(origin.as_str()),
@@ -93,8 +167,14 @@ pub(super) async fn run_with_cors<REv: ReactorEventT>(
     );
 
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
-    let make_svc =
-        hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(service.clone()));
+    let make_svc = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let remote_addr = connection.remote_addr();
+        let service = service.clone();
+        let rate_limiter = rate_limiter.clone();
+        future::ok::<_, Infallible>(hyper::service::service_fn(move |request| {
+            call_rate_limited(&service, &rate_limiter, remote_addr, request)
+        }))
+    });
 
     let rate_limited_service = ServiceBuilder::new()
         .rate_limit(qps_limit, Duration::from_secs(1))
@@ -17,11 +17,13 @@ mod http_server;
 pub mod rpcs;
 mod speculative_exec_config;
 mod speculative_exec_server;
+mod websocket_server;
 
-use std::{fmt::Debug, time::Instant};
+use std::{fmt::Debug, sync::Arc, time::Instant};
 
 use datasize::DataSize;
 use futures::join;
+use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 
 use casper_execution_engine::core::engine_state::{
@@ -34,20 +36,21 @@ use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
 use super::Component;
 use crate::{
     components::{
-        contract_runtime::EraValidatorsRequest, ComponentState, InitializedComponent,
-        PortBoundComponent,
+        contract_runtime::EraValidatorsRequest, event_stream_server::SseData, ComponentState,
+        InitializedComponent, PortBoundComponent,
     },
     effect::{
         requests::{
             AcceptDeployRequest, BlockSynchronizerRequest, ChainspecRawBytesRequest,
-            ConsensusRequest, ContractRuntimeRequest, MetricsRequest, NetworkInfoRequest,
-            ReactorStatusRequest, RpcRequest, StorageRequest, UpgradeWatcherRequest,
+            ConsensusRequest, ContractRuntimeRequest, DeployBufferRequest, MetricsRequest,
+            NetworkInfoRequest, ReactorStatusRequest, RpcRequest, StorageRequest,
+            UpgradeWatcherRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
     reactor::main_reactor::MainEvent,
     types::{ChainspecInfo, StatusFeed},
-    utils::{self, ListeningError},
+    utils::{self, rate_limiter::RateLimiter, ListeningError},
     NodeRng,
 };
 pub use config::Config;
@@ -70,6 +73,7 @@ pub(crate) trait ReactorEventT:
     + From<StorageRequest>
     + From<ReactorStatusRequest>
     + From<BlockSynchronizerRequest>
+    + From<DeployBufferRequest>
     + Send
 {
 }
@@ -87,6 +91,7 @@ impl<REv> ReactorEventT for REv where
         + From<StorageRequest>
         + From<ReactorStatusRequest>
         + From<BlockSynchronizerRequest>
+        + From<DeployBufferRequest>
         + Send
         + 'static
 {
@@ -112,6 +117,9 @@ pub(crate) struct RpcServer {
     /// no fields and no methods because all that is needed to operate it is the
     /// spawned tokio task, so a unit struct will suffice here.
     speculative_exec: Option<()>,
+    /// Sender via which each WebSocket connection subscribes to the event-stream data.
+    #[data_size(skip)]
+    sse_data_broadcaster: broadcast::Sender<SseData>,
 }
 
 impl RpcServer {
@@ -121,6 +129,7 @@ impl RpcServer {
         api_version: ProtocolVersion,
         network_name: String,
         node_startup_instant: Instant,
+        sse_data_broadcaster: broadcast::Sender<SseData>,
     ) -> Self {
         RpcServer {
             state: ComponentState::Uninitialized,
@@ -130,6 +139,7 @@ impl RpcServer {
             network_name,
             node_startup_instant,
             speculative_exec: None,
+            sse_data_broadcaster,
         }
     }
 }
@@ -452,6 +462,7 @@ where
                 effect_builder,
                 self.api_version,
                 cfg.qps_limit,
+                RateLimiter::new(&cfg.rate_limits).map(Arc::new),
                 cfg.max_body_bytes,
                 cfg.cors_origin.clone(),
             ));
@@ -462,13 +473,22 @@ where
 
         let cfg = &self.config;
         let builder = utils::start_listening(&cfg.address)?;
+        let sse_data_broadcaster = cfg
+            .enable_websocket
+            .then(|| self.sse_data_broadcaster.clone());
         tokio::spawn(http_server::run(
             builder,
             effect_builder,
             self.api_version,
             cfg.qps_limit,
+            RateLimiter::new(&cfg.rate_limits).map(Arc::new),
             cfg.max_body_bytes,
+            cfg.max_batch_size,
             cfg.cors_origin.clone(),
+            sse_data_broadcaster,
+            cfg.max_websocket_subscriptions,
+            cfg.max_websocket_connections,
+            cfg.max_websocket_concurrent_requests,
         ));
 
         Ok(Effects::new())
@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::rate_limiter::RateLimitConfig;
+
 /// Default binding address for the JSON-RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -10,8 +12,16 @@ const DEFAULT_QPS_LIMIT: u64 = 100;
 /// Default max body bytes.  This is 2.5MB which should be able to accommodate the largest valid
 /// JSON-RPC request, which would be an "account_put_deploy".
 const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
+/// Default max number of requests in a single JSON-RPC batch request.
+const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
+/// Default max number of subscriptions per WebSocket connection.
+const DEFAULT_MAX_WEBSOCKET_SUBSCRIPTIONS: u32 = 10;
+/// Default max number of concurrently open WebSocket connections.
+const DEFAULT_MAX_WEBSOCKET_CONNECTIONS: u32 = 100;
+/// Default max number of requests handled concurrently per WebSocket connection.
+const DEFAULT_MAX_WEBSOCKET_CONCURRENT_REQUESTS: u32 = 10;
 
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -22,12 +32,26 @@ pub struct Config {
     pub enable_server: bool,
     /// Address to bind JSON-RPC HTTP server to.
     pub address: String,
-    /// Maximum rate limit in queries per second.
+    /// Maximum rate limit in queries per second.  Each request in a JSON-RPC batch, and each
+    /// request received over a WebSocket, counts separately.
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
     pub max_body_bytes: u32,
+    /// Maximum number of requests to accept in a single JSON-RPC batch request.
+    pub max_batch_size: u32,
     /// CORS origin.
     pub cors_origin: String,
+    /// Per-client rate limiting configuration.
+    pub rate_limits: RateLimitConfig,
+    /// Setting to enable the WebSocket endpoint, via which JSON-RPC requests can be made and
+    /// events subscribed to over a single persistent connection.
+    pub enable_websocket: bool,
+    /// Maximum number of event subscriptions to allow on a single WebSocket connection.
+    pub max_websocket_subscriptions: u32,
+    /// Maximum number of WebSocket connections to allow concurrently.
+    pub max_websocket_connections: u32,
+    /// Maximum number of requests to handle concurrently on a single WebSocket connection.
+    pub max_websocket_concurrent_requests: u32,
 }
 
 impl Config {
@@ -38,7 +62,22 @@ impl Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            rate_limits: RateLimitConfig {
+                expensive_methods: vec![
+                    "state_get_trie".to_string(),
+                    "query_global_state".to_string(),
+                    "state_list_keys".to_string(),
+                    "state_get_balance_history".to_string(),
+                    "chain_get_block_range".to_string(),
+                ],
+                ..RateLimitConfig::default()
+            },
+            enable_websocket: false,
+            max_websocket_subscriptions: DEFAULT_MAX_WEBSOCKET_SUBSCRIPTIONS,
+            max_websocket_connections: DEFAULT_MAX_WEBSOCKET_CONNECTIONS,
+            max_websocket_concurrent_requests: DEFAULT_MAX_WEBSOCKET_CONCURRENT_REQUESTS,
         }
     }
 }
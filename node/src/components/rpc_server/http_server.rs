@@ -1,4 +1,7 @@
+use std::sync::Arc;
+
 use hyper::server::{conn::AddrIncoming, Builder};
+use tokio::sync::broadcast;
 
 use casper_json_rpc::{CorsOrigin, RequestHandlersBuilder};
 use casper_types::ProtocolVersion;
@@ -7,19 +10,26 @@ use super::{
     rpcs::{
         account::PutDeploy,
         chain::{
-            GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+            GetBlock, GetBlockRange, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary,
+            GetStateRootHash,
         },
         docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+        info::{
+            GetAccountDeploys, GetChainspec, GetDeploy, GetDeployStatus, GetPeers, GetStatus,
+            GetValidatorChanges,
+        },
         state::{
-            GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
-            QueryBalance, QueryGlobalState,
+            GetAccountInfo, GetAuctionInfo, GetBalance, GetBalanceHistory, GetDictionaryItem,
+            GetItem, GetTrie, ListKeys, QueryBalance, QueryGlobalState, VerifyStateProof,
         },
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     },
-    ReactorEventT,
+    websocket_server, ReactorEventT,
+};
+use crate::{
+    components::event_stream_server::SseData, effect::EffectBuilder,
+    utils::rate_limiter::RateLimiter,
 };
-use crate::effect::EffectBuilder;
 
 /// The URL path for all JSON-RPC requests.
 pub const RPC_API_PATH: &str = "rpc";
@@ -27,24 +37,38 @@ pub const RPC_API_PATH: &str = "rpc";
 pub const RPC_API_SERVER_NAME: &str = "JSON RPC";
 
 /// Run the JSON-RPC server.
+///
+/// If `sse_data_broadcaster` is `Some`, the server also accepts WebSocket connections, via which
+/// the same JSON-RPC requests can be made and events can be subscribed to.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
     max_body_bytes: u32,
+    max_batch_size: u32,
     cors_origin: String,
+    sse_data_broadcaster: Option<broadcast::Sender<SseData>>,
+    max_websocket_subscriptions: u32,
+    max_websocket_connections: u32,
+    max_websocket_concurrent_requests: u32,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
     PutDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBlock::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetBlockRange::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBlockTransfers::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStateRootHash::register_as_handler(effect_builder, api_version, &mut handlers);
     GetItem::register_as_handler(effect_builder, api_version, &mut handlers);
     QueryGlobalState::register_as_handler(effect_builder, api_version, &mut handlers);
+    VerifyStateProof::register_as_handler(effect_builder, api_version, &mut handlers);
     GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetAccountDeploys::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetDeployStatus::register_as_handler(effect_builder, api_version, &mut handlers);
     GetPeers::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStatus::register_as_handler(effect_builder, api_version, &mut handlers);
     GetEraInfoBySwitchBlock::register_as_handler(effect_builder, api_version, &mut handlers);
@@ -56,6 +80,19 @@ pub(super) async fn run<REv: ReactorEventT>(
     GetDictionaryItem::register_as_handler(effect_builder, api_version, &mut handlers);
     GetChainspec::register_as_handler(effect_builder, api_version, &mut handlers);
     QueryBalance::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetBalanceHistory::register_as_handler(effect_builder, api_version, &mut handlers);
+    ListKeys::register_as_handler(effect_builder, api_version, &mut handlers);
+
+    let websocket_route = sse_data_broadcaster.map(|sse_data_broadcaster| {
+        websocket_server::websocket_filter(
+            handlers.clone(),
+            sse_data_broadcaster,
+            max_batch_size,
+            max_websocket_subscriptions,
+            max_websocket_connections,
+            max_websocket_concurrent_requests,
+        )
+    });
     let handlers = handlers.build();
 
     match cors_origin.as_str() {
@@ -64,9 +101,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                max_batch_size,
                 RPC_API_PATH,
                 RPC_API_SERVER_NAME,
+                websocket_route,
             )
             .await
         }
@@ -75,9 +115,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                max_batch_size,
                 RPC_API_PATH,
                 RPC_API_SERVER_NAME,
+                websocket_route,
                 CorsOrigin::Any,
             )
             .await
@@ -87,9 +130,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                max_batch_size,
                 RPC_API_PATH,
                 RPC_API_SERVER_NAME,
+                websocket_route,
                 CorsOrigin::Specified(cors_origin),
             )
             .await
@@ -11,18 +11,24 @@ pub mod info;
 pub mod speculative_exec;
 pub mod state;
 
-use std::{str, sync::Arc, time::Duration};
+use std::{future::Future, net::SocketAddr, str, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use http::header::ACCEPT_ENCODING;
-use hyper::server::{conn::AddrIncoming, Builder};
+use http::{header::ACCEPT_ENCODING, Request};
+use hyper::{
+    server::{
+        conn::{AddrIncoming, AddrStream},
+        Builder,
+    },
+    Body,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::sync::oneshot;
-use tower::ServiceBuilder;
+use tower::{Service, ServiceBuilder};
 use tracing::info;
-use warp::Filter;
+use warp::{filters::BoxedFilter, reply::Response, Filter, Reply};
 
 use casper_json_rpc::{
     CorsOrigin, Error, Params, RequestHandlers, RequestHandlersBuilder, ReservedErrorCode,
@@ -30,7 +36,10 @@ use casper_json_rpc::{
 use casper_types::ProtocolVersion;
 
 use super::{ReactorEventT, RpcRequest};
-use crate::effect::EffectBuilder;
+use crate::{
+    effect::EffectBuilder,
+    utils::rate_limiter::{ClientId, GlobalRateLimiter, RateLimiter},
+};
 pub use common::ErrorData;
 use docs::DocExample;
 pub use error_code::ErrorCode;
@@ -39,7 +48,59 @@ pub use error_code::ErrorCode;
 /// standard 'id', 'jsonrpc', 'method', and 'params' fields.
 ///
 /// It will be changed to `false` for casper-node v2.0.0.
-const ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST: bool = true;
+pub(super) const ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST: bool = true;
+
+tokio::task_local! {
+    /// The rate limiter and identity of the client whose request is being handled by the current
+    /// task, or `None` if per-client rate limiting is disabled.
+    pub(super) static RATE_LIMITED_CLIENT: Option<(Arc<RateLimiter>, ClientId)>;
+
+    /// The rate limiter shared by all clients of the server handling the current task's request.
+    pub(super) static GLOBAL_RATE_LIMITER: Arc<GlobalRateLimiter>;
+}
+
+/// Returns an error if the client whose request is being handled by the current task has exceeded
+/// its rate limit for the given method, otherwise waits until the server's global rate limit
+/// allows the request.
+///
+/// Each request in a JSON-RPC batch request is counted separately.
+async fn check_rate_limit(method: &str) -> Result<(), Error> {
+    RATE_LIMITED_CLIENT
+        .try_with(|maybe_client| match maybe_client {
+            Some((rate_limiter, client_id)) => rate_limiter.check(client_id, method),
+            None => Ok(()),
+        })
+        .unwrap_or(Ok(()))
+        .map_err(|error| Error::new(ErrorCode::RateLimited, error.to_string()))?;
+    if let Ok(global_rate_limiter) = GLOBAL_RATE_LIMITER.try_with(Arc::clone) {
+        global_rate_limiter.acquire(1).await;
+    }
+    Ok(())
+}
+
+/// Handles the given request via the given service, with the requesting client set in
+/// `RATE_LIMITED_CLIENT` and the server's global rate limiter set in `GLOBAL_RATE_LIMITER` for the
+/// duration.
+fn call_with_rate_limited_client<S>(
+    service: &S,
+    global_rate_limiter: &Arc<GlobalRateLimiter>,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+    remote_addr: SocketAddr,
+    request: Request<Body>,
+) -> impl Future<Output = Result<S::Response, S::Error>>
+where
+    S: Service<Request<Body>> + Clone,
+{
+    let maybe_client = rate_limiter.as_ref().map(|rate_limiter| {
+        let client_id = rate_limiter.client_id(remote_addr, request.headers());
+        (Arc::clone(rate_limiter), client_id)
+    });
+    let mut service = service.clone();
+    GLOBAL_RATE_LIMITER.scope(
+        Arc::clone(global_rate_limiter),
+        RATE_LIMITED_CLIENT.scope(maybe_client, service.call(request)),
+    )
+}
 
 /// A JSON-RPC requiring the "params" field to be present.
 #[async_trait]
@@ -91,6 +152,7 @@ pub(super) trait RpcWithParams {
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
         let handler = move |maybe_params| async move {
+            check_rate_limit(Self::METHOD).await?;
             let params = Self::try_parse_params(maybe_params)?;
             Self::do_handle_request(effect_builder, api_version, params).await
         };
@@ -150,6 +212,7 @@ pub(super) trait RpcWithoutParams {
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
         let handler = move |maybe_params| async move {
+            check_rate_limit(Self::METHOD).await?;
             Self::check_no_params(maybe_params)?;
             Self::do_handle_request(effect_builder, api_version).await
         };
@@ -230,6 +293,7 @@ pub(super) trait RpcWithOptionalParams {
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
         let handler = move |maybe_params| async move {
+            check_rate_limit(Self::METHOD).await?;
             let params = Self::try_parse_params(maybe_params)?;
             Self::do_handle_request(effect_builder, api_version, params).await
         };
@@ -255,21 +319,28 @@ pub(super) trait RpcWithOptionalParams {
 }
 
 /// Start JSON RPC server with CORS enabled in a background.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run_with_cors(
     builder: Builder<AddrIncoming>,
     handlers: RequestHandlers,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
     max_body_bytes: u32,
+    max_batch_size: u32,
     api_path: &'static str,
     server_name: &'static str,
+    websocket_route: Option<BoxedFilter<(Response,)>>,
     cors_header: CorsOrigin,
 ) {
-    let make_svc = hyper::service::make_service_fn(move |_| {
-        let service_routes = casper_json_rpc::route_with_cors(
+    let global_rate_limiter = Arc::new(GlobalRateLimiter::new(qps_limit));
+    let make_svc = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let remote_addr = connection.remote_addr();
+        let service_routes = casper_json_rpc::route_with_cors_and_max_batch_size(
             api_path,
             max_body_bytes,
             handlers.clone(),
             ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+            max_batch_size,
             &cors_header,
         );
 
@@ -279,8 +350,30 @@ pub(super) async fn run_with_cors(
             .and(service_routes.clone())
             .with(warp::compression::gzip());
 
-        let service = warp::service(service_routes_gzip.or(service_routes));
-        async move { Ok::<_, Infallible>(service.clone()) }
+        let service_routes = service_routes_gzip
+            .or(service_routes)
+            .map(Reply::into_response)
+            .boxed();
+
+        // The WebSocket route is tried first, as the JSON-RPC routes recover from all rejections.
+        let service_routes = match websocket_route.clone() {
+            Some(websocket_route) => websocket_route.or(service_routes).unify().boxed(),
+            None => service_routes,
+        };
+
+        let service = warp::service(service_routes);
+        let global_rate_limiter = Arc::clone(&global_rate_limiter);
+        let rate_limiter = rate_limiter.clone();
+        let service = hyper::service::service_fn(move |request| {
+            call_with_rate_limited_client(
+                &service,
+                &global_rate_limiter,
+                &rate_limiter,
+                remote_addr,
+                request,
+            )
+        });
+        async move { Ok::<_, Infallible>(service) }
     });
 
     let make_svc = ServiceBuilder::new()
@@ -301,20 +394,27 @@ pub(super) async fn run_with_cors(
 }
 
 /// Start JSON RPC server in a background.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     builder: Builder<AddrIncoming>,
     handlers: RequestHandlers,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
     max_body_bytes: u32,
+    max_batch_size: u32,
     api_path: &'static str,
     server_name: &'static str,
+    websocket_route: Option<BoxedFilter<(Response,)>>,
 ) {
-    let make_svc = hyper::service::make_service_fn(move |_| {
-        let service_routes = casper_json_rpc::route(
+    let global_rate_limiter = Arc::new(GlobalRateLimiter::new(qps_limit));
+    let make_svc = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let remote_addr = connection.remote_addr();
+        let service_routes = casper_json_rpc::route_with_max_batch_size(
             api_path,
             max_body_bytes,
             handlers.clone(),
             ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+            max_batch_size,
         );
 
         // Supports content negotiation for gzip responses. This is an interim fix until
@@ -323,8 +423,30 @@ pub(super) async fn run(
             .and(service_routes.clone())
             .with(warp::compression::gzip());
 
-        let service = warp::service(service_routes_gzip.or(service_routes));
-        async move { Ok::<_, Infallible>(service.clone()) }
+        let service_routes = service_routes_gzip
+            .or(service_routes)
+            .map(Reply::into_response)
+            .boxed();
+
+        // The WebSocket route is tried first, as the JSON-RPC routes recover from all rejections.
+        let service_routes = match websocket_route.clone() {
+            Some(websocket_route) => websocket_route.or(service_routes).unify().boxed(),
+            None => service_routes,
+        };
+
+        let service = warp::service(service_routes);
+        let global_rate_limiter = Arc::clone(&global_rate_limiter);
+        let rate_limiter = rate_limiter.clone();
+        let service = hyper::service::service_fn(move |request| {
+            call_with_rate_limited_client(
+                &service,
+                &global_rate_limiter,
+                &rate_limiter,
+                remote_addr,
+                request,
+            )
+        });
+        async move { Ok::<_, Infallible>(service) }
     });
 
     let make_svc = ServiceBuilder::new()
@@ -356,6 +478,8 @@ mod tests {
     use super::*;
     use crate::types::DeployHash;
 
+    const MAX_BATCH_SIZE: u32 = 10;
+
     async fn send_request(
         method: &str,
         maybe_params: Option<&str>,
@@ -392,9 +516,13 @@ mod tests {
             GetDeploy::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter_with_max_batch_size(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
@@ -459,9 +587,13 @@ mod tests {
             GetPeers::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter_with_max_batch_size(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
@@ -513,9 +645,13 @@ mod tests {
             GetBlock::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter_with_max_batch_size(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
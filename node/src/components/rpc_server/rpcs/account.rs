@@ -18,7 +18,7 @@ use super::{
     Error, ReactorEventT, RpcWithParams,
 };
 use crate::{
-    components::rpc_server::rpcs::ErrorCode,
+    components::{deploy_acceptor, rpc_server::rpcs::ErrorCode},
     effect::EffectBuilder,
     types::{Deploy, DeployHash},
 };
@@ -97,7 +97,13 @@ impl RpcWithParams for PutDeploy {
                     %error,
                     "the deploy submitted by the client was invalid",
                 );
-                Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()))
+                let error_code = match error {
+                    deploy_acceptor::Error::PendingLimitExceeded(_) => {
+                        ErrorCode::TooManyPendingDeploys
+                    }
+                    _ => ErrorCode::InvalidDeploy,
+                };
+                Err(Error::new(error_code, error.to_string()))
             }
         }
     }
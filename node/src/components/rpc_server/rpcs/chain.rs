@@ -19,12 +19,17 @@ use casper_types::{Key, ProtocolVersion, Transfer};
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, ReservedErrorCode, RpcRequest, RpcWithOptionalParams,
+    RpcWithParams,
 };
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{common, state},
-    types::{Block, BlockHash, BlockWithMetadata, JsonBlock},
+    types::{
+        Block, BlockBody, BlockHash, BlockHeaderWithMetadata, BlockWithMetadata, JsonBlock,
+        JsonBlockBody, JsonBlockHeader, JsonProof,
+    },
+    utils::DisplayIter,
 };
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
@@ -36,6 +41,20 @@ static GET_BLOCK_RESULT: Lazy<GetBlockResult> = Lazy::new(|| GetBlockResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block: Some(JsonBlock::doc_example().clone()),
 });
+static GET_BLOCK_RANGE_PARAMS: Lazy<GetBlockRangeParams> = Lazy::new(|| GetBlockRangeParams {
+    start_height: Block::doc_example().header().height(),
+    end_height: Block::doc_example().header().height(),
+    include_bodies: true,
+    include_signatures: true,
+});
+static GET_BLOCK_RANGE_RESULT: Lazy<GetBlockRangeResult> = Lazy::new(|| GetBlockRangeResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    blocks: vec![BlockRangeEntry::new(
+        JsonBlock::doc_example().clone(),
+        true,
+        true,
+    )],
+});
 static GET_BLOCK_TRANSFERS_PARAMS: Lazy<GetBlockTransfersParams> =
     Lazy::new(|| GetBlockTransfersParams {
         block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
@@ -185,6 +204,187 @@ impl RpcWithOptionalParams for GetBlock {
     }
 }
 
+/// Params for "chain_get_block_range" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockRangeParams {
+    /// The height of the first block in the range.
+    pub start_height: u64,
+    /// The height of the last block in the range (inclusive).
+    pub end_height: u64,
+    /// Whether to include the block bodies.
+    #[serde(default)]
+    pub include_bodies: bool,
+    /// Whether to include the blocks' finality signatures.
+    #[serde(default)]
+    pub include_signatures: bool,
+}
+
+impl DocExample for GetBlockRangeParams {
+    fn doc_example() -> &'static Self {
+        &GET_BLOCK_RANGE_PARAMS
+    }
+}
+
+/// A single block in the result of a "chain_get_block_range" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BlockRangeEntry {
+    /// The block hash.
+    pub hash: BlockHash,
+    /// JSON-friendly block header.
+    pub header: JsonBlockHeader,
+    /// JSON-friendly block body, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<JsonBlockBody>,
+    /// JSON-friendly list of proofs for this block, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proofs: Option<Vec<JsonProof>>,
+}
+
+impl BlockRangeEntry {
+    fn new(json_block: JsonBlock, include_body: bool, include_proofs: bool) -> Self {
+        let JsonBlock {
+            hash,
+            header,
+            body,
+            proofs,
+        } = json_block;
+        BlockRangeEntry {
+            hash,
+            header,
+            body: include_body.then_some(body),
+            proofs: include_proofs.then_some(proofs),
+        }
+    }
+
+    fn from_stored(
+        block_header_with_metadata: BlockHeaderWithMetadata,
+        block_body: Option<BlockBody>,
+        include_proofs: bool,
+    ) -> Self {
+        let BlockHeaderWithMetadata {
+            block_header,
+            block_signatures,
+        } = block_header_with_metadata;
+        BlockRangeEntry {
+            hash: block_header.block_hash(),
+            header: JsonBlockHeader::from(block_header),
+            body: block_body.as_ref().map(JsonBlockBody::from),
+            proofs: include_proofs.then(|| {
+                block_signatures
+                    .proofs
+                    .into_iter()
+                    .map(JsonProof::from)
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Result for "chain_get_block_range" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockRangeResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The blocks in the range, in ascending order of height.
+    pub blocks: Vec<BlockRangeEntry>,
+}
+
+impl DocExample for GetBlockRangeResult {
+    fn doc_example() -> &'static Self {
+        &GET_BLOCK_RANGE_RESULT
+    }
+}
+
+/// "chain_get_block_range" RPC.
+pub struct GetBlockRange {}
+
+impl GetBlockRange {
+    /// The maximum number of blocks which can be requested in a single call.
+    pub const MAX_BLOCK_RANGE_LENGTH: u64 = 100;
+}
+
+#[async_trait]
+impl RpcWithParams for GetBlockRange {
+    const METHOD: &'static str = "chain_get_block_range";
+    type RequestParams = GetBlockRangeParams;
+    type ResponseResult = GetBlockRangeResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let GetBlockRangeParams {
+            start_height,
+            end_height,
+            include_bodies,
+            include_signatures,
+        } = params;
+
+        if start_height > end_height {
+            let message = format!(
+                "start_height {} is greater than end_height {}",
+                start_height, end_height
+            );
+            return Err(Error::new(ReservedErrorCode::InvalidParams, message));
+        }
+        if end_height - start_height >= Self::MAX_BLOCK_RANGE_LENGTH {
+            let message = format!(
+                "range of {} blocks exceeds the maximum of {}",
+                end_height - start_height + 1,
+                Self::MAX_BLOCK_RANGE_LENGTH
+            );
+            return Err(Error::new(ReservedErrorCode::InvalidParams, message));
+        }
+
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        let blocks_with_metadata = match effect_builder
+            .get_blocks_with_metadata_by_height_range_from_storage(
+                start_height,
+                end_height,
+                include_bodies,
+                only_from_available_block_range,
+            )
+            .await
+        {
+            Ok(blocks_with_metadata) => blocks_with_metadata,
+            Err(missing_heights) => {
+                return Err(common::missing_block_or_state_root_error(
+                    effect_builder,
+                    ErrorCode::NoSuchBlock,
+                    format!(
+                        "blocks at heights {} not stored on this node",
+                        DisplayIter::new(missing_heights)
+                    ),
+                )
+                .await)
+            }
+        };
+
+        let blocks = blocks_with_metadata
+            .into_iter()
+            .map(|(block_header_with_metadata, block_body)| {
+                BlockRangeEntry::from_stored(
+                    block_header_with_metadata,
+                    block_body,
+                    include_signatures,
+                )
+            })
+            .collect();
+
+        Ok(Self::ResponseResult {
+            api_version,
+            blocks,
+        })
+    }
+}
+
 /// Params for "chain_get_block_transfers" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
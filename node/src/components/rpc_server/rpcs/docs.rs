@@ -18,12 +18,17 @@ use casper_types::ProtocolVersion;
 use super::{
     account::PutDeploy,
     chain::{
-        GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+        GetBlock, GetBlockRange, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary,
+        GetStateRootHash,
     },
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+    info::{
+        GetAccountDeploys, GetChainspec, GetDeploy, GetDeployStatus, GetPeers, GetStatus,
+        GetValidatorChanges,
+    },
+    speculative_exec::{EstimateGas, SpeculativeExec},
     state::{
-        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
-        QueryGlobalState,
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetBalanceHistory, GetDictionaryItem, GetItem,
+        ListKeys, QueryBalance, QueryGlobalState, VerifyStateProof,
     },
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
 };
@@ -36,39 +41,16 @@ const DEFINITIONS_PATH: &str = "#/components/schemas/";
 
 // As per https://spec.open-rpc.org/#service-discovery-method.
 pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
-    let contact = OpenRpcContactField {
-        name: "CasperLabs".to_string(),
-        url: "https://casperlabs.io".to_string(),
-    };
-    let license = OpenRpcLicenseField {
-        name: "CasperLabs Open Source License Version 1.0".to_string(),
-        url: "https://raw.githubusercontent.com/CasperLabs/casper-node/master/LICENSE".to_string(),
-    };
-    let info = OpenRpcInfoField {
-        version: DOCS_EXAMPLE_PROTOCOL_VERSION.to_string(),
-        title: "Client API of Casper Node".to_string(),
-        description: "This describes the JSON-RPC 2.0 API of a node on the Casper network."
-            .to_string(),
-        contact,
-        license,
-    };
-
-    let server = OpenRpcServerEntry {
-        name: "any Casper Network node".to_string(),
-        url: "http://IP:PORT/rpc/".to_string(),
-    };
-
-    let mut schema = OpenRpcSchema {
-        openrpc: "1.0.0-rc1".to_string(),
-        info,
-        servers: vec![server],
-        methods: vec![],
-        components: Components {
-            schemas: Map::new(),
-        },
-    };
+    let mut schema = OpenRpcSchema::new(
+        "Client API of Casper Node",
+        "This describes the JSON-RPC 2.0 API of a node on the Casper network.",
+    );
 
-    schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
+    schema.push_with_params::<PutDeploy>(
+        "receives a Deploy to be executed by the network.  If deploy replacement is enabled in the \
+        chainspec, a Deploy naming a pending Deploy from the same account in its dependencies \
+        replaces it if it has a higher gas price or has empty session module bytes",
+    );
     schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
     schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
     schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
@@ -109,6 +91,27 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
         "returns the era summary at either a specific block (by height or hash), or the most \
         recently added block",
     );
+    schema.push_with_params::<ListKeys>(
+        "returns a page of the keys in global state with a given prefix, optionally with their \
+        values",
+    );
+    schema.push_with_params::<GetBlockRange>(
+        "returns the Block headers, and optionally bodies and finality signatures, for a range of \
+        heights",
+    );
+    schema.push_with_params::<GetAccountDeploys>(
+        "returns a page of the executed deploys signed or paid for by an account",
+    );
+    schema.push_with_params::<VerifyStateProof>(
+        "verifies a Merkle proof of a stored value against a state root hash",
+    );
+    schema.push_with_params::<GetBalanceHistory>(
+        "returns an account's main purse balance and staked and delegated amounts as of the switch \
+        block of each of a range of eras",
+    );
+    schema.push_with_params::<GetDeployStatus>(
+        "returns the lifecycle status of a Deploy as known to this node",
+    );
 
     schema
 });
@@ -117,6 +120,33 @@ static LIST_RPCS_RESULT: Lazy<ListRpcsResult> = Lazy::new(|| ListRpcsResult {
     name: "OpenRPC Schema".to_string(),
     schema: OPEN_RPC_SCHEMA.clone(),
 });
+/// The OpenRPC schema of the speculative execution server.
+pub(crate) static SPECULATIVE_OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
+    let mut schema = OpenRpcSchema::new(
+        "Speculative Execution API of Casper Node",
+        "This describes the JSON-RPC 2.0 API of the speculative execution server of a node on the \
+        Casper network.",
+    );
+
+    schema.push_with_params::<SpeculativeExec>(
+        "receives a Deploy to be executed by the node on top of a given block, without \
+        committing the effects to global state",
+    );
+    schema.push_with_params::<EstimateGas>(
+        "executes a Deploy, which need not be signed, on top of a given block and returns the gas \
+        consumed along with a suggested payment amount",
+    );
+    schema.push_without_params::<ListSpeculativeRpcs>(
+        "returns the OpenRPC schema of the speculative execution server",
+    );
+
+    schema
+});
+static LIST_SPECULATIVE_RPCS_RESULT: Lazy<ListRpcsResult> = Lazy::new(|| ListRpcsResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    name: "OpenRPC Schema".to_string(),
+    schema: SPECULATIVE_OPEN_RPC_SCHEMA.clone(),
+});
 
 /// A trait used to generate a static hardcoded example of `Self`.
 pub trait DocExample {
@@ -136,6 +166,40 @@ pub struct OpenRpcSchema {
 }
 
 impl OpenRpcSchema {
+    fn new(title: &str, description: &str) -> Self {
+        let contact = OpenRpcContactField {
+            name: "CasperLabs".to_string(),
+            url: "https://casperlabs.io".to_string(),
+        };
+        let license = OpenRpcLicenseField {
+            name: "CasperLabs Open Source License Version 1.0".to_string(),
+            url: "https://raw.githubusercontent.com/CasperLabs/casper-node/master/LICENSE"
+                .to_string(),
+        };
+        let info = OpenRpcInfoField {
+            version: DOCS_EXAMPLE_PROTOCOL_VERSION.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            contact,
+            license,
+        };
+
+        let server = OpenRpcServerEntry {
+            name: "any Casper Network node".to_string(),
+            url: "http://IP:PORT/rpc/".to_string(),
+        };
+
+        OpenRpcSchema {
+            openrpc: "1.0.0-rc1".to_string(),
+            info,
+            servers: vec![server],
+            methods: vec![],
+            components: Components {
+                schemas: Map::new(),
+            },
+        }
+    }
+
     fn new_generator() -> SchemaGenerator {
         let settings = SchemaSettings::default().with(|settings| {
             settings.definitions_path = DEFINITIONS_PATH.to_string();
@@ -458,6 +522,24 @@ impl RpcWithoutParams for ListRpcs {
     }
 }
 
+/// "rpc.discover" RPC served by the speculative execution server.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct ListSpeculativeRpcs {}
+
+#[async_trait]
+impl RpcWithoutParams for ListSpeculativeRpcs {
+    // Named as per https://spec.open-rpc.org/#service-discovery-method.
+    const METHOD: &'static str = "rpc.discover";
+    type ResponseResult = ListRpcsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        _effect_builder: EffectBuilder<REv>,
+        _api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        Ok(LIST_SPECULATIVE_RPCS_RESULT.clone())
+    }
+}
+
 mod doc_example_impls {
     use std::str::FromStr;
 
@@ -574,4 +656,25 @@ mod tests {
         let incorrect_optional_params = check_optional_params_fields::<GetAuctionInfo>();
         assert!(incorrect_optional_params.is_empty())
     }
+
+    #[test]
+    fn speculative_schema_should_only_list_speculative_exec_server_rpcs() {
+        let method_names = SPECULATIVE_OPEN_RPC_SCHEMA
+            .methods
+            .iter()
+            .map(|method| method.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            method_names,
+            vec![
+                SpeculativeExec::METHOD,
+                EstimateGas::METHOD,
+                ListSpeculativeRpcs::METHOD
+            ]
+        );
+        assert!(SPECULATIVE_OPEN_RPC_SCHEMA
+            .components
+            .schemas
+            .contains_key("ExecutionResult"));
+    }
 }
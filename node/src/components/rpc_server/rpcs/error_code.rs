@@ -35,6 +35,18 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// The WebSocket connection already has the maximum number of subscriptions.
+    TooManySubscriptions = -32013,
+    /// The requested subscription was not found.
+    NoSuchSubscription = -32014,
+    /// The index of deploys by account is not enabled on this node.
+    AccountDeployIndexNotEnabled = -32015,
+    /// Executing the given Deploy failed.
+    DeployExecutionFailed = -32016,
+    /// The client has exceeded its rate limit.
+    RateLimited = -32017,
+    /// The Deploy's account has too many deploys pending in the deploy buffer.
+    TooManyPendingDeploys = -32018,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,6 +71,14 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::TooManySubscriptions => (error_code as i64, "Too many subscriptions"),
+            ErrorCode::NoSuchSubscription => (error_code as i64, "No such subscription"),
+            ErrorCode::AccountDeployIndexNotEnabled => {
+                (error_code as i64, "Account deploy index not enabled")
+            }
+            ErrorCode::DeployExecutionFailed => (error_code as i64, "Deploy execution failed"),
+            ErrorCode::RateLimited => (error_code as i64, "Rate limit exceeded"),
+            ErrorCode::TooManyPendingDeploys => (error_code as i64, "Too many pending deploys"),
         }
     }
 }
@@ -15,6 +15,7 @@ use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    state::AccountIdentifier,
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams,
 };
 use crate::{
@@ -23,7 +24,7 @@ use crate::{
     reactor::QueueKind,
     types::{
         Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployHash,
-        DeployMetadataExt, GetStatusResult, PeersMap,
+        DeployMetadataExt, DeployStatus, GetStatusResult, PeersMap,
     },
 };
 
@@ -40,6 +41,19 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
     }],
     block_hash_and_height: None,
 });
+static GET_DEPLOY_STATUS_PARAMS: Lazy<GetDeployStatusParams> =
+    Lazy::new(|| GetDeployStatusParams {
+        deploy_hash: *Deploy::doc_example().hash(),
+    });
+static GET_DEPLOY_STATUS_RESULT: Lazy<GetDeployStatusResult> =
+    Lazy::new(|| GetDeployStatusResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        deploy_hash: *Deploy::doc_example().hash(),
+        status: Some(DeployStatus::Executed {
+            block_hash: *Block::doc_example().hash(),
+            success: true,
+        }),
+    });
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
@@ -53,11 +67,35 @@ static GET_VALIDATOR_CHANGES_RESULT: Lazy<GetValidatorChangesResult> = Lazy::new
         changes,
     }
 });
+static GET_ACCOUNT_DEPLOYS_PARAMS: Lazy<GetAccountDeploysParams> =
+    Lazy::new(|| GetAccountDeploysParams {
+        account_identifier: AccountIdentifier::PublicKey(PublicKey::doc_example().clone()),
+        cursor: None,
+        limit: Some(DEFAULT_ACCOUNT_DEPLOYS_LIMIT),
+    });
+static GET_ACCOUNT_DEPLOYS_RESULT: Lazy<GetAccountDeploysResult> = Lazy::new(|| {
+    let account_deploy = AccountDeploy {
+        deploy_hash: *Deploy::doc_example().hash(),
+        block_hash: *Block::doc_example().hash(),
+        block_height: Block::doc_example().header().height(),
+    };
+    GetAccountDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        next_cursor: Some(AccountDeploysCursor::from(&account_deploy)),
+        deploys: vec![account_deploy],
+    }
+});
 static GET_CHAINSPEC_RESULT: Lazy<GetChainspecResult> = Lazy::new(|| GetChainspecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     chainspec_bytes: ChainspecRawBytes::new(vec![42, 42].into(), None, None),
 });
 
+/// The number of deploys returned by "info_get_account_deploys" if the request doesn't specify a
+/// limit.
+const DEFAULT_ACCOUNT_DEPLOYS_LIMIT: u32 = 100;
+/// The maximum number of deploys returned by a single "info_get_account_deploys" request.
+const MAX_ACCOUNT_DEPLOYS_LIMIT: u32 = 1_000;
+
 /// Params for "info_get_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -390,3 +428,205 @@ impl RpcWithoutParams for GetChainspec {
         Ok(result)
     }
 }
+
+/// Position in an account's deploys after which "info_get_account_deploys" continues listing.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountDeploysCursor {
+    /// The height of the block containing the last deploy of the previous page.
+    pub block_height: u64,
+    /// The hash of the last deploy of the previous page.
+    pub deploy_hash: DeployHash,
+}
+
+impl From<&AccountDeploy> for AccountDeploysCursor {
+    fn from(account_deploy: &AccountDeploy) -> Self {
+        AccountDeploysCursor {
+            block_height: account_deploy.block_height,
+            deploy_hash: account_deploy.deploy_hash,
+        }
+    }
+}
+
+/// Params for "info_get_account_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysParams {
+    /// The public key or account hash of the account.
+    pub account_identifier: AccountIdentifier,
+    /// The `next_cursor` of the previous page, or absent to start from the account's first
+    /// deploy.
+    #[serde(default)]
+    pub cursor: Option<AccountDeploysCursor>,
+    /// The maximum number of deploys to return, at most 1000.  Defaults to 100.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetAccountDeploysParams {
+    fn doc_example() -> &'static Self {
+        &GET_ACCOUNT_DEPLOYS_PARAMS
+    }
+}
+
+/// A deploy signed or paid for by an account, as listed by the "info_get_account_deploys" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountDeploy {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block containing the deploy.
+    pub block_hash: BlockHash,
+    /// The height of the block containing the deploy.
+    pub block_height: u64,
+}
+
+/// Result for "info_get_account_deploys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The account's deploys, in ascending order of block height.
+    pub deploys: Vec<AccountDeploy>,
+    /// The cursor to pass in the next request to continue listing, or absent if there are no
+    /// further deploys.
+    pub next_cursor: Option<AccountDeploysCursor>,
+}
+
+impl DocExample for GetAccountDeploysResult {
+    fn doc_example() -> &'static Self {
+        &GET_ACCOUNT_DEPLOYS_RESULT
+    }
+}
+
+/// "info_get_account_deploys" RPC.
+pub struct GetAccountDeploys {}
+
+#[async_trait]
+impl RpcWithParams for GetAccountDeploys {
+    const METHOD: &'static str = "info_get_account_deploys";
+    type RequestParams = GetAccountDeploysParams;
+    type ResponseResult = GetAccountDeploysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let account_hash = match params.account_identifier {
+            AccountIdentifier::PublicKey(public_key) => public_key.to_account_hash(),
+            AccountIdentifier::AccountHash(account_hash) => account_hash,
+        };
+        let cursor = params
+            .cursor
+            .map(|cursor| (cursor.block_height, cursor.deploy_hash));
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_ACCOUNT_DEPLOYS_LIMIT)
+            .clamp(1, MAX_ACCOUNT_DEPLOYS_LIMIT) as usize;
+
+        // Ask for one extra entry to find out whether there is a further page.
+        let mut account_deploys = match effect_builder
+            .get_account_deploys_from_storage(account_hash, cursor, limit + 1)
+            .await
+        {
+            Some(account_deploys) => account_deploys,
+            None => {
+                info!("info-get-account-deploys failed: index not enabled");
+                return Err(Error::new(
+                    ErrorCode::AccountDeployIndexNotEnabled,
+                    "the index of deploys by account is not enabled on this node",
+                ));
+            }
+        };
+        let has_more = account_deploys.len() > limit;
+        account_deploys.truncate(limit);
+
+        let deploys: Vec<_> = account_deploys
+            .into_iter()
+            .map(
+                |(
+                    deploy_hash,
+                    BlockHashAndHeight {
+                        block_hash,
+                        block_height,
+                    },
+                )| AccountDeploy {
+                    deploy_hash,
+                    block_hash,
+                    block_height,
+                },
+            )
+            .collect();
+        let next_cursor = if has_more {
+            deploys.last().map(AccountDeploysCursor::from)
+        } else {
+            None
+        };
+
+        Ok(Self::ResponseResult {
+            api_version,
+            deploys,
+            next_cursor,
+        })
+    }
+}
+
+/// Params for "info_get_deploy_status" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeployStatusParams {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+}
+
+impl DocExample for GetDeployStatusParams {
+    fn doc_example() -> &'static Self {
+        &GET_DEPLOY_STATUS_PARAMS
+    }
+}
+
+/// Result for "info_get_deploy_status" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeployStatusResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The current status of the deploy, or `None` if the deploy has not been seen by this node
+    /// recently.
+    pub status: Option<DeployStatus>,
+}
+
+impl DocExample for GetDeployStatusResult {
+    fn doc_example() -> &'static Self {
+        &GET_DEPLOY_STATUS_RESULT
+    }
+}
+
+/// "info_get_deploy_status" RPC.
+pub struct GetDeployStatus {}
+
+#[async_trait]
+impl RpcWithParams for GetDeployStatus {
+    const METHOD: &'static str = "info_get_deploy_status";
+    type RequestParams = GetDeployStatusParams;
+    type ResponseResult = GetDeployStatusResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let status = effect_builder.get_deploy_status(params.deploy_hash).await;
+        Ok(Self::ResponseResult {
+            api_version,
+            deploy_hash: params.deploy_hash,
+            status,
+        })
+    }
+}
@@ -3,16 +3,25 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::{str, sync::Arc};
+use std::{collections::BTreeSet, str, sync::Arc};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::core::engine_state::Error as EngineStateError;
+use casper_execution_engine::core::{
+    engine_state::{DeployItem, Error as EngineStateError, ExecutableDeployItem},
+    runtime::trace::ExecutionTrace,
+};
 use casper_json_rpc::ReservedErrorCode;
-use casper_types::{ExecutionResult, ProtocolVersion};
+use casper_types::{
+    account::{Account, AccountHash},
+    bytesrepr::Bytes,
+    runtime_args,
+    system::mint::ARG_AMOUNT,
+    ExecutionResult, Key, Phase, ProtocolVersion, RuntimeArgs, U512,
+};
 
 use super::{
     chain::BlockIdentifier,
@@ -29,13 +38,29 @@ use crate::{
 static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
     block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
     deploy: Deploy::doc_example().clone(),
+    trace: None,
 });
 static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
+    execution_trace: None,
+});
+static ESTIMATE_GAS_PARAMS: Lazy<EstimateGasParams> = Lazy::new(|| EstimateGasParams {
+    block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+    deploy: Deploy::doc_example().clone(),
+    safety_margin_percent: Some(DEFAULT_ESTIMATE_GAS_SAFETY_MARGIN_PERCENT),
+});
+static ESTIMATE_GAS_RESULT: Lazy<EstimateGasResult> = Lazy::new(|| EstimateGasResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: *Block::doc_example().hash(),
+    gas_consumed: U512::from(123_456),
+    suggested_payment_amount: U512::from(148_148),
 });
 
+/// The safety margin used by "account_estimate_gas" if the request doesn't specify one.
+const DEFAULT_ESTIMATE_GAS_SAFETY_MARGIN_PERCENT: u32 = 20;
+
 /// Params for "speculative_exec" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -44,6 +69,10 @@ pub struct SpeculativeExecParams {
     pub block_identifier: Option<BlockIdentifier>,
     /// Deploy to execute.
     pub deploy: Deploy,
+    /// Whether to return a trace of the gas charged for each host function call, contract call
+    /// and block of Wasm instructions while executing the deploy.  Defaults to false.
+    #[serde(default)]
+    pub trace: Option<bool>,
 }
 
 impl DocExample for SpeculativeExecParams {
@@ -63,6 +92,9 @@ pub struct SpeculativeExecResult {
     pub block_hash: BlockHash,
     /// Result of the execution.
     pub execution_result: ExecutionResult,
+    /// Trace of the gas charged during the execution, if requested.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub execution_trace: Option<ExecutionTrace>,
 }
 
 impl DocExample for SpeculativeExecResult {
@@ -88,6 +120,7 @@ impl RpcWithParams for SpeculativeExec {
         let SpeculativeExecParams {
             block_identifier: maybe_block_id,
             deploy,
+            trace,
         } = params;
         let deploy = Arc::new(deploy);
         let only_from_available_block_range = true;
@@ -113,16 +146,27 @@ impl RpcWithParams for SpeculativeExec {
             return Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()));
         }
 
-        let result = effect_builder
-            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy))
-            .await;
+        let result = if trace.unwrap_or(false) {
+            effect_builder
+                .speculative_execute_deploy_with_trace(execution_prestate, Arc::clone(&deploy))
+                .await
+                .map(|maybe_result| {
+                    maybe_result.map(|(execution_result, trace)| (execution_result, Some(trace)))
+                })
+        } else {
+            effect_builder
+                .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy))
+                .await
+                .map(|maybe_result| maybe_result.map(|execution_result| (execution_result, None)))
+        };
 
         match result {
-            Ok(Some(execution_result)) => {
+            Ok(Some((execution_result, execution_trace))) => {
                 let result = Self::ResponseResult {
                     api_version,
                     block_hash,
                     execution_result,
+                    execution_trace,
                 };
                 Ok(result)
             }
@@ -130,50 +174,310 @@ impl RpcWithParams for SpeculativeExec {
                 ErrorCode::NoSuchBlock,
                 "block hash not found".to_string(),
             )),
-            Err(error) => {
-                let rpc_error = match error {
-                    EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
-                    EngineStateError::WasmPreprocessing(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, error.to_string())
-                    }
-                    EngineStateError::InvalidDeployItemVariant(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, error)
-                    }
-                    EngineStateError::InvalidProtocolVersion(_) => Error::new(
-                        ErrorCode::InvalidDeploy,
-                        format!("deploy used invalid protocol version {}", error),
-                    ),
-                    EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
-                    EngineStateError::Genesis(_)
-                    | EngineStateError::WasmSerialization(_)
-                    | EngineStateError::Exec(_)
-                    | EngineStateError::Storage(_)
-                    | EngineStateError::Authorization
-                    | EngineStateError::InsufficientPayment
-                    | EngineStateError::GasConversionOverflow
-                    | EngineStateError::Finalization
-                    | EngineStateError::Bytesrepr(_)
-                    | EngineStateError::Mint(_)
-                    | EngineStateError::InvalidKeyVariant
-                    | EngineStateError::ProtocolUpgrade(_)
-                    | EngineStateError::CommitError(_)
-                    | EngineStateError::MissingSystemContractRegistry
-                    | EngineStateError::MissingSystemContractHash(_)
-                    | EngineStateError::RuntimeStackOverflow
-                    | EngineStateError::FailedToGetWithdrawKeys
-                    | EngineStateError::FailedToGetStoredWithdraws
-                    | EngineStateError::FailedToGetWithdrawPurses
-                    | EngineStateError::FailedToRetrieveUnbondingDelay
-                    | EngineStateError::FailedToRetrieveEraId => {
-                        Error::new(ReservedErrorCode::InternalError, error.to_string())
-                    }
-                    _ => Error::new(
-                        ReservedErrorCode::InternalError,
-                        format!("Unhandled engine state error: {}", error),
+            Err(error) => Err(engine_state_error_to_rpc_error(error)),
+        }
+    }
+}
+
+/// Params for "account_estimate_gas" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateGasParams {
+    /// Block hash on top of which to execute the deploy.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// Deploy to execute.  If it has no approvals, it is executed as if signed by the account's
+    /// associated keys with the highest weights, enough of them to meet its deployment threshold.
+    /// If it uses standard payment, the payment amount is replaced by the balance of the account's
+    /// main purse.
+    pub deploy: Deploy,
+    /// Percentage added to the gas consumed when calculating the suggested payment amount.
+    /// Defaults to 20.
+    #[serde(default)]
+    pub safety_margin_percent: Option<u32>,
+}
+
+impl DocExample for EstimateGasParams {
+    fn doc_example() -> &'static Self {
+        &ESTIMATE_GAS_PARAMS
+    }
+}
+
+/// Result for "account_estimate_gas" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateGasResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block on top of which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The gas consumed by executing the deploy's payment and session code.
+    pub gas_consumed: U512,
+    /// The suggested payment amount in motes, i.e. the gas consumed plus the safety margin,
+    /// multiplied by the deploy's gas price.
+    pub suggested_payment_amount: U512,
+}
+
+impl DocExample for EstimateGasResult {
+    fn doc_example() -> &'static Self {
+        &ESTIMATE_GAS_RESULT
+    }
+}
+
+/// "account_estimate_gas" RPC
+pub struct EstimateGas {}
+
+#[async_trait]
+impl RpcWithParams for EstimateGas {
+    const METHOD: &'static str = "account_estimate_gas";
+    type RequestParams = EstimateGasParams;
+    type ResponseResult = EstimateGasResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let EstimateGasParams {
+            block_identifier: maybe_block_id,
+            deploy,
+            safety_margin_percent,
+        } = params;
+        let safety_margin_percent =
+            safety_margin_percent.unwrap_or(DEFAULT_ESTIMATE_GAS_SAFETY_MARGIN_PERCENT);
+        let only_from_available_block_range = true;
+
+        // The deploy need not be signed, so it doesn't go through the deploy acceptor.
+        if let Err(error) = deploy.has_valid_hash() {
+            return Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()));
+        }
+
+        let block = common::get_block(
+            maybe_block_id,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+        let block_hash = *block.hash();
+        let execution_prestate = SpeculativeExecutionState {
+            state_root_hash: *block.state_root_hash(),
+            block_time: block.timestamp(),
+            protocol_version: block.protocol_version(),
+        };
+
+        let gas_price = deploy.header().gas_price();
+        let mut deploy_item = DeployItem::from(deploy);
+        let maybe_account = effect_builder
+            .get_account_from_global_state(
+                execution_prestate.state_root_hash,
+                Key::Account(deploy_item.address),
+            )
+            .await;
+        if deploy_item.authorization_keys.is_empty() {
+            deploy_item.authorization_keys = match &maybe_account {
+                Some(account) => deployment_authorization_keys(account),
+                None => BTreeSet::from([deploy_item.address]),
+            };
+        }
+
+        // Standard payment is made with the account's whole main purse balance, so that the
+        // estimate is not limited by the payment amount given in the deploy.
+        if deploy_item.payment.is_standard_payment(Phase::Payment) {
+            let maybe_balance = match &maybe_account {
+                Some(account) => {
+                    effect_builder
+                        .check_purse_balance(
+                            execution_prestate.state_root_hash,
+                            account.main_purse(),
+                        )
+                        .await
+                }
+                None => None,
+            };
+            if let Some(balance) = maybe_balance {
+                deploy_item.payment = ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::new(),
+                    args: runtime_args! { ARG_AMOUNT => balance },
+                };
+            }
+        }
+
+        let result = effect_builder
+            .speculative_execute_deploy_item(execution_prestate, deploy_item)
+            .await;
+
+        match result {
+            Ok(Some(ExecutionResult::Success { cost, .. })) => {
+                let result = Self::ResponseResult {
+                    api_version,
+                    block_hash,
+                    gas_consumed: cost,
+                    suggested_payment_amount: suggested_payment_amount(
+                        cost,
+                        safety_margin_percent,
+                        gas_price,
                     ),
                 };
-                Err(rpc_error)
+                Ok(result)
             }
+            Ok(Some(ExecutionResult::Failure {
+                cost,
+                error_message,
+                ..
+            })) => Err(Error::new(
+                ErrorCode::DeployExecutionFailed,
+                format!(
+                    "deploy execution failed after consuming {} gas: {}",
+                    cost, error_message
+                ),
+            )),
+            Ok(None) => Err(Error::new(
+                ErrorCode::NoSuchBlock,
+                "block hash not found".to_string(),
+            )),
+            Err(error) => Err(engine_state_error_to_rpc_error(error)),
+        }
+    }
+}
+
+/// Returns the keys an unsigned deploy from the given account is executed as if signed by: the
+/// account's associated keys in descending order of weight, until their combined weight meets the
+/// account's deployment threshold.
+///
+/// If the associated keys can't meet the threshold, all of them are returned, so that the
+/// execution fails with an authorization error.
+fn deployment_authorization_keys(account: &Account) -> BTreeSet<AccountHash> {
+    let mut associated_keys: Vec<_> = account.associated_keys().iter().collect();
+    associated_keys.sort_by(|(_, weight_1), (_, weight_2)| weight_2.cmp(weight_1));
+
+    let deployment_threshold = *account.action_thresholds().deployment();
+    let mut authorization_keys = BTreeSet::new();
+    for (account_hash, _) in associated_keys {
+        if !authorization_keys.is_empty()
+            && account
+                .associated_keys()
+                .calculate_keys_weight(&authorization_keys)
+                >= deployment_threshold
+        {
+            break;
+        }
+        let _ = authorization_keys.insert(*account_hash);
+    }
+    authorization_keys
+}
+
+/// Returns the gas consumed plus the given percentage, rounded up, converted to motes.
+fn suggested_payment_amount(
+    gas_consumed: U512,
+    safety_margin_percent: u32,
+    gas_price: u64,
+) -> U512 {
+    let hundred = U512::from(100);
+    let gas_with_margin = (gas_consumed * (hundred + U512::from(safety_margin_percent)) + hundred
+        - U512::one())
+        / hundred;
+    gas_with_margin * U512::from(gas_price)
+}
+
+fn engine_state_error_to_rpc_error(error: EngineStateError) -> Error {
+    match error {
+        EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
+        EngineStateError::WasmPreprocessing(error) => {
+            Error::new(ErrorCode::InvalidDeploy, error.to_string())
+        }
+        EngineStateError::InvalidDeployItemVariant(error) => {
+            Error::new(ErrorCode::InvalidDeploy, error)
         }
+        EngineStateError::InvalidProtocolVersion(_) => Error::new(
+            ErrorCode::InvalidDeploy,
+            format!("deploy used invalid protocol version {}", error),
+        ),
+        EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
+        EngineStateError::Genesis(_)
+        | EngineStateError::WasmSerialization(_)
+        | EngineStateError::Exec(_)
+        | EngineStateError::Storage(_)
+        | EngineStateError::Authorization
+        | EngineStateError::InsufficientPayment
+        | EngineStateError::GasConversionOverflow
+        | EngineStateError::Finalization
+        | EngineStateError::Bytesrepr(_)
+        | EngineStateError::Mint(_)
+        | EngineStateError::InvalidKeyVariant
+        | EngineStateError::ProtocolUpgrade(_)
+        | EngineStateError::CommitError(_)
+        | EngineStateError::MissingSystemContractRegistry
+        | EngineStateError::MissingSystemContractHash(_)
+        | EngineStateError::RuntimeStackOverflow
+        | EngineStateError::FailedToGetWithdrawKeys
+        | EngineStateError::FailedToGetStoredWithdraws
+        | EngineStateError::FailedToGetWithdrawPurses
+        | EngineStateError::FailedToRetrieveUnbondingDelay
+        | EngineStateError::FailedToRetrieveEraId => {
+            Error::new(ReservedErrorCode::InternalError, error.to_string())
+        }
+        _ => Error::new(
+            ReservedErrorCode::InternalError,
+            format!("Unhandled engine state error: {}", error),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        account::{ActionThresholds, AssociatedKeys, Weight},
+        contracts::NamedKeys,
+        AccessRights, URef,
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_use_heaviest_keys_meeting_deployment_threshold() {
+        let account_hash = AccountHash::new([1; 32]);
+        let heavy_key = AccountHash::new([2; 32]);
+        let light_key = AccountHash::new([3; 32]);
+        let mut associated_keys = AssociatedKeys::new(account_hash, Weight::new(1));
+        associated_keys.add_key(heavy_key, Weight::new(3)).unwrap();
+        associated_keys.add_key(light_key, Weight::new(2)).unwrap();
+        let account_with_threshold = |deployment_threshold| {
+            Account::new(
+                account_hash,
+                NamedKeys::new(),
+                URef::new([4; 32], AccessRights::READ_ADD_WRITE),
+                associated_keys.clone(),
+                ActionThresholds::new(Weight::new(deployment_threshold), Weight::new(6)).unwrap(),
+            )
+        };
+
+        assert_eq!(
+            deployment_authorization_keys(&account_with_threshold(1)),
+            BTreeSet::from([heavy_key])
+        );
+        assert_eq!(
+            deployment_authorization_keys(&account_with_threshold(4)),
+            BTreeSet::from([heavy_key, light_key])
+        );
+        assert_eq!(
+            deployment_authorization_keys(&account_with_threshold(6)),
+            BTreeSet::from([account_hash, heavy_key, light_key])
+        );
+    }
+
+    #[test]
+    fn should_round_up_suggested_payment_amount() {
+        assert_eq!(
+            suggested_payment_amount(U512::from(123_456), 20, 1),
+            U512::from(148_148)
+        );
+        assert_eq!(
+            suggested_payment_amount(U512::from(1_000), 20, 3),
+            U512::from(3_600)
+        );
+        assert_eq!(
+            suggested_payment_amount(U512::from(1_000), 0, 1),
+            U512::from(1_000)
+        );
     }
 }
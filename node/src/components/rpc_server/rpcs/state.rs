@@ -3,7 +3,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::str;
+use std::{convert::TryFrom, str};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
@@ -12,16 +12,18 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult},
+    core::engine_state::{
+        self, BalanceResult, GetBidsResult, KeyPrefix, ListKeysRequest, QueryResult,
+    },
     storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
 use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
-    bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    bytesrepr::{self, Bytes, ToBytes},
+    CLValue, EraId, Key, KeyTag, ProtocolVersion, PublicKey, SecretKey, StateProof,
+    StateProofError, StoredValue as DomainStoredValue, URef, U512,
 };
 
 use crate::{
@@ -129,6 +131,74 @@ static QUERY_BALANCE_RESULT: Lazy<QueryBalanceResult> = Lazy::new(|| QueryBalanc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     balance: U512::from(123_456),
 });
+static LIST_KEYS_PARAMS: Lazy<ListKeysParams> = Lazy::new(|| ListKeysParams {
+    state_identifier: Some(GlobalStateIdentifier::BlockHash(
+        *Block::doc_example().hash(),
+    )),
+    key_prefix: ListKeysPrefix::URef {
+        seed_uref: "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007"
+            .to_string(),
+    },
+    cursor: None,
+    limit: Some(DEFAULT_LIST_KEYS_LIMIT),
+});
+static LIST_KEYS_RESULT: Lazy<ListKeysResult> = Lazy::new(|| ListKeysResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    entries: vec![ListedKey {
+        key: "dictionary-67518854aa916c97d4e53df8570c8217ccc259da2721b692102d76acd0ee8d1f"
+            .to_string(),
+        stored_value: StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
+    }],
+    next_cursor: Some(
+        "dictionary-67518854aa916c97d4e53df8570c8217ccc259da2721b692102d76acd0ee8d1f".to_string(),
+    ),
+});
+
+static VERIFY_STATE_PROOF_PARAMS: Lazy<VerifyStateProofParams> =
+    Lazy::new(|| VerifyStateProofParams {
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        key: "deploy-af684263911154d26fa05be9963171802801a0b6aff8f199b7391eacb8edc9e1".to_string(),
+        path: vec![],
+        stored_value: StoredValue::Account(JsonAccount::doc_example().clone()),
+        merkle_proof: MERKLE_PROOF.clone(),
+    });
+static VERIFY_STATE_PROOF_RESULT: Lazy<VerifyStateProofResult> =
+    Lazy::new(|| VerifyStateProofResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        state_root_hash: *Block::doc_example().header().state_root_hash(),
+        is_valid: false,
+        error: Some(StateProofError::InvalidProofHash.to_string()),
+    });
+
+static GET_BALANCE_HISTORY_PARAMS: Lazy<GetBalanceHistoryParams> =
+    Lazy::new(|| GetBalanceHistoryParams {
+        account_identifier: GET_ACCOUNT_INFO_PARAMS.account_identifier.clone(),
+        start_era_id: Block::doc_example().header().era_id(),
+        end_era_id: Block::doc_example().header().era_id(),
+        include_delegations: true,
+    });
+static GET_BALANCE_HISTORY_RESULT: Lazy<GetBalanceHistoryResult> = Lazy::new(|| {
+    let block_header = Block::doc_example().header();
+    GetBalanceHistoryResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        eras: vec![EraBalance {
+            era_id: block_header.era_id(),
+            block_hash: block_header.block_hash(),
+            block_height: block_header.height(),
+            state_root_hash: *block_header.state_root_hash(),
+            main_purse_balance: Some(U512::from(123_456)),
+            staked_amount: U512::from(1_000),
+            delegated_amount: Some(U512::from(500)),
+        }],
+    }
+});
+
+/// The number of entries returned by "state_list_keys" if the request doesn't specify a limit.
+const DEFAULT_LIST_KEYS_LIMIT: u32 = 100;
+/// The maximum number of entries returned by a single "state_list_keys" request.
+const MAX_LIST_KEYS_LIMIT: u32 = 1_000;
 
 /// Params for "state_get_item" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -616,42 +686,8 @@ impl DictionaryIdentifier {
                 dictionary_item_key,
                 ..
             } => {
-                let named_keys = match &maybe_stored_value {
-                    Some(DomainStoredValue::Account(account)) => account.named_keys(),
-                    Some(DomainStoredValue::Contract(contract)) => contract.named_keys(),
-                    Some(other) => {
-                        return Err(Error::new(
-                            ErrorCode::FailedToGetDictionaryURef,
-                            format!(
-                                "expected account or contract, but got {}",
-                                other.type_name()
-                            ),
-                        ))
-                    }
-                    None => {
-                        return Err(Error::new(
-                            ErrorCode::FailedToGetDictionaryURef,
-                            "could not retrieve account/contract".to_string(),
-                        ))
-                    }
-                };
-
                 let key_bytes = dictionary_item_key.as_str().as_bytes();
-                let seed_uref = match named_keys.get(dictionary_name) {
-                    Some(key) => *key.as_uref().ok_or_else(|| {
-                        Error::new(
-                            ErrorCode::FailedToGetDictionaryURef,
-                            format!("expected uref, but got {}", key),
-                        )
-                    })?,
-                    None => {
-                        return Err(Error::new(
-                            ErrorCode::FailedToGetDictionaryURef,
-                            "seed uref not in named keys".to_string(),
-                        ))
-                    }
-                };
-
+                let seed_uref = get_dictionary_seed_uref(maybe_stored_value, dictionary_name)?;
                 Ok(Key::dictionary(seed_uref, key_bytes))
             }
             DictionaryIdentifier::URef {
@@ -773,6 +809,46 @@ impl RpcWithParams for GetDictionaryItem {
     }
 }
 
+/// Returns the seed URef of the dictionary stored under `dictionary_name` in the named keys of the
+/// given account or contract.
+fn get_dictionary_seed_uref(
+    maybe_stored_value: Option<DomainStoredValue>,
+    dictionary_name: &str,
+) -> Result<URef, Error> {
+    let named_keys = match &maybe_stored_value {
+        Some(DomainStoredValue::Account(account)) => account.named_keys(),
+        Some(DomainStoredValue::Contract(contract)) => contract.named_keys(),
+        Some(other) => {
+            return Err(Error::new(
+                ErrorCode::FailedToGetDictionaryURef,
+                format!(
+                    "expected account or contract, but got {}",
+                    other.type_name()
+                ),
+            ))
+        }
+        None => {
+            return Err(Error::new(
+                ErrorCode::FailedToGetDictionaryURef,
+                "could not retrieve account/contract".to_string(),
+            ))
+        }
+    };
+
+    match named_keys.get(dictionary_name) {
+        Some(key) => key.as_uref().copied().ok_or_else(|| {
+            Error::new(
+                ErrorCode::FailedToGetDictionaryURef,
+                format!("expected uref, but got {}", key),
+            )
+        }),
+        None => Err(Error::new(
+            ErrorCode::FailedToGetDictionaryURef,
+            "seed uref not in named keys".to_string(),
+        )),
+    }
+}
+
 /// Identifier for possible ways to query Global State
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(deny_unknown_fields)]
@@ -1029,6 +1105,576 @@ impl RpcWithParams for QueryBalance {
     }
 }
 
+/// Params for "state_get_balance_history" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBalanceHistoryParams {
+    /// The account whose balance and stake are requested.
+    pub account_identifier: AccountIdentifier,
+    /// The first era of the range.
+    pub start_era_id: EraId,
+    /// The last era of the range, inclusive.
+    pub end_era_id: EraId,
+    /// Whether to return the total amount delegated by the account.  This requires reading all
+    /// bids for each era.  Defaults to false.
+    #[serde(default)]
+    pub include_delegations: bool,
+}
+
+impl DocExample for GetBalanceHistoryParams {
+    fn doc_example() -> &'static Self {
+        &GET_BALANCE_HISTORY_PARAMS
+    }
+}
+
+/// The balance and stake of an account as of the switch block of an era.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EraBalance {
+    /// The era.
+    pub era_id: EraId,
+    /// The hash of the era's switch block.
+    pub block_hash: BlockHash,
+    /// The height of the era's switch block.
+    pub block_height: u64,
+    /// The state root hash of the era's switch block.
+    pub state_root_hash: Digest,
+    /// The balance of the account's main purse, or `None` if the account didn't exist yet.
+    pub main_purse_balance: Option<U512>,
+    /// The amount staked by the account as a validator, i.e. the balance of its bonding purse.
+    pub staked_amount: U512,
+    /// The total amount delegated by the account to validators, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_amount: Option<U512>,
+}
+
+/// Result for "state_get_balance_history" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBalanceHistoryResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The balance and stake of the account for each era in the range, in ascending order.
+    pub eras: Vec<EraBalance>,
+}
+
+impl DocExample for GetBalanceHistoryResult {
+    fn doc_example() -> &'static Self {
+        &GET_BALANCE_HISTORY_RESULT
+    }
+}
+
+/// "state_get_balance_history" RPC.
+pub struct GetBalanceHistory {}
+
+impl GetBalanceHistory {
+    /// The maximum number of eras which can be requested in a single request.
+    pub const MAX_ERA_COUNT: u64 = 20;
+}
+
+#[async_trait]
+impl RpcWithParams for GetBalanceHistory {
+    const METHOD: &'static str = "state_get_balance_history";
+    type RequestParams = GetBalanceHistoryParams;
+    type ResponseResult = GetBalanceHistoryResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        let start_era_id = params.start_era_id;
+        let end_era_id = params.end_era_id;
+        if start_era_id > end_era_id {
+            return Err(Error::new(
+                ReservedErrorCode::InvalidParams,
+                format!(
+                    "start era {} is higher than end era {}",
+                    start_era_id, end_era_id
+                ),
+            ));
+        }
+        if end_era_id.value() - start_era_id.value() >= Self::MAX_ERA_COUNT {
+            return Err(Error::new(
+                ReservedErrorCode::InvalidParams,
+                format!(
+                    "requested range of eras exceeds the maximum of {}",
+                    Self::MAX_ERA_COUNT
+                ),
+            ));
+        }
+
+        let account_hash = match params.account_identifier {
+            AccountIdentifier::PublicKey(public_key) => public_key.to_account_hash(),
+            AccountIdentifier::AccountHash(account_hash) => account_hash,
+        };
+
+        let mut eras = vec![];
+        for era_id in start_era_id.iter_inclusive(end_era_id.value() - start_era_id.value()) {
+            let block_header = match effect_builder
+                .get_switch_block_header_at_era_id_from_storage(
+                    era_id,
+                    only_from_available_block_range,
+                )
+                .await
+            {
+                Some(block_header) => block_header,
+                None => {
+                    return Err(common::missing_block_or_state_root_error(
+                        effect_builder,
+                        ErrorCode::NoSuchBlock,
+                        format!("failed to get switch block of era {}", era_id),
+                    )
+                    .await)
+                }
+            };
+            let state_root_hash = *block_header.state_root_hash();
+
+            let main_purse_balance =
+                match get_main_purse(effect_builder, state_root_hash, account_hash).await? {
+                    Some(main_purse) => {
+                        Some(get_purse_balance(effect_builder, state_root_hash, main_purse).await?)
+                    }
+                    None => None,
+                };
+
+            let staked_amount =
+                match get_bonding_purse(effect_builder, state_root_hash, account_hash).await? {
+                    Some(bonding_purse) => {
+                        get_purse_balance(effect_builder, state_root_hash, bonding_purse).await?
+                    }
+                    None => U512::zero(),
+                };
+
+            let delegated_amount = if params.include_delegations {
+                Some(get_delegated_amount(effect_builder, state_root_hash, account_hash).await?)
+            } else {
+                None
+            };
+
+            eras.push(EraBalance {
+                era_id,
+                block_hash: block_header.block_hash(),
+                block_height: block_header.height(),
+                state_root_hash,
+                main_purse_balance,
+                staked_amount,
+                delegated_amount,
+            });
+        }
+
+        Ok(Self::ResponseResult { api_version, eras })
+    }
+}
+
+/// Returns the main purse of the given account, or `None` if the account doesn't exist under the
+/// given state root hash.
+async fn get_main_purse<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    account_hash: AccountHash,
+) -> Result<Option<URef>, Error> {
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key: Key::Account(account_hash),
+                path: vec![],
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+    if let Ok(QueryResult::ValueNotFound(_)) = query_result {
+        return Ok(None);
+    }
+    match handle_query_result(effect_builder, state_root_hash, query_result).await? {
+        (DomainStoredValue::Account(account), _) => Ok(Some(account.main_purse())),
+        (stored_value, _) => {
+            let error_msg = format!("failed to get account {}", account_hash);
+            info!(?stored_value, "{}", error_msg);
+            Err(Error::new(ErrorCode::NoSuchAccount, error_msg))
+        }
+    }
+}
+
+/// Returns the bonding purse of the given account's validator bid, or `None` if it has no bid under
+/// the given state root hash.
+async fn get_bonding_purse<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    account_hash: AccountHash,
+) -> Result<Option<URef>, Error> {
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key: Key::Bid(account_hash),
+                path: vec![],
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+    if let Ok(QueryResult::ValueNotFound(_)) = query_result {
+        return Ok(None);
+    }
+    match handle_query_result(effect_builder, state_root_hash, query_result).await? {
+        (DomainStoredValue::Bid(bid), _) => Ok(Some(*bid.bonding_purse())),
+        (stored_value, _) => {
+            let error_msg = format!("failed to get bid of {}", account_hash);
+            info!(?stored_value, "{}", error_msg);
+            Err(Error::new(ReservedErrorCode::InternalError, error_msg))
+        }
+    }
+}
+
+/// Returns the balance of the given purse under the given state root hash.
+async fn get_purse_balance<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    purse_uref: URef,
+) -> Result<U512, Error> {
+    let balance_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::GetBalance {
+                state_root_hash,
+                purse_uref,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    match balance_result {
+        Ok(BalanceResult::Success { motes, .. }) => Ok(motes),
+        Ok(BalanceResult::RootNotFound) => Err(Error::new(
+            ErrorCode::FailedToGetBalance,
+            format!(
+                "root hash {} not found when querying for purse {}",
+                state_root_hash, purse_uref
+            ),
+        )),
+        Err(error) => {
+            info!("get-balance failed to execute: {}", error);
+            Err(Error::new(
+                ErrorCode::GetBalanceFailedToExecute,
+                error.to_string(),
+            ))
+        }
+    }
+}
+
+/// Returns the total amount the given account has delegated to validators under the given state
+/// root hash.
+async fn get_delegated_amount<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    account_hash: AccountHash,
+) -> Result<U512, Error> {
+    let get_bids_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::GetBids {
+                state_root_hash,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    let bids = match get_bids_result {
+        Ok(GetBidsResult::Success { bids }) => bids,
+        Ok(GetBidsResult::RootNotFound) => {
+            return Err(Error::new(
+                ReservedErrorCode::InternalError,
+                format!("root not found when getting bids at {}", state_root_hash),
+            ))
+        }
+        Err(error) => {
+            error!(?state_root_hash, ?error, "failed to get bids");
+            return Err(Error::new(
+                ReservedErrorCode::InternalError,
+                format!("error getting bids at {}: {}", state_root_hash, error),
+            ));
+        }
+    };
+
+    let mut delegated_amount = U512::zero();
+    for bid in bids.values() {
+        for (delegator_public_key, delegator) in bid.delegators() {
+            if delegator_public_key.to_account_hash() == account_hash {
+                delegated_amount += *delegator.staked_amount();
+            }
+        }
+    }
+    Ok(delegated_amount)
+}
+
+/// A type of key which can be listed via the "state_list_keys" RPC.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum ListableKeyTag {
+    /// Keys under which accounts are stored.
+    Account,
+    /// Keys under which contracts, contract packages and contract Wasm are stored.
+    Hash,
+    /// Keys under which URef values are stored.
+    URef,
+    /// Keys under which transfers are stored.
+    Transfer,
+    /// Keys under which deploy infos are stored.
+    DeployInfo,
+    /// Keys under which era infos are stored.
+    EraInfo,
+    /// Keys under which purse balances are stored.
+    Balance,
+    /// Keys under which bids are stored.
+    Bid,
+    /// Keys under which withdraws are stored.
+    Withdraw,
+    /// Keys under which dictionary items are stored.
+    Dictionary,
+    /// Keys under which unbonds are stored.
+    Unbond,
+}
+
+impl From<ListableKeyTag> for KeyTag {
+    fn from(key_tag: ListableKeyTag) -> Self {
+        match key_tag {
+            ListableKeyTag::Account => KeyTag::Account,
+            ListableKeyTag::Hash => KeyTag::Hash,
+            ListableKeyTag::URef => KeyTag::URef,
+            ListableKeyTag::Transfer => KeyTag::Transfer,
+            ListableKeyTag::DeployInfo => KeyTag::DeployInfo,
+            ListableKeyTag::EraInfo => KeyTag::EraInfo,
+            ListableKeyTag::Balance => KeyTag::Balance,
+            ListableKeyTag::Bid => KeyTag::Bid,
+            ListableKeyTag::Withdraw => KeyTag::Withdraw,
+            ListableKeyTag::Dictionary => KeyTag::Dictionary,
+            ListableKeyTag::Unbond => KeyTag::Unbond,
+        }
+    }
+}
+
+/// Options for selecting the keys listed by the "state_list_keys" RPC.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum ListKeysPrefix {
+    /// List every key of the given type.
+    KeyTag(ListableKeyTag),
+    /// List the items of a dictionary via an Account's named keys.
+    AccountNamedKey {
+        /// The account key as a formatted string whose named keys contains dictionary_name.
+        key: String,
+        /// The named key under which the dictionary seed URef is stored.
+        dictionary_name: String,
+    },
+    /// List the items of a dictionary via a Contract's named keys.
+    ContractNamedKey {
+        /// The contract key as a formatted string whose named keys contains dictionary_name.
+        key: String,
+        /// The named key under which the dictionary seed URef is stored.
+        dictionary_name: String,
+    },
+    /// List the items of a dictionary via its seed URef.
+    URef {
+        /// The dictionary's seed URef.
+        seed_uref: String,
+    },
+}
+
+/// Params for "state_list_keys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListKeysParams {
+    /// The identifier used for the query. If none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The keys to list.
+    pub key_prefix: ListKeysPrefix,
+    /// `casper_types::Key` as formatted string after which listing starts.  This should be the
+    /// `next_cursor` of the previous page, or absent to start from the first key.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// The maximum number of entries to return, at most 1000.  Defaults to 100.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl DocExample for ListKeysParams {
+    fn doc_example() -> &'static Self {
+        &LIST_KEYS_PARAMS
+    }
+}
+
+/// A key and the value stored under it, as listed by the "state_list_keys" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListedKey {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The stored value.
+    pub stored_value: StoredValue,
+}
+
+/// Result for "state_list_keys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListKeysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The listed keys with their stored values, in ascending order of their serialized bytes.
+    pub entries: Vec<ListedKey>,
+    /// The cursor to pass in the next request to fetch the following page, or `None` if there are
+    /// no further keys.  When listing dictionary items, a page may hold fewer entries than the
+    /// limit while further items remain.
+    pub next_cursor: Option<String>,
+}
+
+impl DocExample for ListKeysResult {
+    fn doc_example() -> &'static Self {
+        &LIST_KEYS_RESULT
+    }
+}
+
+/// "state_list_keys" RPC.
+pub struct ListKeys {}
+
+#[async_trait]
+impl RpcWithParams for ListKeys {
+    const METHOD: &'static str = "state_list_keys";
+    type RequestParams = ListKeysParams;
+    type ResponseResult = ListKeysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let state_root_hash = match params.state_identifier {
+            None => match effect_builder
+                .get_highest_complete_block_header_from_storage()
+                .await
+            {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "state-list-keys failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let key_prefix = match params.key_prefix {
+            ListKeysPrefix::KeyTag(key_tag) => KeyPrefix::Tag(key_tag.into()),
+            ListKeysPrefix::AccountNamedKey {
+                key,
+                dictionary_name,
+            }
+            | ListKeysPrefix::ContractNamedKey {
+                key,
+                dictionary_name,
+            } => {
+                let base_key = Key::from_formatted_str(&key).map_err(|error| {
+                    Error::new(
+                        ErrorCode::FailedToParseQueryKey,
+                        format!("failed to parse base key: {}", error),
+                    )
+                })?;
+                let (value, _proofs) =
+                    run_query(effect_builder, state_root_hash, base_key, vec![]).await?;
+                let seed_uref = get_dictionary_seed_uref(Some(value), &dictionary_name)?;
+                KeyPrefix::DictionaryItems(seed_uref.addr())
+            }
+            ListKeysPrefix::URef { seed_uref } => {
+                let seed_uref = URef::from_formatted_str(&seed_uref).map_err(|error| {
+                    Error::new(
+                        ErrorCode::FailedToGetDictionaryURef,
+                        format!("failed to parse uref: {}", error),
+                    )
+                })?;
+                KeyPrefix::DictionaryItems(seed_uref.addr())
+            }
+        };
+
+        let cursor = match params.cursor {
+            Some(cursor) => Some(Key::from_formatted_str(&cursor).map_err(|error| {
+                Error::new(
+                    ErrorCode::FailedToParseQueryKey,
+                    format!("failed to parse cursor: {}", error),
+                )
+            })?),
+            None => None,
+        };
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_LIST_KEYS_LIMIT)
+            .clamp(1, MAX_LIST_KEYS_LIMIT) as usize;
+
+        let list_keys_request = ListKeysRequest::new(state_root_hash, key_prefix, cursor, limit);
+        let (entries, next_cursor) = match effect_builder.list_keys(list_keys_request).await {
+            Ok(engine_state::ListKeysResult::Success {
+                entries,
+                next_cursor,
+            }) => (entries, next_cursor),
+            Ok(engine_state::ListKeysResult::RootNotFound) => {
+                info!("state-list-keys failed: root not found");
+                let error = common::missing_block_or_state_root_error(
+                    effect_builder,
+                    ErrorCode::NoSuchStateRoot,
+                    format!("failed to get state root at {:?}", state_root_hash),
+                )
+                .await;
+                return Err(error);
+            }
+            Err(error) => {
+                info!(?error, "state-list-keys failed to execute");
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    format!("{:?}", error),
+                ));
+            }
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|(key, stored_value)| {
+                let stored_value = StoredValue::try_from(stored_value).map_err(|error| {
+                    warn!(?error, %key, "failed to encode stored value");
+                    Error::new(
+                        ReservedErrorCode::InternalError,
+                        format!("failed to encode stored value: {}", error),
+                    )
+                })?;
+                Ok(ListedKey {
+                    key: key.to_formatted_string(),
+                    stored_value,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let result = Self::ResponseResult {
+            api_version,
+            entries,
+            next_cursor: next_cursor.map(|key| key.to_formatted_string()),
+        };
+        Ok(result)
+    }
+}
+
 /// Parameters for "state_get_trie" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetTrieParams {
@@ -1098,6 +1744,154 @@ impl RpcWithParams for GetTrie {
     }
 }
 
+/// Params for "verify_state_proof" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyStateProofParams {
+    /// The identifier of the global state against which to verify the proof.  If none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// `casper_types::Key` as formatted string, the base key of the query being proven.
+    pub key: String,
+    /// The path components starting from the key as base.
+    #[serde(default)]
+    pub path: Vec<String>,
+    /// The stored value expected at the end of the path.
+    pub stored_value: StoredValue,
+    /// The Merkle proof, as returned by "query_global_state".
+    pub merkle_proof: String,
+}
+
+impl DocExample for VerifyStateProofParams {
+    fn doc_example() -> &'static Self {
+        &VERIFY_STATE_PROOF_PARAMS
+    }
+}
+
+/// Result for "verify_state_proof" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyStateProofResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The state root hash against which the proof was verified.
+    pub state_root_hash: Digest,
+    /// Whether the proof is valid for the given key, path and stored value.
+    pub is_valid: bool,
+    /// The reason the proof is invalid, if it is.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+impl DocExample for VerifyStateProofResult {
+    fn doc_example() -> &'static Self {
+        &VERIFY_STATE_PROOF_RESULT
+    }
+}
+
+/// "verify_state_proof" RPC.
+pub struct VerifyStateProof {}
+
+#[async_trait]
+impl RpcWithParams for VerifyStateProof {
+    const METHOD: &'static str = "verify_state_proof";
+    type RequestParams = VerifyStateProofParams;
+    type ResponseResult = VerifyStateProofResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let state_root_hash = match params.state_identifier {
+            None => match effect_builder
+                .get_highest_complete_block_header_from_storage()
+                .await
+            {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "verify-state-proof failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let base_key = Key::from_formatted_str(&params.key).map_err(|error| {
+            Error::new(
+                ErrorCode::FailedToParseQueryKey,
+                format!("failed to parse key: {}", error),
+            )
+        })?;
+
+        let proof_bytes = base16::decode(&params.merkle_proof).map_err(|error| {
+            Error::new(
+                ReservedErrorCode::InvalidParams,
+                format!("failed to decode merkle proof from hex: {}", error),
+            )
+        })?;
+
+        let error = check_state_proof(
+            &state_root_hash,
+            &base_key,
+            &params.path,
+            &params.stored_value,
+            &proof_bytes,
+        )
+        .err();
+
+        Ok(Self::ResponseResult {
+            api_version,
+            state_root_hash,
+            is_valid: error.is_none(),
+            error,
+        })
+    }
+}
+
+/// Verifies the given serialized query proofs against the state root hash, returning the reason
+/// if they are invalid.
+///
+/// The proven value is compared with the expected one in its JSON-compatible form, as that is what
+/// the "verify_state_proof" RPC receives.
+fn check_state_proof(
+    state_root_hash: &Digest,
+    base_key: &Key,
+    path: &[String],
+    expected_value: &StoredValue,
+    proof_bytes: &[u8],
+) -> Result<(), String> {
+    let proofs: Vec<StateProof> = bytesrepr::deserialize_from_slice(proof_bytes)
+        .map_err(|error| format!("failed to parse merkle proof: {}", error))?;
+    let proven_value = proofs
+        .last()
+        .map(StateProof::value)
+        .ok_or_else(|| StateProofError::PathLengthDifferentThanProofLessOne.to_string())?;
+    casper_types::verify_state_proofs(
+        &state_root_hash.value(),
+        &proofs,
+        base_key,
+        path,
+        proven_value,
+    )
+    .map_err(|error| error.to_string())?;
+
+    let proven_value = StoredValue::try_from(proven_value.clone())
+        .map_err(|error| format!("failed to convert proven value: {}", error))?;
+    if proven_value != *expected_value {
+        return Err(StateProofError::UnexpectedValue.to_string());
+    }
+    Ok(())
+}
+
 type QuerySuccess = (
     DomainStoredValue,
     Vec<TrieMerkleProof<Key, DomainStoredValue>>,
@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::rate_limiter::RateLimitConfig;
+
 /// Default binding address for the speculative execution RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -21,12 +23,15 @@ pub struct Config {
     pub enable_server: bool,
     /// Address to bind JSON-RPC speculative execution server to.
     pub address: String,
-    /// Maximum rate limit in queries per second.
+    /// Maximum rate limit in queries per second.  Each request in a JSON-RPC batch counts
+    /// separately.
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
     pub max_body_bytes: u32,
     /// CORS origin.
     pub cors_origin: String,
+    /// Per-client rate limiting configuration.
+    pub rate_limits: RateLimitConfig,
 }
 
 impl Config {
@@ -38,6 +43,13 @@ impl Config {
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            rate_limits: RateLimitConfig {
+                expensive_methods: vec![
+                    "speculative_exec".to_string(),
+                    "account_estimate_gas".to_string(),
+                ],
+                ..RateLimitConfig::default()
+            },
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use hyper::server::{conn::AddrIncoming, Builder};
 
 use casper_json_rpc::{CorsOrigin, RequestHandlersBuilder};
@@ -6,7 +8,12 @@ use casper_types::ProtocolVersion;
 use super::ReactorEventT;
 use crate::{
     effect::EffectBuilder,
-    rpcs::{speculative_exec::SpeculativeExec, RpcWithParams},
+    rpcs::{
+        docs::ListSpeculativeRpcs,
+        speculative_exec::{EstimateGas, SpeculativeExec},
+        RpcWithParams, RpcWithoutParams,
+    },
+    utils::rate_limiter::RateLimiter,
 };
 
 /// The URL path for all JSON-RPC requests.
@@ -14,17 +21,24 @@ pub const SPECULATIVE_EXEC_API_PATH: &str = "rpc";
 
 pub const SPECULATIVE_EXEC_SERVER_NAME: &str = "speculative execution";
 
+/// Batch requests are not supported by the speculative execution server, as each request is
+/// relatively expensive to handle.
+const SPECULATIVE_EXEC_MAX_BATCH_SIZE: u32 = 0;
+
 /// Run the speculative execution server.
 pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
+    rate_limiter: Option<Arc<RateLimiter>>,
     max_body_bytes: u32,
     cors_origin: String,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
     SpeculativeExec::register_as_handler(effect_builder, api_version, &mut handlers);
+    EstimateGas::register_as_handler(effect_builder, api_version, &mut handlers);
+    ListSpeculativeRpcs::register_as_handler(effect_builder, api_version, &mut handlers);
     let handlers = handlers.build();
 
     match cors_origin.as_str() {
@@ -33,9 +47,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                SPECULATIVE_EXEC_MAX_BATCH_SIZE,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
+                None,
             )
             .await;
         }
@@ -44,9 +61,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                SPECULATIVE_EXEC_MAX_BATCH_SIZE,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
+                None,
                 CorsOrigin::Any,
             )
             .await
@@ -56,9 +76,12 @@ pub(super) async fn run<REv: ReactorEventT>(
                 builder,
                 handlers,
                 qps_limit,
+                rate_limiter,
                 max_body_bytes,
+                SPECULATIVE_EXEC_MAX_BATCH_SIZE,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
+                None,
                 CorsOrigin::Specified(cors_origin),
             )
             .await
@@ -0,0 +1,688 @@
+//! WebSocket transport for the JSON-RPC server.
+//!
+//! A client connected via a WebSocket can make the same JSON-RPC requests as are served over HTTP,
+//! and can additionally subscribe to be notified of the events which are also published by the
+//! event stream server.
+//!
+//! Subscriptions are made via the `subscribe` method, which returns a subscription ID.  Matching
+//! events are then sent to the client as JSON-RPC notifications of the form:
+//! ```json
+//! {
+//!     "jsonrpc": "2.0",
+//!     "method": "subscription",
+//!     "params": { "subscription_id": <u64>, "event": <SseData> }
+//! }
+//! ```
+//! until the subscription is cancelled via the `unsubscribe` method, or the connection is closed.
+//!
+//! The number of connections, and the number of requests handled concurrently on each connection,
+//! are limited.  A client which doesn't keep up with the responses and notifications sent to it is
+//! disconnected.  Each request received over a WebSocket counts towards the server's global
+//! `qps_limit`, with each request in a batch counted separately.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use http::StatusCode;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use serde_json::{json, Value};
+use tokio::{
+    select,
+    sync::{broadcast, mpsc, OwnedSemaphorePermit, Semaphore},
+};
+use tracing::{debug, info};
+use warp::{
+    filters::BoxedFilter,
+    reply::{self, Response},
+    ws::{Message, WebSocket, Ws},
+    Filter, Reply,
+};
+
+use casper_json_rpc::{filters, Error, Params, RequestHandlersBuilder, ReservedErrorCode};
+use casper_types::PublicKey;
+
+use super::rpcs::{
+    ErrorCode, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST, GLOBAL_RATE_LIMITER, RATE_LIMITED_CLIENT,
+};
+use crate::{
+    components::event_stream_server::SseData,
+    types::DeployHash,
+    utils::rate_limiter::{ClientId, GlobalRateLimiter, RateLimiter},
+};
+
+/// The URL path for WebSocket connections.
+pub const WEBSOCKET_API_PATH: &str = "ws";
+
+/// The JSON-RPC "method" used to create a subscription.
+const SUBSCRIBE_METHOD: &str = "subscribe";
+/// The JSON-RPC "method" used to cancel a subscription.
+const UNSUBSCRIBE_METHOD: &str = "unsubscribe";
+/// The JSON-RPC "method" of the notifications sent to subscribers.
+const NOTIFICATION_METHOD: &str = "subscription";
+/// The maximum number of responses and notifications buffered for sending on a single connection.
+const OUTGOING_BUFFER_LENGTH: usize = 100;
+
+/// The ID of a subscription, unique within a single WebSocket connection.
+type SubscriptionId = u64;
+
+/// The types of event which can be subscribed to.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+enum EventType {
+    BlockAdded,
+    DeployAccepted,
+    DeployProcessed,
+    DeployExpired,
+    Fault,
+    FinalitySignature,
+    Step,
+}
+
+impl EventType {
+    /// Returns the type of the given event, or `None` if it cannot be subscribed to.
+    fn of(sse_data: &SseData) -> Option<Self> {
+        match sse_data {
+            SseData::BlockAdded { .. } => Some(EventType::BlockAdded),
+            SseData::DeployAccepted { .. } => Some(EventType::DeployAccepted),
+            SseData::DeployProcessed { .. } => Some(EventType::DeployProcessed),
+            SseData::DeployExpired { .. } => Some(EventType::DeployExpired),
+            SseData::Fault { .. } => Some(EventType::Fault),
+            SseData::FinalitySignature(_) => Some(EventType::FinalitySignature),
+            SseData::Step { .. } => Some(EventType::Step),
+            SseData::ApiVersion(_)
+            | SseData::DeployStatusChanged { .. }
+            | SseData::EraEnd { .. }
+            | SseData::BlockFinalized { .. }
+            | SseData::NodeStateChanged { .. }
+            | SseData::Shutdown => None,
+        }
+    }
+}
+
+/// Params for the "subscribe" method.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct SubscribeParams {
+    /// The types of event to be notified of.  If empty, all types are included.
+    #[serde(default)]
+    event_types: Vec<EventType>,
+    /// If provided, only events relating to this deploy are included, i.e. the `BlockAdded` of
+    /// the block containing it and its `DeployAccepted`, `DeployProcessed` and `DeployExpired`.
+    #[serde(default)]
+    deploy_hash: Option<DeployHash>,
+    /// If provided, only events relating to this account are included, i.e. the `BlockAdded` of
+    /// blocks it proposed, the `DeployAccepted` and `DeployProcessed` of deploys it sent, and the
+    /// `Fault`s and `FinalitySignature`s attributed to it.
+    #[serde(default)]
+    account: Option<PublicKey>,
+}
+
+impl SubscribeParams {
+    /// Returns `true` if the given event should be sent to the subscriber.
+    fn matches(&self, sse_data: &SseData) -> bool {
+        let event_type = match EventType::of(sse_data) {
+            Some(event_type) => event_type,
+            None => return false,
+        };
+        if !self.event_types.is_empty() && !self.event_types.contains(&event_type) {
+            return false;
+        }
+
+        if let Some(deploy_hash) = self.deploy_hash.as_ref() {
+            let relates_to_deploy = match sse_data {
+                SseData::BlockAdded { block, .. } => {
+                    block.deploy_hashes().contains(deploy_hash)
+                        || block.transfer_hashes().contains(deploy_hash)
+                }
+                SseData::DeployAccepted { deploy } => deploy.hash() == deploy_hash,
+                SseData::DeployProcessed {
+                    deploy_hash: processed_deploy_hash,
+                    ..
+                } => **processed_deploy_hash == *deploy_hash,
+                SseData::DeployExpired {
+                    deploy_hash: expired_deploy_hash,
+                } => expired_deploy_hash == deploy_hash,
+                _ => false,
+            };
+            if !relates_to_deploy {
+                return false;
+            }
+        }
+
+        if let Some(account) = self.account.as_ref() {
+            let relates_to_account = match sse_data {
+                SseData::BlockAdded { block, .. } => block.proposer() == account,
+                SseData::DeployAccepted { deploy } => deploy.header().account() == account,
+                SseData::DeployProcessed {
+                    account: deploy_account,
+                    ..
+                } => **deploy_account == *account,
+                SseData::Fault { public_key, .. } => **public_key == *account,
+                SseData::FinalitySignature(signature) => signature.public_key == *account,
+                _ => false,
+            };
+            if !relates_to_account {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Result for the "subscribe" and "unsubscribe" methods.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct SubscriptionResult {
+    /// The ID of the created or cancelled subscription.
+    subscription_id: SubscriptionId,
+}
+
+/// Params for the "unsubscribe" method.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct UnsubscribeParams {
+    /// The ID of the subscription to cancel.
+    subscription_id: SubscriptionId,
+}
+
+/// The subscriptions held by a single WebSocket connection.
+#[derive(Debug)]
+struct Subscriptions {
+    next_id: SubscriptionId,
+    max_subscriptions: u32,
+    subscriptions: BTreeMap<SubscriptionId, SubscribeParams>,
+}
+
+impl Subscriptions {
+    fn new(max_subscriptions: u32) -> Self {
+        Subscriptions {
+            next_id: 0,
+            max_subscriptions,
+            subscriptions: BTreeMap::new(),
+        }
+    }
+
+    fn subscribe(&mut self, params: SubscribeParams) -> Result<SubscriptionResult, Error> {
+        if self.subscriptions.len() >= self.max_subscriptions as usize {
+            return Err(Error::new(
+                ErrorCode::TooManySubscriptions,
+                format!(
+                    "at most {} subscriptions are allowed per connection",
+                    self.max_subscriptions
+                ),
+            ));
+        }
+        let subscription_id = self.next_id;
+        self.next_id += 1;
+        let _ = self.subscriptions.insert(subscription_id, params);
+        Ok(SubscriptionResult { subscription_id })
+    }
+
+    fn unsubscribe(&mut self, params: UnsubscribeParams) -> Result<SubscriptionResult, Error> {
+        match self.subscriptions.remove(&params.subscription_id) {
+            Some(_) => Ok(SubscriptionResult {
+                subscription_id: params.subscription_id,
+            }),
+            None => Err(Error::new(
+                ErrorCode::NoSuchSubscription,
+                format!("no subscription with ID {}", params.subscription_id),
+            )),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Returns the JSON-encoded notifications of the given event, one per matching subscription.
+    fn notifications(&self, sse_data: &SseData) -> Vec<String> {
+        self.subscriptions
+            .iter()
+            .filter(|(_, params)| params.matches(sse_data))
+            .map(|(subscription_id, _)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": NOTIFICATION_METHOD,
+                    "params": {
+                        "subscription_id": subscription_id,
+                        "event": sse_data,
+                    }
+                })
+                .to_string()
+            })
+            .collect()
+    }
+}
+
+/// Tries to parse the incoming JSON-RPC request's "params" field as `T`.
+fn try_parse_params<T: DeserializeOwned>(maybe_params: Option<Params>) -> Result<T, Error> {
+    let params = match maybe_params {
+        Some(params) => Value::from(params),
+        None => {
+            return Err(Error::new(
+                ReservedErrorCode::InvalidParams,
+                "Missing 'params' field",
+            ))
+        }
+    };
+    serde_json::from_value::<T>(params).map_err(|error| {
+        Error::new(
+            ReservedErrorCode::InvalidParams,
+            format!("Failed to parse 'params' field: {}", error),
+        )
+    })
+}
+
+/// Returns the number of JSON-RPC requests in the given message, for the purposes of rate limiting.
+///
+/// Each entry of a batch counts as a request, unless the batch holds more than `max_batch_size`
+/// entries in which case none of them are handled.  Any other message, including one which is not
+/// valid JSON, counts as a single request.
+fn request_count(message: &[u8], max_batch_size: u32) -> u64 {
+    match serde_json::from_slice::<Vec<IgnoredAny>>(message) {
+        Ok(batch) if batch.len() <= max_batch_size as usize => batch.len().max(1) as u64,
+        _ => 1,
+    }
+}
+
+/// Returns a boxed warp filter which upgrades connections on the WebSocket path, and serves
+/// JSON-RPC requests and subscriptions on them.
+///
+/// `handlers` are the handlers for all JSON-RPC methods other than "subscribe" and "unsubscribe",
+/// which are added to a copy of `handlers` for each connection.
+///
+/// Once `max_connections` connections are open, further connection attempts are rejected with a
+/// 503 (Service Unavailable).
+pub(super) fn websocket_filter(
+    handlers: RequestHandlersBuilder,
+    sse_data_broadcaster: broadcast::Sender<SseData>,
+    max_batch_size: u32,
+    max_subscriptions: u32,
+    max_connections: u32,
+    max_concurrent_requests: u32,
+) -> BoxedFilter<(Response,)> {
+    let connection_permits = Arc::new(Semaphore::new(max_connections as usize));
+    warp::path(WEBSOCKET_API_PATH)
+        .and(warp::path::end())
+        .and(warp::ws())
+        .map(move |ws: Ws| {
+            // The permit is held until the connection is closed, or the upgrade fails.
+            let connection_permit = match Arc::clone(&connection_permits).try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    info!(
+                        %max_connections,
+                        "websocket server has max connections: rejecting new one"
+                    );
+                    return reply::with_status(
+                        "server has reached limit of websocket connections",
+                        StatusCode::SERVICE_UNAVAILABLE,
+                    )
+                    .into_response();
+                }
+            };
+            let handlers = handlers.clone();
+            let sse_data_receiver = sse_data_broadcaster.subscribe();
+            // The connection is served in a new task once upgraded, so the rate limits must be
+            // captured from the upgrade request's task.
+            let maybe_client = RATE_LIMITED_CLIENT.try_with(Clone::clone).ok().flatten();
+            let maybe_global_rate_limiter = GLOBAL_RATE_LIMITER.try_with(Arc::clone).ok();
+            ws.on_upgrade(move |websocket| {
+                handle_connection(
+                    websocket,
+                    handlers,
+                    sse_data_receiver,
+                    maybe_client,
+                    maybe_global_rate_limiter,
+                    max_batch_size,
+                    max_subscriptions,
+                    max_concurrent_requests,
+                    connection_permit,
+                )
+            })
+            .into_response()
+        })
+        .boxed()
+}
+
+/// Serves a single WebSocket connection until it is closed by either side.
+///
+/// Each incoming message is handled as a JSON-RPC request or batch of requests in a separate task,
+/// so that slow requests don't delay other responses or notifications.  At most
+/// `max_concurrent_requests` such tasks run at a time; further messages are not read from the
+/// connection until one of them completes.  Each task waits for the global rate limit to allow the
+/// message's requests before handling them.
+///
+/// The connection is dropped if the client falls behind in receiving notifications.
+#[allow(clippy::too_many_arguments)]
+async fn handle_connection(
+    websocket: WebSocket,
+    mut handlers: RequestHandlersBuilder,
+    mut sse_data_receiver: broadcast::Receiver<SseData>,
+    maybe_client: Option<(Arc<RateLimiter>, ClientId)>,
+    maybe_global_rate_limiter: Option<Arc<GlobalRateLimiter>>,
+    max_batch_size: u32,
+    max_subscriptions: u32,
+    max_concurrent_requests: u32,
+    _connection_permit: OwnedSemaphorePermit,
+) {
+    let subscriptions = Arc::new(Mutex::new(Subscriptions::new(max_subscriptions)));
+
+    let cloned_subscriptions = Arc::clone(&subscriptions);
+    let subscribe_handler = move |maybe_params| {
+        let subscriptions = Arc::clone(&cloned_subscriptions);
+        async move {
+            let params = try_parse_params(maybe_params)?;
+            subscriptions.lock().unwrap().subscribe(params)
+        }
+    };
+    handlers.register_handler(SUBSCRIBE_METHOD, Arc::new(subscribe_handler));
+
+    let cloned_subscriptions = Arc::clone(&subscriptions);
+    let unsubscribe_handler = move |maybe_params| {
+        let subscriptions = Arc::clone(&cloned_subscriptions);
+        async move {
+            let params = try_parse_params(maybe_params)?;
+            subscriptions.lock().unwrap().unsubscribe(params)
+        }
+    };
+    handlers.register_handler(UNSUBSCRIBE_METHOD, Arc::new(unsubscribe_handler));
+
+    let handlers = handlers.build();
+    let (mut websocket_sender, mut websocket_receiver) = websocket.split();
+    let (outgoing_sender, mut outgoing_receiver) = mpsc::channel::<String>(OUTGOING_BUFFER_LENGTH);
+    let request_permits = Arc::new(Semaphore::new(max_concurrent_requests as usize));
+    let mut maybe_request_permit = None;
+
+    'connection: loop {
+        select! {
+            Ok(permit) = Arc::clone(&request_permits).acquire_owned(),
+                if maybe_request_permit.is_none() =>
+            {
+                maybe_request_permit = Some(permit);
+            }
+
+            maybe_message = websocket_receiver.next(), if maybe_request_permit.is_some() => {
+                let message = match maybe_message {
+                    Some(Ok(message)) => message,
+                    Some(Err(error)) => {
+                        debug!(%error, "websocket connection failed");
+                        break;
+                    }
+                    None => break,
+                };
+                if message.is_close() {
+                    break;
+                }
+                // Pings are answered by the underlying WebSocket implementation.
+                if !message.is_text() && !message.is_binary() {
+                    continue;
+                }
+                let request_permit = maybe_request_permit.take();
+                let handlers = handlers.clone();
+                let outgoing_sender = outgoing_sender.clone();
+                let maybe_global_rate_limiter = maybe_global_rate_limiter.clone();
+                let handle_message = async move {
+                    // The handlers aren't run in the scope of `GLOBAL_RATE_LIMITER`, so the
+                    // requests are only counted here.
+                    if let Some(global_rate_limiter) = maybe_global_rate_limiter {
+                        let request_count = request_count(message.as_bytes(), max_batch_size);
+                        global_rate_limiter.acquire(request_count).await;
+                    }
+                    if let Some(response) = filters::handle_message(
+                        Bytes::from(message.into_bytes()),
+                        handlers,
+                        ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                        max_batch_size,
+                    )
+                    .await
+                    {
+                        // This can validly fail if the connection has closed in the meantime.
+                        let _ = outgoing_sender.send(response).await;
+                    }
+                    drop(request_permit);
+                };
+                tokio::spawn(RATE_LIMITED_CLIENT.scope(maybe_client.clone(), handle_message));
+            }
+
+            result = sse_data_receiver.recv() => {
+                match result {
+                    Ok(sse_data) => {
+                        let notifications = subscriptions.lock().unwrap().notifications(&sse_data);
+                        for notification in notifications {
+                            if outgoing_sender.try_send(notification).is_err() {
+                                info!("websocket client too slow: dropping connection to client");
+                                break 'connection;
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(lagged_count)) => {
+                        // Events missed while there are no subscriptions are of no concern.
+                        if !subscriptions.lock().unwrap().is_empty() {
+                            info!(
+                                %lagged_count,
+                                "websocket client lagged: dropping connection to client"
+                            );
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+
+            Some(outgoing) = outgoing_receiver.recv() => {
+                if let Err(error) = websocket_sender.send(Message::text(outgoing)).await {
+                    debug!(%error, "failed to send on websocket connection");
+                    break;
+                }
+            }
+        }
+    }
+
+    let _ = websocket_sender.close().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+    use crate::components::rpc_server::rpcs::{
+        docs::DocExample,
+        info::{GetPeers, GetPeersResult},
+        RpcWithoutParams,
+    };
+
+    fn subscribe_params(value: Value) -> SubscribeParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn should_match_by_event_type() {
+        let mut rng = TestRng::new();
+        let block_added = SseData::random_block_added(&mut rng);
+        let deploy_expired = SseData::random_deploy_expired(&mut rng);
+
+        let all = subscribe_params(json!({}));
+        assert!(all.matches(&block_added));
+        assert!(all.matches(&deploy_expired));
+        assert!(!all.matches(&SseData::Shutdown));
+
+        let blocks_only = subscribe_params(json!({ "event_types": ["BlockAdded"] }));
+        assert!(blocks_only.matches(&block_added));
+        assert!(!blocks_only.matches(&deploy_expired));
+    }
+
+    #[test]
+    fn should_match_by_deploy_hash() {
+        let mut rng = TestRng::new();
+        let (deploy_accepted, deploy) = SseData::random_deploy_accepted(&mut rng);
+        let other_deploy_expired = SseData::random_deploy_expired(&mut rng);
+        let deploy_expired = SseData::DeployExpired {
+            deploy_hash: *deploy.hash(),
+        };
+
+        let params = subscribe_params(json!({ "deploy_hash": deploy.hash() }));
+        assert!(params.matches(&deploy_accepted));
+        assert!(params.matches(&deploy_expired));
+        assert!(!params.matches(&other_deploy_expired));
+        assert!(!params.matches(&SseData::random_step(&mut rng)));
+    }
+
+    #[test]
+    fn should_match_by_account() {
+        let mut rng = TestRng::new();
+        let (deploy_accepted, deploy) = SseData::random_deploy_accepted(&mut rng);
+        let (other_deploy_accepted, _) = SseData::random_deploy_accepted(&mut rng);
+
+        let params = subscribe_params(json!({ "account": deploy.header().account() }));
+        assert!(params.matches(&deploy_accepted));
+        assert!(!params.matches(&other_deploy_accepted));
+        assert!(!params.matches(&SseData::random_deploy_expired(&mut rng)));
+    }
+
+    #[test]
+    fn should_count_requests_in_message() {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "info_get_peers" });
+        let single = request.to_string();
+        assert_eq!(request_count(single.as_bytes(), 3), 1);
+
+        let batch = json!([request, request, request]).to_string();
+        assert_eq!(request_count(batch.as_bytes(), 3), 3);
+        // Oversized batches are rejected without handling any of their requests.
+        assert_eq!(request_count(batch.as_bytes(), 2), 1);
+
+        assert_eq!(request_count(b"[]", 3), 1);
+        assert_eq!(request_count(b"not json", 3), 1);
+    }
+
+    #[test]
+    fn should_limit_and_cancel_subscriptions() {
+        let mut rng = TestRng::new();
+        let mut subscriptions = Subscriptions::new(2);
+        let first = subscriptions
+            .subscribe(subscribe_params(json!({})))
+            .unwrap()
+            .subscription_id;
+        let second = subscriptions
+            .subscribe(subscribe_params(json!({ "event_types": ["Step"] })))
+            .unwrap()
+            .subscription_id;
+        assert_ne!(first, second);
+        assert!(subscriptions
+            .subscribe(subscribe_params(json!({})))
+            .is_err());
+
+        let step = SseData::random_step(&mut rng);
+        assert_eq!(subscriptions.notifications(&step).len(), 2);
+
+        subscriptions
+            .unsubscribe(UnsubscribeParams {
+                subscription_id: second,
+            })
+            .unwrap();
+        assert!(subscriptions
+            .unsubscribe(UnsubscribeParams {
+                subscription_id: second,
+            })
+            .is_err());
+
+        let notifications = subscriptions.notifications(&step);
+        assert_eq!(notifications.len(), 1);
+        let notification: Value = serde_json::from_str(&notifications[0]).unwrap();
+        assert_eq!(notification["method"], NOTIFICATION_METHOD);
+        assert_eq!(notification["params"]["subscription_id"], first);
+        assert_eq!(
+            notification["params"]["event"],
+            serde_json::to_value(&step).unwrap()
+        );
+    }
+
+    /// Returns the next text message received on the given client, parsed as JSON.
+    async fn recv_json(client: &mut warp::test::WsClient) -> Value {
+        let message = client.recv().await.unwrap();
+        serde_json::from_str(message.to_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_serve_requests_and_subscriptions_over_websocket() {
+        let mut rng = TestRng::new();
+        let mut handlers = RequestHandlersBuilder::new();
+        GetPeers::register_as_test_handler(&mut handlers);
+        let (sse_data_broadcaster, _) = broadcast::channel(10);
+        let filter = websocket_filter(handlers, sse_data_broadcaster.clone(), 10, 10, 1, 1);
+
+        let mut client = warp::test::ws()
+            .path("/ws")
+            .handshake(filter.clone())
+            .await
+            .expect("should connect");
+
+        // Only a single connection is allowed.
+        assert!(warp::test::ws()
+            .path("/ws")
+            .handshake(filter.clone())
+            .await
+            .is_err());
+
+        client
+            .send_text(json!({ "jsonrpc": "2.0", "id": 1, "method": GetPeers::METHOD }).to_string())
+            .await;
+        let response = recv_json(&mut client).await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(
+            response["result"],
+            serde_json::to_value(GetPeersResult::doc_example()).unwrap()
+        );
+
+        client
+            .send_text(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": SUBSCRIBE_METHOD,
+                    "params": { "event_types": ["Step"] }
+                })
+                .to_string(),
+            )
+            .await;
+        let response = recv_json(&mut client).await;
+        assert_eq!(response["id"], 2);
+        let subscription_id = response["result"]["subscription_id"].clone();
+
+        // Only the subscribed-to type of event should be notified.
+        let step = SseData::random_step(&mut rng);
+        sse_data_broadcaster
+            .send(SseData::random_deploy_expired(&mut rng))
+            .unwrap();
+        sse_data_broadcaster.send(step.clone()).unwrap();
+        let notification = recv_json(&mut client).await;
+        assert_eq!(notification["method"], NOTIFICATION_METHOD);
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        assert_eq!(
+            notification["params"]["event"],
+            serde_json::to_value(&step).unwrap()
+        );
+
+        // Once the connection is closed, a new one should be allowed.
+        drop(client);
+        let mut attempts = 0;
+        while warp::test::ws()
+            .path("/ws")
+            .handshake(filter.clone())
+            .await
+            .is_err()
+        {
+            attempts += 1;
+            assert!(attempts < 100, "should eventually allow a new connection");
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+}
@@ -6,7 +6,8 @@
 //! * storing and loading blocks,
 //! * storing and loading deploys,
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
-//! * keeping an index of blocks by height and
+//! * keeping an index of blocks by height,
+//! * optionally keeping an index of deploys by the accounts which signed or paid for them and
 //! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
@@ -30,6 +31,7 @@
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
 
+mod account_deploy_index;
 pub(crate) mod disjoint_sequences;
 mod error;
 mod lmdb_ext;
@@ -70,6 +72,7 @@ use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
 use casper_types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp, Transfer, Transform,
 };
@@ -128,7 +131,7 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 9;
+const MAX_DB_COUNT: u32 = 10;
 /// Key under which completed blocks are to be stored.
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
 /// Name of the file created when initializing a force resync.
@@ -191,6 +194,9 @@ pub struct Storage {
     /// The finalized approvals database.
     #[data_size(skip)]
     finalized_approvals_db: Database,
+    /// The database indexing deploys by the accounts which signed or paid for them.
+    #[data_size(skip)]
+    account_deploy_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -205,6 +211,8 @@ pub struct Storage {
     key_block_height_for_activation_point: Option<u64>,
     /// Whether or not memory deduplication is enabled.
     enable_mem_deduplication: bool,
+    /// Whether or not the index of deploys by account is maintained.
+    enable_account_deploy_index: bool,
     /// An in-memory pool of already loaded serialized items.
     ///
     /// Keyed by serialized item ID, contains the serialized item.
@@ -416,6 +424,7 @@ impl Storage {
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
         let approvals_hashes_db =
             env.create_db(Some("approvals_hashes"), DatabaseFlags::empty())?;
+        let account_deploy_db = env.create_db(Some("account_deploys"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("indexing block store");
@@ -491,6 +500,7 @@ impl Storage {
 
         initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
         initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        initialize_account_deploy_db(&env, &account_deploy_db, &deleted_deploy_hashes)?;
 
         let metrics = registry.map(Metrics::new).transpose()?;
 
@@ -506,6 +516,7 @@ impl Storage {
             transfer_db,
             state_store_db,
             finalized_approvals_db,
+            account_deploy_db,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
@@ -513,6 +524,7 @@ impl Storage {
             activation_era,
             key_block_height_for_activation_point: None,
             enable_mem_deduplication: config.enable_mem_deduplication,
+            enable_account_deploy_index: config.enable_account_deploy_index,
             serialized_item_pool: ObjectPool::new(config.mem_pool_prune_interval),
             recent_era_count,
             max_ttl,
@@ -1006,6 +1018,14 @@ impl Storage {
                     }))
                     .ignore()
             }
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                cursor,
+                limit,
+                responder,
+            } => responder
+                .respond(self.read_account_deploys(*account_hash, cursor, limit)?)
+                .ignore(),
             StorageRequest::GetFinalitySignature { id, responder } => {
                 let mut txn = self.env.begin_ro_txn()?;
                 let maybe_sig = self
@@ -1053,6 +1073,20 @@ impl Storage {
                     }))
                     .ignore()
             }
+            StorageRequest::GetBlocksAndMetadataByHeightRange {
+                start_height,
+                end_height,
+                include_bodies,
+                only_from_available_block_range,
+                responder,
+            } => responder
+                .respond(self.read_blocks_with_metadata_by_height_range(
+                    start_height,
+                    end_height,
+                    include_bodies,
+                    only_from_available_block_range,
+                )?)
+                .ignore(),
             StorageRequest::GetHighestBlockWithMetadata {
                 only_from_available_block_range,
                 responder,
@@ -1154,6 +1188,15 @@ impl Storage {
                     .read_block_header_by_height(block_height, only_from_available_block_range)?;
                 responder.respond(maybe_header).ignore()
             }
+            StorageRequest::GetSwitchBlockHeaderByEra {
+                era_id,
+                only_from_available_block_range,
+                responder,
+            } => {
+                let maybe_header = self
+                    .read_switch_block_header_by_era_id(era_id, only_from_available_block_range)?;
+                responder.respond(maybe_header).ignore()
+            }
             StorageRequest::PutBlockHeader {
                 block_header,
                 responder,
@@ -1213,6 +1256,15 @@ impl Storage {
         }: MarkBlockCompletedRequest,
     ) -> Result<Effects<Event>, FatalStorageError> {
         let is_new = self.mark_block_complete(block_height)?;
+        if is_new && self.enable_account_deploy_index {
+            // All the block's deploys are stored by the time it is marked complete.
+            let env = Rc::clone(&self.env);
+            let mut txn = env.begin_rw_txn()?;
+            if let Some(block) = self.get_block_by_height(&mut txn, block_height)? {
+                self.write_account_deploy_index(&mut txn, &block)?;
+                txn.commit()?;
+            }
+        }
         Ok(responder.respond(is_new).ignore())
     }
 
@@ -1455,6 +1507,9 @@ impl Storage {
         let mut txn = env.begin_rw_txn()?;
         let wrote = self.write_validated_block(&mut txn, block)?;
         if wrote {
+            if self.enable_account_deploy_index {
+                self.write_account_deploy_index(&mut txn, block)?;
+            }
             // Update the `completed_blocks` index only if the block was actually stored.
             let _ = self.mark_block_complete(block.height())?;
             txn.commit()?;
@@ -1462,6 +1517,30 @@ impl Storage {
         Ok(wrote)
     }
 
+    /// Indexes the block's deploys by the accounts which signed or paid for them.
+    ///
+    /// Deploys which are not held in storage are skipped.
+    fn write_account_deploy_index(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), FatalStorageError> {
+        for deploy_hash in block.deploy_and_transfer_hashes() {
+            let deploy: Deploy = match txn.get_value(self.deploy_db, deploy_hash)? {
+                Some(deploy) => deploy,
+                None => {
+                    debug!(%deploy_hash, block_hash = %block.hash(), "not indexing missing deploy");
+                    continue;
+                }
+            };
+            for account_hash in account_deploy_index::accounts_of(&deploy) {
+                let key = account_deploy_index::key(&account_hash, block.height(), deploy_hash);
+                let _ = txn.put_value(self.account_deploy_db, &key, block.hash(), true)?;
+            }
+        }
+        Ok(())
+    }
+
     fn write_execution_results(
         &mut self,
         txn: &mut RwTransaction,
@@ -1689,6 +1768,145 @@ impl Storage {
         res
     }
 
+    /// Retrieves the header of the switch block of the given era.
+    pub(crate) fn read_switch_block_header_by_era_id(
+        &self,
+        era_id: EraId,
+        only_from_available_block_range: bool,
+    ) -> Result<Option<BlockHeader>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_header = self.get_switch_block_header_by_era_id(&mut txn, era_id)?;
+        match maybe_header {
+            Some(header)
+                if self
+                    .should_return_block(header.height(), only_from_available_block_range)? =>
+            {
+                Ok(Some(header))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Retrieves the headers of the blocks in the inclusive range of heights, in ascending order,
+    /// along with their finality signatures and, if `include_bodies` is set, their bodies.
+    ///
+    /// Returns `Err` with the heights of the blocks which are missing, or which are outside the
+    /// available block range if `only_from_available_block_range` is set.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn read_blocks_with_metadata_by_height_range(
+        &self,
+        start_height: u64,
+        end_height: u64,
+        include_bodies: bool,
+        only_from_available_block_range: bool,
+    ) -> Result<
+        Result<Vec<(BlockHeaderWithMetadata, Option<BlockBody>)>, Vec<u64>>,
+        FatalStorageError,
+    > {
+        let mut txn = self.env.begin_ro_txn()?;
+        let mut blocks = Vec::new();
+        let mut missing_heights = Vec::new();
+        for height in start_height..=end_height {
+            if !self.should_return_block(height, only_from_available_block_range)? {
+                missing_heights.push(height);
+                continue;
+            }
+            let block_header = match self.block_height_index.get(&height) {
+                Some(block_hash) => self.get_single_block_header(&mut txn, block_hash)?,
+                None => None,
+            };
+            let block_header = match block_header {
+                Some(block_header) => block_header,
+                None => {
+                    missing_heights.push(height);
+                    continue;
+                }
+            };
+            let block_body = if include_bodies {
+                match get_body_for_block_header(
+                    &mut txn,
+                    block_header.body_hash(),
+                    self.block_body_db,
+                )? {
+                    Some(block_body) => Some(block_body),
+                    None => {
+                        missing_heights.push(height);
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+            let block_hash = block_header.block_hash();
+            let block_signatures = match self.get_block_signatures(&mut txn, &block_hash)? {
+                Some(signatures) => signatures,
+                None => BlockSignatures::new(block_hash, block_header.era_id()),
+            };
+            let block_header_with_metadata = BlockHeaderWithMetadata {
+                block_header,
+                block_signatures,
+            };
+            blocks.push((block_header_with_metadata, block_body));
+        }
+        if !missing_heights.is_empty() {
+            return Ok(Err(missing_heights));
+        }
+        Ok(Ok(blocks))
+    }
+
+    /// Retrieves up to `limit` of the deploys signed or paid for by the given account, along with
+    /// the hashes and heights of the blocks containing them, in ascending order of block height.
+    ///
+    /// If `cursor` is `Some`, only deploys after the given block height and deploy hash are
+    /// returned.  Returns `None` if the index of deploys by account is not enabled.
+    pub(crate) fn read_account_deploys(
+        &self,
+        account_hash: AccountHash,
+        cursor: Option<(u64, DeployHash)>,
+        limit: usize,
+    ) -> Result<Option<Vec<(DeployHash, BlockHashAndHeight)>>, FatalStorageError> {
+        if !self.enable_account_deploy_index {
+            return Ok(None);
+        }
+
+        let start_key = match &cursor {
+            Some((block_height, deploy_hash)) => {
+                account_deploy_index::key(&account_hash, *block_height, deploy_hash)
+            }
+            None => account_hash.as_bytes().to_vec(),
+        };
+
+        let txn = self.env.begin_ro_txn()?;
+        let mut lmdb_cursor = txn.open_ro_cursor(self.account_deploy_db)?;
+        let mut account_deploys = Vec::new();
+        for row in lmdb_cursor.iter_from(&start_key) {
+            if account_deploys.len() >= limit {
+                break;
+            }
+            let (raw_key, raw_val) = row?;
+            if !account_deploy_index::is_for_account(raw_key, &account_hash) {
+                break;
+            }
+            if raw_key == start_key.as_slice() {
+                // Skip the entry the cursor points at, as it was part of the previous page.
+                continue;
+            }
+            let (block_height, deploy_hash) = match account_deploy_index::parse_key(raw_key) {
+                Some(parsed) => parsed,
+                None => {
+                    warn!(?raw_key, "malformed key in account deploy index");
+                    continue;
+                }
+            };
+            let block_hash: BlockHash = lmdb_ext::deserialize(raw_val)?;
+            account_deploys.push((
+                deploy_hash,
+                BlockHashAndHeight::new(block_hash, block_height),
+            ));
+        }
+        Ok(Some(account_deploys))
+    }
+
     /// Retrieves a single block header by hash.
     pub fn read_block_header(
         &self,
@@ -2784,6 +3002,11 @@ pub struct Config {
     pub max_state_store_size: usize,
     /// Whether or not memory deduplication is enabled.
     pub enable_mem_deduplication: bool,
+    /// Whether or not to maintain an index of deploys by the accounts which signed or paid for
+    /// them.
+    ///
+    /// Only blocks completed while the index is enabled are indexed.
+    pub enable_account_deploy_index: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
 }
@@ -2798,6 +3021,7 @@ impl Default for Config {
             max_deploy_metadata_store_size: DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE,
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
+            enable_account_deploy_index: false,
             mem_pool_prune_interval: 4096,
         }
     }
@@ -3019,3 +3243,32 @@ fn initialize_deploy_metadata_db(
     info!("deploy metadata database initialized");
     Ok(())
 }
+
+/// Purges stale entries from the account deploy index.
+fn initialize_account_deploy_db(
+    env: &Environment,
+    account_deploy_db: &Database,
+    deleted_deploy_hashes: &HashSet<DeployHash>,
+) -> Result<(), FatalStorageError> {
+    // The index is keyed by account, so purging requires a full scan; skip it if possible.
+    if deleted_deploy_hashes.is_empty() {
+        return Ok(());
+    }
+
+    info!("initializing account deploy database");
+    let mut txn = env.begin_rw_txn()?;
+    let mut cursor = txn.open_rw_cursor(*account_deploy_db)?;
+    for row in cursor.iter() {
+        let (raw_key, _) = row?;
+        if let Some((_, deploy_hash)) = account_deploy_index::parse_key(raw_key) {
+            if deleted_deploy_hashes.contains(&deploy_hash) {
+                cursor.del(WriteFlags::empty())?;
+            }
+        }
+    }
+    drop(cursor);
+    txn.commit()?;
+
+    info!("account deploy database initialized");
+    Ok(())
+}
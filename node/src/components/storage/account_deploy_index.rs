@@ -0,0 +1,93 @@
+//! Key encoding for the optional index of deploys by the accounts which signed or paid for them.
+//!
+//! Each key is the account hash, followed by the big-endian height of the block containing the
+//! deploy, followed by the deploy hash.  Iterating the database from an account hash therefore
+//! yields that account's deploys in ascending order of block height.  The value stored under each
+//! key is the hash of the block containing the deploy.
+
+use std::{collections::BTreeSet, convert::TryFrom, iter, mem};
+
+use casper_hashing::Digest;
+use casper_types::account::{AccountHash, ACCOUNT_HASH_LENGTH};
+
+use crate::types::{Deploy, DeployHash};
+
+const BLOCK_HEIGHT_LENGTH: usize = mem::size_of::<u64>();
+const KEY_LENGTH: usize = ACCOUNT_HASH_LENGTH + BLOCK_HEIGHT_LENGTH + Digest::LENGTH;
+
+/// Returns the index key for the given account, block height and deploy hash.
+pub(super) fn key(
+    account_hash: &AccountHash,
+    block_height: u64,
+    deploy_hash: &DeployHash,
+) -> Vec<u8> {
+    let mut key = Vec::with_capacity(KEY_LENGTH);
+    key.extend_from_slice(account_hash.as_bytes());
+    key.extend_from_slice(&block_height.to_be_bytes());
+    key.extend_from_slice(deploy_hash.as_ref());
+    key
+}
+
+/// Returns `true` if the given raw key belongs to the given account.
+pub(super) fn is_for_account(raw_key: &[u8], account_hash: &AccountHash) -> bool {
+    raw_key.starts_with(account_hash.as_bytes())
+}
+
+/// Parses the block height and deploy hash from a raw index key.
+///
+/// Returns `None` if the key is malformed.
+pub(super) fn parse_key(raw_key: &[u8]) -> Option<(u64, DeployHash)> {
+    if raw_key.len() != KEY_LENGTH {
+        return None;
+    }
+    let (height_bytes, deploy_hash_bytes) =
+        raw_key[ACCOUNT_HASH_LENGTH..].split_at(BLOCK_HEIGHT_LENGTH);
+    let block_height =
+        u64::from_be_bytes(<[u8; BLOCK_HEIGHT_LENGTH]>::try_from(height_bytes).ok()?);
+    let deploy_hash = DeployHash::new(Digest::try_from(deploy_hash_bytes).ok()?);
+    Some((block_height, deploy_hash))
+}
+
+/// Returns the hashes of the accounts under which the given deploy is indexed: the account paying
+/// for it, and the accounts of all approvers.
+pub(super) fn accounts_of(deploy: &Deploy) -> BTreeSet<AccountHash> {
+    deploy
+        .approvals()
+        .iter()
+        .map(|approval| approval.signer().to_account_hash())
+        .chain(iter::once(deploy.header().account().to_account_hash()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_roundtrip_key() {
+        let rng = &mut TestRng::new();
+        let account_hash = AccountHash::new(rng.gen());
+        let deploy_hash = DeployHash::random(rng);
+        let block_height = rng.gen();
+
+        let raw_key = key(&account_hash, block_height, &deploy_hash);
+        assert!(is_for_account(&raw_key, &account_hash));
+        assert!(!is_for_account(&raw_key, &AccountHash::new(rng.gen())));
+        assert_eq!(parse_key(&raw_key), Some((block_height, deploy_hash)));
+        assert_eq!(parse_key(&raw_key[1..]), None);
+    }
+
+    #[test]
+    fn should_order_keys_by_block_height() {
+        let rng = &mut TestRng::new();
+        let account_hash = AccountHash::new(rng.gen());
+
+        let low = key(&account_hash, 255, &DeployHash::random(rng));
+        let high = key(&account_hash, 256, &DeployHash::random(rng));
+        assert!(low < high);
+    }
+}
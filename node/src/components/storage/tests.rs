@@ -13,8 +13,9 @@ use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 
 use casper_types::{
-    generate_ed25519_keypair, system::auction::UnbondingPurse, testing::TestRng, AccessRights,
-    EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey, TimeDiff, URef, U512,
+    account::AccountHash, generate_ed25519_keypair, system::auction::UnbondingPurse,
+    testing::TestRng, AccessRights, EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey,
+    TimeDiff, URef, U512,
 };
 
 use super::{
@@ -55,6 +56,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_deploy_metadata_store_size: 50 * MIB,
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
+        enable_account_deploy_index: true,
         mem_pool_prune_interval: 4,
     }
 }
@@ -408,6 +410,27 @@ fn get_highest_complete_block_header(
     response
 }
 
+/// Loads a page of an account's deploys from a storage component.
+fn get_account_deploys(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    account_hash: AccountHash,
+    cursor: Option<(u64, DeployHash)>,
+    limit: usize,
+) -> Option<Vec<(DeployHash, BlockHashAndHeight)>> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetAccountDeploys {
+            account_hash: Box::new(account_hash),
+            cursor,
+            limit,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Stores a block in a storage component.
 fn put_complete_block(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -551,6 +574,229 @@ fn read_block_by_height_with_available_block_range() {
     assert!(get_block_header_at_height(&mut storage, 14, true).is_none());
 }
 
+#[test]
+fn read_blocks_with_metadata_by_height_range() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    // Store a contiguous run of complete blocks, with signatures for only one of them.
+    let blocks: Vec<_> = (10..13)
+        .map(|height| {
+            Arc::new(Block::random_with_specifics(
+                &mut harness.rng,
+                EraId::new(1),
+                height,
+                ProtocolVersion::from_parts(1, 5, 0),
+                false,
+                None,
+            ))
+        })
+        .collect();
+    for block in &blocks {
+        assert!(put_complete_block(
+            &mut harness,
+            &mut storage,
+            block.clone()
+        ));
+    }
+    let signatures = random_signatures(&mut harness.rng, &blocks[1]);
+    assert!(put_block_signatures(
+        &mut harness,
+        &mut storage,
+        signatures.clone()
+    ));
+
+    let range = storage
+        .read_blocks_with_metadata_by_height_range(10, 12, true, true)
+        .expect("should read range")
+        .expect("should have all blocks in range");
+    assert_eq!(range.len(), 3);
+    for ((block_header_with_metadata, block_body), block) in range.iter().zip(&blocks) {
+        assert_eq!(&block_header_with_metadata.block_header, block.header());
+        assert_eq!(block_body.as_ref(), Some(block.body()));
+    }
+    assert!(range[0].0.block_signatures.proofs.is_empty());
+    assert_eq!(range[1].0.block_signatures, signatures);
+
+    // Bodies are only read if requested.
+    let range = storage
+        .read_blocks_with_metadata_by_height_range(10, 12, false, true)
+        .expect("should read range")
+        .expect("should have all blocks in range");
+    assert_eq!(range.len(), 3);
+    for ((block_header_with_metadata, block_body), block) in range.iter().zip(&blocks) {
+        assert_eq!(&block_header_with_metadata.block_header, block.header());
+        assert!(block_body.is_none());
+    }
+
+    // A single-block range is allowed.
+    let range = storage
+        .read_blocks_with_metadata_by_height_range(12, 12, true, true)
+        .expect("should read range")
+        .expect("should have block");
+    assert_eq!(range.len(), 1);
+
+    // Any gap in the range results in the missing heights being returned.
+    assert_eq!(
+        storage
+            .read_blocks_with_metadata_by_height_range(11, 14, true, false)
+            .expect("should read range"),
+        Err(vec![13, 14])
+    );
+
+    // Store a block below the available range: it's only returned if not restricted.
+    let block_8 = Arc::new(Block::random_with_specifics(
+        &mut harness.rng,
+        EraId::new(1),
+        8,
+        ProtocolVersion::from_parts(1, 5, 0),
+        false,
+        None,
+    ));
+    assert!(put_complete_block(&mut harness, &mut storage, block_8));
+    assert!(storage
+        .read_blocks_with_metadata_by_height_range(8, 8, true, true)
+        .expect("should read range")
+        .is_err());
+    assert!(storage
+        .read_blocks_with_metadata_by_height_range(8, 8, true, false)
+        .expect("should read range")
+        .is_ok());
+
+    // Every height in the range is checked against the available block range, not only the ends.
+    assert_eq!(
+        storage
+            .read_blocks_with_metadata_by_height_range(8, 10, false, true)
+            .expect("should read range"),
+        Err(vec![8, 9])
+    );
+}
+
+#[test]
+fn read_switch_block_header_by_era_id() {
+    // Block 3 is left incomplete, so the available block range starts at height 4.
+    let (storage, _, blocks) = create_sync_leap_test_chain(&[3], false, None);
+
+    let header = storage
+        .read_switch_block_header_by_era_id(EraId::new(2), true)
+        .expect("should read header")
+        .expect("should have switch block of era 2");
+    assert_eq!(&header, blocks[4].header());
+
+    // The switch block of era 1 is below the available range.
+    assert!(storage
+        .read_switch_block_header_by_era_id(EraId::new(1), true)
+        .expect("should read header")
+        .is_none());
+    assert_eq!(
+        storage
+            .read_switch_block_header_by_era_id(EraId::new(1), false)
+            .expect("should read header")
+            .as_ref(),
+        Some(blocks[1].header())
+    );
+
+    // There is no switch block for the current era yet.
+    assert!(storage
+        .read_switch_block_header_by_era_id(EraId::new(5), false)
+        .expect("should read header")
+        .is_none());
+}
+
+#[test]
+fn should_index_deploys_by_account() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+
+    // Include a different deploy from the same account in each of several blocks, stored out of
+    // order of height.
+    let mut expected = vec![];
+    for height in [12, 10, 11] {
+        let deploy = Deploy::random_valid_native_transfer_with_dependencies(
+            &mut harness.rng,
+            &secret_key,
+            1,
+            vec![],
+        );
+        assert!(put_deploy(
+            &mut harness,
+            &mut storage,
+            Arc::new(deploy.clone())
+        ));
+        let block = Arc::new(Block::random_with_specifics(
+            &mut harness.rng,
+            EraId::new(1),
+            height,
+            ProtocolVersion::from_parts(1, 5, 0),
+            false,
+            iter::once(&deploy),
+        ));
+        expected.push((
+            *deploy.hash(),
+            BlockHashAndHeight::new(*block.hash(), height),
+        ));
+        assert!(put_complete_block(&mut harness, &mut storage, block));
+    }
+    expected.sort_by_key(|(_, block_hash_and_height)| block_hash_and_height.block_height);
+
+    let all = get_account_deploys(&mut harness, &mut storage, account_hash, None, 10);
+    assert_eq!(all.as_ref(), Some(&expected));
+
+    // Page through the deploys two at a time.
+    let first_page = get_account_deploys(&mut harness, &mut storage, account_hash, None, 2)
+        .expect("index should be enabled");
+    assert_eq!(first_page, expected[..2]);
+    let (last_deploy_hash, last_block) = first_page[1];
+    let cursor = Some((last_block.block_height, last_deploy_hash));
+    let second_page = get_account_deploys(&mut harness, &mut storage, account_hash, cursor, 2)
+        .expect("index should be enabled");
+    assert_eq!(second_page, expected[2..]);
+
+    // An unrelated account has no deploys.
+    let other_account_hash = AccountHash::new(harness.rng.gen());
+    let none = get_account_deploys(&mut harness, &mut storage, other_account_hash, None, 10);
+    assert_eq!(none, Some(vec![]));
+}
+
+#[test]
+fn should_not_get_account_deploys_if_index_disabled() {
+    let mut harness = ComponentHarness::default();
+    let cfg = Config {
+        enable_account_deploy_index: false,
+        ..new_config(&harness)
+    };
+    let mut storage = Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        EraId::default(),
+        "test",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    )
+    .expect("could not create storage component fixture");
+
+    let deploy = Deploy::random(&mut harness.rng);
+    let account_hash = deploy.header().account().to_account_hash();
+    assert!(put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(deploy.clone())
+    ));
+    let block = Arc::new(Block::random_with_deploys(
+        &mut harness.rng,
+        iter::once(&deploy),
+    ));
+    assert!(put_complete_block(&mut harness, &mut storage, block));
+
+    assert!(get_account_deploys(&mut harness, &mut storage, account_hash, None, 10).is_none());
+}
+
 #[test]
 fn can_retrieve_block_by_height() {
     let mut harness = ComponentHarness::default();
@@ -115,18 +115,24 @@ use tokio::{sync::Semaphore, time};
 use tracing::{debug, error, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{
-        self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, GetBidsRequest,
-        GetBidsResult, QueryRequest, QueryResult,
+    core::{
+        engine_state::{
+            self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, DeployItem,
+            GetBidsRequest, GetBidsResult, ListKeysRequest, ListKeysResult, QueryRequest,
+            QueryResult,
+        },
+        runtime::trace::ExecutionTrace,
     },
     shared::execution_journal::ExecutionJournal,
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer, URef,
-    U512,
+    account::{Account, AccountHash},
+    bytesrepr::Bytes,
+    system::auction::EraValidators,
+    Contract, ContractPackage, EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff,
+    Timestamp, Transfer, URef, U512,
 };
 
 use crate::{
@@ -138,6 +144,7 @@ use crate::{
         consensus::{ClContext, EraDump, ProposedBlock, ValidatorChange},
         contract_runtime::{ContractRuntimeError, EraValidatorsRequest},
         deploy_acceptor,
+        deploy_buffer::{PendingDeploy, PendingLimitExceeded},
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
@@ -147,10 +154,11 @@ use crate::{
     contract_runtime::SpeculativeExecutionState,
     reactor::{main_reactor::ReactorState, EventQueueHandle, QueueKind},
     types::{
-        appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
-        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
+        appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block, BlockBody,
+        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
+        BlockHashAndHeight, BlockHeader, BlockHeaderWithMetadata, BlockSignatures,
+        BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader, DeployId,
+        DeployMetadataExt, DeployStatus, DeployWithFinalizedApprovals, FinalitySignature,
         FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlock,
         MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
     },
@@ -810,6 +818,21 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the statuses of the given deploys have changed.
+    pub(crate) async fn announce_deploy_statuses_changed(
+        self,
+        statuses: Vec<(DeployHash, DeployStatus)>,
+    ) where
+        REv: From<DeployBufferAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                DeployBufferAnnouncement::DeployStatusesChanged(statuses),
+                QueueKind::Validation,
+            )
+            .await;
+    }
+
     /// Announces an incoming network message.
     pub(crate) async fn announce_incoming<P>(self, sender: NodeId, payload: P)
     where
@@ -873,6 +896,28 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that the block accumulator has collected sufficient finality signatures for a
+    /// block.
+    pub(crate) async fn announce_block_finalized(
+        self,
+        block_hash: BlockHash,
+        era_id: EraId,
+        height: u64,
+    ) where
+        REv: From<BlockAccumulatorAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                BlockAccumulatorAnnouncement::BlockFinalized {
+                    block_hash,
+                    era_id,
+                    height,
+                },
+                QueueKind::Regular,
+            )
+            .await;
+    }
+
     /// Request that a block be made executable (i.e. produce a FinalizedBlock plus any Deploys),
     /// if able to.
     ///
@@ -983,12 +1028,17 @@ impl<REv> EffectBuilder<REv> {
         self,
         deploy: Arc<Deploy>,
         source: Source,
+        reason: String,
     ) -> impl Future<Output = ()>
     where
         REv: From<DeployAcceptorAnnouncement>,
     {
         self.event_queue.schedule(
-            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source },
+            DeployAcceptorAnnouncement::InvalidDeploy {
+                deploy,
+                source,
+                reason,
+            },
             QueueKind::Validation,
         )
     }
@@ -1200,6 +1250,26 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the header of the switch block of the given era.
+    pub(crate) async fn get_switch_block_header_at_era_id_from_storage(
+        self,
+        era_id: EraId,
+        only_from_available_block_range: bool,
+    ) -> Option<BlockHeader>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetSwitchBlockHeaderByEra {
+                era_id,
+                only_from_available_block_range,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested signature for a given block hash.
     pub(crate) async fn get_signature_from_storage(
         self,
@@ -1578,6 +1648,31 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets a page of the deploys signed or paid for by the given account, along with the hashes
+    /// and heights of the blocks containing them.
+    ///
+    /// Returns `None` if the index of deploys by account is not enabled.
+    pub(crate) async fn get_account_deploys_from_storage(
+        self,
+        account_hash: AccountHash,
+        cursor: Option<(u64, DeployHash)>,
+        limit: usize,
+    ) -> Option<Vec<(DeployHash, BlockHashAndHeight)>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAccountDeploys {
+                account_hash: Box::new(account_hash),
+                cursor,
+                limit,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested block and its finality signatures.
     pub(crate) async fn get_block_at_height_with_metadata_from_storage(
         self,
@@ -1598,6 +1693,33 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the headers of the blocks in the inclusive range of heights, along with their finality
+    /// signatures and, if `include_bodies` is set, their bodies.
+    ///
+    /// Returns `Err` with the heights of the blocks which are not stored.
+    pub(crate) async fn get_blocks_with_metadata_by_height_range_from_storage(
+        self,
+        start_height: u64,
+        end_height: u64,
+        include_bodies: bool,
+        only_from_available_block_range: bool,
+    ) -> Result<Vec<(BlockHeaderWithMetadata, Option<BlockBody>)>, Vec<u64>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlocksAndMetadataByHeightRange {
+                start_height,
+                end_height,
+                include_bodies,
+                only_from_available_block_range,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested finality signature from storage.
     pub(crate) async fn get_finality_signature_from_storage(
         self,
@@ -1719,6 +1841,53 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the deploys currently known to the deploy buffer, optionally restricted to those
+    /// from the given account.
+    pub(crate) async fn get_pending_deploys(self, account: Option<PublicKey>) -> Vec<PendingDeploy>
+    where
+        REv: From<DeployBufferRequest>,
+    {
+        self.make_request(
+            |responder| DeployBufferRequest::GetPendingDeploys {
+                account: account.map(Box::new),
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Checks with the deploy buffer that buffering the given deploy wouldn't exceed the limits on
+    /// pending deploys for its account.
+    pub(crate) async fn check_pending_deploy_limits(
+        self,
+        deploy: Arc<Deploy>,
+    ) -> Result<(), PendingLimitExceeded>
+    where
+        REv: From<DeployBufferRequest>,
+    {
+        self.make_request(
+            |responder| DeployBufferRequest::CheckPendingLimits { deploy, responder },
+            QueueKind::Validation,
+        )
+        .await
+    }
+
+    /// Requests the status of a recently-seen deploy from the deploy buffer.
+    pub(crate) async fn get_deploy_status(self, deploy_hash: DeployHash) -> Option<DeployStatus>
+    where
+        REv: From<DeployBufferRequest>,
+    {
+        self.make_request(
+            |responder| DeployBufferRequest::GetDeployStatus {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Enqueues a finalized block execution.
     pub(crate) async fn enqueue_block_for_execution(
         self,
@@ -2024,6 +2193,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests a page of the keys matching a prefix from the Contract Runtime component.
+    pub(crate) async fn list_keys(
+        self,
+        list_keys_request: ListKeysRequest,
+    ) -> Result<ListKeysResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::ListKeys {
+                list_keys_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     pub(crate) async fn get_execution_results_checksum(
@@ -2188,13 +2375,52 @@ impl<REv> EffectBuilder<REv> {
         execution_prestate: SpeculativeExecutionState,
         deploy: Arc<Deploy>,
     ) -> Result<Option<ExecutionResult>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        let deploy_item = DeployItem::from((*deploy).clone());
+        self.speculative_execute_deploy_item(execution_prestate, deploy_item)
+            .await
+    }
+
+    /// Requests execution of a single deploy item, without commiting its effects.
+    ///
+    /// Unlike `speculative_execute_deploy`, the deploy item's authorization keys need not be
+    /// derived from the approvals of a signed deploy.
+    pub(crate) async fn speculative_execute_deploy_item(
+        self,
+        execution_prestate: SpeculativeExecutionState,
+        deploy_item: DeployItem,
+    ) -> Result<Option<ExecutionResult>, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
             |responder| ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
-                deploy,
+                deploy_item: Box::new(deploy_item),
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
+    /// Requests execution of a single deploy, without commiting its effects, recording a trace of
+    /// the gas charged while executing it.
+    pub(crate) async fn speculative_execute_deploy_with_trace(
+        self,
+        execution_prestate: SpeculativeExecutionState,
+        deploy: Arc<Deploy>,
+    ) -> Result<Option<(ExecutionResult, ExecutionTrace)>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        let deploy_item = DeployItem::from((*deploy).clone());
+        self.make_request(
+            |responder| ContractRuntimeRequest::SpeculativeDeployExecutionWithTrace {
+                execution_prestate,
+                deploy_item: Box::new(deploy_item),
                 responder,
             },
             QueueKind::ContractRuntime,
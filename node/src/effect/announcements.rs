@@ -26,7 +26,10 @@ use crate::{
         upgrade_watcher::NextUpgrade,
     },
     effect::Responder,
-    types::{Block, Deploy, DeployHash, FinalitySignature, FinalizedBlock, MetaBlock, NodeId},
+    types::{
+        Block, BlockHash, Deploy, DeployHash, DeployStatus, FinalitySignature, FinalizedBlock,
+        MetaBlock, NodeId,
+    },
     utils::Source,
 };
 
@@ -182,6 +185,8 @@ pub(crate) enum DeployAcceptorAnnouncement {
         deploy: Arc<Deploy>,
         /// The source (peer or client) of the deploy.
         source: Source,
+        /// The reason the deploy was rejected.
+        reason: String,
     },
 }
 
@@ -194,12 +199,17 @@ impl Display for DeployAcceptorAnnouncement {
                 deploy.hash(),
                 source
             ),
-            DeployAcceptorAnnouncement::InvalidDeploy { deploy, source } => {
+            DeployAcceptorAnnouncement::InvalidDeploy {
+                deploy,
+                source,
+                reason,
+            } => {
                 write!(
                     formatter,
-                    "invalid deploy {} from {}",
+                    "invalid deploy {} from {}: {}",
                     deploy.hash(),
-                    source
+                    source,
+                    reason
                 )
             }
         }
@@ -210,6 +220,8 @@ impl Display for DeployAcceptorAnnouncement {
 pub(crate) enum DeployBufferAnnouncement {
     /// Hashes of the deploys that expired.
     DeploysExpired(Vec<DeployHash>),
+    /// The statuses of the given deploys have changed.
+    DeployStatusesChanged(Vec<(DeployHash, DeployStatus)>),
 }
 
 impl Display for DeployBufferAnnouncement {
@@ -218,6 +230,9 @@ impl Display for DeployBufferAnnouncement {
             DeployBufferAnnouncement::DeploysExpired(hashes) => {
                 write!(f, "pruned hashes: {}", hashes.iter().join(", "))
             }
+            DeployBufferAnnouncement::DeployStatusesChanged(statuses) => {
+                write!(f, "{} deploy statuses changed", statuses.len())
+            }
         }
     }
 }
@@ -382,6 +397,13 @@ pub(crate) enum BlockAccumulatorAnnouncement {
     AcceptedNewFinalitySignature {
         finality_signature: Box<FinalitySignature>,
     },
+    /// A block has been signed by validators with sufficient combined weight to be considered
+    /// finalized.
+    BlockFinalized {
+        block_hash: BlockHash,
+        era_id: EraId,
+        height: u64,
+    },
 }
 
 impl Display for BlockAccumulatorAnnouncement {
@@ -394,6 +416,11 @@ impl Display for BlockAccumulatorAnnouncement {
                     finality_signature.gossip_id()
                 )
             }
+            BlockAccumulatorAnnouncement::BlockFinalized {
+                block_hash, height, ..
+            } => {
+                write!(f, "block {} at height {} finalized", block_hash, height)
+            }
         }
     }
 }
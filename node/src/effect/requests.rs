@@ -11,24 +11,30 @@ use std::{
 };
 
 use datasize::DataSize;
+use schemars::schema::RootSchema;
 use serde::Serialize;
 use smallvec::SmallVec;
 use static_assertions::const_assert;
 
 use casper_execution_engine::{
-    core::engine_state::{
-        self,
-        balance::{BalanceRequest, BalanceResult},
-        era_validators::GetEraValidatorsError,
-        get_bids::{GetBidsRequest, GetBidsResult},
-        query::{QueryRequest, QueryResult},
+    core::{
+        engine_state::{
+            self,
+            balance::{BalanceRequest, BalanceResult},
+            deploy_item::DeployItem,
+            era_validators::GetEraValidatorsError,
+            get_bids::{GetBidsRequest, GetBidsResult},
+            list_keys::{ListKeysRequest, ListKeysResult},
+            query::{QueryRequest, QueryResult},
+        },
+        runtime::trace::ExecutionTrace,
     },
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion,
-    PublicKey, TimeDiff, Timestamp, Transfer, URef,
+    account::AccountHash, bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult,
+    Key, ProtocolVersion, PublicKey, TimeDiff, Timestamp, Transfer, URef,
 };
 
 use crate::{
@@ -40,6 +46,7 @@ use crate::{
         consensus::{ClContext, ProposedBlock, ValidatorChange},
         contract_runtime::EraValidatorsRequest,
         deploy_acceptor,
+        deploy_buffer::{PendingDeploy, PendingLimitExceeded},
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
@@ -51,10 +58,11 @@ use crate::{
     reactor::main_reactor::ReactorState,
     rpcs::docs::OpenRpcSchema,
     types::{
-        appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
-        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
+        appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block, BlockBody,
+        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
+        BlockHashAndHeight, BlockHeader, BlockHeaderWithMetadata, BlockSignatures,
+        BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader, DeployId,
+        DeployMetadataExt, DeployStatus, DeployWithFinalizedApprovals, FinalitySignature,
         FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlockState,
         NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
     },
@@ -346,6 +354,17 @@ pub(crate) enum StorageRequest {
         /// local storage.
         responder: Responder<Option<BlockHeader>>,
     },
+    /// Retrieve the header of the switch block of the given era.
+    GetSwitchBlockHeaderByEra {
+        /// The era whose switch block header is requested.
+        era_id: EraId,
+        /// If true, only return `Some` if the block is in the available block range, i.e. the
+        /// highest contiguous range of complete blocks.
+        only_from_available_block_range: bool,
+        /// Responder to call with the result.  Returns `None` if the block header doesn't exist in
+        /// local storage.
+        responder: Responder<Option<BlockHeader>>,
+    },
     /// Retrieve all transfers in a block with given hash.
     GetBlockTransfers {
         /// Hash of block to get transfers of.
@@ -416,6 +435,18 @@ pub(crate) enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(DeployWithFinalizedApprovals, DeployMetadataExt)>>,
     },
+    /// Retrieve a page of the deploys signed or paid for by an account.
+    GetAccountDeploys {
+        /// Hash of the account.
+        account_hash: Box<AccountHash>,
+        /// If `Some`, only deploys after this block height and deploy hash are retrieved.
+        cursor: Option<(u64, DeployHash)>,
+        /// The maximum number of deploys to retrieve.
+        limit: usize,
+        /// Responder to call with the results, or `None` if the index of deploys by account is
+        /// not enabled.
+        responder: Responder<Option<Vec<(DeployHash, BlockHashAndHeight)>>>,
+    },
     /// Retrieve block and its metadata by its hash.
     GetBlockAndMetadataByHash {
         /// The hash of the block.
@@ -445,6 +476,22 @@ pub(crate) enum StorageRequest {
         /// The responder to call with the results.
         responder: Responder<Option<BlockWithMetadata>>,
     },
+    /// Retrieve the blocks and their metadata for an inclusive range of heights.
+    GetBlocksAndMetadataByHeightRange {
+        /// The height of the first block in the range.
+        start_height: BlockHeight,
+        /// The height of the last block in the range.
+        end_height: BlockHeight,
+        /// Whether to read the block bodies along with the headers.
+        include_bodies: bool,
+        /// If true, treat blocks outside the available block range, i.e. the highest contiguous
+        /// range of complete blocks, as missing.
+        only_from_available_block_range: bool,
+        /// The responder to call with the results, or with the heights of the missing blocks.
+        #[allow(clippy::type_complexity)]
+        responder:
+            Responder<Result<Vec<(BlockHeaderWithMetadata, Option<BlockBody>)>, Vec<BlockHeight>>>,
+    },
     /// Get the highest block and its metadata.
     GetHighestBlockWithMetadata {
         /// If true, only consider blocks in the available block range, i.e. the highest contiguous
@@ -535,6 +582,9 @@ impl Display for StorageRequest {
             StorageRequest::GetBlockHeaderByHeight { block_height, .. } => {
                 write!(formatter, "get header for height {}", block_height)
             }
+            StorageRequest::GetSwitchBlockHeaderByEra { era_id, .. } => {
+                write!(formatter, "get switch block header for era {}", era_id)
+            }
             StorageRequest::GetBlockTransfers { block_hash, .. } => {
                 write!(formatter, "get transfers for {}", block_hash)
             }
@@ -564,6 +614,9 @@ impl Display for StorageRequest {
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetAccountDeploys { account_hash, .. } => {
+                write!(formatter, "get deploys of account {}", account_hash)
+            }
             StorageRequest::GetFinalitySignature { id, .. } => {
                 write!(formatter, "get finality signature {}", id)
             }
@@ -584,6 +637,17 @@ impl Display for StorageRequest {
                     block_height
                 )
             }
+            StorageRequest::GetBlocksAndMetadataByHeightRange {
+                start_height,
+                end_height,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "get blocks and metadata for heights {} to {}",
+                    start_height, end_height
+                )
+            }
             StorageRequest::GetHighestBlockWithMetadata { .. } => {
                 write!(formatter, "get highest block with metadata")
             }
@@ -666,6 +730,23 @@ pub(crate) enum DeployBufferRequest {
         timestamp: Timestamp,
         responder: Responder<AppendableBlock>,
     },
+    /// Request for the status of a recently-seen deploy.
+    GetDeployStatus {
+        deploy_hash: DeployHash,
+        responder: Responder<Option<DeployStatus>>,
+    },
+    /// Request for the deploys currently known to the deploy buffer, optionally restricted to
+    /// those from the given account.
+    GetPendingDeploys {
+        account: Option<Box<PublicKey>>,
+        responder: Responder<Vec<PendingDeploy>>,
+    },
+    /// Request to check that buffering the given deploy wouldn't exceed the limits on pending
+    /// deploys for its account.
+    CheckPendingLimits {
+        deploy: Arc<Deploy>,
+        responder: Responder<Result<(), PendingLimitExceeded>>,
+    },
 }
 
 impl Display for DeployBufferRequest {
@@ -678,6 +759,20 @@ impl Display for DeployBufferRequest {
                     timestamp
                 )
             }
+            DeployBufferRequest::GetDeployStatus { deploy_hash, .. } => {
+                write!(formatter, "request for status of deploy {}", deploy_hash)
+            }
+            DeployBufferRequest::GetPendingDeploys { account, .. } => match account {
+                Some(account) => write!(formatter, "request for pending deploys of {}", account),
+                None => write!(formatter, "request for pending deploys"),
+            },
+            DeployBufferRequest::CheckPendingLimits { deploy, .. } => {
+                write!(
+                    formatter,
+                    "request to check pending limits for {}",
+                    deploy.hash()
+                )
+            }
         }
     }
 }
@@ -833,6 +928,16 @@ pub(crate) enum RestRequest {
         /// Responder to call with the result
         responder: Responder<OpenRpcSchema>,
     },
+    /// Returns schema of the speculative execution server's JSON-RPCs in OpenRPC format.
+    SpeculativeRpcSchema {
+        /// Responder to call with the result
+        responder: Responder<OpenRpcSchema>,
+    },
+    /// Returns JSON schema of the events emitted by the event stream server.
+    SseDataSchema {
+        /// Responder to call with the result
+        responder: Responder<RootSchema>,
+    },
 }
 
 impl Display for RestRequest {
@@ -841,6 +946,10 @@ impl Display for RestRequest {
             RestRequest::Status { .. } => write!(formatter, "get status"),
             RestRequest::Metrics { .. } => write!(formatter, "get metrics"),
             RestRequest::RpcSchema { .. } => write!(formatter, "get openrpc"),
+            RestRequest::SpeculativeRpcSchema { .. } => {
+                write!(formatter, "get speculative execution openrpc")
+            }
+            RestRequest::SseDataSchema { .. } => write!(formatter, "get event stream schema"),
         }
     }
 }
@@ -891,6 +1000,14 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Return a page of the keys matching a prefix at a given state root hash
+    ListKeys {
+        /// List keys request.
+        #[serde(skip_serializing)]
+        list_keys_request: ListKeysRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<ListKeysResult, engine_state::Error>>,
+    },
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     GetExecutionResultsChecksum {
@@ -923,10 +1040,22 @@ pub(crate) enum ContractRuntimeRequest {
         /// Hash of a block on top of which to execute the deploy.
         execution_prestate: SpeculativeExecutionState,
         /// Deploy to execute.
-        deploy: Arc<Deploy>,
+        #[serde(skip_serializing)]
+        deploy_item: Box<DeployItem>,
         /// Results
         responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
     },
+    /// Execute deploys without commiting results, recording a trace of the gas charged.
+    SpeculativeDeployExecutionWithTrace {
+        /// Hash of a block on top of which to execute the deploy.
+        execution_prestate: SpeculativeExecutionState,
+        /// Deploy to execute.
+        #[serde(skip_serializing)]
+        deploy_item: Box<DeployItem>,
+        /// Results
+        responder:
+            Responder<Result<Option<(ExecutionResult, ExecutionTrace)>, engine_state::Error>>,
+    },
 }
 
 impl Display for ContractRuntimeRequest {
@@ -951,6 +1080,11 @@ impl Display for ContractRuntimeRequest {
             } => {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
+            ContractRuntimeRequest::ListKeys {
+                list_keys_request, ..
+            } => {
+                write!(formatter, "list keys request: {:?}", list_keys_request)
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash, ..
             } => write!(
@@ -971,13 +1105,25 @@ impl Display for ContractRuntimeRequest {
             }
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
-                deploy,
+                deploy_item,
                 ..
             } => {
                 write!(
                     formatter,
                     "Execute {} on {}",
-                    deploy.hash(),
+                    DeployHash::from(deploy_item.deploy_hash),
+                    execution_prestate.state_root_hash
+                )
+            }
+            ContractRuntimeRequest::SpeculativeDeployExecutionWithTrace {
+                execution_prestate,
+                deploy_item,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "Execute {} with trace on {}",
+                    DeployHash::from(deploy_item.deploy_hash),
                     execution_prestate.state_root_hash
                 )
             }
@@ -58,6 +58,7 @@ pub(crate) use components::{
     event_stream_server::Config as EventStreamServerConfig,
     fetcher::Config as FetcherConfig,
     gossiper::Config as GossipConfig,
+    grpc_server::Config as GrpcServerConfig,
     network::Config as NetworkConfig,
     rest_server::Config as RestServerConfig,
     rpc_server::{Config as RpcServerConfig, SpeculativeExecConfig},
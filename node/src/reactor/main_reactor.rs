@@ -25,7 +25,7 @@ use memory_metrics::MemoryMetrics;
 use prometheus::Registry;
 use tracing::{debug, error, info, warn};
 
-use casper_types::{EraId, PublicKey, TimeDiff, Timestamp, U512};
+use casper_types::{EraId, ExecutionResult, PublicKey, TimeDiff, Timestamp, U512};
 
 #[cfg(test)]
 use crate::testing::network::NetworkedReactor;
@@ -41,6 +41,7 @@ use crate::{
         diagnostics_port::DiagnosticsPort,
         event_stream_server::{self, EventStreamServer},
         gossiper::{self, GossipItem, Gossiper},
+        grpc_server::GrpcServer,
         metrics::Metrics,
         network::{self, GossipedAddress, Identity as NetworkIdentity, Network},
         rest_server::RestServer,
@@ -137,6 +138,7 @@ pub(crate) struct MainReactor {
     upgrade_watcher: UpgradeWatcher,
     rpc_server: RpcServer,
     rest_server: RestServer,
+    grpc_server: GrpcServer,
     event_stream_server: EventStreamServer,
     diagnostics_port: DiagnosticsPort,
     shutdown_trigger: ShutdownTrigger,
@@ -312,6 +314,10 @@ impl reactor::Reactor for MainReactor {
                 MainEvent::RestServer,
                 self.rest_server.handle_event(effect_builder, rng, event),
             ),
+            MainEvent::GrpcServer(event) => reactor::wrap_effects(
+                MainEvent::GrpcServer,
+                self.grpc_server.handle_event(effect_builder, rng, event),
+            ),
             MainEvent::MetricsRequest(req) => reactor::wrap_effects(
                 MainEvent::MetricsRequest,
                 self.metrics.handle_event(effect_builder, rng, req),
@@ -482,6 +488,24 @@ impl reactor::Reactor for MainReactor {
                 self.block_synchronizer
                     .handle_event(effect_builder, rng, req.into()),
             ),
+            MainEvent::BlockAccumulatorAnnouncement(
+                BlockAccumulatorAnnouncement::BlockFinalized {
+                    block_hash,
+                    era_id,
+                    height,
+                },
+            ) => reactor::wrap_effects(
+                MainEvent::EventStreamServer,
+                self.event_stream_server.handle_event(
+                    effect_builder,
+                    rng,
+                    event_stream_server::Event::BlockFinalized {
+                        block_hash,
+                        era_id,
+                        height,
+                    },
+                ),
+            ),
             MainEvent::BlockAccumulatorAnnouncement(
                 BlockAccumulatorAnnouncement::AcceptedNewFinalitySignature { finality_signature },
             ) => {
@@ -723,6 +747,14 @@ impl reactor::Reactor for MainReactor {
                                 target: deploy.gossip_target(),
                             }),
                         ));
+                        // track its status
+                        effects.extend(self.dispatch_event(
+                            effect_builder,
+                            rng,
+                            MainEvent::DeployBuffer(deploy_buffer::Event::DeployAccepted(
+                                *deploy.hash(),
+                            )),
+                        ));
                         // notify event stream
                         effects.extend(self.dispatch_event(
                             effect_builder,
@@ -743,9 +775,21 @@ impl reactor::Reactor for MainReactor {
                 effects
             }
             MainEvent::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
-                deploy: _,
-                source: _,
-            }) => Effects::new(),
+                deploy,
+                source,
+                reason,
+            }) => {
+                // Only rejections of deploys submitted by clients are of interest to clients
+                // querying deploy statuses.
+                if !matches!(source, Source::Client) {
+                    return Effects::new();
+                }
+                let reactor_event = MainEvent::DeployBuffer(deploy_buffer::Event::DeployRejected {
+                    deploy_hash: *deploy.hash(),
+                    reason,
+                });
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
             MainEvent::DeployGossiper(event) => reactor::wrap_effects(
                 MainEvent::DeployGossiper,
                 self.deploy_gossiper
@@ -806,6 +850,14 @@ impl reactor::Reactor for MainReactor {
                 );
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
+            MainEvent::DeployBufferAnnouncement(
+                DeployBufferAnnouncement::DeployStatusesChanged(statuses),
+            ) => {
+                let reactor_event = MainEvent::EventStreamServer(
+                    event_stream_server::Event::DeployStatusesChanged(statuses),
+                );
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
 
             // CONTRACT RUNTIME & GLOBAL STATE
             MainEvent::ContractRuntime(event) => reactor::wrap_effects(
@@ -1076,12 +1128,18 @@ impl reactor::Reactor for MainReactor {
             registry,
         )?;
 
+        let event_stream_server = EventStreamServer::new(
+            config.event_stream_server.clone(),
+            storage.root_path().to_path_buf(),
+            protocol_version,
+        )?;
         let rpc_server = RpcServer::new(
             config.rpc_server.clone(),
             config.speculative_exec_server.clone(),
             protocol_version,
             chainspec.network_config.name.clone(),
             node_startup_instant,
+            event_stream_server.sse_data_broadcaster(),
         );
         let rest_server = RestServer::new(
             config.rest_server.clone(),
@@ -1089,10 +1147,12 @@ impl reactor::Reactor for MainReactor {
             chainspec.network_config.name.clone(),
             node_startup_instant,
         );
-        let event_stream_server = EventStreamServer::new(
-            config.event_stream_server.clone(),
-            storage.root_path().to_path_buf(),
+        let grpc_server = GrpcServer::new(
+            config.grpc_server.clone(),
             protocol_version,
+            chainspec.network_config.name.clone(),
+            node_startup_instant,
+            event_stream_server.sse_data_broadcaster(),
         );
         let diagnostics_port =
             DiagnosticsPort::new(WithDir::new(&root_dir, config.diagnostics_port));
@@ -1145,6 +1205,7 @@ impl reactor::Reactor for MainReactor {
             chainspec.clone(),
             chainspec.core_config.simultaneous_peer_requests,
             validator_matrix.clone(),
+            contract_runtime.global_state_sync_tracker(),
             registry,
         )?;
         let block_validator = BlockValidator::new(Arc::clone(&chainspec));
@@ -1165,6 +1226,7 @@ impl reactor::Reactor for MainReactor {
 
             rpc_server,
             rest_server,
+            grpc_server,
             event_stream_server,
             deploy_acceptor,
             fetchers,
@@ -1497,6 +1559,42 @@ impl MainReactor {
             ),
         ));
 
+        if let Some(era_end) = block.header().era_end() {
+            effects.extend(reactor::wrap_effects(
+                MainEvent::EventStreamServer,
+                self.event_stream_server.handle_event(
+                    effect_builder,
+                    rng,
+                    event_stream_server::Event::EraEnd {
+                        era_id: block.header().era_id(),
+                        block_hash: *block.hash(),
+                        era_end: Box::new(era_end.clone()),
+                    },
+                ),
+            ));
+        }
+
+        if !execution_results.is_empty() {
+            let executed = execution_results
+                .iter()
+                .map(|(deploy_hash, _, execution_result)| {
+                    let success = matches!(execution_result, ExecutionResult::Success { .. });
+                    (*deploy_hash, success)
+                })
+                .collect();
+            effects.extend(reactor::wrap_effects(
+                MainEvent::DeployBuffer,
+                self.deploy_buffer.handle_event(
+                    effect_builder,
+                    rng,
+                    deploy_buffer::Event::DeploysExecuted {
+                        block_hash: *block.hash(),
+                        executed,
+                    },
+                ),
+            ));
+        }
+
         for (deploy_hash, deploy_header, execution_result) in execution_results {
             let event = event_stream_server::Event::DeployProcessed {
                 deploy_hash,
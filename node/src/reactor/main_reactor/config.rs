@@ -4,8 +4,8 @@ use serde::{Deserialize, Serialize};
 use crate::{
     logging::LoggingConfig, types::NodeConfig, BlockAccumulatorConfig, BlockSynchronizerConfig,
     ConsensusConfig, ContractRuntimeConfig, DeployBufferConfig, DiagnosticsPortConfig,
-    EventStreamServerConfig, FetcherConfig, GossipConfig, NetworkConfig, RestServerConfig,
-    RpcServerConfig, SpeculativeExecConfig, StorageConfig, UpgradeWatcherConfig,
+    EventStreamServerConfig, FetcherConfig, GossipConfig, GrpcServerConfig, NetworkConfig,
+    RestServerConfig, RpcServerConfig, SpeculativeExecConfig, StorageConfig, UpgradeWatcherConfig,
 };
 
 /// Root configuration.
@@ -25,6 +25,8 @@ pub struct Config {
     pub event_stream_server: EventStreamServerConfig,
     /// Config values for the REST server.
     pub rest_server: RestServerConfig,
+    /// Config values for the gRPC server.
+    pub grpc_server: GrpcServerConfig,
     /// Config values for the Json-RPC server.
     pub rpc_server: RpcServerConfig,
     /// Config values for speculative execution.
@@ -7,16 +7,19 @@ use casper_types::{EraId, PublicKey, Timestamp};
 use crate::{
     components::{
         block_synchronizer, block_synchronizer::BlockSynchronizerProgress, consensus::EraReport,
-        contract_runtime::ExecutionPreState, diagnostics_port, event_stream_server, network,
-        rest_server, rpc_server, upgrade_watcher,
+        contract_runtime::ExecutionPreState, diagnostics_port, event_stream_server, grpc_server,
+        network, rest_server, rpc_server, upgrade_watcher, Component,
     },
     effect::{EffectBuilder, EffectExt, Effects},
     fatal,
-    reactor::main_reactor::{
-        catch_up::CatchUpInstruction, genesis_instruction::GenesisInstruction,
-        keep_up::KeepUpInstruction, upgrade_shutdown::UpgradeShutdownInstruction,
-        upgrading_instruction::UpgradingInstruction, utils, validate::ValidateInstruction,
-        MainEvent, MainReactor, ReactorState,
+    reactor::{
+        self,
+        main_reactor::{
+            catch_up::CatchUpInstruction, genesis_instruction::GenesisInstruction,
+            keep_up::KeepUpInstruction, upgrade_shutdown::UpgradeShutdownInstruction,
+            upgrading_instruction::UpgradingInstruction, utils, validate::ValidateInstruction,
+            MainEvent, MainReactor, ReactorState,
+        },
     },
     types::{BlockHash, BlockHeader, BlockPayload, FinalizedBlock, MetaBlockState},
     NodeRng,
@@ -31,7 +34,21 @@ impl MainReactor {
         if self.attempts > self.max_attempts {
             return fatal!(effect_builder, "exceeded reattempt tolerance").ignore();
         }
+        let previous_state = self.state;
         let (delay, mut effects) = self.do_crank(effect_builder, rng);
+        if self.state != previous_state {
+            effects.extend(reactor::wrap_effects(
+                MainEvent::EventStreamServer,
+                self.event_stream_server.handle_event(
+                    effect_builder,
+                    rng,
+                    event_stream_server::Event::NodeStateChanged {
+                        previous_state,
+                        new_state: self.state,
+                    },
+                ),
+            ));
+        }
         effects.extend(
             async move {
                 if !delay.is_zero() {
@@ -290,7 +307,7 @@ impl MainReactor {
             return Some(effects);
         }
 
-        // bring up rpc and rest server last to defer complications (such as put_deploy) and
+        // bring up rpc, rest and grpc server last to defer complications (such as put_deploy) and
         // for it to be able to answer to /status, which requires various other components to be
         // initialized
         if let Some(effects) = utils::initialize_component(
@@ -307,6 +324,13 @@ impl MainReactor {
         ) {
             return Some(effects);
         }
+        if let Some(effects) = utils::initialize_component(
+            effect_builder,
+            &mut self.grpc_server,
+            MainEvent::GrpcServer(grpc_server::Event::Initialize),
+        ) {
+            return Some(effects);
+        }
 
         None
     }
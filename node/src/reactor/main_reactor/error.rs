@@ -5,8 +5,8 @@ use casper_types::{bytesrepr, crypto::ErrorExt as CryptoError};
 
 use crate::{
     components::{
-        contract_runtime, contract_runtime::BlockExecutionError, diagnostics_port, network,
-        storage, upgrade_watcher,
+        contract_runtime, contract_runtime::BlockExecutionError, diagnostics_port,
+        event_stream_server, network, storage, upgrade_watcher,
     },
     utils::{ListeningError, LoadError},
 };
@@ -42,6 +42,10 @@ pub(crate) enum Error {
     #[error("contract runtime config error: {0}")]
     ContractRuntime(#[from] contract_runtime::ConfigError),
 
+    /// `EventStreamServer` component error.
+    #[error("event stream server config error: {0}")]
+    EventStreamServer(#[from] event_stream_server::ConfigError),
+
     /// Block execution error.
     #[error(transparent)]
     BlockExecution(#[from] BlockExecutionError),
@@ -14,7 +14,7 @@ use crate::{
         block_accumulator,
         block_synchronizer::{self, GlobalStateSynchronizerEvent, TrieAccumulatorEvent},
         block_validator, consensus, contract_runtime, deploy_acceptor, deploy_buffer,
-        diagnostics_port, event_stream_server, fetcher, gossiper,
+        diagnostics_port, event_stream_server, fetcher, gossiper, grpc_server,
         network::{self, GossipedAddress},
         rest_server, rpc_server, shutdown_trigger, storage, sync_leaper, upgrade_watcher,
     },
@@ -79,6 +79,8 @@ pub(crate) enum MainEvent {
     #[from]
     RestServer(#[serde(skip_serializing)] rest_server::Event),
     #[from]
+    GrpcServer(#[serde(skip_serializing)] grpc_server::Event),
+    #[from]
     MetricsRequest(#[serde(skip_serializing)] MetricsRequest),
     #[from]
     ChainspecRawBytesRequest(#[serde(skip_serializing)] ChainspecRawBytesRequest),
@@ -272,6 +274,7 @@ impl ReactorEvent for MainEvent {
             MainEvent::Storage(_) => "Storage",
             MainEvent::RpcServer(_) => "RpcServer",
             MainEvent::RestServer(_) => "RestServer",
+            MainEvent::GrpcServer(_) => "GrpcServer",
             MainEvent::EventStreamServer(_) => "EventStreamServer",
             MainEvent::UpgradeWatcher(_) => "UpgradeWatcher",
             MainEvent::Consensus(_) => "Consensus",
@@ -376,6 +379,7 @@ impl Display for MainEvent {
             MainEvent::DeployBuffer(event) => write!(f, "deploy buffer: {}", event),
             MainEvent::RpcServer(event) => write!(f, "rpc server: {}", event),
             MainEvent::RestServer(event) => write!(f, "rest server: {}", event),
+            MainEvent::GrpcServer(event) => write!(f, "grpc server: {}", event),
             MainEvent::EventStreamServer(event) => {
                 write!(f, "event stream server: {}", event)
             }
@@ -16,6 +16,7 @@ pub(super) struct MemoryMetrics {
     mem_contract_runtime: IntGauge,
     mem_rpc_server: IntGauge,
     mem_rest_server: IntGauge,
+    mem_grpc_server: IntGauge,
     mem_event_stream_server: IntGauge,
     mem_consensus: IntGauge,
     mem_deploy_gossiper: IntGauge,
@@ -53,6 +54,8 @@ impl MemoryMetrics {
         let mem_rpc_server = IntGauge::new("mem_rpc_server", "rpc server memory usage in bytes")?;
         let mem_rest_server =
             IntGauge::new("mem_rest_server", "rest server memory usage in bytes")?;
+        let mem_grpc_server =
+            IntGauge::new("mem_grpc_server", "grpc server memory usage in bytes")?;
         let mem_event_stream_server = IntGauge::new(
             "mem_event_stream_server",
             "event stream server memory usage in bytes",
@@ -114,6 +117,7 @@ impl MemoryMetrics {
         registry.register(Box::new(mem_contract_runtime.clone()))?;
         registry.register(Box::new(mem_rpc_server.clone()))?;
         registry.register(Box::new(mem_rest_server.clone()))?;
+        registry.register(Box::new(mem_grpc_server.clone()))?;
         registry.register(Box::new(mem_event_stream_server.clone()))?;
         registry.register(Box::new(mem_consensus.clone()))?;
         registry.register(Box::new(mem_fetchers.clone()))?;
@@ -139,6 +143,7 @@ impl MemoryMetrics {
             mem_contract_runtime,
             mem_rpc_server,
             mem_rest_server,
+            mem_grpc_server,
             mem_event_stream_server,
             mem_consensus,
             mem_fetchers,
@@ -169,6 +174,7 @@ impl MemoryMetrics {
         let contract_runtime = reactor.contract_runtime.estimate_heap_size() as i64;
         let rpc_server = reactor.rpc_server.estimate_heap_size() as i64;
         let rest_server = reactor.rest_server.estimate_heap_size() as i64;
+        let grpc_server = reactor.grpc_server.estimate_heap_size() as i64;
         let event_stream_server = reactor.event_stream_server.estimate_heap_size() as i64;
         let consensus = reactor.consensus.estimate_heap_size() as i64;
         let fetchers = reactor.fetchers.estimate_heap_size() as i64;
@@ -192,6 +198,7 @@ impl MemoryMetrics {
             + contract_runtime
             + rpc_server
             + rest_server
+            + grpc_server
             + event_stream_server
             + consensus
             + fetchers
@@ -213,6 +220,7 @@ impl MemoryMetrics {
         self.mem_contract_runtime.set(contract_runtime);
         self.mem_rpc_server.set(rpc_server);
         self.mem_rest_server.set(rest_server);
+        self.mem_grpc_server.set(grpc_server);
         self.mem_event_stream_server.set(event_stream_server);
         self.mem_consensus.set(consensus);
         self.mem_fetchers.set(fetchers);
@@ -244,6 +252,7 @@ impl MemoryMetrics {
                %contract_runtime,
                %rpc_server,
                %rest_server,
+               %grpc_server,
                %event_stream_server,
                %consensus,
                %fetchers,
@@ -274,6 +283,7 @@ impl Drop for MemoryMetrics {
         unregister_metric!(self.registry, self.mem_contract_runtime);
         unregister_metric!(self.registry, self.mem_rpc_server);
         unregister_metric!(self.registry, self.mem_rest_server);
+        unregister_metric!(self.registry, self.mem_grpc_server);
         unregister_metric!(self.registry, self.mem_event_stream_server);
         unregister_metric!(self.registry, self.mem_consensus);
         unregister_metric!(self.registry, self.mem_fetchers);
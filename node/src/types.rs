@@ -26,15 +26,15 @@ use rand_chacha::ChaCha20Rng;
 
 pub use available_block_range::AvailableBlockRange;
 pub(crate) use block::{
-    compute_approvals_checksum, ApprovalsHashes, BlockHashAndHeight, BlockHeaderWithMetadata,
-    BlockPayload, BlockWithMetadata, FinalitySignatureId, MetaBlock, MetaBlockMergeError,
-    MetaBlockState,
+    compute_approvals_checksum, json_compatibility::JsonEraEnd, ApprovalsHashes,
+    BlockHashAndHeight, BlockHeaderWithMetadata, BlockPayload, BlockWithMetadata,
+    FinalitySignatureId, MetaBlock, MetaBlockMergeError, MetaBlockState,
 };
 pub use block::{
-    json_compatibility::{JsonBlock, JsonBlockHeader},
+    json_compatibility::{JsonBlock, JsonBlockBody, JsonBlockHeader, JsonProof},
     Block, BlockAndDeploys, BlockBody, BlockExecutionResultsOrChunk,
     BlockExecutionResultsOrChunkId, BlockExecutionResultsOrChunkIdDisplay, BlockHash, BlockHeader,
-    BlockSignatures, FinalitySignature, FinalizedBlock,
+    BlockSignatures, EraEnd, FinalitySignature, FinalizedBlock,
 };
 pub use chainspec::Chainspec;
 pub(crate) use chainspec::{ActivationPoint, ChainspecRawBytes};
@@ -42,7 +42,8 @@ pub use chunkable::Chunkable;
 pub use datasize::DataSize;
 pub use deploy::{
     Approval, ApprovalsHash, Deploy, DeployConfigurationFailure, DeployError, DeployHash,
-    DeployHeader, DeployOrTransferHash, ExcessiveSizeError as ExcessiveSizeDeployError,
+    DeployHeader, DeployOrTransferHash, DeployStatus,
+    ExcessiveSizeError as ExcessiveSizeDeployError,
 };
 pub(crate) use deploy::{
     DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata, DeployMetadataExt,
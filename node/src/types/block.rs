@@ -2259,6 +2259,11 @@ pub(crate) mod json_compatibility {
         pub fn transfer_hashes(&self) -> &Vec<DeployHash> {
             &self.body.transfer_hashes
         }
+
+        /// Returns the public key of the validator which proposed the `Block`.
+        pub fn proposer(&self) -> &PublicKey {
+            &self.body.proposer
+        }
     }
 
     impl DocExample for JsonBlock {
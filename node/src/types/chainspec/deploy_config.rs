@@ -34,6 +34,13 @@ pub struct DeployConfig {
     pub(crate) payment_args_max_length: u32,
     pub(crate) session_args_max_length: u32,
     pub(crate) native_transfer_minimum_motes: u64,
+    /// Whether a pending deploy may be replaced by a later deploy from the same account.
+    ///
+    /// Deploys have no field naming a deploy they replace, so when enabled, a deploy's
+    /// `dependencies` take on this meaning: a deploy from the same account as a pending deploy
+    /// named in its dependencies replaces it, rather than waiting for it to execute, if it has a
+    /// higher gas price or is a cancellation.
+    pub(crate) allow_deploy_replacement: bool,
 }
 
 impl DeployConfig {
@@ -69,6 +76,7 @@ impl DeployConfig {
         let session_args_max_length = rng.gen();
         let native_transfer_minimum_motes =
             rng.gen_range(MAX_PAYMENT_AMOUNT..1_000_000_000_000_000);
+        let allow_deploy_replacement = rng.gen();
 
         DeployConfig {
             max_payment_cost,
@@ -83,6 +91,7 @@ impl DeployConfig {
             payment_args_max_length,
             session_args_max_length,
             native_transfer_minimum_motes,
+            allow_deploy_replacement,
         }
     }
 }
@@ -103,6 +112,7 @@ impl Default for DeployConfig {
             payment_args_max_length: 1024,
             session_args_max_length: 1024,
             native_transfer_minimum_motes: MAX_PAYMENT_AMOUNT,
+            allow_deploy_replacement: false,
         }
     }
 }
@@ -122,6 +132,7 @@ impl ToBytes for DeployConfig {
         buffer.extend(self.payment_args_max_length.to_bytes()?);
         buffer.extend(self.session_args_max_length.to_bytes()?);
         buffer.extend(self.native_transfer_minimum_motes.to_bytes()?);
+        buffer.extend(self.allow_deploy_replacement.to_bytes()?);
         Ok(buffer)
     }
 
@@ -138,6 +149,7 @@ impl ToBytes for DeployConfig {
             + self.payment_args_max_length.serialized_length()
             + self.session_args_max_length.serialized_length()
             + self.native_transfer_minimum_motes.serialized_length()
+            + self.allow_deploy_replacement.serialized_length()
     }
 }
 
@@ -156,6 +168,7 @@ impl FromBytes for DeployConfig {
         let (payment_args_max_length, remainder) = u32::from_bytes(remainder)?;
         let (session_args_max_length, remainder) = u32::from_bytes(remainder)?;
         let (native_transfer_minimum_motes, remainder) = u64::from_bytes(remainder)?;
+        let (allow_deploy_replacement, remainder) = bool::from_bytes(remainder)?;
         let config = DeployConfig {
             max_payment_cost,
             max_ttl,
@@ -169,6 +182,7 @@ impl FromBytes for DeployConfig {
             payment_args_max_length,
             session_args_max_length,
             native_transfer_minimum_motes,
+            allow_deploy_replacement,
         };
         Ok((config, remainder))
     }
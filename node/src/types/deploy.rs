@@ -7,6 +7,7 @@ mod deploy_hash;
 mod deploy_hash_with_approvals;
 mod deploy_header;
 mod deploy_or_transfer_hash;
+mod deploy_status;
 mod deploy_with_finalized_approvals;
 mod error;
 mod finalized_approvals;
@@ -64,6 +65,7 @@ pub use deploy_hash::DeployHash;
 pub(crate) use deploy_hash_with_approvals::DeployHashWithApprovals;
 pub use deploy_header::DeployHeader;
 pub use deploy_or_transfer_hash::DeployOrTransferHash;
+pub use deploy_status::DeployStatus;
 pub(crate) use deploy_with_finalized_approvals::DeployWithFinalizedApprovals;
 pub use error::{DeployConfigurationFailure, Error as DeployError, ExcessiveSizeError};
 pub(crate) use finalized_approvals::FinalizedApprovals;
@@ -230,6 +232,30 @@ impl Deploy {
         }
     }
 
+    /// Returns `true` if this deploy is a cancellation, i.e. it names at least one deploy in its
+    /// dependencies and its session is empty module bytes.
+    ///
+    /// Cancellations only have an effect if deploy replacement is enabled in the chainspec, in
+    /// which case the dependencies name the deploys being cancelled.
+    pub(crate) fn is_cancellation(&self) -> bool {
+        let has_empty_session = matches!(
+            &self.session,
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } if module_bytes.is_empty()
+        );
+        has_empty_session && !self.header.dependencies().is_empty()
+    }
+
+    /// Returns `true` if this deploy may replace a pending deploy with the given header, i.e. both
+    /// are from the same account and this deploy is either a cancellation or has a higher gas
+    /// price.
+    ///
+    /// This doesn't check that the pending deploy is named in this deploy's dependencies, which is
+    /// how a deploy names the deploys it replaces.
+    pub(crate) fn can_replace(&self, pending: &DeployHeader) -> bool {
+        self.header.account() == pending.account()
+            && (self.is_cancellation() || self.header.gas_price() > pending.gas_price())
+    }
+
     pub(crate) fn with_approvals(mut self, approvals: BTreeSet<Approval>) -> Self {
         self.approvals = approvals;
         self
@@ -717,6 +743,52 @@ impl Deploy {
         Self::random_valid_native_transfer_with_timestamp_and_ttl(rng, timestamp, ttl)
     }
 
+    /// Returns a random deploy for a native transfer signed by the given key, with the given gas
+    /// price and dependencies.
+    pub(crate) fn random_valid_native_transfer_with_dependencies(
+        rng: &mut TestRng,
+        secret_key: &SecretKey,
+        gas_price: u64,
+        dependencies: Vec<DeployHash>,
+    ) -> Self {
+        let deploy = Self::random_valid_native_transfer(rng);
+        Deploy::new(
+            deploy.header.timestamp(),
+            deploy.header.ttl(),
+            gas_price,
+            dependencies,
+            deploy.header.chain_name().to_string(),
+            deploy.payment,
+            deploy.session,
+            secret_key,
+            None,
+        )
+    }
+
+    /// Returns a random cancellation of the given deploys, signed by the given key.
+    pub(crate) fn random_cancellation(
+        rng: &mut TestRng,
+        secret_key: &SecretKey,
+        dependencies: Vec<DeployHash>,
+    ) -> Self {
+        let deploy = Self::random_valid_native_transfer(rng);
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: Default::default(),
+        };
+        Deploy::new(
+            deploy.header.timestamp(),
+            deploy.header.ttl(),
+            deploy.header.gas_price(),
+            dependencies,
+            deploy.header.chain_name().to_string(),
+            deploy.payment,
+            session,
+            secret_key,
+            None,
+        )
+    }
+
     /// Returns a random deploy for a native transfer with timestamp and ttl.
     pub(crate) fn random_valid_native_transfer_with_timestamp_and_ttl(
         rng: &mut TestRng,
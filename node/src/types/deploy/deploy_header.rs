@@ -83,6 +83,9 @@ impl DeployHeader {
     }
 
     /// Other deploys that have to be run before this one.
+    ///
+    /// If deploy replacement is enabled in the chainspec, these also name the pending deploys from
+    /// the same account which this deploy replaces.
     pub fn dependencies(&self) -> &Vec<DeployHash> {
         &self.dependencies
     }
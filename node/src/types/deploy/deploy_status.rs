@@ -0,0 +1,91 @@
+use datasize::DataSize;
+use derive_more::Display;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::Timestamp;
+
+use crate::types::{BlockHash, DeployHash};
+
+/// The lifecycle status of a recently-seen deploy, as observed by this node.
+#[derive(Clone, DataSize, Eq, PartialEq, Serialize, Deserialize, Debug, Display, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum DeployStatus {
+    /// The deploy has been accepted and stored by this node, and is being gossiped.
+    #[display(fmt = "received")]
+    Received,
+    /// The deploy is in the deploy buffer, waiting to be proposed.
+    #[display(fmt = "buffered")]
+    Buffered,
+    /// The deploy is held by a proposed block which has not yet been finalized.
+    #[display(fmt = "proposed in block at {}", timestamp)]
+    Proposed {
+        /// The timestamp of the proposed block.
+        timestamp: Timestamp,
+    },
+    /// The deploy is included in a finalized block which has not yet been executed.
+    #[display(fmt = "included in block at height {}", block_height)]
+    Included {
+        /// The height of the finalized block.
+        block_height: u64,
+    },
+    /// The deploy has been executed as part of the given block.
+    #[display(fmt = "executed in block {}, success: {}", block_hash, success)]
+    Executed {
+        /// The hash of the executed block.
+        block_hash: BlockHash,
+        /// Whether execution succeeded or not.
+        success: bool,
+    },
+    /// The deploy was superseded by a later deploy from the same account.
+    #[display(fmt = "replaced by {}", replacement)]
+    Replaced {
+        /// The hash of the replacement deploy.
+        replacement: DeployHash,
+    },
+    /// The deploy was evicted from this node's deploy buffer as the buffer was full.
+    #[display(fmt = "evicted")]
+    Evicted,
+    /// The deploy expired without being included in a block.
+    #[display(fmt = "expired")]
+    Expired,
+    /// The deploy was rejected by this node.
+    #[display(fmt = "rejected: {}", reason)]
+    Rejected {
+        /// The reason for rejection.
+        reason: String,
+    },
+}
+
+impl DeployStatus {
+    /// Returns `true` if a deploy currently in status `current` (or untracked if `None`) may move
+    /// to status `self`.
+    ///
+    /// Statuses only move forwards through the lifecycle, with the exception that a deploy held by
+    /// a proposed block which failed to get finalized reverts to `Buffered`.  A `Replaced` deploy
+    /// can still become `Included` if another node proposed it before seeing its replacement.
+    /// `Rejected` only applies to deploys not otherwise known, as the same deploy may have been
+    /// received with valid approvals from elsewhere.
+    pub(crate) fn supersedes(&self, current: Option<&DeployStatus>) -> bool {
+        let current = match current {
+            Some(current) => current,
+            None => return true,
+        };
+        match (current, self) {
+            (DeployStatus::Executed { .. }, _) => false,
+            (_, DeployStatus::Executed { .. }) => true,
+            (DeployStatus::Included { .. }, _) => false,
+            (_, DeployStatus::Included { .. }) => true,
+            (DeployStatus::Replaced { .. } | DeployStatus::Evicted, _) => false,
+            (_, DeployStatus::Replaced { .. } | DeployStatus::Evicted) => true,
+            (DeployStatus::Expired, _) => false,
+            (_, DeployStatus::Expired) => true,
+            (DeployStatus::Rejected { .. }, _) => true,
+            (_, DeployStatus::Rejected { .. }) => false,
+            (DeployStatus::Buffered | DeployStatus::Proposed { .. }, DeployStatus::Received) => {
+                false
+            }
+            (_, _) => current != self,
+        }
+    }
+}
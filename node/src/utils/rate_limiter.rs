@@ -0,0 +1,478 @@
+//! Rate limiting for the node's HTTP servers.
+//!
+//! `GlobalRateLimiter` limits the rate of requests to a server from all clients combined, delaying
+//! requests which exceed the limit.
+//!
+//! `RateLimiter` limits the rate of requests from each client, rejecting those which exceed it.
+//! Each client is identified by its IP address, or by the value of a configurable API key header
+//! if that holds one of the configured API keys, and is allowed a sustained rate of requests with
+//! some burst capacity via a token bucket.  Requests for methods deemed expensive additionally
+//! draw from a separate, usually smaller, token bucket.
+//!
+//! The buckets of a bounded number of clients are tracked; once the bound is reached, the buckets
+//! of the least recently seen client are discarded.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    net::{IpAddr, SocketAddr},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+/// The duration of each window of `GlobalRateLimiter`.
+const GLOBAL_WINDOW: Duration = Duration::from_secs(1);
+
+/// The maximum number of clients whose token buckets are tracked.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Per-client rate limiting configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Setting to enable per-client rate limiting.
+    pub enable: bool,
+    /// Maximum sustained rate of requests per second from a single client.
+    pub qps_limit: u64,
+    /// Maximum number of requests a single client can make in a burst.
+    pub burst_limit: u64,
+    /// The methods (or for the REST server, the URL paths) which are subject to the additional
+    /// `expensive_qps_limit` and `expensive_burst_limit`.
+    pub expensive_methods: Vec<String>,
+    /// Maximum sustained rate of requests per second for expensive methods from a single client.
+    pub expensive_qps_limit: u64,
+    /// Maximum number of requests for expensive methods a single client can make in a burst.
+    pub expensive_burst_limit: u64,
+    /// Name of an HTTP header carrying an API key.  Requests where this header holds one of
+    /// `api_keys` are limited per API key rather than per IP address.  If empty, all requests are
+    /// limited per IP address.
+    pub api_key_header: String,
+    /// The API keys accepted in `api_key_header`.  Requests with any other value in the header are
+    /// limited per IP address.
+    pub api_keys: Vec<String>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            enable: false,
+            qps_limit: 10,
+            burst_limit: 20,
+            expensive_methods: vec![],
+            expensive_qps_limit: 1,
+            expensive_burst_limit: 5,
+            api_key_header: String::new(),
+            api_keys: vec![],
+        }
+    }
+}
+
+/// A window of time in which a limited number of requests may start.
+#[derive(Debug)]
+struct Window {
+    start: Instant,
+    remaining: u64,
+}
+
+/// Rate limiter shared by all clients of a server.
+///
+/// Like the tower rate limit layer applied to each server's connections, this allows `qps_limit`
+/// requests in each one second window, delaying further requests to the next window in which
+/// there is capacity.
+#[derive(Debug)]
+pub(crate) struct GlobalRateLimiter {
+    qps_limit: u64,
+    /// The latest window in which capacity has been reserved.
+    window: Mutex<Window>,
+}
+
+impl GlobalRateLimiter {
+    /// Returns a new rate limiter allowing `qps_limit` requests per second.
+    pub(crate) fn new(qps_limit: u64) -> Self {
+        GlobalRateLimiter {
+            qps_limit,
+            window: Mutex::new(Window {
+                start: Instant::now(),
+                remaining: qps_limit,
+            }),
+        }
+    }
+
+    /// Waits until the given number of requests may start.
+    pub(crate) async fn acquire(&self, request_count: u64) {
+        let start = tokio::time::Instant::from_std(self.reserve_at(Instant::now(), request_count));
+        tokio::time::sleep_until(start).await;
+    }
+
+    /// Reserves capacity for the given number of requests in the current or later windows, and
+    /// returns the start of the window in which the last of them is reserved.
+    fn reserve_at(&self, now: Instant, request_count: u64) -> Instant {
+        let mut window = self.window.lock().expect("rate limiter lock poisoned");
+        if now >= window.start + GLOBAL_WINDOW {
+            window.start = now;
+            window.remaining = self.qps_limit;
+        }
+        for _ in 0..request_count {
+            if window.remaining == 0 {
+                window.start += GLOBAL_WINDOW;
+                window.remaining = self.qps_limit;
+            }
+            window.remaining = window.remaining.saturating_sub(1);
+        }
+        window.start
+    }
+}
+
+/// The identity of a client for the purposes of rate limiting.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    /// A client identified by the IP address from which it connected.
+    Ip(IpAddr),
+    /// A client identified by the API key it provided.
+    ApiKey(String),
+}
+
+impl Display for ClientId {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientId::Ip(ip_address) => write!(formatter, "{}", ip_address),
+            // Avoid logging the API key itself.
+            ClientId::ApiKey(_) => formatter.write_str("API key client"),
+        }
+    }
+}
+
+/// The error returned when a client has exceeded its rate limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RateLimited {
+    /// Whether the limit exceeded was that for expensive methods.
+    pub(crate) expensive: bool,
+}
+
+impl Display for RateLimited {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        if self.expensive {
+            formatter.write_str("rate limit for expensive requests exceeded")
+        } else {
+            formatter.write_str("rate limit exceeded")
+        }
+    }
+}
+
+/// A bucket of tokens, refilled at a constant rate up to a maximum.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(now: Instant, burst_limit: u64) -> Self {
+        TokenBucket {
+            tokens: burst_limit as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Refills the bucket and returns whether it holds at least one token.
+    fn refill_and_check(&mut self, now: Instant, qps_limit: u64, burst_limit: u64) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * qps_limit as f64).min(burst_limit as f64);
+        self.last_refill = now;
+        self.tokens >= 1.0
+    }
+}
+
+/// The token buckets of a single client.
+#[derive(Debug)]
+struct ClientBuckets {
+    all: TokenBucket,
+    expensive: TokenBucket,
+    /// The sequence number of the client's most recent request.
+    last_seen: u64,
+}
+
+/// The token buckets of the most recently seen clients.
+#[derive(Debug)]
+struct TrackedClients {
+    max_tracked_clients: usize,
+    by_client: HashMap<ClientId, ClientBuckets>,
+    /// The tracked clients keyed by the sequence number of their most recent request, i.e. least
+    /// recently seen first.
+    by_last_seen: BTreeMap<u64, ClientId>,
+    next_sequence_number: u64,
+}
+
+impl TrackedClients {
+    fn new(max_tracked_clients: usize) -> Self {
+        TrackedClients {
+            max_tracked_clients,
+            by_client: HashMap::new(),
+            by_last_seen: BTreeMap::new(),
+            next_sequence_number: 0,
+        }
+    }
+
+    /// Returns the buckets of the given client and marks it as the most recently seen.
+    ///
+    /// An untracked client is given full buckets, first discarding those of the least recently
+    /// seen client if the maximum number of clients are already tracked.
+    fn get_mut(
+        &mut self,
+        client_id: &ClientId,
+        now: Instant,
+        config: &RateLimitConfig,
+    ) -> &mut ClientBuckets {
+        if !self.by_client.contains_key(client_id)
+            && self.by_client.len() >= self.max_tracked_clients
+        {
+            if let Some((_, least_recent_client_id)) = self.by_last_seen.pop_first() {
+                self.by_client.remove(&least_recent_client_id);
+            }
+        }
+
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number += 1;
+        let by_last_seen = &mut self.by_last_seen;
+        let client_buckets = self
+            .by_client
+            .entry(client_id.clone())
+            .and_modify(|client_buckets| {
+                by_last_seen.remove(&client_buckets.last_seen);
+            })
+            .or_insert_with(|| ClientBuckets {
+                all: TokenBucket::new(now, config.burst_limit),
+                expensive: TokenBucket::new(now, config.expensive_burst_limit),
+                last_seen: sequence_number,
+            });
+        client_buckets.last_seen = sequence_number;
+        by_last_seen.insert(sequence_number, client_id.clone());
+        client_buckets
+    }
+}
+
+/// Per-client token-bucket rate limiter.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    api_keys: HashSet<String>,
+    tracked_clients: Mutex<TrackedClients>,
+}
+
+impl RateLimiter {
+    /// Returns a new rate limiter, or `None` if per-client rate limiting is disabled.
+    pub(crate) fn new(config: &RateLimitConfig) -> Option<Self> {
+        config.enable.then(|| RateLimiter {
+            config: config.clone(),
+            api_keys: config.api_keys.iter().cloned().collect(),
+            tracked_clients: Mutex::new(TrackedClients::new(MAX_TRACKED_CLIENTS)),
+        })
+    }
+
+    /// Returns the identity of the client making a request from the given address with the given
+    /// headers.
+    pub(crate) fn client_id(&self, remote_addr: SocketAddr, headers: &HeaderMap) -> ClientId {
+        if !self.config.api_key_header.is_empty() {
+            if let Some(api_key) = headers
+                .get(self.config.api_key_header.as_str())
+                .and_then(|value| value.to_str().ok())
+                .filter(|api_key| self.api_keys.contains(*api_key))
+            {
+                return ClientId::ApiKey(api_key.to_string());
+            }
+        }
+        ClientId::Ip(remote_addr.ip())
+    }
+
+    /// Takes a token for a request for the given method from the client's buckets.
+    ///
+    /// Returns an error without taking any tokens if the client has exceeded its rate limit.
+    pub(crate) fn check(&self, client_id: &ClientId, method: &str) -> Result<(), RateLimited> {
+        self.check_at(Instant::now(), client_id, method)
+    }
+
+    fn check_at(
+        &self,
+        now: Instant,
+        client_id: &ClientId,
+        method: &str,
+    ) -> Result<(), RateLimited> {
+        let is_expensive = self
+            .config
+            .expensive_methods
+            .iter()
+            .any(|expensive_method| expensive_method == method);
+
+        let mut tracked_clients = self
+            .tracked_clients
+            .lock()
+            .expect("rate limiter lock poisoned");
+        let client_buckets = tracked_clients.get_mut(client_id, now, &self.config);
+
+        if !client_buckets
+            .all
+            .refill_and_check(now, self.config.qps_limit, self.config.burst_limit)
+        {
+            return Err(RateLimited { expensive: false });
+        }
+        if is_expensive {
+            if !client_buckets.expensive.refill_and_check(
+                now,
+                self.config.expensive_qps_limit,
+                self.config.expensive_burst_limit,
+            ) {
+                return Err(RateLimited { expensive: true });
+            }
+            client_buckets.expensive.tokens -= 1.0;
+        }
+        client_buckets.all.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::Ipv4Addr, time::Duration};
+
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn limiter() -> RateLimiter {
+        let config = RateLimitConfig {
+            enable: true,
+            qps_limit: 2,
+            burst_limit: 4,
+            expensive_methods: vec!["expensive".to_string()],
+            expensive_qps_limit: 1,
+            expensive_burst_limit: 2,
+            api_key_header: "x-api-key".to_string(),
+            api_keys: vec!["key".to_string()],
+        };
+        RateLimiter::new(&config).expect("should be enabled")
+    }
+
+    fn client(last_octet: u8) -> ClientId {
+        ClientId::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, last_octet)))
+    }
+
+    #[test]
+    fn should_not_construct_if_disabled() {
+        assert!(RateLimiter::new(&RateLimitConfig::default()).is_none());
+    }
+
+    #[test]
+    fn should_limit_bursts_and_refill() {
+        let limiter = limiter();
+        let now = Instant::now();
+        for _ in 0..4 {
+            assert!(limiter.check_at(now, &client(1), "cheap").is_ok());
+        }
+        assert_eq!(
+            limiter.check_at(now, &client(1), "cheap"),
+            Err(RateLimited { expensive: false })
+        );
+
+        // Other clients are unaffected.
+        assert!(limiter.check_at(now, &client(2), "cheap").is_ok());
+
+        // After half a second, one more token should be available.
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.check_at(later, &client(1), "cheap").is_ok());
+        assert!(limiter.check_at(later, &client(1), "cheap").is_err());
+    }
+
+    #[test]
+    fn should_limit_expensive_methods_separately() {
+        let limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.check_at(now, &client(1), "expensive").is_ok());
+        assert!(limiter.check_at(now, &client(1), "expensive").is_ok());
+        assert_eq!(
+            limiter.check_at(now, &client(1), "expensive"),
+            Err(RateLimited { expensive: true })
+        );
+
+        // The rejected request shouldn't have used a general token, leaving two of the four.
+        assert!(limiter.check_at(now, &client(1), "cheap").is_ok());
+        assert!(limiter.check_at(now, &client(1), "cheap").is_ok());
+        assert!(limiter.check_at(now, &client(1), "cheap").is_err());
+    }
+
+    #[test]
+    fn should_identify_client_by_api_key_if_configured() {
+        let limiter = limiter();
+        let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 12345);
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            limiter.client_id(remote_addr, &headers),
+            ClientId::Ip(remote_addr.ip())
+        );
+
+        headers.insert("x-api-key", HeaderValue::from_static("key"));
+        assert_eq!(
+            limiter.client_id(remote_addr, &headers),
+            ClientId::ApiKey("key".to_string())
+        );
+
+        // Unknown API keys are ignored.
+        headers.insert("x-api-key", HeaderValue::from_static("other key"));
+        assert_eq!(
+            limiter.client_id(remote_addr, &headers),
+            ClientId::Ip(remote_addr.ip())
+        );
+    }
+
+    #[test]
+    fn should_delay_requests_exceeding_global_limit() {
+        let limiter = GlobalRateLimiter::new(2);
+        let now = limiter.window.lock().unwrap().start;
+        assert_eq!(limiter.reserve_at(now, 1), now);
+        assert_eq!(limiter.reserve_at(now, 1), now);
+
+        // Further requests, e.g. those in a batch, are delayed to later windows.
+        let next_window = now + Duration::from_secs(1);
+        assert_eq!(limiter.reserve_at(now, 1), next_window);
+        assert_eq!(
+            limiter.reserve_at(now, 3),
+            next_window + Duration::from_secs(1)
+        );
+
+        // Once the reserved windows have passed, a new window starts.
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve_at(later, 2), later);
+        assert_eq!(limiter.reserve_at(later, 1), later + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn should_discard_least_recently_seen_client() {
+        let config = limiter().config;
+        let now = Instant::now();
+        let mut tracked_clients = TrackedClients::new(2);
+        tracked_clients.get_mut(&client(1), now, &config).all.tokens = 0.0;
+        tracked_clients.get_mut(&client(2), now, &config).all.tokens = 0.0;
+        // Seeing client 1 again makes client 2 the least recently seen.
+        tracked_clients.get_mut(&client(1), now, &config);
+        tracked_clients.get_mut(&client(3), now, &config);
+
+        assert_eq!(tracked_clients.by_client.len(), 2);
+        assert_eq!(tracked_clients.by_last_seen.len(), 2);
+        assert!(!tracked_clients.by_client.contains_key(&client(2)));
+        // Client 1's buckets are retained, while client 2 is given full buckets again.
+        assert_eq!(
+            tracked_clients.get_mut(&client(1), now, &config).all.tokens,
+            0.0
+        );
+        assert_eq!(
+            tracked_clients.get_mut(&client(2), now, &config).all.tokens,
+            4.0
+        );
+    }
+}
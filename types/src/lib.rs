@@ -48,6 +48,7 @@ mod phase;
 mod protocol_version;
 pub mod runtime_args;
 mod semver;
+mod state_proof;
 mod stored_value;
 pub mod system;
 mod tagged;
@@ -93,6 +94,9 @@ pub use protocol_version::{ProtocolVersion, VersionCheckResult};
 #[doc(inline)]
 pub use runtime_args::{NamedArg, RuntimeArgs};
 pub use semver::{ParseSemVerError, SemVer, SEM_VER_SERIALIZED_LENGTH};
+pub use state_proof::{
+    verify_state_proofs, verify_state_proofs_for_block, StateProof, StateProofError,
+};
 pub use stored_value::{StoredValue, TypeMismatch as StoredValueTypeMismatch};
 pub use tagged::Tagged;
 #[cfg(any(feature = "std", test))]
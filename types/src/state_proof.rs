@@ -0,0 +1,576 @@
+//! Standalone verification of Merkle proofs of global state entries.
+//!
+//! A node's `query_global_state` RPC returns the queried value along with a hex-encoded
+//! [`bytesrepr`] serialization of the Merkle proofs for each key traversed by the query.  The types
+//! and functions here allow a client to check such proofs against a trusted state root hash or
+//! block header without depending on the execution engine.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
+
+use crate::{
+    blake2b,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    Key, StoredValue, BLAKE2B_DIGEST_LENGTH,
+};
+
+/// The size of the chunks into which a trie leaf is split for hashing if it is too large to be
+/// hashed directly.  Must match the chunk size used by the node when hashing global state.
+const CHUNK_SIZE_BYTES: usize = 8 * 1024 * 1024;
+
+const STEP_NODE_TAG: u8 = 0;
+const STEP_EXTENSION_TAG: u8 = 1;
+
+const POINTER_LEAF_TAG: u8 = 0;
+const POINTER_NODE_TAG: u8 = 1;
+
+const TRIE_LEAF_TAG: u8 = 0;
+const TRIE_NODE_TAG: u8 = 1;
+const TRIE_EXTENSION_TAG: u8 = 2;
+
+/// The number of children of a trie node.
+const RADIX: usize = 256;
+
+/// The offset of the state root hash in a serialized block header, which starts with the hash of
+/// the parent block.
+const BLOCK_HEADER_STATE_ROOT_HASH_OFFSET: usize = BLAKE2B_DIGEST_LENGTH;
+
+/// Errors arising when verifying a state proof.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum StateProofError {
+    /// The number of proofs is not one more than the length of the path.
+    PathLengthDifferentThanProofLessOne,
+    /// The key in a proof does not match the expected key.
+    UnexpectedKey,
+    /// The value at the end of the path does not match the expected value.
+    UnexpectedValue,
+    /// A proof does not hash to the expected state root hash.
+    InvalidProofHash,
+    /// A path component does not name a key of the preceding account or contract.
+    PathCold,
+    /// The block header does not hash to the expected block hash.
+    InvalidBlockHeaderHash,
+    /// (De)serialization error.
+    BytesRepr(bytesrepr::Error),
+}
+
+impl Display for StateProofError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            StateProofError::PathLengthDifferentThanProofLessOne => formatter
+                .write_str("the path should not have a different length than the proof less one"),
+            StateProofError::UnexpectedKey => {
+                formatter.write_str("the provided key does not match the key in the proof")
+            }
+            StateProofError::UnexpectedValue => {
+                formatter.write_str("the provided value does not match the value in the proof")
+            }
+            StateProofError::InvalidProofHash => formatter.write_str("the proof hash is invalid"),
+            StateProofError::PathCold => formatter.write_str("the path went cold"),
+            StateProofError::InvalidBlockHeaderHash => {
+                formatter.write_str("the block header does not match the block hash")
+            }
+            StateProofError::BytesRepr(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl From<bytesrepr::Error> for StateProofError {
+    fn from(error: bytesrepr::Error) -> Self {
+        StateProofError::BytesRepr(error)
+    }
+}
+
+/// A pointer to a child of a trie node.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TriePointer {
+    Leaf([u8; BLAKE2B_DIGEST_LENGTH]),
+    Node([u8; BLAKE2B_DIGEST_LENGTH]),
+}
+
+impl ToBytes for TriePointer {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + BLAKE2B_DIGEST_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        let (tag, hash) = match self {
+            TriePointer::Leaf(hash) => (POINTER_LEAF_TAG, hash),
+            TriePointer::Node(hash) => (POINTER_NODE_TAG, hash),
+        };
+        writer.push(tag);
+        writer.extend_from_slice(hash);
+        Ok(())
+    }
+}
+
+impl FromBytes for TriePointer {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let (hash, remainder) = FromBytes::from_bytes(remainder)?;
+        match tag {
+            POINTER_LEAF_TAG => Ok((TriePointer::Leaf(hash), remainder)),
+            POINTER_NODE_TAG => Ok((TriePointer::Node(hash), remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A single step of a [`StateProof`], corresponding to one trie node or extension on the path from
+/// the leaf to the root.
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum StateProofStep {
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, TriePointer)>,
+    },
+    Extension {
+        affix: Bytes,
+    },
+}
+
+impl StateProofStep {
+    /// Returns the serialized trie node or extension represented by this step, with `pointer` in
+    /// place of the child on the path to the leaf.
+    fn trie_bytes(&self, pointer: TriePointer) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = Vec::new();
+        match self {
+            StateProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                let mut pointer_block = [None; RADIX];
+                for (index, indexed_pointer) in indexed_pointers_with_hole {
+                    pointer_block[*index as usize] = Some(*indexed_pointer);
+                }
+                pointer_block[*hole_index as usize] = Some(pointer);
+
+                bytes.push(TRIE_NODE_TAG);
+                for maybe_pointer in pointer_block.iter() {
+                    match maybe_pointer {
+                        Some(pointer) => {
+                            bytes.push(1);
+                            pointer.write_bytes(&mut bytes)?;
+                        }
+                        None => bytes.push(0),
+                    }
+                }
+            }
+            StateProofStep::Extension { affix } => {
+                bytes.push(TRIE_EXTENSION_TAG);
+                affix.write_bytes(&mut bytes)?;
+                pointer.write_bytes(&mut bytes)?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+impl ToBytes for StateProofStep {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                StateProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    hole_index.serialized_length() + indexed_pointers_with_hole.serialized_length()
+                }
+                StateProofStep::Extension { affix } => affix.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            StateProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                writer.push(STEP_NODE_TAG);
+                writer.push(*hole_index);
+                indexed_pointers_with_hole.write_bytes(writer)
+            }
+            StateProofStep::Extension { affix } => {
+                writer.push(STEP_EXTENSION_TAG);
+                affix.write_bytes(writer)
+            }
+        }
+    }
+}
+
+impl FromBytes for StateProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            STEP_NODE_TAG => {
+                let (hole_index, remainder) = u8::from_bytes(remainder)?;
+                let (indexed_pointers_with_hole, remainder) = FromBytes::from_bytes(remainder)?;
+                let step = StateProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                };
+                Ok((step, remainder))
+            }
+            STEP_EXTENSION_TAG => {
+                let (affix, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((StateProofStep::Extension { affix }, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A proof that a given key and value are present in global state under some state root hash.
+///
+/// The byte representation is identical to that of the execution engine's
+/// `TrieMerkleProof<Key, StoredValue>`, so a `StateProof` can be parsed from the proofs returned
+/// by a node.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StateProof {
+    key: Key,
+    value: StoredValue,
+    steps: Vec<StateProofStep>,
+}
+
+impl StateProof {
+    /// Returns the key proven by this proof.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns the value proven by this proof.
+    pub fn value(&self) -> &StoredValue {
+        &self.value
+    }
+
+    /// Recomputes the state root hash implied by this proof.
+    ///
+    /// The proof is valid for a given state root hash if and only if the two are equal.
+    pub fn compute_state_root_hash(&self) -> Result<[u8; BLAKE2B_DIGEST_LENGTH], bytesrepr::Error> {
+        let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+        self.key.write_bytes(&mut leaf_bytes)?;
+        self.value.write_bytes(&mut leaf_bytes)?;
+        let mut hash = hash_into_chunks_if_necessary(&leaf_bytes);
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let pointer = if index == 0 {
+                TriePointer::Leaf(hash)
+            } else {
+                TriePointer::Node(hash)
+            };
+            hash = blake2b(step.trie_bytes(pointer)?);
+        }
+        Ok(hash)
+    }
+}
+
+impl ToBytes for StateProof {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + self.steps.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.key.write_bytes(writer)?;
+        self.value.write_bytes(writer)?;
+        self.steps.write_bytes(writer)
+    }
+}
+
+impl FromBytes for StateProof {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, remainder) = Key::from_bytes(bytes)?;
+        let (value, remainder) = StoredValue::from_bytes(remainder)?;
+        let (steps, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((StateProof { key, value, steps }, remainder))
+    }
+}
+
+/// Verifies the result of a global state query.
+///
+/// `proofs` must hold one proof for `base_key` followed by one proof for the key found under each
+/// component of `path`, as returned by a node's `query_global_state` RPC.  Each proof is checked
+/// against `state_root_hash`, and the value at the end of the path must equal `expected_value`.
+pub fn verify_state_proofs(
+    state_root_hash: &[u8; BLAKE2B_DIGEST_LENGTH],
+    proofs: &[StateProof],
+    base_key: &Key,
+    path: &[String],
+    expected_value: &StoredValue,
+) -> Result<(), StateProofError> {
+    if proofs.len() != path.len() + 1 {
+        return Err(StateProofError::PathLengthDifferentThanProofLessOne);
+    }
+
+    let mut expected_key = base_key.normalize();
+    let mut maybe_value: Option<&StoredValue> = None;
+    for (index, proof) in proofs.iter().enumerate() {
+        if let Some(value) = maybe_value {
+            let named_keys = match value {
+                StoredValue::Account(account) => account.named_keys(),
+                StoredValue::Contract(contract) => contract.named_keys(),
+                _ => return Err(StateProofError::PathCold),
+            };
+            expected_key = named_keys
+                .get(&path[index - 1])
+                .ok_or(StateProofError::PathCold)?
+                .normalize();
+        }
+
+        if proof.key() != &expected_key {
+            return Err(StateProofError::UnexpectedKey);
+        }
+        if state_root_hash != &proof.compute_state_root_hash()? {
+            return Err(StateProofError::InvalidProofHash);
+        }
+        maybe_value = Some(proof.value());
+    }
+
+    if maybe_value != Some(expected_value) {
+        return Err(StateProofError::UnexpectedValue);
+    }
+
+    Ok(())
+}
+
+/// Verifies the result of a global state query against a block header.
+///
+/// `block_header_bytes` is the [`bytesrepr`] serialization of a block header, which is checked to
+/// hash to `block_hash` before its state root hash is used to verify `proof_bytes`, the serialized
+/// proofs as returned hex-encoded by a node's `query_global_state` RPC.  See
+/// [`verify_state_proofs`] for details of the remaining arguments.
+pub fn verify_state_proofs_for_block(
+    block_hash: &[u8; BLAKE2B_DIGEST_LENGTH],
+    block_header_bytes: &[u8],
+    proof_bytes: &[u8],
+    base_key: &Key,
+    path: &[String],
+    expected_value: &StoredValue,
+) -> Result<(), StateProofError> {
+    if &blake2b(block_header_bytes) != block_hash {
+        return Err(StateProofError::InvalidBlockHeaderHash);
+    }
+    let state_root_hash = block_header_bytes
+        .get(BLOCK_HEADER_STATE_ROOT_HASH_OFFSET..)
+        .ok_or(bytesrepr::Error::EarlyEndOfStream)
+        .and_then(<[u8; BLAKE2B_DIGEST_LENGTH]>::from_bytes)?
+        .0;
+    let proofs: Vec<StateProof> = bytesrepr::deserialize_from_slice(proof_bytes)?;
+    verify_state_proofs(&state_root_hash, &proofs, base_key, path, expected_value)
+}
+
+/// Hashes the given data, splitting it into chunks and computing the root of the Merkle tree of
+/// the chunks' hashes if it exceeds the chunk size.
+fn hash_into_chunks_if_necessary(bytes: &[u8]) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    if bytes.len() <= CHUNK_SIZE_BYTES {
+        return blake2b(bytes);
+    }
+
+    let mut leaves = bytes.chunks(CHUNK_SIZE_BYTES).map(blake2b);
+    let leaf_count = leaves.len() as u64;
+    let raw_root = match tree_fold(usize::MAX, &mut leaves) {
+        Ok(root) | Err(Some(root)) => root,
+        Err(None) => unreachable!("should have at least two chunks"),
+    };
+
+    // The final hash is prefixed with a chunk's worth of zeros to prevent pre-image attacks.
+    let mut hasher = new_hasher();
+    let zeros = [0; 1024];
+    for _ in 0..(CHUNK_SIZE_BYTES / zeros.len()) {
+        hasher.update(zeros);
+    }
+    hasher.update(leaf_count.to_le_bytes());
+    hasher.update(raw_root);
+    finalize(hasher)
+}
+
+/// Folds the leaves into a balanced binary tree of pairwise hashes, matching the order used by the
+/// node.
+///
+/// Returns `Ok` with a complete tree of `2^(height + 1)` leaves, or `Err` with the partial tree if
+/// the leaves ran out.
+fn tree_fold<I>(height: usize, leaves: &mut I) -> Result<[u8; 32], Option<[u8; 32]>>
+where
+    I: Iterator<Item = [u8; BLAKE2B_DIGEST_LENGTH]>,
+{
+    let mut tree = match (leaves.next(), leaves.next()) {
+        (Some(left), Some(right)) => hash_pair(left, right),
+        (Some(left), None) => return Err(Some(left)),
+        (None, _) => return Err(None),
+    };
+    for subtree_height in 0..height {
+        match tree_fold(subtree_height, leaves) {
+            Ok(subtree) => tree = hash_pair(tree, subtree),
+            Err(None) => return Err(Some(tree)),
+            Err(Some(subtree)) => return Err(Some(hash_pair(tree, subtree))),
+        }
+    }
+    Ok(tree)
+}
+
+fn hash_pair(
+    left: [u8; BLAKE2B_DIGEST_LENGTH],
+    right: [u8; BLAKE2B_DIGEST_LENGTH],
+) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut hasher = new_hasher();
+    hasher.update(left);
+    hasher.update(right);
+    finalize(hasher)
+}
+
+fn new_hasher() -> VarBlake2b {
+    // NOTE: Assumed safe as `BLAKE2B_DIGEST_LENGTH` is a valid value for a hasher
+    VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).expect("should create hasher")
+}
+
+fn finalize(hasher: VarBlake2b) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut result = [0; BLAKE2B_DIGEST_LENGTH];
+    hasher.finalize_variable(|slice| {
+        result.copy_from_slice(slice);
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CLValue;
+
+    fn leaf_proof(key: Key, value: StoredValue) -> StateProof {
+        StateProof {
+            key,
+            value,
+            steps: vec![],
+        }
+    }
+
+    #[test]
+    fn should_verify_single_leaf_proof() {
+        let key = Key::Hash([1; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(42_u64).unwrap());
+        let proof = leaf_proof(key, value.clone());
+        let state_root_hash = proof.compute_state_root_hash().unwrap();
+
+        let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+        leaf_bytes.extend(key.to_bytes().unwrap());
+        leaf_bytes.extend(value.to_bytes().unwrap());
+        assert_eq!(state_root_hash, blake2b(leaf_bytes));
+
+        let proofs = vec![proof];
+        verify_state_proofs(&state_root_hash, &proofs, &key, &[], &value).unwrap();
+        assert_eq!(
+            verify_state_proofs(&[0; 32], &proofs, &key, &[], &value),
+            Err(StateProofError::InvalidProofHash)
+        );
+        assert_eq!(
+            verify_state_proofs(&state_root_hash, &proofs, &Key::Hash([2; 32]), &[], &value),
+            Err(StateProofError::UnexpectedKey)
+        );
+        let other_value = StoredValue::CLValue(CLValue::from_t(43_u64).unwrap());
+        assert_eq!(
+            verify_state_proofs(&state_root_hash, &proofs, &key, &[], &other_value),
+            Err(StateProofError::UnexpectedValue)
+        );
+    }
+
+    #[test]
+    fn should_verify_proof_against_block_header() {
+        let key = Key::Hash([3; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(String::from("value")).unwrap());
+        let proof = StateProof {
+            key,
+            value: value.clone(),
+            steps: vec![
+                StateProofStep::Node {
+                    hole_index: 3,
+                    indexed_pointers_with_hole: vec![(0, TriePointer::Leaf([4; 32]))],
+                },
+                StateProofStep::Extension {
+                    affix: vec![1, 2].into(),
+                },
+            ],
+        };
+        let state_root_hash = proof.compute_state_root_hash().unwrap();
+        let proof_bytes = vec![proof].to_bytes().unwrap();
+
+        let mut block_header_bytes = vec![5; BLAKE2B_DIGEST_LENGTH];
+        block_header_bytes.extend_from_slice(&state_root_hash);
+        block_header_bytes.extend_from_slice(&[6; 100]);
+        let block_hash = blake2b(&block_header_bytes);
+
+        verify_state_proofs_for_block(
+            &block_hash,
+            &block_header_bytes,
+            &proof_bytes,
+            &key,
+            &[],
+            &value,
+        )
+        .unwrap();
+        assert_eq!(
+            verify_state_proofs_for_block(
+                &[0; 32],
+                &block_header_bytes,
+                &proof_bytes,
+                &key,
+                &[],
+                &value,
+            ),
+            Err(StateProofError::InvalidBlockHeaderHash)
+        );
+        assert_eq!(
+            verify_state_proofs_for_block(
+                &block_hash,
+                &block_header_bytes,
+                &proof_bytes[..proof_bytes.len() - 1],
+                &key,
+                &[],
+                &value,
+            ),
+            Err(StateProofError::BytesRepr(
+                bytesrepr::Error::EarlyEndOfStream
+            ))
+        );
+    }
+
+    #[test]
+    fn should_fold_chunk_hashes_like_a_balanced_tree() {
+        let leaves: Vec<[u8; 32]> = (0..5_u8).map(|index| blake2b([index])).collect();
+        let expected = hash_pair(
+            hash_pair(
+                hash_pair(leaves[0], leaves[1]),
+                hash_pair(leaves[2], leaves[3]),
+            ),
+            leaves[4],
+        );
+        assert_eq!(
+            tree_fold(usize::MAX, &mut leaves.into_iter()),
+            Err(Some(expected))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 mod admins;
 mod balances;
 mod generic;
+mod state_diff;
 mod system_contract_registry;
 mod utils;
 mod validators;
@@ -10,7 +11,7 @@ use clap::{crate_version, App, Arg, SubCommand};
 
 use crate::{
     balances::generate_balances_update, generic::generate_generic_update,
-    system_contract_registry::generate_system_contract_registry,
+    state_diff::print_state_diff, system_contract_registry::generate_system_contract_registry,
     validators::generate_validators_update,
 };
 
@@ -184,6 +185,37 @@ fn main() {
                         .number_of_values(1),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Prints the differences between the global state under two state hashes")
+                .arg(
+                    Arg::with_name("data_dir")
+                        .short("d")
+                        .long("data-dir")
+                        .value_name("PATH")
+                        .help("Data storage directory containing the global state database file")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("hash")
+                        .short("s")
+                        .long("state-hash")
+                        .value_name("HEX_STRING")
+                        .help("The global state hash to compare from")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("after_hash")
+                        .short("a")
+                        .long("after-state-hash")
+                        .value_name("HEX_STRING")
+                        .help("The global state hash to compare to")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -194,6 +226,7 @@ fn main() {
         }
         ("generic", Some(sub_matches)) => generate_generic_update(sub_matches),
         ("generate-admins", Some(sub_matches)) => generate_admins(sub_matches),
+        ("diff", Some(sub_matches)) => print_state_diff(sub_matches),
         (subcommand, _) => {
             println!("Unknown subcommand: \"{}\"", subcommand);
         }
@@ -0,0 +1,19 @@
+use casper_engine_test_support::LmdbWasmTestBuilder;
+use casper_execution_engine::shared::newtypes::CorrelationId;
+use clap::ArgMatches;
+
+use crate::utils::hash_from_str;
+
+pub(crate) fn print_state_diff(matches: &ArgMatches<'_>) {
+    let data_dir = matches.value_of("data_dir").unwrap_or(".");
+    let before_state_hash = hash_from_str(matches.value_of("hash").unwrap());
+    let after_state_hash = hash_from_str(matches.value_of("after_hash").unwrap());
+
+    let builder = LmdbWasmTestBuilder::open_raw(data_dir, Default::default(), before_state_hash);
+    let diff = builder
+        .get_engine_state()
+        .diff_state_roots(CorrelationId::new(), before_state_hash, after_state_hash)
+        .unwrap();
+
+    println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+}